@@ -21,6 +21,10 @@ class ShareService {
     return export(docId, ExportType.Markdown);
   }
 
+  Future<Either<ExportDataPB, FlowyError>> exportHtml(String docId) {
+    return export(docId, ExportType.Html);
+  }
+
   Future<Either<ExportDataPB, FlowyError>> exportURL(String docId) {
     return export(docId, ExportType.Link);
   }
@@ -165,6 +165,7 @@ class DocumentShareButton extends StatelessWidget {
       case ExportType.Link:
         break;
       case ExportType.Markdown:
+      case ExportType.Html:
         FlutterClipboard.copy(exportData.data).then((value) => Log.info('copied to clipboard'));
         break;
       case ExportType.Text:
@@ -1,6 +1,6 @@
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 mod c;
-mod model;
+pub mod model;
 mod protobuf;
 mod util;
 
@@ -64,10 +64,14 @@ pub extern "C" fn sync_event(input: *const u8, len: usize) -> *const u8 {
         }
         Some(e) => e.dispatcher.clone(),
     };
-    let _response = EventDispatcher::sync_send(dispatcher, request);
-
-    // FFIResponse {  }
-    let response_bytes = vec![];
+    let response = EventDispatcher::sync_send(dispatcher, request);
+    let response_bytes = match FFIResponse::from(response).into_bytes() {
+        Ok(bytes) => bytes.to_vec(),
+        Err(e) => {
+            log::error!("[FFI]: Serialize the sync event response failed: {:?}", e);
+            vec![]
+        }
+    };
     let result = extend_front_four_bytes_into_bytes(&response_bytes);
     forget_rust(result)
 }
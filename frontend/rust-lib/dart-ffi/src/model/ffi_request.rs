@@ -13,6 +13,13 @@ pub struct FFIRequest {
 }
 
 impl FFIRequest {
+    pub fn new<T: ToString>(event: T, payload: Vec<u8>) -> Self {
+        Self {
+            event: event.to_string(),
+            payload,
+        }
+    }
+
     pub fn from_u8_pointer(pointer: *const u8, len: usize) -> Self {
         let buffer = unsafe { std::slice::from_raw_parts(pointer, len) }.to_vec();
         let bytes = Bytes::from(buffer);
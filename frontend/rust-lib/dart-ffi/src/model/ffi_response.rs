@@ -1,7 +1,7 @@
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use lib_dispatch::prelude::{EventResponse, Payload, StatusCode};
 
-#[derive(ProtoBuf_Enum, Clone, Copy)]
+#[derive(ProtoBuf_Enum, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FFIStatusCode {
     Ok = 0,
     Err = 1,
@@ -21,6 +21,25 @@ pub struct FFIResponse {
 
     #[pb(index = 2)]
     code: FFIStatusCode,
+
+    /// The message of the dispatcher's internal error, e.g. the event isn't registered. The
+    /// errors that are returned by the event handlers are kept in the `payload`.
+    #[pb(index = 3)]
+    error: String,
+}
+
+impl FFIResponse {
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    pub fn code(&self) -> FFIStatusCode {
+        self.code
+    }
+
+    pub fn error(&self) -> &str {
+        &self.error
+    }
 }
 
 impl std::convert::From<EventResponse> for FFIResponse {
@@ -30,17 +49,12 @@ impl std::convert::From<EventResponse> for FFIResponse {
             Payload::None => vec![],
         };
 
-        let code = match resp.status_code {
-            StatusCode::Ok => FFIStatusCode::Ok,
-            StatusCode::Err => FFIStatusCode::Err,
-            StatusCode::Internal => FFIStatusCode::Internal,
+        let (code, error) = match resp.status_code {
+            StatusCode::Ok => (FFIStatusCode::Ok, "".to_owned()),
+            StatusCode::Err => (FFIStatusCode::Err, "".to_owned()),
+            StatusCode::Internal => (FFIStatusCode::Internal, String::from_utf8_lossy(&payload).into_owned()),
         };
 
-        // let msg = match resp.error {
-        //     None => "".to_owned(),
-        //     Some(e) => format!("{:?}", e),
-        // };
-
-        FFIResponse { payload, code }
+        FFIResponse { payload, code, error }
     }
 }
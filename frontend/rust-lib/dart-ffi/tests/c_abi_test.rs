@@ -0,0 +1,99 @@
+//! Drives the SDK through its C functions the way a host that isn't written in Dart does. The
+//! async responses are posted to the Dart port, so the test stores its own post function to
+//! receive them.
+use allo_isolate::ffi::{DartCObject, DartCObjectType, DartPort};
+use byteorder::{BigEndian, ByteOrder};
+use bytes::Bytes;
+use dart_ffi::model::{FFIRequest, FFIResponse, FFIStatusCode};
+use dart_ffi::{async_event, init_sdk, sync_event};
+use flowy_user::entities::{SignUpPayloadPB, UserProfilePB};
+use nanoid::nanoid;
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    convert::{TryFrom, TryInto},
+    ffi::CString,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+static POSTED_MESSAGES: Lazy<Mutex<HashMap<DartPort, Vec<u8>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+unsafe extern "C" fn post_cobject(port: DartPort, message: *mut DartCObject) -> bool {
+    let message = &*message;
+    let bytes = match message.ty {
+        DartCObjectType::DartTypedData => {
+            let typed_data = message.value.as_typed_data;
+            std::slice::from_raw_parts(typed_data.values, typed_data.length as usize).to_vec()
+        }
+        DartCObjectType::DartExternalTypedData => {
+            let typed_data = message.value.as_external_typed_data;
+            std::slice::from_raw_parts(typed_data.data, typed_data.length as usize).to_vec()
+        }
+        _ => return false,
+    };
+    POSTED_MESSAGES.lock().unwrap().insert(port, bytes);
+    true
+}
+
+fn init() {
+    let root = std::env::temp_dir().join(nanoid!(6));
+    std::fs::create_dir_all(&root).unwrap();
+    let path = CString::new(root.to_str().unwrap()).unwrap();
+    assert_eq!(init_sdk(path.into_raw()), 0);
+    unsafe { allo_isolate::store_dart_post_cobject(post_cobject) };
+}
+
+fn request_bytes<T: ToString>(event: T, payload: Vec<u8>) -> Vec<u8> {
+    let bytes: Bytes = FFIRequest::new(event, payload).try_into().unwrap();
+    bytes.to_vec()
+}
+
+/// The response of the `sync_event` starts with its length in four bytes.
+fn call_sync_event(input: &[u8]) -> FFIResponse {
+    let pointer = sync_event(input.as_ptr(), input.len());
+    let len = BigEndian::read_u32(unsafe { std::slice::from_raw_parts(pointer, 4) }) as usize;
+    let bytes = unsafe { std::slice::from_raw_parts(pointer.add(4), len) }.to_vec();
+    FFIResponse::try_from(Bytes::from(bytes)).unwrap()
+}
+
+fn call_async_event(port: DartPort, input: &[u8]) -> FFIResponse {
+    async_event(port, input.as_ptr(), input.len());
+    let start = Instant::now();
+    loop {
+        if let Some(bytes) = POSTED_MESSAGES.lock().unwrap().remove(&port) {
+            return FFIResponse::try_from(Bytes::from(bytes)).unwrap();
+        }
+        assert!(start.elapsed() < Duration::from_secs(10), "No response was posted");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn c_abi_sync_and_async_event_test() {
+    init();
+
+    let email = format!("{}@appflowy.io", nanoid!(6));
+    let payload = SignUpPayloadPB {
+        email: email.clone(),
+        name: "annie".to_owned(),
+        password: "HelloWorld!123".to_owned(),
+    };
+    let payload: Bytes = payload.try_into().unwrap();
+    let response = call_sync_event(&request_bytes("SignUp", payload.to_vec()));
+    assert_eq!(response.code(), FFIStatusCode::Ok);
+    let profile = UserProfilePB::try_from(Bytes::from(response.payload().to_vec())).unwrap();
+    assert_eq!(profile.email, email);
+
+    let response = call_async_event(1, &request_bytes("GetUserProfile", vec![]));
+    assert_eq!(response.code(), FFIStatusCode::Ok);
+    let async_profile = UserProfilePB::try_from(Bytes::from(response.payload().to_vec())).unwrap();
+    assert_eq!(async_profile.id, profile.id);
+
+    let response = call_sync_event(&request_bytes("NotExistEvent", vec![]));
+    assert_eq!(response.code(), FFIStatusCode::Internal);
+    assert!(response.error().contains("Can not find the event handler"));
+
+    let response = call_async_event(2, &request_bytes("NotExistEvent", vec![]));
+    assert_eq!(response.code(), FFIStatusCode::Internal);
+}
@@ -1,4 +1,4 @@
-use crate::entities::SubscribeObject;
+use crate::{entities::SubscribeObject, sink::NotificationSink};
 use bytes::Bytes;
 use lazy_static::lazy_static;
 use std::{convert::TryInto, sync::RwLock};
@@ -55,3 +55,15 @@ impl DartStreamSender {
         Ok(())
     }
 }
+
+/// Posts the notifications to the Dart isolate whose port is set by `DartStreamSender::set_port`.
+pub struct DartNotificationSink;
+
+impl NotificationSink for DartNotificationSink {
+    fn send(&self, subject: &SubscribeObject) {
+        match DartStreamSender::post(subject.clone()) {
+            Ok(_) => {}
+            Err(error) => log::error!("Send observable subject failed: {}", error),
+        }
+    }
+}
@@ -3,8 +3,9 @@ use bytes::Bytes;
 pub mod dart;
 pub mod entities;
 mod protobuf;
+pub mod sink;
 
-use crate::{dart::DartStreamSender, entities::SubscribeObject};
+use crate::{entities::SubscribeObject, sink::send_to_sinks};
 use lib_dispatch::prelude::ToBytes;
 
 pub struct DartNotifyBuilder {
@@ -66,9 +67,6 @@ impl DartNotifyBuilder {
             error,
         };
 
-        match DartStreamSender::post(subject) {
-            Ok(_) => {}
-            Err(error) => log::error!("Send observable subject failed: {}", error),
-        }
+        send_to_sinks(subject);
     }
 }
@@ -0,0 +1,56 @@
+use crate::{
+    entities::SubscribeObject,
+    sink::{NotificationFilter, NotificationSink},
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Forwards the notifications to the subscribers in the same process, e.g. the tests or the
+/// bindings of other languages.
+pub struct BroadcastNotificationSink {
+    sender: broadcast::Sender<SubscribeObject>,
+}
+
+impl BroadcastNotificationSink {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self, filter: NotificationFilter) -> NotificationReceiver {
+        NotificationReceiver {
+            receiver: self.sender.subscribe(),
+            filter,
+        }
+    }
+}
+
+impl NotificationSink for BroadcastNotificationSink {
+    fn send(&self, subject: &SubscribeObject) {
+        if self.sender.receiver_count() > 0 {
+            let _ = self.sender.send(subject.clone());
+        }
+    }
+}
+
+pub struct NotificationReceiver {
+    receiver: broadcast::Receiver<SubscribeObject>,
+    filter: NotificationFilter,
+}
+
+impl NotificationReceiver {
+    /// Wait for the next notification that matches the filter. Returns `None` if the sink is
+    /// dropped.
+    pub async fn recv(&mut self) -> Option<SubscribeObject> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(subject) => {
+                    if self.filter.is_match(&subject) {
+                        return Some(subject);
+                    }
+                }
+                Err(RecvError::Lagged(count)) => log::warn!("Notification receiver missed {} notifications", count),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+mod broadcast;
+
+pub use broadcast::*;
+
+use crate::{dart::DartNotificationSink, entities::SubscribeObject};
+use lazy_static::lazy_static;
+use std::sync::{Arc, RwLock};
+
+lazy_static! {
+    static ref NOTIFICATION_SINKS: RwLock<Vec<Arc<dyn NotificationSink>>> = {
+        let dart_sink: Arc<dyn NotificationSink> = Arc::new(DartNotificationSink);
+        let broadcast_sink: Arc<dyn NotificationSink> = BROADCAST_NOTIFICATION_SINK.clone();
+        RwLock::new(vec![dart_sink, broadcast_sink])
+    };
+    static ref BROADCAST_NOTIFICATION_SINK: Arc<BroadcastNotificationSink> =
+        Arc::new(BroadcastNotificationSink::new(BROADCAST_NOTIFICATION_CAPACITY));
+}
+
+// The number of the notifications that a subscriber of the in-process sink can fall behind
+// before it starts missing them.
+const BROADCAST_NOTIFICATION_CAPACITY: usize = 1024;
+
+/// A sink receives every notification that is sent by the `DartNotifyBuilder`. The Dart stream
+/// port and the in-process broadcast sink are registered by default.
+pub trait NotificationSink: Send + Sync {
+    fn send(&self, subject: &SubscribeObject);
+}
+
+pub fn register_notification_sink(sink: Arc<dyn NotificationSink>) {
+    match NOTIFICATION_SINKS.write() {
+        Ok(mut sinks) => sinks.push(sink),
+        Err(e) => log::error!("Get notification sinks lock fail. {:?}", e),
+    }
+}
+
+/// Subscribe to the notifications that match the filter without going through the Dart port.
+pub fn subscribe_notifications(filter: NotificationFilter) -> NotificationReceiver {
+    BROADCAST_NOTIFICATION_SINK.subscribe(filter)
+}
+
+pub(crate) fn send_to_sinks(subject: SubscribeObject) {
+    match NOTIFICATION_SINKS.read() {
+        Ok(sinks) => sinks.iter().for_each(|sink| sink.send(&subject)),
+        Err(e) => log::error!("Get notification sinks lock fail. {:?}", e),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NotificationFilter {
+    source: Option<String>,
+    id: Option<String>,
+}
+
+impl NotificationFilter {
+    pub fn source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_owned());
+        self
+    }
+
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_owned());
+        self
+    }
+
+    pub fn is_match(&self, subject: &SubscribeObject) -> bool {
+        let is_source_match = self.source.as_ref().map_or(true, |source| source == &subject.source);
+        let is_id_match = self.id.as_ref().map_or(true, |id| id == &subject.id);
+        is_source_match && is_id_match
+    }
+}
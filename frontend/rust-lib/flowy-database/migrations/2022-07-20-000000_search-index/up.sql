@@ -0,0 +1,7 @@
+-- Your SQL goes here
+CREATE VIRTUAL TABLE search_index USING fts5(
+     view_id UNINDEXED,
+     view_type UNINDEXED,
+     name,
+     content
+);
\ No newline at end of file
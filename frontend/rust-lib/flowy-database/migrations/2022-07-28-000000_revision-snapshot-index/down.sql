@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX rev_table_doc_id_rev_id;
+DROP INDEX rev_snapshot_object_id_rev_id;
\ No newline at end of file
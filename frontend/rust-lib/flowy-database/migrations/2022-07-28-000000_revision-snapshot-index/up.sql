@@ -0,0 +1,3 @@
+-- Your SQL goes here
+CREATE INDEX rev_table_doc_id_rev_id ON rev_table (doc_id, rev_id);
+CREATE INDEX rev_snapshot_object_id_rev_id ON rev_snapshot (object_id, rev_id);
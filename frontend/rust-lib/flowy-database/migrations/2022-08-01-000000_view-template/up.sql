@@ -0,0 +1,10 @@
+-- Your SQL goes here
+CREATE TABLE view_template_table (
+     id TEXT NOT NULL PRIMARY KEY,
+     name TEXT NOT NULL DEFAULT '',
+     desc TEXT NOT NULL DEFAULT '',
+     view_type INTEGER NOT NULL DEFAULT 0,
+     plugin_type INTEGER NOT NULL DEFAULT 0,
+     data BLOB NOT NULL DEFAULT (x''),
+     create_time BIGINT NOT NULL DEFAULT 0
+);
\ No newline at end of file
@@ -49,6 +49,18 @@ table! {
     }
 }
 
+table! {
+    rev_history (id) {
+        id -> Integer,
+        object_id -> Text,
+        start_rev_id -> BigInt,
+        end_rev_id -> BigInt,
+        data -> Binary,
+        user_id -> Text,
+        timestamp -> BigInt,
+    }
+}
+
 table! {
     rev_snapshot (id) {
         id -> Integer,
@@ -107,6 +119,18 @@ table! {
     }
 }
 
+table! {
+    view_template_table (id) {
+        id -> Text,
+        name -> Text,
+        desc -> Text,
+        view_type -> Integer,
+        plugin_type -> Integer,
+        data -> Binary,
+        create_time -> BigInt,
+    }
+}
+
 table! {
     workspace_table (id) {
         id -> Text,
@@ -125,10 +149,12 @@ allow_tables_to_appear_in_same_query!(
     grid_meta_rev_table,
     grid_rev_table,
     kv_table,
+    rev_history,
     rev_snapshot,
     rev_table,
     trash_table,
     user_table,
     view_table,
+    view_template_table,
     workspace_table,
 );
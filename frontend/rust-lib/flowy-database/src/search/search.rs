@@ -0,0 +1,190 @@
+use diesel::sql_types::{Integer, Text};
+use diesel::{RunQueryDsl, SqliteConnection};
+
+/// The text of the view that is stored in the full-text search index.
+#[derive(Clone, Debug)]
+pub struct SearchIndexRecord {
+    pub view_id: String,
+    pub view_type: i32,
+    pub name: String,
+    pub content: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchMatch {
+    pub view_id: String,
+    pub view_type: i32,
+    pub name: String,
+    pub snippet: String,
+    /// The offset, in chars, of the first matched term in the content, or in the name if the
+    /// content doesn't contain any of the terms.
+    pub position: usize,
+    pub is_name_match: bool,
+}
+
+#[derive(QueryableByName)]
+struct SearchRow {
+    #[sql_type = "Text"]
+    view_id: String,
+    #[sql_type = "Integer"]
+    view_type: i32,
+    #[sql_type = "Text"]
+    name: String,
+    #[sql_type = "Text"]
+    content: String,
+    #[sql_type = "Text"]
+    snippet: String,
+}
+
+#[derive(QueryableByName)]
+struct NameRow {
+    #[sql_type = "Text"]
+    view_id: String,
+    #[sql_type = "Text"]
+    name: String,
+}
+
+/// The FTS5 index over the names and the plain text of the views. It's created by the
+/// `search-index` migration.
+pub struct SearchIndex;
+
+impl SearchIndex {
+    /// Replace the indexed text of the view.
+    pub fn upsert(record: SearchIndexRecord, conn: &SqliteConnection) -> Result<(), diesel::result::Error> {
+        conn.immediate_transaction::<_, diesel::result::Error, _>(|| {
+            let _ = Self::delete(&record.view_id, conn)?;
+            let _ =
+                diesel::sql_query("INSERT INTO search_index (view_id, view_type, name, content) VALUES (?, ?, ?, ?)")
+                    .bind::<Text, _>(&record.view_id)
+                    .bind::<Integer, _>(record.view_type)
+                    .bind::<Text, _>(&record.name)
+                    .bind::<Text, _>(&record.content)
+                    .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// Update the name of the view. Do nothing if the view isn't indexed yet.
+    pub fn update_name(view_id: &str, name: &str, conn: &SqliteConnection) -> Result<(), diesel::result::Error> {
+        let _ = diesel::sql_query("UPDATE search_index SET name = ? WHERE view_id = ?")
+            .bind::<Text, _>(name)
+            .bind::<Text, _>(view_id)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Return the ids and the names of the indexed views.
+    pub fn read_names(conn: &SqliteConnection) -> Result<Vec<(String, String)>, diesel::result::Error> {
+        let rows = diesel::sql_query("SELECT view_id, name FROM search_index").load::<NameRow>(conn)?;
+        Ok(rows.into_iter().map(|row| (row.view_id, row.name)).collect())
+    }
+
+    pub fn delete(view_id: &str, conn: &SqliteConnection) -> Result<(), diesel::result::Error> {
+        let _ = diesel::sql_query("DELETE FROM search_index WHERE view_id = ?")
+            .bind::<Text, _>(view_id)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn clear(conn: &SqliteConnection) -> Result<(), diesel::result::Error> {
+        let _ = diesel::sql_query("DELETE FROM search_index").execute(conn)?;
+        Ok(())
+    }
+
+    /// Return the views whose name or content contains all the terms of the query, the best
+    /// matches first. Each term matches the words that start with it.
+    pub fn search(query: &str, limit: i32, conn: &SqliteConnection) -> Result<Vec<SearchMatch>, diesel::result::Error> {
+        let fts_query = match fts_query(query) {
+            None => return Ok(vec![]),
+            Some(fts_query) => fts_query,
+        };
+
+        let rows = diesel::sql_query(
+            "SELECT view_id, CAST(view_type AS INTEGER) AS view_type, name, content, \
+             snippet(search_index, -1, '', '', '...', 16) AS snippet \
+             FROM search_index WHERE search_index MATCH ? ORDER BY rank LIMIT ?",
+        )
+        .bind::<Text, _>(fts_query)
+        .bind::<Integer, _>(limit)
+        .load::<SearchRow>(conn)?;
+
+        let terms = query_terms(query);
+        let matches = rows
+            .into_iter()
+            .map(|row| {
+                let (position, is_name_match) = match match_position(&row.content, &terms) {
+                    Some(position) => (position, false),
+                    None => (match_position(&row.name, &terms).unwrap_or(0), true),
+                };
+                SearchMatch {
+                    view_id: row.view_id,
+                    view_type: row.view_type,
+                    name: row.name,
+                    snippet: row.snippet,
+                    position,
+                    is_name_match,
+                }
+            })
+            .collect();
+        Ok(matches)
+    }
+}
+
+fn query_terms(query: &str) -> Vec<String> {
+    query.split_whitespace().map(|term| term.to_lowercase()).collect()
+}
+
+/// Quote each term of the query so that the FTS5 operators in the user's input are matched as
+/// plain text, e.g. `foo AND` becomes `"foo"* "AND"*`.
+fn fts_query(query: &str) -> Option<String> {
+    let terms = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect::<Vec<String>>();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Return the char offset of the first occurrence of any of the lowercased terms in the text.
+fn match_position(text: &str, terms: &[String]) -> Option<usize> {
+    let chars = text
+        .chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect::<Vec<char>>();
+    terms
+        .iter()
+        .flat_map(|term| {
+            let term = term.chars().collect::<Vec<char>>();
+            if term.is_empty() || term.len() > chars.len() {
+                return None;
+            }
+            chars.windows(term.len()).position(|window| window == term.as_slice())
+        })
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fts_query, match_position, query_terms};
+
+    #[test]
+    fn fts_query_test() {
+        assert_eq!(fts_query("  "), None);
+        assert_eq!(fts_query("hello world"), Some("\"hello\"* \"world\"*".to_owned()));
+        assert_eq!(
+            fts_query("say \"hi\" OR"),
+            Some("\"say\"* \"\"\"hi\"\"\"* \"OR\"*".to_owned())
+        );
+    }
+
+    #[test]
+    fn match_position_test() {
+        let terms = query_terms("World hé");
+        assert_eq!(match_position("Hello world", &terms), Some(6));
+        assert_eq!(match_position("Ça, Héllo world", &terms), Some(4));
+        assert_eq!(match_position("Nothing", &terms), None);
+    }
+}
@@ -1,9 +1,9 @@
 use dart_notify::DartNotifyBuilder;
 use flowy_derive::ProtoBuf_Enum;
-const OBSERVABLE_CATEGORY: &str = "Workspace";
+pub const OBSERVABLE_CATEGORY: &str = "Workspace";
 
 #[derive(ProtoBuf_Enum, Debug)]
-pub(crate) enum FolderNotification {
+pub enum FolderNotification {
     Unknown = 0,
     UserCreateWorkspace = 10,
     UserDeleteWorkspace = 11,
@@ -0,0 +1,61 @@
+use crate::{
+    entities::parser::{app::AppIdentify, view::ViewName},
+    errors::ErrorCode,
+};
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use std::convert::TryInto;
+
+#[derive(PartialEq, Debug, ProtoBuf_Enum, Clone)]
+pub enum ImportType {
+    Markdown = 0,
+    Html = 1,
+}
+
+impl std::default::Default for ImportType {
+    fn default() -> Self {
+        ImportType::Markdown
+    }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct ImportDocumentPayloadPB {
+    #[pb(index = 1)]
+    pub belong_to_id: String,
+
+    #[pb(index = 2)]
+    pub name: String,
+
+    #[pb(index = 3)]
+    pub import_type: ImportType,
+
+    #[pb(index = 4)]
+    pub data: String,
+
+    #[pb(index = 5)]
+    pub plugin_type: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportDocumentParams {
+    pub belong_to_id: String,
+    pub name: String,
+    pub import_type: ImportType,
+    pub data: String,
+    pub plugin_type: i32,
+}
+
+impl TryInto<ImportDocumentParams> for ImportDocumentPayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<ImportDocumentParams, Self::Error> {
+        let belong_to_id = AppIdentify::parse(self.belong_to_id)?.0;
+        let name = ViewName::parse(self.name)?.0;
+        Ok(ImportDocumentParams {
+            belong_to_id,
+            name,
+            import_type: self.import_type,
+            data: self.data,
+            plugin_type: self.plugin_type,
+        })
+    }
+}
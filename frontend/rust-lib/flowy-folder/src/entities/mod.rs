@@ -1,11 +1,17 @@
 pub mod app;
+pub mod import;
 mod parser;
+pub mod search;
+pub mod template;
 pub mod trash;
 pub mod view;
 mod view_info;
 pub mod workspace;
 
 pub use app::*;
+pub use import::*;
+pub use search::*;
+pub use template::*;
 pub use trash::*;
 pub use view::*;
 pub use view_info::*;
@@ -0,0 +1,70 @@
+use crate::entities::ViewDataType;
+use crate::impl_def_and_def_mut;
+use flowy_database::search::SearchMatch;
+use flowy_derive::ProtoBuf;
+
+#[derive(Default, ProtoBuf)]
+pub struct SearchPayloadPB {
+    #[pb(index = 1)]
+    pub query: String,
+
+    /// The max number of the results. The default limit is used if it's zero.
+    #[pb(index = 2)]
+    pub limit: i32,
+}
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct SearchResultPB {
+    #[pb(index = 1)]
+    pub view_id: String,
+
+    #[pb(index = 2)]
+    pub name: String,
+
+    #[pb(index = 3)]
+    pub data_type: ViewDataType,
+
+    /// The text around the matched terms.
+    #[pb(index = 4)]
+    pub snippet: String,
+
+    /// The offset, in chars, of the first matched term in the plain text of the view, or in the
+    /// name if `is_name_match` is true.
+    #[pb(index = 5)]
+    pub position: i64,
+
+    #[pb(index = 6)]
+    pub is_name_match: bool,
+}
+
+impl std::convert::From<SearchMatch> for SearchResultPB {
+    fn from(search_match: SearchMatch) -> Self {
+        let data_type = if search_match.view_type == ViewDataType::Grid as i32 {
+            ViewDataType::Grid
+        } else {
+            ViewDataType::TextBlock
+        };
+        SearchResultPB {
+            view_id: search_match.view_id,
+            name: search_match.name,
+            data_type,
+            snippet: search_match.snippet,
+            position: search_match.position as i64,
+            is_name_match: search_match.is_name_match,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Default, ProtoBuf, Clone)]
+pub struct RepeatedSearchResultPB {
+    #[pb(index = 1)]
+    pub items: Vec<SearchResultPB>,
+}
+
+impl_def_and_def_mut!(RepeatedSearchResultPB, SearchResultPB);
+impl std::convert::From<Vec<SearchMatch>> for RepeatedSearchResultPB {
+    fn from(matches: Vec<SearchMatch>) -> Self {
+        let items = matches.into_iter().map(|search_match| search_match.into()).collect();
+        RepeatedSearchResultPB { items }
+    }
+}
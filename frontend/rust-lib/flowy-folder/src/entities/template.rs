@@ -0,0 +1,108 @@
+use crate::{
+    entities::{
+        parser::{
+            app::AppIdentify,
+            view::{ViewIdentify, ViewName},
+        },
+        ViewDataType,
+    },
+    errors::ErrorCode,
+};
+use flowy_derive::ProtoBuf;
+use std::convert::TryInto;
+
+#[derive(Eq, PartialEq, ProtoBuf, Default, Debug, Clone)]
+pub struct ViewTemplatePB {
+    #[pb(index = 1)]
+    pub id: String,
+
+    #[pb(index = 2)]
+    pub name: String,
+
+    #[pb(index = 3)]
+    pub desc: String,
+
+    #[pb(index = 4)]
+    pub data_type: ViewDataType,
+
+    #[pb(index = 5)]
+    pub create_time: i64,
+}
+
+#[derive(Eq, PartialEq, Debug, Default, ProtoBuf, Clone)]
+pub struct RepeatedViewTemplatePB {
+    #[pb(index = 1)]
+    pub items: Vec<ViewTemplatePB>,
+}
+
+#[derive(Default, ProtoBuf, Clone, Debug)]
+pub struct ViewTemplateIdPB {
+    #[pb(index = 1)]
+    pub value: String,
+}
+
+impl std::convert::From<&str> for ViewTemplateIdPB {
+    fn from(value: &str) -> Self {
+        ViewTemplateIdPB {
+            value: value.to_string(),
+        }
+    }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct CreateTemplatePayloadPB {
+    #[pb(index = 1)]
+    pub view_id: String,
+
+    #[pb(index = 2)]
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateTemplateParams {
+    pub view_id: String,
+    pub name: String,
+}
+
+impl TryInto<CreateTemplateParams> for CreateTemplatePayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<CreateTemplateParams, Self::Error> {
+        let view_id = ViewIdentify::parse(self.view_id)?.0;
+        let name = ViewName::parse(self.name)?.0;
+        Ok(CreateTemplateParams { view_id, name })
+    }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct CreateViewFromTemplatePayloadPB {
+    #[pb(index = 1)]
+    pub template_id: String,
+
+    #[pb(index = 2)]
+    pub belong_to_id: String,
+
+    #[pb(index = 3)]
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateViewFromTemplateParams {
+    pub template_id: String,
+    pub belong_to_id: String,
+    pub name: String,
+}
+
+impl TryInto<CreateViewFromTemplateParams> for CreateViewFromTemplatePayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<CreateViewFromTemplateParams, Self::Error> {
+        let belong_to_id = AppIdentify::parse(self.belong_to_id)?.0;
+        let name = ViewName::parse(self.name)?.0;
+        Ok(CreateViewFromTemplateParams {
+            template_id: self.template_id,
+            belong_to_id,
+            name,
+        })
+    }
+}
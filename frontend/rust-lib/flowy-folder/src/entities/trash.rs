@@ -20,6 +20,9 @@ pub struct TrashPB {
 
     #[pb(index = 5)]
     pub ty: TrashType,
+
+    #[pb(index = 6)]
+    pub deleted_time: i64,
 }
 
 impl std::convert::From<TrashRevision> for TrashPB {
@@ -30,6 +33,7 @@ impl std::convert::From<TrashRevision> for TrashPB {
             modified_time: trash_rev.modified_time,
             create_time: trash_rev.create_time,
             ty: trash_rev.ty.into(),
+            deleted_time: trash_rev.deleted_time,
         }
     }
 }
@@ -42,6 +46,7 @@ impl std::convert::From<TrashPB> for TrashRevision {
             modified_time: trash.modified_time,
             create_time: trash.create_time,
             ty: trash.ty.into(),
+            deleted_time: trash.deleted_time,
         }
     }
 }
@@ -259,6 +259,11 @@ pub struct MoveFolderItemPayloadPB {
 
     #[pb(index = 4)]
     pub ty: MoveFolderItemType,
+
+    /// The id of the app or the view that the view is moved to. The view is moved within its
+    /// current app or view if it's None.
+    #[pb(index = 5, one_of)]
+    pub belong_to_id: Option<String>,
 }
 
 pub struct MoveFolderItemParams {
@@ -266,6 +271,7 @@ pub struct MoveFolderItemParams {
     pub from: usize,
     pub to: usize,
     pub ty: MoveFolderItemType,
+    pub belong_to_id: Option<String>,
 }
 
 impl TryInto<MoveFolderItemParams> for MoveFolderItemPayloadPB {
@@ -273,11 +279,16 @@ impl TryInto<MoveFolderItemParams> for MoveFolderItemPayloadPB {
 
     fn try_into(self) -> Result<MoveFolderItemParams, Self::Error> {
         let view_id = ViewIdentify::parse(self.item_id)?.0;
+        let belong_to_id = match self.belong_to_id {
+            None => None,
+            Some(belong_to_id) => Some(AppIdentify::parse(belong_to_id)?.0),
+        };
         Ok(MoveFolderItemParams {
             item_id: view_id,
             from: self.from as usize,
             to: self.to as usize,
             ty: self.ty,
+            belong_to_id,
         })
     }
 }
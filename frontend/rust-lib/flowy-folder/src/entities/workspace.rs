@@ -136,3 +136,38 @@ impl TryInto<UpdateWorkspaceParams> for UpdateWorkspacePayloadPB {
         })
     }
 }
+
+#[derive(ProtoBuf, Default)]
+pub struct ExportWorkspacePayloadPB {
+    #[pb(index = 1)]
+    pub workspace_id: String,
+
+    /// The path of the archive file to write.
+    #[pb(index = 2)]
+    pub path: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ExportWorkspaceParams {
+    pub workspace_id: String,
+    pub path: String,
+}
+
+impl TryInto<ExportWorkspaceParams> for ExportWorkspacePayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<ExportWorkspaceParams, Self::Error> {
+        let workspace_id = WorkspaceIdentify::parse(self.workspace_id)?.0;
+        Ok(ExportWorkspaceParams {
+            workspace_id,
+            path: self.path,
+        })
+    }
+}
+
+#[derive(ProtoBuf, Default)]
+pub struct ImportWorkspacePayloadPB {
+    /// The path of the archive file that was written by the export.
+    #[pb(index = 1)]
+    pub path: String,
+}
@@ -7,7 +7,10 @@ use crate::{
     },
     errors::FlowyError,
     manager::FolderManager,
-    services::{app::event_handler::*, trash::event_handler::*, view::event_handler::*, workspace::event_handler::*},
+    services::{
+        app::event_handler::*, search::event_handler::*, template::event_handler::*, trash::event_handler::*,
+        view::event_handler::*, workspace::event_handler::*,
+    },
 };
 use flowy_database::{ConnectionPool, DBConnection};
 use flowy_derive::{Flowy_Event, ProtoBuf_Enum};
@@ -41,6 +44,8 @@ pub fn create(folder: Arc<FolderManager>) -> Module {
         .data(folder.app_controller.clone())
         .data(folder.view_controller.clone())
         .data(folder.trash_controller.clone())
+        .data(folder.search_controller.clone())
+        .data(folder.template_controller.clone())
         .data(folder.clone());
 
     // Workspace
@@ -49,7 +54,9 @@ pub fn create(folder: Arc<FolderManager>) -> Module {
         .event(FolderEvent::ReadCurWorkspace, read_cur_workspace_handler)
         .event(FolderEvent::ReadWorkspaces, read_workspaces_handler)
         .event(FolderEvent::OpenWorkspace, open_workspace_handler)
-        .event(FolderEvent::ReadWorkspaceApps, read_workspace_apps_handler);
+        .event(FolderEvent::ReadWorkspaceApps, read_workspace_apps_handler)
+        .event(FolderEvent::ExportWorkspace, export_workspace_handler)
+        .event(FolderEvent::ImportWorkspace, import_workspace_handler);
 
     // App
     module = module
@@ -68,7 +75,8 @@ pub fn create(folder: Arc<FolderManager>) -> Module {
         .event(FolderEvent::DuplicateView, duplicate_view_handler)
         .event(FolderEvent::SetLatestView, set_latest_view_handler)
         .event(FolderEvent::CloseView, close_view_handler)
-        .event(FolderEvent::MoveFolderItem, move_item_handler);
+        .event(FolderEvent::MoveFolderItem, move_item_handler)
+        .event(FolderEvent::ImportDocument, import_document_handler);
 
     // Trash
     module = module
@@ -78,6 +86,18 @@ pub fn create(folder: Arc<FolderManager>) -> Module {
         .event(FolderEvent::RestoreAllTrash, restore_all_trash_handler)
         .event(FolderEvent::DeleteAllTrash, delete_all_trash_handler);
 
+    // Search
+    module = module
+        .event(FolderEvent::Search, search_handler)
+        .event(FolderEvent::RebuildSearchIndex, rebuild_search_index_handler);
+
+    // Template
+    module = module
+        .event(FolderEvent::CreateTemplate, create_template_handler)
+        .event(FolderEvent::ReadTemplates, read_templates_handler)
+        .event(FolderEvent::DeleteTemplate, delete_template_handler)
+        .event(FolderEvent::CreateViewFromTemplate, create_view_from_template_handler);
+
     module
 }
 
@@ -102,6 +122,12 @@ pub enum FolderEvent {
     #[event(input = "WorkspaceIdPB", output = "RepeatedAppPB")]
     ReadWorkspaceApps = 5,
 
+    #[event(input = "ExportWorkspacePayloadPB")]
+    ExportWorkspace = 6,
+
+    #[event(input = "ImportWorkspacePayloadPB", output = "WorkspacePB")]
+    ImportWorkspace = 7,
+
     #[event(input = "CreateAppPayloadPB", output = "AppPB")]
     CreateApp = 101,
 
@@ -144,6 +170,9 @@ pub enum FolderEvent {
     #[event(input = "MoveFolderItemPayloadPB")]
     MoveFolderItem = 230,
 
+    #[event(input = "ImportDocumentPayloadPB", output = "ViewPB")]
+    ImportDocument = 240,
+
     #[event(output = "RepeatedTrashPB")]
     ReadTrash = 300,
 
@@ -158,6 +187,24 @@ pub enum FolderEvent {
 
     #[event()]
     DeleteAllTrash = 304,
+
+    #[event(input = "SearchPayloadPB", output = "RepeatedSearchResultPB")]
+    Search = 400,
+
+    #[event()]
+    RebuildSearchIndex = 401,
+
+    #[event(input = "CreateTemplatePayloadPB", output = "ViewTemplatePB")]
+    CreateTemplate = 500,
+
+    #[event(output = "RepeatedViewTemplatePB")]
+    ReadTemplates = 501,
+
+    #[event(input = "ViewTemplateIdPB")]
+    DeleteTemplate = 502,
+
+    #[event(input = "CreateViewFromTemplatePayloadPB", output = "ViewPB")]
+    CreateViewFromTemplate = 503,
 }
 
 pub trait FolderCouldServiceV1: Send + Sync {
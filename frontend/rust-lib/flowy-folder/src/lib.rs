@@ -8,7 +8,7 @@ mod macros;
 #[macro_use]
 extern crate flowy_database;
 
-mod dart_notification;
+pub mod dart_notification;
 pub mod manager;
 pub mod protobuf;
 mod util;
@@ -2,16 +2,20 @@ use crate::entities::view::ViewDataType;
 use crate::services::folder_editor::FolderRevisionCompactor;
 use crate::{
     dart_notification::{send_dart_notification, FolderNotification},
-    entities::workspace::RepeatedWorkspacePB,
+    entities::workspace::{ExportWorkspaceParams, RepeatedWorkspacePB},
     errors::FlowyResult,
     event_map::{FolderCouldServiceV1, WorkspaceDatabase, WorkspaceUser},
     services::{
-        folder_editor::FolderEditor, persistence::FolderPersistence, set_current_workspace, AppController,
-        TrashController, ViewController, WorkspaceController,
+        archive::{regenerate_workspace_ids, remove_trash, workspace_views, WorkspaceArchive},
+        folder_editor::FolderEditor,
+        persistence::{FolderPersistence, FolderPersistenceTransaction},
+        set_current_workspace, spawn_purge_expired_trash, AppController, SearchController, TemplateController,
+        TrashController, TrashRetention, ViewController, WorkspaceController,
     },
 };
 use bytes::Bytes;
 use flowy_error::FlowyError;
+use flowy_folder_data_model::revision::WorkspaceRevision;
 use flowy_folder_data_model::user_default;
 use flowy_revision::disk::SQLiteTextBlockRevisionPersistence;
 use flowy_revision::{RevisionManager, RevisionPersistence, RevisionWebSocket, SQLiteRevisionSnapshotPersistence};
@@ -20,7 +24,7 @@ use flowy_sync::{client_folder::FolderPad, entities::ws_data::ServerRevisionWSDa
 use lazy_static::lazy_static;
 use lib_infra::future::FutureResult;
 use std::{collections::HashMap, convert::TryInto, fmt::Formatter, sync::Arc};
-use tokio::sync::RwLock as TokioRwLock;
+use tokio::sync::{broadcast, RwLock as TokioRwLock};
 lazy_static! {
     static ref INIT_FOLDER_FLAG: TokioRwLock<HashMap<String, bool>> = TokioRwLock::new(HashMap::new());
 }
@@ -60,6 +64,8 @@ pub struct FolderManager {
     pub(crate) app_controller: Arc<AppController>,
     pub(crate) view_controller: Arc<ViewController>,
     pub(crate) trash_controller: Arc<TrashController>,
+    pub(crate) search_controller: Arc<SearchController>,
+    pub(crate) template_controller: Arc<TemplateController>,
     web_socket: Arc<dyn RevisionWebSocket>,
     folder_editor: Arc<TokioRwLock<Option<Arc<FolderEditor>>>>,
     data_processors: ViewDataProcessorMap,
@@ -72,6 +78,7 @@ impl FolderManager {
         database: Arc<dyn WorkspaceDatabase>,
         data_processors: ViewDataProcessorMap,
         web_socket: Arc<dyn RevisionWebSocket>,
+        trash_retention: TrashRetention,
     ) -> Self {
         if let Ok(user_id) = user.user_id() {
             // Reset the flag if the folder manager gets initialized, otherwise,
@@ -86,6 +93,7 @@ impl FolderManager {
             persistence.clone(),
             cloud_service.clone(),
             user.clone(),
+            trash_retention,
         ));
 
         let view_controller = Arc::new(ViewController::new(
@@ -110,6 +118,19 @@ impl FolderManager {
             cloud_service.clone(),
         ));
 
+        let search_controller = Arc::new(SearchController::new(
+            user.clone(),
+            persistence.clone(),
+            data_processors.clone(),
+        ));
+        search_controller.listen_data_changed();
+
+        let template_controller = Arc::new(TemplateController::new(
+            persistence.clone(),
+            view_controller.clone(),
+            data_processors.clone(),
+        ));
+
         Self {
             user,
             cloud_service,
@@ -118,6 +139,8 @@ impl FolderManager {
             app_controller,
             view_controller,
             trash_controller,
+            search_controller,
+            template_controller,
             web_socket,
             folder_editor,
             data_processors,
@@ -166,18 +189,17 @@ impl FolderManager {
         let disk_cache = SQLiteTextBlockRevisionPersistence::new(user_id, pool.clone());
         let rev_persistence = RevisionPersistence::new(user_id, object_id, disk_cache);
         let rev_compactor = FolderRevisionCompactor();
-        // let history_persistence = SQLiteRevisionHistoryPersistence::new(object_id, pool.clone());
         let snapshot_persistence = SQLiteRevisionSnapshotPersistence::new(object_id, pool);
         let rev_manager = RevisionManager::new(
             user_id,
             folder_id.as_ref(),
             rev_persistence,
             rev_compactor,
-            // history_persistence,
             snapshot_persistence,
         );
 
         let folder_editor = FolderEditor::new(user_id, &folder_id, token, rev_manager, self.web_socket.clone()).await?;
+        let folder_rev_rx = folder_editor.subscribe_revisions();
         *self.folder_editor.write().await = Some(Arc::new(folder_editor));
 
         let _ = self.app_controller.initialize()?;
@@ -187,6 +209,9 @@ impl FolderManager {
             processor.initialize();
         });
 
+        let _ = self.search_controller.initialize(folder_rev_rx).await?;
+        spawn_purge_expired_trash(&self.trash_controller);
+
         write_guard.insert(user_id.to_owned(), true);
         Ok(())
     }
@@ -199,6 +224,100 @@ impl FolderManager {
     pub async fn clear(&self) {
         *self.folder_editor.write().await = None;
     }
+
+    /// Write the workspace and the data of its views into the archive file. The apps and the views
+    /// in the trash are left out.
+    #[tracing::instrument(level = "debug", skip(self, params), fields(workspace_id = %params.workspace_id), err)]
+    pub async fn export_workspace(&self, params: ExportWorkspaceParams) -> FlowyResult<()> {
+        let user_id = self.user.user_id()?;
+        let (mut workspace_rev, trash_ids) = self
+            .persistence
+            .begin_transaction(|transaction| {
+                let workspace_rev = transaction
+                    .read_workspaces(&user_id, Some(params.workspace_id.clone()))?
+                    .pop()
+                    .ok_or_else(FlowyError::record_not_found)?;
+                let trash_ids = self.trash_controller.read_trash_ids(&transaction)?;
+                Ok((workspace_rev, trash_ids))
+            })
+            .await?;
+        remove_trash(&mut workspace_rev, &trash_ids.into_iter().collect());
+
+        let mut view_data = HashMap::new();
+        for view_rev in workspace_views(&workspace_rev) {
+            let processor = self.get_data_processor(view_rev.data_type.clone())?;
+            let delta_bytes = processor.get_delta_data(&view_rev.id).await?;
+            let data = String::from_utf8(delta_bytes.to_vec()).map_err(|e| FlowyError::internal().context(e))?;
+            view_data.insert(view_rev.id.clone(), data);
+        }
+
+        let archive = WorkspaceArchive::new(workspace_rev, view_data);
+        let _ = std::fs::write(&params.path, archive.to_bytes()?)?;
+        Ok(())
+    }
+
+    /// Create a new workspace from the archive file. The workspace, its apps and views get new ids,
+    /// so the same archive can be imported more than once.
+    #[tracing::instrument(level = "debug", skip(self), err)]
+    pub async fn import_workspace(&self, path: &str) -> FlowyResult<WorkspaceRevision> {
+        let bytes = std::fs::read(path)?;
+        let WorkspaceArchive {
+            workspace: mut workspace_rev,
+            mut view_data,
+            ..
+        } = WorkspaceArchive::from_bytes(&bytes)?;
+        let view_ids = regenerate_workspace_ids(&mut workspace_rev);
+
+        let user_id = self.user.user_id()?;
+        for view_rev in workspace_views(&workspace_rev) {
+            let data_type: ViewDataType = view_rev.data_type.clone().into();
+            let processor = self.get_data_processor(data_type.clone())?;
+            match view_ids.get(&view_rev.id).and_then(|view_id| view_data.remove(view_id)) {
+                None => {
+                    let _ = processor.create_default_view(&user_id, &view_rev.id).await?;
+                }
+                Some(data) => {
+                    let delta_data = processor
+                        .create_view_from_delta_data(&user_id, &view_rev.id, data.into_bytes())
+                        .await?;
+                    let _ = self
+                        .view_controller
+                        .create_view(&view_rev.id, data_type, delta_data)
+                        .await?;
+                }
+            }
+        }
+
+        let workspaces = self
+            .persistence
+            .begin_transaction(|transaction| {
+                let _ = transaction.create_workspace(&user_id, workspace_rev.clone())?;
+                transaction.read_workspaces(&user_id, None)
+            })
+            .await?
+            .into_iter()
+            .map(|workspace_rev| workspace_rev.into())
+            .collect();
+        let token = self.user.token()?;
+        send_dart_notification(&token, FolderNotification::UserCreateWorkspace)
+            .payload(RepeatedWorkspacePB { items: workspaces })
+            .send();
+        Ok(workspace_rev)
+    }
+
+    fn get_data_processor<T: Into<ViewDataType>>(
+        &self,
+        data_type: T,
+    ) -> FlowyResult<Arc<dyn ViewDataProcessor + Send + Sync>> {
+        let data_type = data_type.into();
+        match self.data_processors.get(&data_type) {
+            None => Err(FlowyError::internal().context(format!(
+                "Get data processor failed. Unknown view data type: {:?}",
+                data_type
+            ))),
+            Some(processor) => Ok(processor.clone()),
+        }
+    }
 }
 
 struct DefaultFolderBuilder();
@@ -256,6 +375,12 @@ pub trait ViewDataProcessor {
 
     fn get_delta_data(&self, view_id: &str) -> FutureResult<Bytes, FlowyError>;
 
+    /// Return the text of the view that is indexed by the full-text search.
+    fn get_plain_text(&self, view_id: &str) -> FutureResult<String, FlowyError>;
+
+    /// Subscribe the ids of the views whose data was changed.
+    fn subscribe_data_changed(&self) -> broadcast::Receiver<String>;
+
     fn create_default_view(&self, user_id: &str, view_id: &str) -> FutureResult<Bytes, FlowyError>;
 
     fn create_view_from_delta_data(
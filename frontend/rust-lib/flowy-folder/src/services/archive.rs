@@ -0,0 +1,100 @@
+use crate::errors::{FlowyError, FlowyResult};
+use flowy_folder_data_model::revision::{gen_app_id, gen_view_id, gen_workspace_id, ViewRevision, WorkspaceRevision};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Increase it whenever the format of the archive changes. The archives written by a newer
+/// version are rejected by the import.
+pub const WORKSPACE_ARCHIVE_VERSION: i64 = 1;
+
+/// A workspace and the data of its views, stored in one JSON file.
+#[derive(Serialize, Deserialize)]
+pub struct WorkspaceArchive {
+    pub version: i64,
+
+    /// The workspace with its apps and views, in the same format as the folder stores it.
+    pub workspace: WorkspaceRevision,
+
+    /// The data of the views keyed by the view id. It's the same data that is used to duplicate
+    /// the view, e.g. the delta of a text block or the `BuildGridContext` of a grid.
+    pub view_data: HashMap<String, String>,
+}
+
+impl WorkspaceArchive {
+    pub fn new(workspace: WorkspaceRevision, view_data: HashMap<String, String>) -> Self {
+        Self {
+            version: WORKSPACE_ARCHIVE_VERSION,
+            workspace,
+            view_data,
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> FlowyResult<Self> {
+        let archive: WorkspaceArchive = serde_json::from_slice(bytes)?;
+        if archive.version > WORKSPACE_ARCHIVE_VERSION {
+            return Err(FlowyError::internal().context(format!(
+                "The archive version {} is newer than the supported version {}",
+                archive.version, WORKSPACE_ARCHIVE_VERSION
+            )));
+        }
+        Ok(archive)
+    }
+
+    pub fn to_bytes(&self) -> FlowyResult<Vec<u8>> {
+        let bytes = serde_json::to_vec(self)?;
+        Ok(bytes)
+    }
+}
+
+/// Remove the apps and the views that are in the trash, including the children of the trashed views.
+pub(crate) fn remove_trash(workspace_rev: &mut WorkspaceRevision, trash_ids: &HashSet<String>) {
+    workspace_rev.apps.retain(|app_rev| !trash_ids.contains(&app_rev.id));
+    for app_rev in workspace_rev.apps.iter_mut() {
+        remove_trash_views(&mut app_rev.belongings, trash_ids);
+    }
+}
+
+fn remove_trash_views(view_revs: &mut Vec<ViewRevision>, trash_ids: &HashSet<String>) {
+    view_revs.retain(|view_rev| !trash_ids.contains(&view_rev.id));
+    for view_rev in view_revs.iter_mut() {
+        remove_trash_views(&mut view_rev.belongings, trash_ids);
+    }
+}
+
+/// Give the workspace, its apps and views new ids, so the imported workspace doesn't conflict
+/// with the existing one. Returns the old ids of the views keyed by their new ids.
+pub(crate) fn regenerate_workspace_ids(workspace_rev: &mut WorkspaceRevision) -> HashMap<String, String> {
+    let mut view_ids = HashMap::new();
+    workspace_rev.id = gen_workspace_id();
+    for app_rev in workspace_rev.apps.iter_mut() {
+        app_rev.id = gen_app_id();
+        app_rev.workspace_id = workspace_rev.id.clone();
+        regenerate_view_ids(&mut app_rev.belongings, &app_rev.id, &mut view_ids);
+    }
+    view_ids
+}
+
+fn regenerate_view_ids(view_revs: &mut [ViewRevision], belong_to_id: &str, view_ids: &mut HashMap<String, String>) {
+    for view_rev in view_revs.iter_mut() {
+        let view_id = gen_view_id();
+        view_ids.insert(view_id.clone(), std::mem::replace(&mut view_rev.id, view_id));
+        view_rev.belong_to_id = belong_to_id.to_owned();
+        regenerate_view_ids(&mut view_rev.belongings, &view_rev.id, view_ids);
+    }
+}
+
+/// Return the views of the workspace, the parent views come before their children.
+pub(crate) fn workspace_views(workspace_rev: &WorkspaceRevision) -> Vec<&ViewRevision> {
+    let mut views = vec![];
+    let mut stack = workspace_rev
+        .apps
+        .iter()
+        .rev()
+        .flat_map(|app_rev| app_rev.belongings.iter().rev())
+        .collect::<Vec<&ViewRevision>>();
+    while let Some(view_rev) = stack.pop() {
+        views.push(view_rev);
+        stack.extend(view_rev.belongings.iter().rev());
+    }
+    views
+}
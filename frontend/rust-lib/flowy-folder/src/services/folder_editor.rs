@@ -14,6 +14,7 @@ use lib_ot::core::PhantomAttributes;
 
 use parking_lot::RwLock;
 use std::sync::Arc;
+use tokio::sync::broadcast;
 
 pub struct FolderEditor {
     user_id: String,
@@ -93,6 +94,10 @@ impl FolderEditor {
         Ok(())
     }
 
+    pub(crate) fn subscribe_revisions(&self) -> broadcast::Receiver<Revision> {
+        self.rev_manager.subscribe_revisions()
+    }
+
     #[allow(dead_code)]
     pub fn folder_json(&self) -> FlowyResult<String> {
         let json = self.folder.read().to_json()?;
@@ -1,11 +1,17 @@
 pub(crate) use app::controller::*;
+pub(crate) use search::controller::*;
+pub(crate) use template::controller::*;
 pub(crate) use trash::controller::*;
+pub use trash::retention::*;
 pub(crate) use view::controller::*;
 pub(crate) use workspace::controller::*;
 
 pub(crate) mod app;
+pub(crate) mod archive;
 pub mod folder_editor;
 pub(crate) mod persistence;
+pub(crate) mod search;
+pub(crate) mod template;
 pub(crate) mod trash;
 pub(crate) mod view;
 mod web_socket;
@@ -6,7 +6,7 @@ use crate::{
 use flowy_database::kv::KV;
 use flowy_error::{FlowyError, FlowyResult};
 
-use flowy_folder_data_model::revision::{AppRevision, ViewRevision, WorkspaceRevision};
+use flowy_folder_data_model::revision::{AppRevision, WorkspaceRevision};
 use flowy_revision::disk::SQLiteTextBlockRevisionPersistence;
 use flowy_revision::{RevisionLoader, RevisionPersistence};
 use flowy_sync::{client_folder::FolderPad, entities::revision::md5};
@@ -49,12 +49,7 @@ impl FolderMigration {
                     .collect::<Vec<_>>();
 
                 for app in apps.iter_mut() {
-                    let views = ViewTableSql::read_views(&app.id, conn)?
-                        .into_iter()
-                        .map(ViewRevision::from)
-                        .collect::<Vec<_>>();
-
-                    app.belongings = views;
+                    app.belongings = ViewTableSql::read_views_with_children(&app.id, conn)?;
                 }
 
                 workspace.apps = apps;
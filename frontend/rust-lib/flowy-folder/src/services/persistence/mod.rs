@@ -37,6 +37,7 @@ pub trait FolderPersistenceTransaction {
     fn update_view(&self, changeset: ViewChangeset) -> FlowyResult<()>;
     fn delete_view(&self, view_id: &str) -> FlowyResult<()>;
     fn move_view(&self, view_id: &str, from: usize, to: usize) -> FlowyResult<()>;
+    fn move_view_to(&self, view_id: &str, belong_to_id: &str, index: usize) -> FlowyResult<()>;
 
     fn create_trash(&self, trashes: Vec<TrashRevision>) -> FlowyResult<()>;
     fn read_trash(&self, trash_id: Option<String>) -> FlowyResult<Vec<TrashRevision>>;
@@ -115,6 +115,7 @@ impl std::convert::From<AppTable> for TrashPB {
             modified_time: table.modified_time,
             create_time: table.create_time,
             ty: TrashType::TrashApp,
+            deleted_time: 0,
         }
     }
 }
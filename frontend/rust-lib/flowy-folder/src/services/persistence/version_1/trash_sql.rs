@@ -81,6 +81,7 @@ impl std::convert::From<TrashTable> for TrashRevision {
             modified_time: trash.modified_time,
             create_time: trash.create_time,
             ty: trash.ty.into(),
+            deleted_time: 0,
         }
     }
 }
@@ -93,6 +93,11 @@ impl<'a> FolderPersistenceTransaction for V1Transaction<'a> {
         Ok(())
     }
 
+    fn move_view_to(&self, view_id: &str, belong_to_id: &str, _index: usize) -> FlowyResult<()> {
+        let _ = ViewTableSql::update_belong_to_id(view_id, belong_to_id, &*self.0)?;
+        Ok(())
+    }
+
     fn create_trash(&self, trashes: Vec<TrashRevision>) -> FlowyResult<()> {
         let _ = TrashTableSql::create_trash(trashes, &*self.0)?;
         Ok(())
@@ -190,6 +195,10 @@ where
         Ok(())
     }
 
+    fn move_view_to(&self, view_id: &str, belong_to_id: &str, index: usize) -> FlowyResult<()> {
+        (**self).move_view_to(view_id, belong_to_id, index)
+    }
+
     fn create_trash(&self, trashes: Vec<TrashRevision>) -> FlowyResult<()> {
         (**self).create_trash(trashes)
     }
@@ -56,12 +56,43 @@ impl ViewTableSql {
         Ok(view_tables)
     }
 
+    /// Read the views that belong to the app or the view, with their child views in the
+    /// `belongings`.
+    pub(crate) fn read_views_with_children(
+        belong_to_id: &str,
+        conn: &SqliteConnection,
+    ) -> Result<Vec<ViewRevision>, FlowyError> {
+        let mut view_revs = Self::read_views(belong_to_id, conn)?
+            .into_iter()
+            .map(ViewRevision::from)
+            .collect::<Vec<_>>();
+        for view_rev in view_revs.iter_mut() {
+            view_rev.belongings = Self::read_views_with_children(&view_rev.id, conn)?;
+        }
+        Ok(view_revs)
+    }
+
     pub(crate) fn update_view(changeset: ViewChangeset, conn: &SqliteConnection) -> Result<(), FlowyError> {
         diesel_update_table!(view_table, changeset, conn);
         Ok(())
     }
 
+    pub(crate) fn update_belong_to_id(
+        view_id: &str,
+        belong_to_id: &str,
+        conn: &SqliteConnection,
+    ) -> Result<(), FlowyError> {
+        let _ = diesel::update(dsl::view_table.filter(view_table::id.eq(view_id)))
+            .set(view_table::belong_to_id.eq(belong_to_id))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Delete the view with all its child views.
     pub(crate) fn delete_view(view_id: &str, conn: &SqliteConnection) -> Result<(), FlowyError> {
+        for child_view in Self::read_views(view_id, conn)? {
+            let _ = Self::delete_view(&child_view.id, conn)?;
+        }
         diesel_delete_table!(view_table, view_id, conn);
         Ok(())
     }
@@ -141,6 +172,7 @@ impl std::convert::From<ViewTable> for TrashPB {
             modified_time: table.modified_time,
             create_time: table.create_time,
             ty: TrashType::TrashView,
+            deleted_time: 0,
         }
     }
 }
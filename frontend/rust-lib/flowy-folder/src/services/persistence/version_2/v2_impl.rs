@@ -127,6 +127,13 @@ impl FolderPersistenceTransaction for FolderEditor {
         Ok(())
     }
 
+    fn move_view_to(&self, view_id: &str, belong_to_id: &str, index: usize) -> FlowyResult<()> {
+        if let Some(change) = self.folder.write().move_view_to(view_id, belong_to_id, index)? {
+            let _ = self.apply_change(change)?;
+        }
+        Ok(())
+    }
+
     fn create_trash(&self, trashes: Vec<TrashRevision>) -> FlowyResult<()> {
         if let Some(change) = self.folder.write().create_trash(trashes)? {
             let _ = self.apply_change(change)?;
@@ -215,6 +222,10 @@ where
         (**self).move_view(view_id, from, to)
     }
 
+    fn move_view_to(&self, view_id: &str, belong_to_id: &str, index: usize) -> FlowyResult<()> {
+        (**self).move_view_to(view_id, belong_to_id, index)
+    }
+
     fn create_trash(&self, trashes: Vec<TrashRevision>) -> FlowyResult<()> {
         (**self).create_trash(trashes)
     }
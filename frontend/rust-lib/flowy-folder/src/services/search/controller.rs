@@ -0,0 +1,238 @@
+use crate::{
+    entities::{RepeatedSearchResultPB, ViewDataType},
+    errors::{FlowyError, FlowyResult},
+    event_map::WorkspaceUser,
+    manager::ViewDataProcessorMap,
+    services::persistence::FolderPersistence,
+};
+use flowy_database::{
+    search::{SearchIndex, SearchIndexRecord},
+    DBConnection,
+};
+use flowy_folder_data_model::revision::ViewRevision;
+use flowy_sync::entities::revision::Revision;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::RwLock;
+
+const DEFAULT_SEARCH_LIMIT: i32 = 20;
+
+/// Keeps the full-text search index of the views up to date. The names of the views are updated
+/// whenever the folder is changed, and the content of the view is indexed again whenever its data
+/// is changed.
+#[derive(Clone)]
+pub struct SearchController {
+    user: Arc<dyn WorkspaceUser>,
+    persistence: Arc<FolderPersistence>,
+    data_processors: ViewDataProcessorMap,
+    /// The names of the indexed views, keyed by the view id.
+    indexed_views: Arc<RwLock<HashMap<String, String>>>,
+}
+
+impl SearchController {
+    pub(crate) fn new(
+        user: Arc<dyn WorkspaceUser>,
+        persistence: Arc<FolderPersistence>,
+        data_processors: ViewDataProcessorMap,
+    ) -> Self {
+        Self {
+            user,
+            persistence,
+            data_processors,
+            indexed_views: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Index the views that were created since the last time the folder was opened, and keep the
+    /// index up to date with the revisions of the folder.
+    pub(crate) async fn initialize(&self, folder_rev_rx: broadcast::Receiver<Revision>) -> FlowyResult<()> {
+        let conn = self.db_connection()?;
+        let indexed_views = SearchIndex::read_names(&conn)?;
+        *self.indexed_views.write().await = indexed_views.into_iter().collect();
+        self.listen_folder_revisions(folder_rev_rx);
+        Ok(())
+    }
+
+    pub(crate) async fn search(&self, query: &str, limit: i32) -> FlowyResult<RepeatedSearchResultPB> {
+        let limit = if limit > 0 { limit } else { DEFAULT_SEARCH_LIMIT };
+        let conn = self.db_connection()?;
+        let matches = SearchIndex::search(query, limit, &conn)?;
+        Ok(matches.into())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn rebuild(&self) -> FlowyResult<()> {
+        let mut indexed_views = self.indexed_views.write().await;
+        let conn = self.db_connection()?;
+        let _ = SearchIndex::clear(&conn)?;
+        indexed_views.clear();
+        drop(conn);
+
+        let view_revs = self.read_live_views().await?;
+        for view_rev in view_revs.iter() {
+            let _ = self.index_view(view_rev).await?;
+            indexed_views.insert(view_rev.id.clone(), view_rev.name.clone());
+        }
+        Ok(())
+    }
+
+    /// Listen to the data of the views, e.g. the text of the document or the cells of the grid.
+    pub(crate) fn listen_data_changed(&self) {
+        for processor in self.data_processors.values() {
+            let mut rx = processor.subscribe_data_changed();
+            let controller = self.clone();
+            tokio::spawn(async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(view_id) => controller.did_change_view_data(&view_id).await,
+                        Err(RecvError::Lagged(count)) => {
+                            tracing::warn!("Skip {} changes of the view data", count);
+                        }
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+            });
+        }
+    }
+
+    fn listen_folder_revisions(&self, mut rx: broadcast::Receiver<Revision>) {
+        let controller = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = controller.sync_views().await {
+                tracing::error!("Sync the search index failed: {:?}", e);
+            }
+
+            loop {
+                match rx.recv().await {
+                    Ok(_) | Err(RecvError::Lagged(_)) => {
+                        if let Err(e) = controller.sync_views().await {
+                            tracing::error!("Sync the search index failed: {:?}", e);
+                        }
+                    }
+                    // The folder editor was closed.
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    async fn did_change_view_data(&self, view_id: &str) {
+        // The view that is not indexed yet will be indexed after the folder was changed.
+        if !self.indexed_views.read().await.contains_key(view_id) {
+            return;
+        }
+
+        let result = match self
+            .persistence
+            .begin_transaction(|transaction| transaction.read_view(view_id))
+            .await
+        {
+            Ok(view_rev) => self.index_view(&view_rev).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::error!("Index the view {} failed: {:?}", view_id, e);
+        }
+    }
+
+    /// Index the new views, update the names of the renamed views and remove the views that were
+    /// deleted or moved to the trash.
+    async fn sync_views(&self) -> FlowyResult<()> {
+        let view_revs = self.read_live_views().await?;
+        let mut indexed_views = self.indexed_views.write().await;
+
+        let view_ids = view_revs
+            .iter()
+            .map(|view_rev| view_rev.id.as_str())
+            .collect::<HashSet<&str>>();
+        let removed_view_ids = indexed_views
+            .keys()
+            .filter(|view_id| !view_ids.contains(view_id.as_str()))
+            .cloned()
+            .collect::<Vec<String>>();
+        for view_id in removed_view_ids {
+            let _ = SearchIndex::delete(&view_id, &*self.db_connection()?)?;
+            indexed_views.remove(&view_id);
+        }
+
+        for view_rev in view_revs.iter() {
+            match indexed_views.get(&view_rev.id) {
+                None => {
+                    let _ = self.index_view(view_rev).await?;
+                }
+                Some(name) if name != &view_rev.name => {
+                    let _ = SearchIndex::update_name(&view_rev.id, &view_rev.name, &*self.db_connection()?)?;
+                }
+                Some(_) => continue,
+            }
+            indexed_views.insert(view_rev.id.clone(), view_rev.name.clone());
+        }
+        Ok(())
+    }
+
+    async fn index_view(&self, view_rev: &ViewRevision) -> FlowyResult<()> {
+        let data_type: ViewDataType = view_rev.data_type.clone().into();
+        let content = match self.data_processors.get(&data_type) {
+            None => "".to_owned(),
+            Some(processor) => match processor.get_plain_text(&view_rev.id).await {
+                Ok(content) => content,
+                Err(e) => {
+                    // The name of the view is still searchable.
+                    tracing::error!("Read the text of the view {} failed: {:?}", view_rev.id, e);
+                    "".to_owned()
+                }
+            },
+        };
+
+        let record = SearchIndexRecord {
+            view_id: view_rev.id.clone(),
+            view_type: data_type as i32,
+            name: view_rev.name.clone(),
+            content,
+        };
+        let _ = SearchIndex::upsert(record, &*self.db_connection()?)?;
+        Ok(())
+    }
+
+    /// Return all the views of the workspaces except the ones in the trash.
+    async fn read_live_views(&self) -> FlowyResult<Vec<ViewRevision>> {
+        let user_id = self.user.user_id()?;
+        self.persistence
+            .begin_transaction(|transaction| {
+                let trash_ids = transaction
+                    .read_trash(None)?
+                    .into_iter()
+                    .map(|trash_rev| trash_rev.id)
+                    .collect::<HashSet<String>>();
+
+                let mut view_revs = vec![];
+                for workspace_rev in transaction.read_workspaces(&user_id, None)? {
+                    for app_rev in workspace_rev.apps {
+                        if !trash_ids.contains(&app_rev.id) {
+                            collect_live_views(app_rev.belongings, &trash_ids, &mut view_revs);
+                        }
+                    }
+                }
+                Ok(view_revs)
+            })
+            .await
+    }
+
+    fn db_connection(&self) -> FlowyResult<DBConnection> {
+        let pool = self.persistence.db_pool()?;
+        let conn = pool.get().map_err(|e| FlowyError::internal().context(e))?;
+        Ok(conn)
+    }
+}
+
+fn collect_live_views(view_revs: Vec<ViewRevision>, trash_ids: &HashSet<String>, output: &mut Vec<ViewRevision>) {
+    for mut view_rev in view_revs {
+        if trash_ids.contains(&view_rev.id) {
+            continue;
+        }
+        let belongings = std::mem::take(&mut view_rev.belongings);
+        output.push(view_rev);
+        collect_live_views(belongings, trash_ids, output);
+    }
+}
@@ -0,0 +1,22 @@
+use crate::{
+    entities::search::{RepeatedSearchResultPB, SearchPayloadPB},
+    errors::FlowyError,
+    services::SearchController,
+};
+use lib_dispatch::prelude::{data_result, AppData, Data, DataResult};
+use std::sync::Arc;
+
+pub(crate) async fn search_handler(
+    data: Data<SearchPayloadPB>,
+    controller: AppData<Arc<SearchController>>,
+) -> DataResult<RepeatedSearchResultPB, FlowyError> {
+    let payload: SearchPayloadPB = data.into_inner();
+    let results = controller.search(&payload.query, payload.limit).await?;
+    data_result(results)
+}
+
+#[tracing::instrument(level = "debug", skip(controller), err)]
+pub(crate) async fn rebuild_search_index_handler(controller: AppData<Arc<SearchController>>) -> Result<(), FlowyError> {
+    let _ = controller.rebuild().await?;
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use crate::{
+    entities::{
+        view::{CreateViewParams, ViewDataType},
+        CreateTemplateParams, CreateViewFromTemplateParams, RepeatedViewTemplatePB, ViewTemplatePB,
+    },
+    errors::{FlowyError, FlowyResult},
+    manager::{ViewDataProcessor, ViewDataProcessorMap},
+    services::{
+        persistence::{FolderPersistence, FolderPersistenceTransaction},
+        template::persistence::{ViewTemplateTable, ViewTemplateTableSql},
+        ViewController,
+    },
+};
+use flowy_database::DBConnection;
+use flowy_folder_data_model::revision::{gen_view_id, ViewRevision};
+use lib_infra::util::timestamp;
+use nanoid::nanoid;
+use std::sync::Arc;
+
+/// Saves the views as templates and creates new views from them. A template keeps a copy of the
+/// view's data, so it isn't affected by the later changes of the view.
+pub(crate) struct TemplateController {
+    persistence: Arc<FolderPersistence>,
+    view_controller: Arc<ViewController>,
+    data_processors: ViewDataProcessorMap,
+}
+
+impl TemplateController {
+    pub(crate) fn new(
+        persistence: Arc<FolderPersistence>,
+        view_controller: Arc<ViewController>,
+        data_processors: ViewDataProcessorMap,
+    ) -> Self {
+        Self {
+            persistence,
+            view_controller,
+            data_processors,
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, params), fields(view_id = %params.view_id), err)]
+    pub(crate) async fn create_template(&self, params: CreateTemplateParams) -> FlowyResult<ViewTemplatePB> {
+        let view_rev = self
+            .persistence
+            .begin_transaction(|transaction| transaction.read_view(&params.view_id))
+            .await?;
+        let data_type: ViewDataType = view_rev.data_type.clone().into();
+        let processor = self.get_data_processor(&data_type)?;
+        let delta_data = processor.get_delta_data(&view_rev.id).await?;
+        let table = ViewTemplateTable {
+            id: nanoid!(10),
+            name: params.name,
+            desc: view_rev.desc,
+            view_type: data_type as i32,
+            plugin_type: view_rev.plugin_type,
+            data: delta_data.to_vec(),
+            create_time: timestamp(),
+        };
+
+        let conn = self.db_connection()?;
+        let _ = ViewTemplateTableSql::create_template(table.clone(), &*conn)?;
+        Ok(table.into())
+    }
+
+    pub(crate) fn read_templates(&self) -> FlowyResult<RepeatedViewTemplatePB> {
+        let conn = self.db_connection()?;
+        let items = ViewTemplateTableSql::read_templates(&*conn)?
+            .into_iter()
+            .map(|table| table.into())
+            .collect::<Vec<ViewTemplatePB>>();
+        Ok(RepeatedViewTemplatePB { items })
+    }
+
+    pub(crate) fn delete_template(&self, template_id: &str) -> FlowyResult<()> {
+        let conn = self.db_connection()?;
+        ViewTemplateTableSql::delete_template(template_id, &*conn)
+    }
+
+    /// Create the view with a copy of the template's data. The processor of the view's data type
+    /// gives the copied objects fresh ids, e.g. the blocks and the rows of a grid, so the same
+    /// template can be used many times.
+    #[tracing::instrument(level = "debug", skip(self, params), fields(template_id = %params.template_id), err)]
+    pub(crate) async fn create_view_from_template(
+        &self,
+        params: CreateViewFromTemplateParams,
+    ) -> FlowyResult<ViewRevision> {
+        let table = {
+            let conn = self.db_connection()?;
+            ViewTemplateTableSql::read_template(&params.template_id, &*conn)?
+        };
+        let params = CreateViewParams {
+            belong_to_id: params.belong_to_id,
+            name: params.name,
+            desc: table.desc.clone(),
+            thumbnail: "".to_owned(),
+            data_type: table.data_type(),
+            view_id: gen_view_id(),
+            data: table.data,
+            plugin_type: table.plugin_type,
+        };
+        self.view_controller.create_view_from_params(params).await
+    }
+
+    fn get_data_processor(&self, data_type: &ViewDataType) -> FlowyResult<Arc<dyn ViewDataProcessor + Send + Sync>> {
+        match self.data_processors.get(data_type) {
+            None => Err(FlowyError::internal().context(format!(
+                "Get data processor failed. Unknown view data type: {:?}",
+                data_type
+            ))),
+            Some(processor) => Ok(processor.clone()),
+        }
+    }
+
+    fn db_connection(&self) -> FlowyResult<DBConnection> {
+        let pool = self.persistence.db_pool()?;
+        let conn = pool.get().map_err(|e| FlowyError::internal().context(e))?;
+        Ok(conn)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    entities::{
+        view::ViewPB, CreateTemplateParams, CreateTemplatePayloadPB, CreateViewFromTemplateParams,
+        CreateViewFromTemplatePayloadPB, RepeatedViewTemplatePB, ViewTemplateIdPB, ViewTemplatePB,
+    },
+    errors::FlowyError,
+    services::TemplateController,
+};
+use lib_dispatch::prelude::{data_result, AppData, Data, DataResult};
+use std::{convert::TryInto, sync::Arc};
+
+#[tracing::instrument(level = "debug", skip(data, controller), err)]
+pub(crate) async fn create_template_handler(
+    data: Data<CreateTemplatePayloadPB>,
+    controller: AppData<Arc<TemplateController>>,
+) -> DataResult<ViewTemplatePB, FlowyError> {
+    let params: CreateTemplateParams = data.into_inner().try_into()?;
+    let template = controller.create_template(params).await?;
+    data_result(template)
+}
+
+pub(crate) async fn read_templates_handler(
+    controller: AppData<Arc<TemplateController>>,
+) -> DataResult<RepeatedViewTemplatePB, FlowyError> {
+    let templates = controller.read_templates()?;
+    data_result(templates)
+}
+
+#[tracing::instrument(level = "debug", skip(data, controller), err)]
+pub(crate) async fn delete_template_handler(
+    data: Data<ViewTemplateIdPB>,
+    controller: AppData<Arc<TemplateController>>,
+) -> Result<(), FlowyError> {
+    let template_id: ViewTemplateIdPB = data.into_inner();
+    let _ = controller.delete_template(&template_id.value)?;
+    Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, controller), err)]
+pub(crate) async fn create_view_from_template_handler(
+    data: Data<CreateViewFromTemplatePayloadPB>,
+    controller: AppData<Arc<TemplateController>>,
+) -> DataResult<ViewPB, FlowyError> {
+    let params: CreateViewFromTemplateParams = data.into_inner().try_into()?;
+    let view_rev = controller.create_view_from_template(params).await?;
+    data_result(view_rev.into())
+}
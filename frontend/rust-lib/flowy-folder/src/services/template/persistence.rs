@@ -0,0 +1,75 @@
+use crate::entities::{ViewDataType, ViewTemplatePB};
+use crate::errors::{FlowyError, FlowyResult};
+use diesel::OptionalExtension;
+use flowy_database::{
+    prelude::*,
+    schema::{view_template_table, view_template_table::dsl},
+    SqliteConnection,
+};
+
+pub(crate) struct ViewTemplateTableSql();
+impl ViewTemplateTableSql {
+    pub(crate) fn create_template(table: ViewTemplateTable, conn: &SqliteConnection) -> FlowyResult<()> {
+        let _ = diesel::insert_into(view_template_table::table)
+            .values(table)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub(crate) fn read_template(template_id: &str, conn: &SqliteConnection) -> FlowyResult<ViewTemplateTable> {
+        let table = dsl::view_template_table
+            .filter(view_template_table::id.eq(template_id))
+            .first::<ViewTemplateTable>(conn)
+            .optional()?;
+        table.ok_or_else(|| FlowyError::record_not_found().context(format!("Can't find the template {}", template_id)))
+    }
+
+    /// Read all the templates, the latest created one comes first.
+    pub(crate) fn read_templates(conn: &SqliteConnection) -> FlowyResult<Vec<ViewTemplateTable>> {
+        let tables = dsl::view_template_table
+            .order(view_template_table::create_time.desc())
+            .load::<ViewTemplateTable>(conn)?;
+        Ok(tables)
+    }
+
+    pub(crate) fn delete_template(template_id: &str, conn: &SqliteConnection) -> FlowyResult<()> {
+        diesel_delete_table!(view_template_table, template_id, conn);
+        Ok(())
+    }
+}
+
+/// The copy of the view's data that new views are created from. For a grid, it is the serialized
+/// `BuildGridContext`, so the rows of the grid are stored in the template too.
+#[derive(PartialEq, Clone, Debug, Queryable, Identifiable, Insertable)]
+#[table_name = "view_template_table"]
+pub(crate) struct ViewTemplateTable {
+    pub id: String,
+    pub name: String,
+    pub desc: String,
+    pub view_type: i32,
+    pub plugin_type: i32,
+    pub data: Vec<u8>,
+    pub create_time: i64,
+}
+
+impl ViewTemplateTable {
+    pub(crate) fn data_type(&self) -> ViewDataType {
+        if self.view_type == ViewDataType::Grid as i32 {
+            ViewDataType::Grid
+        } else {
+            ViewDataType::TextBlock
+        }
+    }
+}
+
+impl std::convert::From<ViewTemplateTable> for ViewTemplatePB {
+    fn from(table: ViewTemplateTable) -> Self {
+        ViewTemplatePB {
+            data_type: table.data_type(),
+            id: table.id,
+            name: table.name,
+            desc: table.desc,
+            create_time: table.create_time,
+        }
+    }
+}
@@ -4,17 +4,22 @@ use crate::{
     errors::{FlowyError, FlowyResult},
     event_map::{FolderCouldServiceV1, WorkspaceUser},
     services::persistence::{FolderPersistence, FolderPersistenceTransaction},
+    services::TrashRetention,
 };
 
 use flowy_folder_data_model::revision::TrashRevision;
+use std::sync::Weak;
 use std::{fmt::Formatter, sync::Arc};
 use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
 
 pub struct TrashController {
     persistence: Arc<FolderPersistence>,
     notify: broadcast::Sender<TrashEvent>,
     cloud_service: Arc<dyn FolderCouldServiceV1>,
     user: Arc<dyn WorkspaceUser>,
+    retention: TrashRetention,
+    purge_task: parking_lot::Mutex<Option<JoinHandle<()>>>,
 }
 
 impl TrashController {
@@ -22,6 +27,7 @@ impl TrashController {
         persistence: Arc<FolderPersistence>,
         cloud_service: Arc<dyn FolderCouldServiceV1>,
         user: Arc<dyn WorkspaceUser>,
+        retention: TrashRetention,
     ) -> Self {
         let (tx, _) = broadcast::channel(10);
         Self {
@@ -29,6 +35,8 @@ impl TrashController {
             notify: tx,
             cloud_service,
             user,
+            retention,
+            purge_task: parking_lot::Mutex::new(None),
         }
     }
 
@@ -152,7 +160,15 @@ impl TrashController {
     #[tracing::instrument(name = "add_trash", level = "debug", skip(self, trash), fields(trash_ids), err)]
     pub async fn add<T: Into<TrashRevision>>(&self, trash: Vec<T>) -> Result<(), FlowyError> {
         let (tx, mut rx) = mpsc::channel::<FlowyResult<()>>(1);
-        let trash_revs: Vec<TrashRevision> = trash.into_iter().map(|t| t.into()).collect();
+        let deleted_time = self.retention.clock.now();
+        let trash_revs: Vec<TrashRevision> = trash
+            .into_iter()
+            .map(|t| {
+                let mut trash_rev: TrashRevision = t.into();
+                trash_rev.deleted_time = deleted_time;
+                trash_rev
+            })
+            .collect();
         let identifiers = trash_revs.iter().map(|t| t.into()).collect::<Vec<TrashIdPB>>();
 
         tracing::Span::current().record(
@@ -183,6 +199,46 @@ impl TrashController {
         Ok(())
     }
 
+    /// Delete the trash that has been kept longer than the retention period, including the data
+    /// of the deleted views.
+    #[tracing::instrument(level = "debug", skip(self), err)]
+    pub async fn purge_expired_trash(&self) -> FlowyResult<()> {
+        let now = self.retention.clock.now();
+        let expired_trash = self
+            .persistence
+            .begin_transaction(|transaction| {
+                let trash_revs = transaction.read_trash(None)?;
+                // The trash created before the deleted time was recorded starts its retention now.
+                let (legacy_trash, trash_revs): (Vec<TrashRevision>, Vec<TrashRevision>) = trash_revs
+                    .into_iter()
+                    .partition(|trash_rev| trash_rev.deleted_time == 0);
+                if !legacy_trash.is_empty() {
+                    let ids = legacy_trash.iter().map(|trash_rev| trash_rev.id.clone()).collect();
+                    let _ = transaction.delete_trash(Some(ids))?;
+                    let legacy_trash = legacy_trash
+                        .into_iter()
+                        .map(|mut trash_rev| {
+                            trash_rev.deleted_time = now;
+                            trash_rev
+                        })
+                        .collect();
+                    let _ = transaction.create_trash(legacy_trash)?;
+                }
+
+                Ok(trash_revs
+                    .into_iter()
+                    .filter(|trash_rev| self.retention.is_expired(trash_rev.deleted_time, now))
+                    .collect::<Vec<TrashRevision>>())
+            })
+            .await?;
+
+        if expired_trash.is_empty() {
+            return Ok(());
+        }
+        tracing::debug!("Purge {} expired trash", expired_trash.len());
+        self.delete(expired_trash.into()).await
+    }
+
     pub fn subscribe(&self) -> broadcast::Receiver<TrashEvent> {
         self.notify.subscribe()
     }
@@ -281,6 +337,30 @@ impl TrashController {
     }
 }
 
+/// Purge the expired trash right away, then every `purge_interval` until the controller is dropped.
+/// The previous purging task is stopped, e.g. the folder was initialized for another user.
+pub(crate) fn spawn_purge_expired_trash(controller: &Arc<TrashController>) {
+    let weak_controller: Weak<TrashController> = Arc::downgrade(controller);
+    let purge_interval = controller.retention.purge_interval;
+    let handle = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(purge_interval);
+        loop {
+            interval.tick().await;
+            let controller = match weak_controller.upgrade() {
+                None => break,
+                Some(controller) => controller,
+            };
+            if let Err(e) = controller.purge_expired_trash().await {
+                tracing::error!("Purge expired trash failed: {:?}", e);
+            }
+        }
+    });
+
+    if let Some(old_handle) = controller.purge_task.lock().replace(handle) {
+        old_handle.abort();
+    }
+}
+
 #[tracing::instrument(level = "debug", skip(repeated_trash), fields(n_trash))]
 fn notify_trash_changed<T: Into<RepeatedTrashPB>>(repeated_trash: T) {
     let repeated_trash = repeated_trash.into();
@@ -0,0 +1,41 @@
+use lib_infra::util::timestamp;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Returns the current time in seconds. The trash uses it to record when the items were deleted
+/// and to find the expired ones, so tests can replace it to move the time forward.
+pub trait TrashClock: Send + Sync {
+    fn now(&self) -> i64;
+}
+
+pub struct SystemTrashClock();
+impl TrashClock for SystemTrashClock {
+    fn now(&self) -> i64 {
+        timestamp()
+    }
+}
+
+#[derive(Clone)]
+pub struct TrashRetention {
+    /// The trash that was deleted longer than the period ago gets purged.
+    pub period: Duration,
+    /// How often the expired trash is looked for after the startup.
+    pub purge_interval: Duration,
+    pub clock: Arc<dyn TrashClock>,
+}
+
+impl TrashRetention {
+    pub fn is_expired(&self, deleted_time: i64, now: i64) -> bool {
+        now - deleted_time >= self.period.as_secs() as i64
+    }
+}
+
+impl std::default::Default for TrashRetention {
+    fn default() -> Self {
+        Self {
+            period: Duration::from_secs(30 * 24 * 60 * 60),
+            purge_interval: Duration::from_secs(60 * 60),
+            clock: Arc::new(SystemTrashClock()),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 pub use crate::entities::view::ViewDataType;
-use crate::entities::ViewInfoPB;
+use crate::entities::{ImportDocumentParams, ImportType, ViewInfoPB};
 use crate::manager::{ViewDataProcessor, ViewDataProcessorMap};
 use crate::{
     dart_notification::{send_dart_notification, FolderNotification},
@@ -18,6 +18,7 @@ use bytes::Bytes;
 use flowy_database::kv::KV;
 use flowy_folder_data_model::revision::{gen_view_id, ViewRevision};
 use flowy_sync::entities::text_block::TextBlockIdPB;
+use flowy_text_block::format::{html_to_delta, markdown_to_delta};
 use futures::{FutureExt, StreamExt};
 use std::{collections::HashSet, sync::Arc};
 
@@ -77,6 +78,26 @@ impl ViewController {
         Ok(view_rev)
     }
 
+    /// Create the text block from the content of the imported document.
+    #[tracing::instrument(level = "debug", skip(self, params), err)]
+    pub(crate) async fn import_document(&self, params: ImportDocumentParams) -> Result<ViewRevision, FlowyError> {
+        let delta = match params.import_type {
+            ImportType::Markdown => markdown_to_delta(&params.data),
+            ImportType::Html => html_to_delta(&params.data),
+        };
+        let params = CreateViewParams {
+            belong_to_id: params.belong_to_id,
+            name: params.name,
+            desc: "".to_owned(),
+            thumbnail: "".to_owned(),
+            data_type: ViewDataType::TextBlock,
+            view_id: gen_view_id(),
+            data: delta.to_json_bytes().to_vec(),
+            plugin_type: params.plugin_type,
+        };
+        self.create_view_from_params(params).await
+    }
+
     #[tracing::instrument(level = "debug", skip(self, view_id, delta_data), err)]
     pub(crate) async fn create_view(
         &self,
@@ -112,7 +133,7 @@ impl ViewController {
             .begin_transaction(|transaction| {
                 let view = transaction.read_view(&view_id.value)?;
                 let trash_ids = self.trash_controller.read_trash_ids(&transaction)?;
-                if trash_ids.contains(&view.id) {
+                if is_view_in_trash(&view, &trash_ids, &transaction) {
                     return Err(FlowyError::record_not_found());
                 }
                 Ok(view)
@@ -128,10 +149,12 @@ impl ViewController {
             .persistence
             .begin_transaction(|transaction| {
                 let view_rev = transaction.read_view(&view_id.value)?;
+                let trash_ids = self.trash_controller.read_trash_ids(&transaction)?;
 
                 let items: Vec<ViewPB> = view_rev
                     .belongings
                     .into_iter()
+                    .filter(|view_rev| !trash_ids.contains(&view_rev.id))
                     .map(|view_rev| view_rev.into())
                     .collect();
 
@@ -202,27 +225,60 @@ impl ViewController {
         Ok(())
     }
 
+    /// Move the view with its child views under another app or view.
+    #[tracing::instrument(level = "debug", skip(self), err)]
+    pub(crate) async fn move_view_to(&self, view_id: &str, belong_to_id: &str, index: usize) -> Result<(), FlowyError> {
+        let _ = self
+            .persistence
+            .begin_transaction(|transaction| {
+                let view = transaction.read_view(view_id)?;
+                let _ = transaction.move_view_to(view_id, belong_to_id, index)?;
+                let _ = notify_views_changed(&view.belong_to_id, self.trash_controller.clone(), &transaction)?;
+                let _ = notify_views_changed(belong_to_id, self.trash_controller.clone(), &transaction)?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Duplicate the view with its child views. The child views in the trash are not duplicated.
     #[tracing::instrument(level = "debug", skip(self), err)]
     pub(crate) async fn duplicate_view(&self, view_id: &str) -> Result<(), FlowyError> {
-        let view_rev = self
+        let (view_rev, trash_ids) = self
             .persistence
-            .begin_transaction(|transaction| transaction.read_view(view_id))
+            .begin_transaction(|transaction| {
+                let view_rev = transaction.read_view(view_id)?;
+                let trash_ids = self.trash_controller.read_trash_ids(&transaction)?;
+                Ok((view_rev, trash_ids))
+            })
             .await?;
 
-        let processor = self.get_data_processor(view_rev.data_type.clone())?;
-        let delta_bytes = processor.get_delta_data(view_id).await?;
-        let duplicate_params = CreateViewParams {
-            belong_to_id: view_rev.belong_to_id.clone(),
-            name: format!("{} (copy)", &view_rev.name),
-            desc: view_rev.desc,
-            thumbnail: view_rev.thumbnail,
-            data_type: view_rev.data_type.into(),
-            data: delta_bytes.to_vec(),
-            view_id: gen_view_id(),
-            plugin_type: view_rev.plugin_type,
-        };
+        let name = format!("{} (copy)", &view_rev.name);
+        let belong_to_id = view_rev.belong_to_id.clone();
+        let mut stack = vec![(view_rev, belong_to_id, name)];
+        while let Some((view_rev, belong_to_id, name)) = stack.pop() {
+            let processor = self.get_data_processor(view_rev.data_type.clone())?;
+            let delta_bytes = processor.get_delta_data(&view_rev.id).await?;
+            let duplicate_params = CreateViewParams {
+                belong_to_id,
+                name,
+                desc: view_rev.desc,
+                thumbnail: view_rev.thumbnail,
+                data_type: view_rev.data_type.into(),
+                data: delta_bytes.to_vec(),
+                view_id: gen_view_id(),
+                plugin_type: view_rev.plugin_type,
+            };
 
-        let _ = self.create_view_from_params(duplicate_params).await?;
+            let duplicated_view_rev = self.create_view_from_params(duplicate_params).await?;
+            // Push the child views in reverse order, so they are duplicated in the original order.
+            for child_view_rev in view_rev.belongings.into_iter().rev() {
+                if !trash_ids.contains(&child_view_rev.id) {
+                    let name = child_view_rev.name.clone();
+                    stack.push((child_view_rev, duplicated_view_rev.id.clone(), name));
+                }
+            }
+        }
         Ok(())
     }
 
@@ -422,6 +478,13 @@ async fn handle_trash_event(
                         for identifier in identifiers.items {
                             let view = transaction.read_view(&identifier.id)?;
                             let _ = transaction.delete_view(&view.id)?;
+                            // The child views are deleted with the view, so remove the ones that
+                            // were moved to the trash separately.
+                            let child_view_ids = child_views(&view)
+                                .into_iter()
+                                .map(|view| view.id.clone())
+                                .collect::<Vec<String>>();
+                            let _ = transaction.delete_trash(Some(child_view_ids))?;
                             notify_ids.insert(view.belong_to_id.clone());
                             views.push(view);
                         }
@@ -432,11 +495,14 @@ async fn handle_trash_event(
                     })
                     .await?;
 
-                for view in views {
+                for view in views
+                    .iter()
+                    .flat_map(|view| std::iter::once(view).chain(child_views(view)))
+                {
                     let data_type = view.data_type.clone().into();
                     match get_data_processor(data_processors.clone(), &data_type) {
                         Ok(processor) => {
-                            let _ = processor.close_container(&view.id).await?;
+                            let _ = processor.delete_container(&view.id).await?;
                         }
                         Err(e) => {
                             tracing::error!("{}", e)
@@ -513,3 +579,34 @@ fn read_belonging_views_on_local<'a>(
 
     Ok(view_revs)
 }
+
+/// Return true if the view or any of the views that it belongs to is in the trash.
+fn is_view_in_trash<'a>(
+    view_rev: &ViewRevision,
+    trash_ids: &[String],
+    transaction: &'a (dyn FolderPersistenceTransaction + 'a),
+) -> bool {
+    if trash_ids.contains(&view_rev.id) {
+        return true;
+    }
+
+    // The read fails when the belong_to_id points to the app.
+    let mut belong_to_id = view_rev.belong_to_id.clone();
+    while let Ok(parent_view_rev) = transaction.read_view(&belong_to_id) {
+        if trash_ids.contains(&parent_view_rev.id) {
+            return true;
+        }
+        belong_to_id = parent_view_rev.belong_to_id;
+    }
+    false
+}
+
+/// Return all the child views of the view, including the child views of the child views.
+fn child_views(view_rev: &ViewRevision) -> Vec<&ViewRevision> {
+    let mut views = vec![];
+    for child_view_rev in view_rev.belongings.iter() {
+        views.push(child_view_rev);
+        views.extend(child_views(child_view_rev));
+    }
+    views
+}
@@ -1,5 +1,5 @@
 use crate::entities::view::{MoveFolderItemParams, MoveFolderItemPayloadPB, MoveFolderItemType};
-use crate::entities::ViewInfoPB;
+use crate::entities::{ImportDocumentParams, ImportDocumentPayloadPB, ViewInfoPB};
 use crate::manager::FolderManager;
 use crate::services::{notify_workspace_setting_did_change, AppController};
 use crate::{
@@ -26,6 +26,16 @@ pub(crate) async fn create_view_handler(
     data_result(view_rev.into())
 }
 
+#[tracing::instrument(level = "debug", skip(data, controller), err)]
+pub(crate) async fn import_document_handler(
+    data: Data<ImportDocumentPayloadPB>,
+    controller: AppData<Arc<ViewController>>,
+) -> DataResult<ViewPB, FlowyError> {
+    let params: ImportDocumentParams = data.into_inner().try_into()?;
+    let view_rev = controller.import_document(params).await?;
+    data_result(view_rev.into())
+}
+
 pub(crate) async fn read_view_handler(
     data: Data<ViewIdPB>,
     controller: AppData<Arc<ViewController>>,
@@ -110,11 +120,18 @@ pub(crate) async fn move_item_handler(
         MoveFolderItemType::MoveApp => {
             let _ = app_controller.move_app(&params.item_id, params.from, params.to).await?;
         }
-        MoveFolderItemType::MoveView => {
-            let _ = view_controller
-                .move_view(&params.item_id, params.from, params.to)
-                .await?;
-        }
+        MoveFolderItemType::MoveView => match params.belong_to_id {
+            None => {
+                let _ = view_controller
+                    .move_view(&params.item_id, params.from, params.to)
+                    .await?;
+            }
+            Some(belong_to_id) => {
+                let _ = view_controller
+                    .move_view_to(&params.item_id, &belong_to_id, params.to)
+                    .await?;
+            }
+        },
     }
     Ok(())
 }
@@ -47,6 +47,26 @@ pub(crate) async fn open_workspace_handler(
     data_result(workspaces)
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn export_workspace_handler(
+    data: Data<ExportWorkspacePayloadPB>,
+    folder: AppData<Arc<FolderManager>>,
+) -> Result<(), FlowyError> {
+    let params: ExportWorkspaceParams = data.into_inner().try_into()?;
+    let _ = folder.export_workspace(params).await?;
+    Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn import_workspace_handler(
+    data: Data<ImportWorkspacePayloadPB>,
+    folder: AppData<Arc<FolderManager>>,
+) -> DataResult<WorkspacePB, FlowyError> {
+    let payload: ImportWorkspacePayloadPB = data.into_inner();
+    let workspace_rev = folder.import_workspace(&payload.path).await?;
+    data_result(workspace_rev.into())
+}
+
 #[tracing::instrument(level = "debug", skip(data, folder), err)]
 pub(crate) async fn read_workspaces_handler(
     data: Data<WorkspaceIdPB>,
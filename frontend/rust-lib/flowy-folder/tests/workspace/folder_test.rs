@@ -1,9 +1,25 @@
-use crate::script::{invalid_workspace_name_test_case, FolderScript::*, FolderTest};
+use crate::script::{
+    create_view, export_document, export_markdown, export_workspace, import_workspace,
+    invalid_workspace_name_test_case, FolderScript::*, FolderTest,
+};
+use bytes::Bytes;
+use dart_notify::sink::{subscribe_notifications, NotificationFilter};
+use flowy_folder::dart_notification::{FolderNotification, OBSERVABLE_CATEGORY};
+use flowy_folder::entities::app::RepeatedAppPB;
 use flowy_folder::entities::view::ViewDataType;
 use flowy_folder::entities::workspace::CreateWorkspacePayloadPB;
-
-use flowy_revision::disk::RevisionState;
-use flowy_test::{event_builder::*, FlowySDKTest};
+use flowy_folder::services::{TrashClock, TrashRetention};
+
+use flowy_revision::disk::{RevisionDiskCache, RevisionState, SQLiteTextBlockRevisionPersistence};
+use flowy_test::{event_builder::*, helper::receive_notification, FlowySDKTest};
+use flowy_text_block::entities::ExportType;
+use flowy_text_block::format::{view_id_from_link, view_link};
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
 
 #[tokio::test]
 async fn workspace_read_all() {
@@ -46,6 +62,79 @@ async fn workspace_read() {
     .await;
 }
 
+#[tokio::test]
+async fn workspace_export_and_import_archive() {
+    let mut test = FolderTest::new().await;
+    let markdown = "# Notes\n\nSome **bold** text\n".to_owned();
+    test.run_scripts(vec![
+        ImportMarkdown {
+            name: "Notes".to_owned(),
+            markdown: markdown.clone(),
+        },
+        CreateView {
+            name: "Tasks".to_owned(),
+            desc: "".to_owned(),
+            data_type: ViewDataType::Grid,
+        },
+        CreateView {
+            name: "Trashed".to_owned(),
+            desc: "".to_owned(),
+            data_type: ViewDataType::TextBlock,
+        },
+        DeleteView,
+        ReadApp(test.app.id.clone()),
+    ])
+    .await;
+    let workspace = test.workspace.clone();
+    let app = test.app.clone();
+    let path = std::env::temp_dir().join(format!("{}.workspace.json", nanoid::nanoid!(6)));
+    let path = path.to_str().unwrap();
+    export_workspace(&test.sdk, &workspace.id, path).await;
+
+    let imported = import_workspace(&test.sdk, path).await;
+    let _ = std::fs::remove_file(path);
+    assert_ne!(imported.id, workspace.id);
+    assert_eq!(imported.name, workspace.name);
+    assert_eq!(imported.apps.items.len(), 1);
+    let imported_app = imported.apps.items[0].clone();
+    assert_ne!(imported_app.id, app.id);
+    assert_eq!(imported_app.workspace_id, imported.id);
+
+    let view_names = imported_app
+        .belongings
+        .items
+        .iter()
+        .map(|view| view.name.clone())
+        .collect::<Vec<String>>();
+    assert_eq!(view_names, vec!["Folder View", "Notes", "Tasks"]);
+    for (imported_view, view) in imported_app.belongings.items.iter().zip(app.belongings.items.iter()) {
+        assert_ne!(imported_view.id, view.id);
+        assert_eq!(imported_view.belong_to_id, imported_app.id);
+        assert_eq!(imported_view.data_type, view.data_type);
+    }
+
+    let imported_notes = &imported_app.belongings.items[1];
+    assert_eq!(export_markdown(&test.sdk, &imported_notes.id).await, markdown);
+
+    let grid_ids = vec![
+        app.belongings.items[2].id.clone(),
+        imported_app.belongings.items[2].id.clone(),
+    ];
+    let mut row_ids = vec![];
+    for grid_id in grid_ids {
+        let editor = test.sdk.grid_manager.open_grid(&grid_id).await.unwrap();
+        let mut ids = HashSet::new();
+        for block_meta_rev in editor.get_block_meta_revs().await.unwrap() {
+            for row in editor.get_rows(&block_meta_rev.block_id).await.unwrap().items {
+                ids.insert(row.id);
+            }
+        }
+        row_ids.push(ids);
+    }
+    assert_eq!(row_ids[0].len(), row_ids[1].len());
+    assert!(row_ids[0].is_disjoint(&row_ids[1]));
+}
+
 #[tokio::test]
 async fn workspace_create_with_apps() {
     let mut test = FolderTest::new().await;
@@ -151,6 +240,24 @@ async fn app_create_with_view() {
     assert_eq!(app.belongings[2].name, "Grid")
 }
 
+#[tokio::test]
+async fn app_create_notify_workspace_apps_changed() {
+    let mut test = FolderTest::new().await;
+    let filter = NotificationFilter::default()
+        .source(OBSERVABLE_CATEGORY)
+        .id(&test.workspace.id);
+    let mut receiver = subscribe_notifications(filter);
+    test.run_scripts(vec![CreateApp {
+        name: "App A".to_owned(),
+        desc: "App A description".to_owned(),
+    }])
+    .await;
+
+    let subject = receive_notification(&mut receiver, FolderNotification::WorkspaceAppsChanged).await;
+    let apps = RepeatedAppPB::try_from(Bytes::from(subject.payload.unwrap())).unwrap();
+    assert!(apps.items.iter().any(|app| app.id == test.app.id));
+}
+
 #[tokio::test]
 async fn view_update() {
     let mut test = FolderTest::new().await;
@@ -190,6 +297,202 @@ async fn view_delete_then_restore() {
     .await;
 }
 
+#[tokio::test]
+async fn view_create_child_view() {
+    let mut test = FolderTest::new().await;
+    let parent_view = test.view.clone();
+    test.run_scripts(vec![CreateChildView {
+        name: "Child view".to_owned(),
+        desc: "".to_owned(),
+        data_type: ViewDataType::TextBlock,
+    }])
+    .await;
+
+    let child_view = test.view.clone();
+    assert_eq!(child_view.belong_to_id, parent_view.id);
+    test.run_scripts(vec![ReadView(child_view.id.clone()), AssertView(child_view)])
+        .await;
+}
+
+#[tokio::test]
+async fn view_import_markdown() {
+    let mut test = FolderTest::new().await;
+    let markdown = "# Notes\n\nSome **bold** text\n\n- [x] done\n- [ ] todo\n".to_owned();
+    test.run_scripts(vec![ImportMarkdown {
+        name: "Notes".to_owned(),
+        markdown: markdown.clone(),
+    }])
+    .await;
+
+    let view_id = test.view.id.clone();
+    test.run_scripts(vec![ReadView(view_id), AssertExportedMarkdown(markdown)])
+        .await;
+    assert_eq!(test.view.name, "Notes");
+    assert_eq!(test.view.data_type, ViewDataType::TextBlock);
+}
+
+#[tokio::test]
+async fn template_create_text_block_view() {
+    let mut test = FolderTest::new().await;
+    let markdown = "# Weekly\n\n- [ ] Plan\n- [ ] Review\n".to_owned();
+    test.run_scripts(vec![
+        ImportMarkdown {
+            name: "Weekly".to_owned(),
+            markdown: markdown.clone(),
+        },
+        CreateTemplate {
+            name: "Weekly template".to_owned(),
+        },
+        ReadTemplates,
+    ])
+    .await;
+    let source_view = test.view.clone();
+    assert_eq!(test.templates.len(), 1);
+    assert_eq!(test.templates[0].name, "Weekly template");
+    assert_eq!(test.templates[0].data_type, ViewDataType::TextBlock);
+
+    test.run_scripts(vec![
+        CreateViewFromTemplate {
+            name: "Week 1".to_owned(),
+        },
+        AssertExportedMarkdown(markdown),
+        DeleteTemplate,
+        ReadTemplates,
+    ])
+    .await;
+    assert_ne!(test.view.id, source_view.id);
+    assert_eq!(test.view.name, "Week 1");
+    assert!(test.templates.is_empty());
+}
+
+#[tokio::test]
+async fn template_create_grid_view_with_new_ids() {
+    let mut test = FolderTest::new().await;
+    test.run_scripts(vec![
+        CreateView {
+            name: "Tasks".to_owned(),
+            desc: "".to_owned(),
+            data_type: ViewDataType::Grid,
+        },
+        CreateTemplate {
+            name: "Tasks template".to_owned(),
+        },
+    ])
+    .await;
+
+    let mut grid_ids = vec![test.view.id.clone()];
+    for name in ["Tasks 1", "Tasks 2"].iter() {
+        test.run_scripts(vec![CreateViewFromTemplate { name: name.to_string() }])
+            .await;
+        assert_eq!(test.view.data_type, ViewDataType::Grid);
+        grid_ids.push(test.view.id.clone());
+    }
+
+    let mut block_ids = HashSet::new();
+    let mut row_ids = HashSet::new();
+    let mut number_of_rows = vec![];
+    for grid_id in grid_ids {
+        let editor = test.sdk.grid_manager.open_grid(&grid_id).await.unwrap();
+        let mut rows = 0;
+        for block_meta_rev in editor.get_block_meta_revs().await.unwrap() {
+            assert!(block_ids.insert(block_meta_rev.block_id.clone()));
+            for row in editor.get_rows(&block_meta_rev.block_id).await.unwrap().items {
+                assert!(row_ids.insert(row.id.clone()));
+                rows += 1;
+            }
+        }
+        number_of_rows.push(rows);
+    }
+    assert!(number_of_rows[0] > 0);
+    assert!(number_of_rows.iter().all(|rows| *rows == number_of_rows[0]));
+}
+
+#[tokio::test]
+async fn view_export_text_and_link() {
+    let mut test = FolderTest::new().await;
+    test.run_scripts(vec![ImportMarkdown {
+        name: "Notes".to_owned(),
+        markdown: "# Notes\n\nSome **bold** text\n".to_owned(),
+    }])
+    .await;
+
+    let view_id = test.view.id.clone();
+    let text = export_document(&test.sdk, &view_id, ExportType::Text).await;
+    assert_eq!(text, "Notes\nSome bold text\n");
+    let link = export_document(&test.sdk, &view_id, ExportType::Link).await;
+    assert_eq!(link, view_link(&view_id));
+    assert_eq!(view_id_from_link(&link), Some(view_id.as_str()));
+}
+
+#[tokio::test]
+async fn view_move_to_another_view() {
+    let mut test = FolderTest::new().await;
+    let parent_view = test.view.clone();
+    test.run_scripts(vec![
+        CreateView {
+            name: "View A".to_owned(),
+            desc: "".to_owned(),
+            data_type: ViewDataType::TextBlock,
+        },
+        MoveViewTo {
+            belong_to_id: parent_view.id.clone(),
+            index: 0,
+        },
+    ])
+    .await;
+
+    let view_id = test.view.id.clone();
+    test.run_scripts(vec![ReadView(view_id)]).await;
+    assert_eq!(test.view.belong_to_id, parent_view.id);
+}
+
+#[tokio::test]
+#[should_panic]
+async fn view_delete_parent_then_read_child_view() {
+    let mut test = FolderTest::new().await;
+    let parent_view = test.view.clone();
+    test.run_scripts(vec![CreateChildView {
+        name: "Child view".to_owned(),
+        desc: "".to_owned(),
+        data_type: ViewDataType::TextBlock,
+    }])
+    .await;
+
+    // The child view is moved to the trash with its parent.
+    let child_view = test.view.clone();
+    test.view = parent_view;
+    test.run_scripts(vec![DeleteView, ReadView(child_view.id)]).await;
+}
+
+#[tokio::test]
+async fn view_search_by_name() {
+    let mut test = FolderTest::new().await;
+    let view = test.view.clone();
+    test.run_scripts(vec![
+        RebuildSearchIndex,
+        AssertSearch {
+            query: "folder vie".to_owned(),
+            expected_view_ids: vec![view.id.clone()],
+        },
+        UpdateView {
+            name: Some("Renamed view".to_owned()),
+            desc: None,
+        },
+        RebuildSearchIndex,
+        AssertSearch {
+            query: "renamed".to_owned(),
+            expected_view_ids: vec![view.id.clone()],
+        },
+        DeleteView,
+        RebuildSearchIndex,
+        AssertSearch {
+            query: "renamed".to_owned(),
+            expected_view_ids: vec![],
+        },
+    ])
+    .await;
+}
+
 #[tokio::test]
 async fn view_delete_all() {
     let mut test = FolderTest::new().await;
@@ -343,3 +646,43 @@ async fn folder_sync_revision_with_new_view() {
     test.run_scripts(vec![ReadView(view.id.clone()), AssertView(view)])
         .await;
 }
+
+const DAY_IN_SECONDS: i64 = 24 * 60 * 60;
+
+struct MockTrashClock(AtomicI64);
+impl TrashClock for MockTrashClock {
+    fn now(&self) -> i64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[tokio::test]
+async fn trash_purge_expired_items() {
+    let clock = Arc::new(MockTrashClock(AtomicI64::new(DAY_IN_SECONDS)));
+    let retention = TrashRetention {
+        period: Duration::from_secs(30 * DAY_IN_SECONDS as u64),
+        purge_interval: Duration::from_millis(100),
+        clock: clock.clone(),
+    };
+    let mut test = FolderTest::with_sdk(FlowySDKTest::with_trash_retention(retention)).await;
+    let expired_view = test.view.clone();
+    let view = create_view(&test.sdk, &test.app.id, "Kept", "", ViewDataType::TextBlock).await;
+
+    test.run_scripts(vec![DeleteView]).await;
+    clock.0.fetch_add(20 * DAY_IN_SECONDS, Ordering::SeqCst);
+    test.run_scripts(vec![DeleteViews(vec![view.id.clone()])]).await;
+    clock.0.fetch_add(11 * DAY_IN_SECONDS, Ordering::SeqCst);
+    sleep(Duration::from_millis(500)).await;
+
+    test.run_scripts(vec![ReadTrash]).await;
+    assert_eq!(test.trash.len(), 1);
+    assert_eq!(test.trash[0].id, view.id);
+
+    let user_id = test.sdk.user_session.user_id().unwrap();
+    let pool = test.sdk.user_session.db_pool().unwrap();
+    let disk_cache = SQLiteTextBlockRevisionPersistence::new(&user_id, pool);
+    let records = disk_cache.read_revision_records(&expired_view.id, None).unwrap();
+    assert!(records.is_empty());
+    let records = disk_cache.read_revision_records(&view.id, None).unwrap();
+    assert!(!records.is_empty());
+}
@@ -2,9 +2,15 @@ use flowy_folder::entities::view::{RepeatedViewIdPB, ViewIdPB};
 use flowy_folder::entities::workspace::WorkspaceIdPB;
 use flowy_folder::entities::{
     app::{AppIdPB, CreateAppPayloadPB, UpdateAppPayloadPB},
+    import::{ImportDocumentPayloadPB, ImportType},
+    search::{RepeatedSearchResultPB, SearchPayloadPB},
+    template::{
+        CreateTemplatePayloadPB, CreateViewFromTemplatePayloadPB, RepeatedViewTemplatePB, ViewTemplateIdPB,
+        ViewTemplatePB,
+    },
     trash::{RepeatedTrashPB, TrashIdPB, TrashType},
-    view::{CreateViewPayloadPB, UpdateViewPayloadPB},
-    workspace::{CreateWorkspacePayloadPB, RepeatedWorkspacePB},
+    view::{CreateViewPayloadPB, MoveFolderItemPayloadPB, MoveFolderItemType, UpdateViewPayloadPB},
+    workspace::{CreateWorkspacePayloadPB, ExportWorkspacePayloadPB, ImportWorkspacePayloadPB, RepeatedWorkspacePB},
 };
 use flowy_folder::entities::{
     app::{AppPB, RepeatedAppPB},
@@ -19,6 +25,8 @@ use flowy_revision::disk::RevisionState;
 use flowy_revision::REVISION_WRITE_INTERVAL_IN_MILLIS;
 use flowy_sync::entities::text_block::DocumentPB;
 use flowy_test::{event_builder::*, FlowySDKTest};
+use flowy_text_block::entities::{ExportDataPB, ExportPayloadPB, ExportType};
+use flowy_text_block::event_map::TextBlockEvent::ExportDocument;
 use std::{sync::Arc, time::Duration};
 use tokio::time::sleep;
 
@@ -53,8 +61,22 @@ pub enum FolderScript {
         desc: String,
         data_type: ViewDataType,
     },
+    CreateChildView {
+        name: String,
+        desc: String,
+        data_type: ViewDataType,
+    },
+    ImportMarkdown {
+        name: String,
+        markdown: String,
+    },
+    AssertExportedMarkdown(String),
     AssertView(ViewPB),
     ReadView(String),
+    MoveViewTo {
+        belong_to_id: String,
+        index: usize,
+    },
     UpdateView {
         name: Option<String>,
         desc: Option<String>,
@@ -68,6 +90,23 @@ pub enum FolderScript {
     ReadTrash,
     DeleteAllTrash,
 
+    // Template
+    CreateTemplate {
+        name: String,
+    },
+    CreateViewFromTemplate {
+        name: String,
+    },
+    ReadTemplates,
+    DeleteTemplate,
+
+    // Search
+    RebuildSearchIndex,
+    AssertSearch {
+        query: String,
+        expected_view_ids: Vec<String>,
+    },
+
     // Sync
     AssertCurrentRevId(i64),
     AssertNextSyncRevId(Option<i64>),
@@ -84,12 +123,17 @@ pub struct FolderTest {
     pub app: AppPB,
     pub view: ViewPB,
     pub trash: Vec<TrashPB>,
+    pub template: ViewTemplatePB,
+    pub templates: Vec<ViewTemplatePB>,
     // pub folder_editor:
 }
 
 impl FolderTest {
     pub async fn new() -> Self {
-        let sdk = FlowySDKTest::default();
+        Self::with_sdk(FlowySDKTest::default()).await
+    }
+
+    pub async fn with_sdk(sdk: FlowySDKTest) -> Self {
         let _ = sdk.init_user().await;
         let mut workspace = create_workspace(&sdk, "FolderWorkspace", "Folder test workspace").await;
         let mut app = create_app(&sdk, &workspace.id, "Folder App", "Folder test app").await;
@@ -115,6 +159,8 @@ impl FolderTest {
             app,
             view,
             trash: vec![],
+            template: ViewTemplatePB::default(),
+            templates: vec![],
         }
     }
 
@@ -182,6 +228,18 @@ impl FolderTest {
                 let view = create_view(sdk, &self.app.id, &name, &desc, data_type).await;
                 self.view = view;
             }
+            FolderScript::CreateChildView { name, desc, data_type } => {
+                let view = create_view(sdk, &self.view.id, &name, &desc, data_type).await;
+                self.view = view;
+            }
+            FolderScript::ImportMarkdown { name, markdown } => {
+                let view = import_markdown(sdk, &self.app.id, &name, &markdown).await;
+                self.view = view;
+            }
+            FolderScript::AssertExportedMarkdown(expected) => {
+                let markdown = export_markdown(sdk, &self.view.id).await;
+                assert_eq!(markdown, expected);
+            }
             FolderScript::AssertView(view) => {
                 assert_eq!(self.view, view);
             }
@@ -189,6 +247,9 @@ impl FolderTest {
                 let view = read_view(sdk, &view_id).await;
                 self.view = view;
             }
+            FolderScript::MoveViewTo { belong_to_id, index } => {
+                move_view_to(sdk, &self.view.id, &belong_to_id, index).await;
+            }
             FolderScript::UpdateView { name, desc } => {
                 update_view(sdk, &self.view.id, name, desc).await;
             }
@@ -212,6 +273,33 @@ impl FolderTest {
                 delete_all_trash(sdk).await;
                 self.trash = vec![];
             }
+            FolderScript::CreateTemplate { name } => {
+                self.template = create_template(sdk, &self.view.id, &name).await;
+            }
+            FolderScript::CreateViewFromTemplate { name } => {
+                self.view = create_view_from_template(sdk, &self.template.id, &self.app.id, &name).await;
+            }
+            FolderScript::ReadTemplates => {
+                self.templates = read_templates(sdk).await.items;
+            }
+            FolderScript::DeleteTemplate => {
+                delete_template(sdk, &self.template.id).await;
+            }
+            FolderScript::RebuildSearchIndex => {
+                rebuild_search_index(sdk).await;
+            }
+            FolderScript::AssertSearch {
+                query,
+                expected_view_ids,
+            } => {
+                let view_ids = search(sdk, &query)
+                    .await
+                    .into_inner()
+                    .into_iter()
+                    .map(|result| result.view_id)
+                    .collect::<Vec<String>>();
+                assert_eq!(view_ids, expected_view_ids);
+            }
             FolderScript::AssertRevisionState { rev_id, state } => {
                 let record = cache.get(rev_id).await.unwrap();
                 assert_eq!(record.state, state);
@@ -365,6 +453,40 @@ pub async fn create_view(sdk: &FlowySDKTest, app_id: &str, name: &str, desc: &st
     view
 }
 
+pub async fn import_markdown(sdk: &FlowySDKTest, app_id: &str, name: &str, markdown: &str) -> ViewPB {
+    let request = ImportDocumentPayloadPB {
+        belong_to_id: app_id.to_string(),
+        name: name.to_string(),
+        import_type: ImportType::Markdown,
+        data: markdown.to_string(),
+        plugin_type: 0,
+    };
+    FolderEventBuilder::new(sdk.clone())
+        .event(ImportDocument)
+        .payload(request)
+        .async_send()
+        .await
+        .parse::<ViewPB>()
+}
+
+pub async fn export_markdown(sdk: &FlowySDKTest, view_id: &str) -> String {
+    export_document(sdk, view_id, ExportType::Markdown).await
+}
+
+pub async fn export_document(sdk: &FlowySDKTest, view_id: &str, export_type: ExportType) -> String {
+    let request = ExportPayloadPB {
+        view_id: view_id.to_string(),
+        export_type,
+    };
+    FolderEventBuilder::new(sdk.clone())
+        .event(ExportDocument)
+        .payload(request)
+        .async_send()
+        .await
+        .parse::<ExportDataPB>()
+        .data
+}
+
 pub async fn read_view(sdk: &FlowySDKTest, view_id: &str) -> ViewPB {
     let view_id: ViewIdPB = view_id.into();
     FolderEventBuilder::new(sdk.clone())
@@ -389,6 +511,21 @@ pub async fn update_view(sdk: &FlowySDKTest, view_id: &str, name: Option<String>
         .await;
 }
 
+pub async fn move_view_to(sdk: &FlowySDKTest, view_id: &str, belong_to_id: &str, index: usize) {
+    let payload = MoveFolderItemPayloadPB {
+        item_id: view_id.to_owned(),
+        from: 0,
+        to: index as i32,
+        ty: MoveFolderItemType::MoveView,
+        belong_to_id: Some(belong_to_id.to_owned()),
+    };
+    FolderEventBuilder::new(sdk.clone())
+        .event(MoveFolderItem)
+        .payload(payload)
+        .async_send()
+        .await;
+}
+
 pub async fn delete_view(sdk: &FlowySDKTest, view_ids: Vec<String>) {
     let request = RepeatedViewIdPB { items: view_ids };
     FolderEventBuilder::new(sdk.clone())
@@ -447,3 +584,89 @@ pub async fn delete_all_trash(sdk: &FlowySDKTest) {
         .async_send()
         .await;
 }
+
+pub async fn search(sdk: &FlowySDKTest, query: &str) -> RepeatedSearchResultPB {
+    let payload = SearchPayloadPB {
+        query: query.to_owned(),
+        limit: 0,
+    };
+    FolderEventBuilder::new(sdk.clone())
+        .event(Search)
+        .payload(payload)
+        .async_send()
+        .await
+        .parse::<RepeatedSearchResultPB>()
+}
+
+pub async fn rebuild_search_index(sdk: &FlowySDKTest) {
+    FolderEventBuilder::new(sdk.clone())
+        .event(RebuildSearchIndex)
+        .async_send()
+        .await;
+}
+
+pub async fn create_template(sdk: &FlowySDKTest, view_id: &str, name: &str) -> ViewTemplatePB {
+    let request = CreateTemplatePayloadPB {
+        view_id: view_id.to_string(),
+        name: name.to_string(),
+    };
+    FolderEventBuilder::new(sdk.clone())
+        .event(CreateTemplate)
+        .payload(request)
+        .async_send()
+        .await
+        .parse::<ViewTemplatePB>()
+}
+
+pub async fn read_templates(sdk: &FlowySDKTest) -> RepeatedViewTemplatePB {
+    FolderEventBuilder::new(sdk.clone())
+        .event(ReadTemplates)
+        .async_send()
+        .await
+        .parse::<RepeatedViewTemplatePB>()
+}
+
+pub async fn delete_template(sdk: &FlowySDKTest, template_id: &str) {
+    let template_id: ViewTemplateIdPB = template_id.into();
+    FolderEventBuilder::new(sdk.clone())
+        .event(DeleteTemplate)
+        .payload(template_id)
+        .async_send()
+        .await;
+}
+
+pub async fn create_view_from_template(sdk: &FlowySDKTest, template_id: &str, app_id: &str, name: &str) -> ViewPB {
+    let request = CreateViewFromTemplatePayloadPB {
+        template_id: template_id.to_string(),
+        belong_to_id: app_id.to_string(),
+        name: name.to_string(),
+    };
+    FolderEventBuilder::new(sdk.clone())
+        .event(CreateViewFromTemplate)
+        .payload(request)
+        .async_send()
+        .await
+        .parse::<ViewPB>()
+}
+
+pub async fn export_workspace(sdk: &FlowySDKTest, workspace_id: &str, path: &str) {
+    let request = ExportWorkspacePayloadPB {
+        workspace_id: workspace_id.to_string(),
+        path: path.to_string(),
+    };
+    FolderEventBuilder::new(sdk.clone())
+        .event(ExportWorkspace)
+        .payload(request)
+        .async_send()
+        .await;
+}
+
+pub async fn import_workspace(sdk: &FlowySDKTest, path: &str) -> WorkspacePB {
+    let request = ImportWorkspacePayloadPB { path: path.to_string() };
+    FolderEventBuilder::new(sdk.clone())
+        .event(ImportWorkspace)
+        .payload(request)
+        .async_send()
+        .await
+        .parse::<WorkspacePB>()
+}
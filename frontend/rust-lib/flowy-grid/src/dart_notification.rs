@@ -1,6 +1,6 @@
 use dart_notify::DartNotifyBuilder;
 use flowy_derive::ProtoBuf_Enum;
-const OBSERVABLE_CATEGORY: &str = "Grid";
+pub const OBSERVABLE_CATEGORY: &str = "Grid";
 
 #[derive(ProtoBuf_Enum, Debug)]
 pub enum GridNotification {
@@ -8,9 +8,13 @@ pub enum GridNotification {
     DidCreateBlock = 11,
     DidUpdateGridBlock = 20,
     DidUpdateGridField = 21,
+    DidReorderRows = 22,
     DidUpdateRow = 30,
     DidUpdateCell = 40,
     DidUpdateField = 50,
+    DidUpdateGroups = 60,
+    DidUpdateGroupRows = 61,
+    DidUpdateCalculations = 70,
 }
 
 impl std::default::Default for GridNotification {
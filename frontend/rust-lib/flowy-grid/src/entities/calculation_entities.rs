@@ -0,0 +1,190 @@
+use crate::entities::GridLayoutType;
+use crate::services::setting::GridSettingChangesetBuilder;
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+use flowy_grid_data_model::parser::NotEmptyStr;
+use flowy_grid_data_model::revision::{GridCalculationRevision, GridCalculationTypeRevision};
+use flowy_sync::entities::grid::{CreateGridCalculationParams, GridSettingChangesetParams};
+use std::convert::TryInto;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum GridCalculationType {
+    Sum = 0,
+    Average = 1,
+    Min = 2,
+    Max = 3,
+    /// The number of the rows.
+    Count = 4,
+    CountEmpty = 5,
+    /// The number of the distinct values of the cells that are not empty.
+    CountUnique = 6,
+    PercentChecked = 7,
+}
+
+impl std::default::Default for GridCalculationType {
+    fn default() -> Self {
+        GridCalculationType::Count
+    }
+}
+
+impl std::convert::From<GridCalculationTypeRevision> for GridCalculationType {
+    fn from(rev: GridCalculationTypeRevision) -> Self {
+        match rev {
+            GridCalculationTypeRevision::Sum => GridCalculationType::Sum,
+            GridCalculationTypeRevision::Average => GridCalculationType::Average,
+            GridCalculationTypeRevision::Min => GridCalculationType::Min,
+            GridCalculationTypeRevision::Max => GridCalculationType::Max,
+            GridCalculationTypeRevision::Count => GridCalculationType::Count,
+            GridCalculationTypeRevision::CountEmpty => GridCalculationType::CountEmpty,
+            GridCalculationTypeRevision::CountUnique => GridCalculationType::CountUnique,
+            GridCalculationTypeRevision::PercentChecked => GridCalculationType::PercentChecked,
+        }
+    }
+}
+
+impl std::convert::From<GridCalculationType> for GridCalculationTypeRevision {
+    fn from(ty: GridCalculationType) -> Self {
+        match ty {
+            GridCalculationType::Sum => GridCalculationTypeRevision::Sum,
+            GridCalculationType::Average => GridCalculationTypeRevision::Average,
+            GridCalculationType::Min => GridCalculationTypeRevision::Min,
+            GridCalculationType::Max => GridCalculationTypeRevision::Max,
+            GridCalculationType::Count => GridCalculationTypeRevision::Count,
+            GridCalculationType::CountEmpty => GridCalculationTypeRevision::CountEmpty,
+            GridCalculationType::CountUnique => GridCalculationTypeRevision::CountUnique,
+            GridCalculationType::PercentChecked => GridCalculationTypeRevision::PercentChecked,
+        }
+    }
+}
+
+/// The value of the calculation that is displayed in the summary row under the field.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct GridCalculationPB {
+    #[pb(index = 1)]
+    pub field_id: String,
+
+    #[pb(index = 2)]
+    pub calculation_type: GridCalculationType,
+
+    /// The value is empty if there is no cell that can be calculated, e.g. summing a field whose
+    /// cells are all empty.
+    #[pb(index = 3)]
+    pub value: String,
+}
+
+impl std::convert::From<&GridCalculationRevision> for GridCalculationPB {
+    fn from(rev: &GridCalculationRevision) -> Self {
+        GridCalculationPB {
+            field_id: rev.field_id.clone(),
+            calculation_type: rev.calculation_type.clone().into(),
+            value: "".to_owned(),
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct RepeatedGridCalculationPB {
+    #[pb(index = 1)]
+    pub items: Vec<GridCalculationPB>,
+}
+
+impl std::convert::From<Vec<GridCalculationPB>> for RepeatedGridCalculationPB {
+    fn from(items: Vec<GridCalculationPB>) -> Self {
+        Self { items }
+    }
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct CreateGridCalculationPayloadPB {
+    #[pb(index = 1)]
+    pub field_id: String,
+
+    #[pb(index = 2)]
+    pub calculation_type: GridCalculationType,
+}
+
+impl TryInto<CreateGridCalculationParams> for CreateGridCalculationPayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<CreateGridCalculationParams, Self::Error> {
+        let field_id = NotEmptyStr::parse(self.field_id)
+            .map_err(|_| ErrorCode::FieldIdIsEmpty)?
+            .0;
+
+        Ok(CreateGridCalculationParams {
+            field_id,
+            calculation_type: self.calculation_type.into(),
+        })
+    }
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct QueryGridCalculationPayloadPB {
+    #[pb(index = 1)]
+    pub grid_id: String,
+
+    #[pb(index = 2)]
+    pub layout_type: GridLayoutType,
+}
+
+pub struct QueryGridCalculationParams {
+    pub grid_id: String,
+    pub layout_type: GridLayoutType,
+}
+
+impl TryInto<QueryGridCalculationParams> for QueryGridCalculationPayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<QueryGridCalculationParams, Self::Error> {
+        let grid_id = NotEmptyStr::parse(self.grid_id)
+            .map_err(|_| ErrorCode::GridIdIsEmpty)?
+            .0;
+        Ok(QueryGridCalculationParams {
+            grid_id,
+            layout_type: self.layout_type,
+        })
+    }
+}
+
+/// Set the calculation of the field. The calculation of the field is removed if the
+/// calculation_type is None.
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct GridCalculationChangesetPayloadPB {
+    #[pb(index = 1)]
+    pub grid_id: String,
+
+    #[pb(index = 2)]
+    pub layout_type: GridLayoutType,
+
+    #[pb(index = 3)]
+    pub field_id: String,
+
+    #[pb(index = 4, one_of)]
+    pub calculation_type: Option<GridCalculationType>,
+}
+
+impl TryInto<GridSettingChangesetParams> for GridCalculationChangesetPayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<GridSettingChangesetParams, Self::Error> {
+        let grid_id = NotEmptyStr::parse(self.grid_id)
+            .map_err(|_| ErrorCode::GridIdIsEmpty)?
+            .0;
+        let field_id = NotEmptyStr::parse(self.field_id)
+            .map_err(|_| ErrorCode::FieldIdIsEmpty)?
+            .0;
+
+        let builder = GridSettingChangesetBuilder::new(&grid_id, &self.layout_type);
+        let params = match self.calculation_type {
+            None => builder.delete_calculation(&field_id).build(),
+            Some(calculation_type) => builder
+                .insert_calculation(CreateGridCalculationParams {
+                    field_id,
+                    calculation_type: calculation_type.into(),
+                })
+                .build(),
+        };
+        Ok(params)
+    }
+}
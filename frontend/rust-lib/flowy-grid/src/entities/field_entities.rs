@@ -511,6 +511,9 @@ pub enum FieldType {
     MultiSelect = 4,
     Checkbox = 5,
     URL = 6,
+    Formula = 7,
+    Relation = 8,
+    Rollup = 9,
 }
 
 impl std::default::Default for FieldType {
@@ -571,6 +574,18 @@ impl FieldType {
         self == &FieldType::URL
     }
 
+    pub fn is_formula(&self) -> bool {
+        self == &FieldType::Formula
+    }
+
+    pub fn is_relation(&self) -> bool {
+        self == &FieldType::Relation
+    }
+
+    pub fn is_rollup(&self) -> bool {
+        self == &FieldType::Rollup
+    }
+
     pub fn is_select_option(&self) -> bool {
         self == &FieldType::MultiSelect || self == &FieldType::SingleSelect
     }
@@ -603,6 +618,9 @@ impl std::convert::From<FieldTypeRevision> for FieldType {
             4 => FieldType::MultiSelect,
             5 => FieldType::Checkbox,
             6 => FieldType::URL,
+            7 => FieldType::Formula,
+            8 => FieldType::Relation,
+            9 => FieldType::Rollup,
             _ => {
                 tracing::error!("Can't parser FieldTypeRevision: {} to FieldType", ty);
                 FieldType::RichText
@@ -17,8 +17,16 @@ pub struct GridDateFilter {
 
     #[pb(index = 3, one_of)]
     pub end: Option<i64>,
+
+    /// The number of days of the DateIsInLastDays and DateIsInNextDays conditions.
+    #[pb(index = 4, one_of)]
+    pub days: Option<i64>,
 }
 
+/// The maximum number of days of the DateIsInLastDays and DateIsInNextDays conditions, about a
+/// hundred years.
+pub const MAX_FILTER_DAYS: i64 = 36500;
+
 #[derive(ProtoBuf, Default, Clone, Debug)]
 pub struct CreateGridDateFilterPayload {
     #[pb(index = 1)]
@@ -35,6 +43,9 @@ pub struct CreateGridDateFilterPayload {
 
     #[pb(index = 5, one_of)]
     pub end: Option<i64>,
+
+    #[pb(index = 6, one_of)]
+    pub days: Option<i64>,
 }
 
 pub struct CreateGridDateFilterParams {
@@ -47,6 +58,8 @@ pub struct CreateGridDateFilterParams {
     pub start: Option<i64>,
 
     pub end: Option<i64>,
+
+    pub days: Option<i64>,
 }
 
 impl TryInto<CreateGridDateFilterParams> for CreateGridDateFilterPayload {
@@ -56,20 +69,31 @@ impl TryInto<CreateGridDateFilterParams> for CreateGridDateFilterPayload {
         let field_id = NotEmptyStr::parse(self.field_id)
             .map_err(|_| ErrorCode::FieldIdIsEmpty)?
             .0;
+        if let Some(days) = self.days {
+            if days <= 0 || days > MAX_FILTER_DAYS {
+                return Err(ErrorCode::InvalidData);
+            }
+        }
         Ok(CreateGridDateFilterParams {
             field_id,
             condition: self.condition,
             start: self.start,
             field_type: self.field_type,
             end: self.end,
+            days: self.days,
         })
     }
 }
 
+/// The content of the date filter's GridFilterRevision. The relative conditions only store the
+/// number of days, so they are evaluated against the current date every time.
 #[derive(Serialize, Deserialize, Default)]
-struct DateRange {
-    start: Option<i64>,
-    end: Option<i64>,
+pub struct DateRange {
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days: Option<i64>,
 }
 
 impl ToString for DateRange {
@@ -96,6 +120,18 @@ pub enum DateFilterCondition {
     DateOnOrAfter = 4,
     DateWithIn = 5,
     DateIsEmpty = 6,
+    DateIsToday = 7,
+    DateIsYesterday = 8,
+    DateIsThisWeek = 9,
+    DateIsInLastDays = 10,
+    DateIsInNextDays = 11,
+    DateIsOverdue = 12,
+}
+
+impl std::convert::From<DateFilterCondition> for i32 {
+    fn from(value: DateFilterCondition) -> Self {
+        value as i32
+    }
 }
 
 impl std::default::Default for DateFilterCondition {
@@ -116,6 +152,12 @@ impl std::convert::TryFrom<u8> for DateFilterCondition {
             4 => Ok(DateFilterCondition::DateOnOrAfter),
             5 => Ok(DateFilterCondition::DateWithIn),
             6 => Ok(DateFilterCondition::DateIsEmpty),
+            7 => Ok(DateFilterCondition::DateIsToday),
+            8 => Ok(DateFilterCondition::DateIsYesterday),
+            9 => Ok(DateFilterCondition::DateIsThisWeek),
+            10 => Ok(DateFilterCondition::DateIsInLastDays),
+            11 => Ok(DateFilterCondition::DateIsInNextDays),
+            12 => Ok(DateFilterCondition::DateIsOverdue),
             _ => Err(ErrorCode::InvalidData),
         }
     }
@@ -135,6 +177,7 @@ impl std::convert::From<Arc<GridFilterRevision>> for GridDateFilter {
         {
             filter.start = range.start;
             filter.end = range.end;
+            filter.days = range.days;
         };
 
         filter
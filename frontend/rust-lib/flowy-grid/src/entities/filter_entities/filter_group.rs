@@ -0,0 +1,114 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+use flowy_grid_data_model::parser::NotEmptyStr;
+use flowy_grid_data_model::revision::{GridFilterGroupRevision, GridFilterLogicRevision};
+use flowy_sync::entities::grid::CreateGridFilterGroupParams;
+use std::convert::TryInto;
+
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct GridFilterGroup {
+    #[pb(index = 1)]
+    pub id: String,
+
+    #[pb(index = 2)]
+    pub logic: GridFilterLogic,
+
+    #[pb(index = 3)]
+    pub filter_ids: Vec<String>,
+
+    #[pb(index = 4)]
+    pub groups: Vec<GridFilterGroup>,
+}
+
+impl std::convert::From<&GridFilterGroupRevision> for GridFilterGroup {
+    fn from(rev: &GridFilterGroupRevision) -> Self {
+        GridFilterGroup {
+            id: rev.id.clone(),
+            logic: rev.logic.clone().into(),
+            filter_ids: rev.filter_ids.clone(),
+            groups: rev.groups.iter().map(GridFilterGroup::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum GridFilterLogic {
+    And = 0,
+    Or = 1,
+}
+
+impl std::default::Default for GridFilterLogic {
+    fn default() -> Self {
+        GridFilterLogic::And
+    }
+}
+
+impl std::convert::From<GridFilterLogicRevision> for GridFilterLogic {
+    fn from(rev: GridFilterLogicRevision) -> Self {
+        match rev {
+            GridFilterLogicRevision::And => GridFilterLogic::And,
+            GridFilterLogicRevision::Or => GridFilterLogic::Or,
+        }
+    }
+}
+
+impl std::convert::From<GridFilterLogic> for GridFilterLogicRevision {
+    fn from(logic: GridFilterLogic) -> Self {
+        match logic {
+            GridFilterLogic::And => GridFilterLogicRevision::And,
+            GridFilterLogic::Or => GridFilterLogicRevision::Or,
+        }
+    }
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct CreateGridFilterGroupPayloadPB {
+    #[pb(index = 1)]
+    pub logic: GridFilterLogic,
+
+    #[pb(index = 2)]
+    pub filter_ids: Vec<String>,
+
+    #[pb(index = 3)]
+    pub groups: Vec<CreateGridFilterGroupPayloadPB>,
+}
+
+impl CreateGridFilterGroupPayloadPB {
+    #[allow(dead_code)]
+    pub fn new(logic: GridFilterLogic, filter_ids: Vec<String>, groups: Vec<CreateGridFilterGroupPayloadPB>) -> Self {
+        Self {
+            logic,
+            filter_ids,
+            groups,
+        }
+    }
+}
+
+impl TryInto<CreateGridFilterGroupParams> for CreateGridFilterGroupPayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<CreateGridFilterGroupParams, Self::Error> {
+        let filter_ids = self
+            .filter_ids
+            .into_iter()
+            .map(|filter_id| {
+                NotEmptyStr::parse(filter_id)
+                    .map(|filter_id| filter_id.0)
+                    .map_err(|_| ErrorCode::UnexpectedEmptyString)
+            })
+            .collect::<Result<Vec<String>, ErrorCode>>()?;
+
+        let groups = self
+            .groups
+            .into_iter()
+            .map(|group| group.try_into())
+            .collect::<Result<Vec<CreateGridFilterGroupParams>, ErrorCode>>()?;
+
+        Ok(CreateGridFilterGroupParams {
+            logic: self.logic.into(),
+            filter_ids,
+            groups,
+        })
+    }
+}
@@ -1,5 +1,6 @@
 mod checkbox_filter;
 mod date_filter;
+mod filter_group;
 mod number_filter;
 mod select_option_filter;
 mod text_filter;
@@ -7,6 +8,7 @@ mod util;
 
 pub use checkbox_filter::*;
 pub use date_filter::*;
+pub use filter_group::*;
 pub use number_filter::*;
 pub use select_option_filter::*;
 pub use text_filter::*;
@@ -123,6 +123,12 @@ impl TryInto<CreateGridFilterParams> for CreateGridFilterPayloadPB {
             FieldType::SingleSelect | FieldType::MultiSelect => {
                 let _ = SelectOptionCondition::try_from(condition)?;
             }
+            // The condition of the formula field depends on the result type of the formula, which
+            // is unknown here.
+            FieldType::Formula => {}
+            // The condition of the rollup field depends on its calculation.
+            FieldType::Rollup => {}
+            FieldType::Relation => return Err(ErrorCode::FieldInvalidOperation),
         }
 
         Ok(CreateGridFilterParams {
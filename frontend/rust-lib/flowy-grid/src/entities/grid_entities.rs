@@ -104,3 +104,46 @@ impl TryInto<MoveItemParams> for MoveItemPayloadPB {
         })
     }
 }
+
+#[derive(Clone, ProtoBuf, Default, Debug)]
+pub struct ExportGridPB {
+    #[pb(index = 1)]
+    pub content: String,
+}
+
+#[derive(Clone, ProtoBuf, Default, Debug)]
+pub struct ImportGridPayloadPB {
+    /// The id of the view that the imported grid belongs to.
+    #[pb(index = 1)]
+    pub view_id: String,
+
+    /// The CSV content, the first record contains the names of the fields.
+    #[pb(index = 2)]
+    pub content: String,
+}
+
+pub struct ImportGridParams {
+    pub view_id: String,
+    pub content: String,
+}
+
+impl TryInto<ImportGridParams> for ImportGridPayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<ImportGridParams, Self::Error> {
+        let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::GridIdIsEmpty)?;
+        Ok(ImportGridParams {
+            view_id: view_id.0,
+            content: self.content,
+        })
+    }
+}
+
+#[derive(Clone, ProtoBuf, Default, Debug)]
+pub struct GridHistoryStatePB {
+    #[pb(index = 1)]
+    pub can_undo: bool,
+
+    #[pb(index = 2)]
+    pub can_redo: bool,
+}
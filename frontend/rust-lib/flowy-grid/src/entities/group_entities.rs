@@ -1,3 +1,4 @@
+use crate::entities::GridRowPB;
 use flowy_derive::ProtoBuf;
 use flowy_error::ErrorCode;
 use flowy_grid_data_model::parser::NotEmptyStr;
@@ -74,3 +75,99 @@ impl TryInto<CreateGridGroupParams> for CreateGridGroupPayloadPB {
         Ok(CreateGridGroupParams { field_id, sub_field_id })
     }
 }
+
+/// The rows of the grid are bucketed into groups by the group field, for example, the columns of
+/// the Board layout. The rows that don't belong to any group are put into the group whose id is
+/// the id of the group field.
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct GroupPB {
+    #[pb(index = 1)]
+    pub group_id: String,
+
+    #[pb(index = 2)]
+    pub desc: String,
+
+    #[pb(index = 3)]
+    pub rows: Vec<GridRowPB>,
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct RepeatedGroupPB {
+    #[pb(index = 1)]
+    pub items: Vec<GroupPB>,
+}
+
+impl std::convert::From<Vec<GroupPB>> for RepeatedGroupPB {
+    fn from(items: Vec<GroupPB>) -> Self {
+        Self { items }
+    }
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct MoveGroupRowPayloadPB {
+    #[pb(index = 1)]
+    pub grid_id: String,
+
+    #[pb(index = 2)]
+    pub row_id: String,
+
+    #[pb(index = 3)]
+    pub from_group_id: String,
+
+    #[pb(index = 4)]
+    pub to_group_id: String,
+}
+
+pub struct MoveGroupRowParams {
+    pub grid_id: String,
+    pub row_id: String,
+    pub from_group_id: String,
+    pub to_group_id: String,
+}
+
+impl TryInto<MoveGroupRowParams> for MoveGroupRowPayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<MoveGroupRowParams, Self::Error> {
+        let grid_id = NotEmptyStr::parse(self.grid_id).map_err(|_| ErrorCode::GridIdIsEmpty)?;
+        let row_id = NotEmptyStr::parse(self.row_id).map_err(|_| ErrorCode::RowIdIsEmpty)?;
+        let from_group_id = NotEmptyStr::parse(self.from_group_id).map_err(|_| ErrorCode::GroupIdIsEmpty)?;
+        let to_group_id = NotEmptyStr::parse(self.to_group_id).map_err(|_| ErrorCode::GroupIdIsEmpty)?;
+        Ok(MoveGroupRowParams {
+            grid_id: grid_id.0,
+            row_id: row_id.0,
+            from_group_id: from_group_id.0,
+            to_group_id: to_group_id.0,
+        })
+    }
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct GroupRowsChangesetPB {
+    #[pb(index = 1)]
+    pub group_id: String,
+
+    #[pb(index = 2)]
+    pub inserted_rows: Vec<GridRowPB>,
+
+    #[pb(index = 3)]
+    pub deleted_rows: Vec<String>,
+}
+
+impl GroupRowsChangesetPB {
+    pub fn insert(group_id: String, inserted_rows: Vec<GridRowPB>) -> Self {
+        Self {
+            group_id,
+            inserted_rows,
+            ..Default::default()
+        }
+    }
+
+    pub fn delete(group_id: String, deleted_rows: Vec<String>) -> Self {
+        Self {
+            group_id,
+            deleted_rows,
+            ..Default::default()
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod block_entities;
+mod calculation_entities;
 mod cell_entities;
 mod field_entities;
 mod filter_entities;
@@ -9,6 +10,7 @@ mod setting_entities;
 mod sort_entities;
 
 pub use block_entities::*;
+pub use calculation_entities::*;
 pub use cell_entities::*;
 pub use field_entities::*;
 pub use filter_entities::*;
@@ -1,6 +1,7 @@
 use crate::entities::{
-    CreateGridFilterPayloadPB, CreateGridGroupPayloadPB, CreateGridSortPayloadPB, DeleteFilterPayloadPB,
-    RepeatedGridFilterPB, RepeatedGridGroupPB, RepeatedGridSortPB,
+    CreateGridCalculationPayloadPB, CreateGridFilterGroupPayloadPB, CreateGridFilterPayloadPB,
+    CreateGridGroupPayloadPB, CreateGridSortPayloadPB, DeleteFilterPayloadPB, GridFilterGroup, RepeatedGridFilterPB,
+    RepeatedGridGroupPB, RepeatedGridSortPB,
 };
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
@@ -28,6 +29,9 @@ pub struct GridSettingPB {
 
     #[pb(index = 5)]
     pub sorts_by_field_id: HashMap<String, RepeatedGridSortPB>,
+
+    #[pb(index = 6, one_of)]
+    pub filter_group: Option<GridFilterGroup>,
 }
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
@@ -103,6 +107,19 @@ pub struct GridSettingChangesetPayloadPB {
 
     #[pb(index = 8, one_of)]
     pub delete_sort: Option<String>,
+
+    #[pb(index = 9, one_of)]
+    pub insert_filter_group: Option<CreateGridFilterGroupPayloadPB>,
+
+    #[pb(index = 10, one_of)]
+    pub delete_filter_group: Option<String>,
+
+    #[pb(index = 11, one_of)]
+    pub insert_calculation: Option<CreateGridCalculationPayloadPB>,
+
+    /// The id of the field whose calculation will be removed.
+    #[pb(index = 12, one_of)]
+    pub delete_calculation: Option<String>,
 }
 
 impl TryInto<GridSettingChangesetParams> for GridSettingChangesetPayloadPB {
@@ -143,6 +160,30 @@ impl TryInto<GridSettingChangesetParams> for GridSettingChangesetPayloadPB {
             Some(filter_id) => Some(NotEmptyStr::parse(filter_id).map_err(|_| ErrorCode::FieldIdIsEmpty)?.0),
         };
 
+        let insert_filter_group = match self.insert_filter_group {
+            None => None,
+            Some(payload) => Some(payload.try_into()?),
+        };
+
+        let delete_filter_group = match self.delete_filter_group {
+            None => None,
+            Some(filter_group_id) => Some(
+                NotEmptyStr::parse(filter_group_id)
+                    .map_err(|_| ErrorCode::UnexpectedEmptyString)?
+                    .0,
+            ),
+        };
+
+        let insert_calculation = match self.insert_calculation {
+            None => None,
+            Some(payload) => Some(payload.try_into()?),
+        };
+
+        let delete_calculation = match self.delete_calculation {
+            None => None,
+            Some(field_id) => Some(NotEmptyStr::parse(field_id).map_err(|_| ErrorCode::FieldIdIsEmpty)?.0),
+        };
+
         Ok(GridSettingChangesetParams {
             grid_id: view_id,
             layout_type: self.layout_type.into(),
@@ -152,6 +193,10 @@ impl TryInto<GridSettingChangesetParams> for GridSettingChangesetPayloadPB {
             delete_group,
             insert_sort,
             delete_sort,
+            insert_filter_group,
+            delete_filter_group,
+            insert_calculation,
+            delete_calculation,
         })
     }
 }
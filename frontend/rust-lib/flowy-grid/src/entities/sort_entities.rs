@@ -1,7 +1,8 @@
-use flowy_derive::ProtoBuf;
+use crate::entities::{FieldType, GridLayoutType};
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
 use flowy_grid_data_model::parser::NotEmptyStr;
-use flowy_grid_data_model::revision::GridSortRevision;
+use flowy_grid_data_model::revision::{FieldRevision, GridSortConditionRevision, GridSortRevision};
 use flowy_sync::entities::grid::CreateGridSortParams;
 use std::convert::TryInto;
 use std::sync::Arc;
@@ -11,16 +12,54 @@ pub struct GridSort {
     #[pb(index = 1)]
     pub id: String,
 
-    #[pb(index = 2, one_of)]
-    pub field_id: Option<String>,
+    #[pb(index = 2)]
+    pub field_id: String,
+
+    #[pb(index = 3)]
+    pub field_type: FieldType,
+
+    #[pb(index = 4)]
+    pub condition: GridSortCondition,
 }
 
 impl std::convert::From<&GridSortRevision> for GridSort {
     fn from(rev: &GridSortRevision) -> Self {
         GridSort {
             id: rev.id.clone(),
-
             field_id: rev.field_id.clone(),
+            field_type: rev.field_type_rev.into(),
+            condition: rev.condition.clone().into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum GridSortCondition {
+    Ascending = 0,
+    Descending = 1,
+}
+
+impl std::default::Default for GridSortCondition {
+    fn default() -> Self {
+        GridSortCondition::Ascending
+    }
+}
+
+impl std::convert::From<GridSortConditionRevision> for GridSortCondition {
+    fn from(rev: GridSortConditionRevision) -> Self {
+        match rev {
+            GridSortConditionRevision::Ascending => GridSortCondition::Ascending,
+            GridSortConditionRevision::Descending => GridSortCondition::Descending,
+        }
+    }
+}
+
+impl std::convert::From<GridSortCondition> for GridSortConditionRevision {
+    fn from(condition: GridSortCondition) -> Self {
+        match condition {
+            GridSortCondition::Ascending => GridSortConditionRevision::Ascending,
+            GridSortCondition::Descending => GridSortConditionRevision::Descending,
         }
     }
 }
@@ -47,19 +86,78 @@ impl std::convert::From<Vec<GridSort>> for RepeatedGridSortPB {
 
 #[derive(ProtoBuf, Debug, Default, Clone)]
 pub struct CreateGridSortPayloadPB {
-    #[pb(index = 1, one_of)]
-    pub field_id: Option<String>,
+    #[pb(index = 1)]
+    pub field_id: String,
+
+    #[pb(index = 2)]
+    pub field_type: FieldType,
+
+    #[pb(index = 3)]
+    pub condition: GridSortCondition,
+}
+
+impl CreateGridSortPayloadPB {
+    #[allow(dead_code)]
+    pub fn new(field_rev: &FieldRevision, condition: GridSortCondition) -> Self {
+        Self {
+            field_id: field_rev.id.clone(),
+            field_type: field_rev.field_type_rev.into(),
+            condition,
+        }
+    }
 }
 
 impl TryInto<CreateGridSortParams> for CreateGridSortPayloadPB {
     type Error = ErrorCode;
 
     fn try_into(self) -> Result<CreateGridSortParams, Self::Error> {
-        let field_id = match self.field_id {
-            None => None,
-            Some(field_id) => Some(NotEmptyStr::parse(field_id).map_err(|_| ErrorCode::FieldIdIsEmpty)?.0),
-        };
+        let field_id = NotEmptyStr::parse(self.field_id)
+            .map_err(|_| ErrorCode::FieldIdIsEmpty)?
+            .0;
 
-        Ok(CreateGridSortParams { field_id })
+        Ok(CreateGridSortParams {
+            field_id,
+            field_type_rev: self.field_type.into(),
+            condition: self.condition.into(),
+        })
     }
 }
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct QueryGridSortPayloadPB {
+    #[pb(index = 1)]
+    pub grid_id: String,
+
+    #[pb(index = 2)]
+    pub layout_type: GridLayoutType,
+}
+
+pub struct QueryGridSortParams {
+    pub grid_id: String,
+    pub layout_type: GridLayoutType,
+}
+
+impl TryInto<QueryGridSortParams> for QueryGridSortPayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<QueryGridSortParams, Self::Error> {
+        let grid_id = NotEmptyStr::parse(self.grid_id)
+            .map_err(|_| ErrorCode::GridIdIsEmpty)?
+            .0;
+        Ok(QueryGridSortParams {
+            grid_id,
+            layout_type: self.layout_type,
+        })
+    }
+}
+
+/// Notify the rows of the block were reordered by the sorts. The row_ids contains all the rows of the block
+/// in the new order.
+#[derive(Debug, Default, ProtoBuf)]
+pub struct GridRowOrderChangesetPB {
+    #[pb(index = 1)]
+    pub block_id: String,
+
+    #[pb(index = 2)]
+    pub row_ids: Vec<String>,
+}
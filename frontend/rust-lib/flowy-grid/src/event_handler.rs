@@ -1,11 +1,12 @@
 use crate::entities::*;
-use crate::manager::GridManager;
+use crate::manager::{import_grid_from_csv, GridManager};
 use crate::services::cell::AnyCellData;
 use crate::services::field::{
     default_type_option_builder_from_type, select_option_operation, type_option_builder_from_json_str,
-    DateChangesetParams, DateChangesetPayloadPB, SelectOptionCellChangeset, SelectOptionCellChangesetParams,
-    SelectOptionCellChangesetPayloadPB, SelectOptionCellDataPB, SelectOptionChangeset, SelectOptionChangesetPayloadPB,
-    SelectOptionPB,
+    DateChangesetParams, DateChangesetPayloadPB, RelationCellChangesetParams, RelationCellChangesetPayloadPB,
+    RichTextCellChangesetParams, RichTextCellChangesetPayloadPB, RichTextCellDataPB, RichTextTypeOption,
+    SelectOptionCellChangeset, SelectOptionCellChangesetParams, SelectOptionCellChangesetPayloadPB,
+    SelectOptionCellDataPB, SelectOptionChangeset, SelectOptionChangesetPayloadPB, SelectOptionPB,
 };
 use crate::services::row::make_row_from_row_rev;
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
@@ -25,6 +26,29 @@ pub(crate) async fn get_grid_handler(
     data_result(grid)
 }
 
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn export_grid_handler(
+    data: Data<GridIdPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> DataResult<ExportGridPB, FlowyError> {
+    let grid_id: GridIdPB = data.into_inner();
+    let editor = manager.open_grid(grid_id).await?;
+    let content = editor.export_csv().await?;
+    data_result(ExportGridPB { content })
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn import_grid_handler(
+    data: Data<ImportGridPayloadPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> DataResult<GridPB, FlowyError> {
+    let params: ImportGridParams = data.into_inner().try_into()?;
+    let _ = import_grid_from_csv(manager.get_ref().clone(), &params.view_id, &params.content).await?;
+    let editor = manager.open_grid(&params.view_id).await?;
+    let grid = editor.get_grid_data().await?;
+    data_result(grid)
+}
+
 #[tracing::instrument(level = "trace", skip(data, manager), err)]
 pub(crate) async fn get_grid_setting_handler(
     data: Data<GridIdPB>,
@@ -47,6 +71,39 @@ pub(crate) async fn update_grid_setting_handler(
     Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_grid_sorts_handler(
+    data: Data<QueryGridSortPayloadPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> DataResult<RepeatedGridSortPB, FlowyError> {
+    let params: QueryGridSortParams = data.into_inner().try_into()?;
+    let editor = manager.open_grid(&params.grid_id).await?;
+    let sorts = editor.get_grid_sorts(&params.layout_type).await?;
+    data_result(sorts.into())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_groups_handler(
+    data: Data<GridIdPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> DataResult<RepeatedGroupPB, FlowyError> {
+    let grid_id: GridIdPB = data.into_inner();
+    let editor = manager.open_grid(grid_id).await?;
+    let groups = editor.get_groups().await?;
+    data_result(groups.into())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn move_group_row_handler(
+    data: Data<MoveGroupRowPayloadPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> Result<(), FlowyError> {
+    let params: MoveGroupRowParams = data.into_inner().try_into()?;
+    let editor = manager.get_grid_editor(&params.grid_id)?;
+    let _ = editor.move_group_row(params).await?;
+    Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip(data, manager), err)]
 pub(crate) async fn get_grid_blocks_handler(
     data: Data<QueryGridBlocksPayloadPB>,
@@ -297,6 +354,44 @@ pub(crate) async fn update_cell_handler(
     Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_rich_text_cell_data_handler(
+    data: Data<GridCellIdPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> DataResult<RichTextCellDataPB, FlowyError> {
+    let params: GridCellIdParams = data.into_inner().try_into()?;
+    let editor = manager.get_grid_editor(&params.grid_id)?;
+    match editor.get_field_rev(&params.field_id).await {
+        None => {
+            tracing::error!("Can't find the rich text field with id: {}", params.field_id);
+            data_result(RichTextCellDataPB::default())
+        }
+        Some(field_rev) => {
+            let cell_rev = editor.get_cell_rev(&params.row_id, &params.field_id).await?;
+            let type_option = RichTextTypeOption::from(&field_rev);
+            let any_cell_data: AnyCellData = match cell_rev {
+                None => AnyCellData {
+                    data: "".to_string(),
+                    field_type: field_rev.field_type_rev.into(),
+                },
+                Some(cell_rev) => cell_rev.try_into()?,
+            };
+            data_result(type_option.rich_text_cell_data(any_cell_data))
+        }
+    }
+}
+
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn update_rich_text_cell_handler(
+    data: Data<RichTextCellChangesetPayloadPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> Result<(), FlowyError> {
+    let params: RichTextCellChangesetParams = data.into_inner().try_into()?;
+    let editor = manager.get_grid_editor(&params.cell_identifier.grid_id)?;
+    let _ = editor.update_rich_text_cell(params).await?;
+    Ok(())
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 pub(crate) async fn new_select_option_handler(
     data: Data<CreateSelectOptionPayloadPB>,
@@ -405,3 +500,58 @@ pub(crate) async fn update_date_cell_handler(
     let _ = editor.update_cell(params.into()).await?;
     Ok(())
 }
+
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn update_relation_cell_handler(
+    data: Data<RelationCellChangesetPayloadPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> Result<(), FlowyError> {
+    let params: RelationCellChangesetParams = data.into_inner().try_into()?;
+    let editor = manager.get_grid_editor(&params.cell_identifier.grid_id)?;
+    let _ = editor.update_cell(params.into()).await?;
+    Ok(())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn undo_grid_handler(
+    data: Data<GridIdPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> DataResult<GridHistoryStatePB, FlowyError> {
+    let grid_id: GridIdPB = data.into_inner();
+    let editor = manager.open_grid(grid_id).await?;
+    let history_state = editor.undo().await?;
+    data_result(history_state)
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn redo_grid_handler(
+    data: Data<GridIdPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> DataResult<GridHistoryStatePB, FlowyError> {
+    let grid_id: GridIdPB = data.into_inner();
+    let editor = manager.open_grid(grid_id).await?;
+    let history_state = editor.redo().await?;
+    data_result(history_state)
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn get_grid_calculations_handler(
+    data: Data<QueryGridCalculationPayloadPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> DataResult<RepeatedGridCalculationPB, FlowyError> {
+    let params: QueryGridCalculationParams = data.into_inner().try_into()?;
+    let editor = manager.open_grid(&params.grid_id).await?;
+    let calculations = editor.get_grid_calculations(&params.layout_type).await?;
+    data_result(calculations.into())
+}
+
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn update_grid_calculation_handler(
+    data: Data<GridCalculationChangesetPayloadPB>,
+    manager: AppData<Arc<GridManager>>,
+) -> Result<(), FlowyError> {
+    let params: GridSettingChangesetParams = data.into_inner().try_into()?;
+    let editor = manager.open_grid(&params.grid_id).await?;
+    let _ = editor.update_grid_setting(params).await?;
+    Ok(())
+}
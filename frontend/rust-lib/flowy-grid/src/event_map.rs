@@ -12,6 +12,11 @@ pub fn create(grid_manager: Arc<GridManager>) -> Module {
         .event(GridEvent::GetGridBlocks, get_grid_blocks_handler)
         .event(GridEvent::GetGridSetting, get_grid_setting_handler)
         .event(GridEvent::UpdateGridSetting, update_grid_setting_handler)
+        .event(GridEvent::GetGridSorts, get_grid_sorts_handler)
+        .event(GridEvent::GetGroups, get_groups_handler)
+        .event(GridEvent::MoveGroupRow, move_group_row_handler)
+        .event(GridEvent::ExportGrid, export_grid_handler)
+        .event(GridEvent::ImportGrid, import_grid_handler)
         // Field
         .event(GridEvent::GetFields, get_fields_handler)
         .event(GridEvent::UpdateField, update_field_handler)
@@ -31,13 +36,23 @@ pub fn create(grid_manager: Arc<GridManager>) -> Module {
         // Cell
         .event(GridEvent::GetCell, get_cell_handler)
         .event(GridEvent::UpdateCell, update_cell_handler)
+        .event(GridEvent::GetRichTextCellData, get_rich_text_cell_data_handler)
+        .event(GridEvent::UpdateRichTextCell, update_rich_text_cell_handler)
         // SelectOption
         .event(GridEvent::NewSelectOption, new_select_option_handler)
         .event(GridEvent::UpdateSelectOption, update_select_option_handler)
         .event(GridEvent::GetSelectOptionCellData, get_select_option_handler)
         .event(GridEvent::UpdateSelectOptionCell, update_select_option_cell_handler)
         // Date
-        .event(GridEvent::UpdateDateCell, update_date_cell_handler);
+        .event(GridEvent::UpdateDateCell, update_date_cell_handler)
+        // Relation
+        .event(GridEvent::UpdateRelationCell, update_relation_cell_handler)
+        // History
+        .event(GridEvent::Undo, undo_grid_handler)
+        .event(GridEvent::Redo, redo_grid_handler)
+        // Calculation
+        .event(GridEvent::GetGridCalculations, get_grid_calculations_handler)
+        .event(GridEvent::UpdateGridCalculation, update_grid_calculation_handler);
 
     module
 }
@@ -57,6 +72,21 @@ pub enum GridEvent {
     #[event(input = "GridIdPB", input = "GridSettingChangesetPayloadPB")]
     UpdateGridSetting = 3,
 
+    #[event(input = "QueryGridSortPayloadPB", output = "RepeatedGridSortPB")]
+    GetGridSorts = 4,
+
+    #[event(input = "GridIdPB", output = "RepeatedGroupPB")]
+    GetGroups = 5,
+
+    #[event(input = "MoveGroupRowPayloadPB")]
+    MoveGroupRow = 6,
+
+    #[event(input = "GridIdPB", output = "ExportGridPB")]
+    ExportGrid = 7,
+
+    #[event(input = "ImportGridPayloadPB", output = "GridPB")]
+    ImportGrid = 8,
+
     #[event(input = "QueryFieldPayloadPB", output = "RepeatedGridFieldPB")]
     GetFields = 10,
 
@@ -117,6 +147,31 @@ pub enum GridEvent {
     #[event(input = "SelectOptionCellChangesetPayloadPB")]
     UpdateSelectOptionCell = 72,
 
+    #[event(input = "GridCellIdPB", output = "RichTextCellDataPB")]
+    GetRichTextCellData = 73,
+
+    #[event(input = "RichTextCellChangesetPayloadPB")]
+    UpdateRichTextCell = 74,
+
     #[event(input = "DateChangesetPayloadPB")]
     UpdateDateCell = 80,
+
+    #[event(input = "RelationCellChangesetPayloadPB")]
+    UpdateRelationCell = 90,
+
+    /// Revert the latest change of the fields, the rows or the cells in the grid.
+    #[event(input = "GridIdPB", output = "GridHistoryStatePB")]
+    Undo = 100,
+
+    #[event(input = "GridIdPB", output = "GridHistoryStatePB")]
+    Redo = 101,
+
+    /// Return the calculations of the fields with their values, which are shown in the summary row
+    /// under the grid.
+    #[event(input = "QueryGridCalculationPayloadPB", output = "RepeatedGridCalculationPB")]
+    GetGridCalculations = 110,
+
+    /// Set or remove the calculation of the field.
+    #[event(input = "GridCalculationChangesetPayloadPB")]
+    UpdateGridCalculation = 111,
 }
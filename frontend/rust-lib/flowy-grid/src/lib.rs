@@ -5,7 +5,7 @@ mod event_handler;
 pub mod event_map;
 pub mod manager;
 
-mod dart_notification;
+pub mod dart_notification;
 pub mod entities;
 mod protobuf;
 pub mod services;
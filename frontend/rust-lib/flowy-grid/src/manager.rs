@@ -1,9 +1,11 @@
 use crate::services::block_revision_editor::GridBlockRevisionCompactor;
+use crate::services::csv::make_grid_from_csv;
 use crate::services::grid_editor::{GridRevisionCompactor, GridRevisionEditor};
 use crate::services::persistence::block_index::BlockIndexCache;
 use crate::services::persistence::kv::GridKVPersistence;
 use crate::services::persistence::migration::GridMigration;
 use crate::services::persistence::GridDatabase;
+use crate::services::relation::GridRelationResolver;
 use crate::services::tasks::GridTaskScheduler;
 use bytes::Bytes;
 use dashmap::DashMap;
@@ -11,11 +13,18 @@ use flowy_database::ConnectionPool;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_grid_data_model::revision::{BuildGridContext, GridRevision};
 use flowy_revision::disk::{SQLiteGridBlockRevisionPersistence, SQLiteGridRevisionPersistence};
-use flowy_revision::{RevisionManager, RevisionPersistence, RevisionWebSocket, SQLiteRevisionSnapshotPersistence};
-use flowy_sync::client_grid::{make_grid_block_delta, make_grid_delta};
+use flowy_revision::{
+    delete_revision_object, RevisionLoader, RevisionManager, RevisionPersistence, RevisionWebSocket,
+    SQLiteRevisionSnapshotPersistence,
+};
+use flowy_sync::client_grid::{make_grid_block_delta, make_grid_delta, GridRevisionPad};
 use flowy_sync::entities::revision::{RepeatedRevision, Revision};
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use flowy_sync::entities::ws_data::ServerRevisionWSData;
+use futures::future::BoxFuture;
+use std::convert::TryInto;
+use std::sync::{Arc, Weak};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, RwLock};
 
 pub trait GridUser: Send + Sync {
     fn user_id(&self) -> Result<String, FlowyError>;
@@ -25,20 +34,23 @@ pub trait GridUser: Send + Sync {
 
 pub type GridTaskSchedulerRwLock = Arc<RwLock<GridTaskScheduler>>;
 
+type GridEditorMap = DashMap<String, Arc<GridRevisionEditor>>;
+
 pub struct GridManager {
-    grid_editors: Arc<DashMap<String, Arc<GridRevisionEditor>>>,
+    grid_editors: Arc<GridEditorMap>,
     grid_user: Arc<dyn GridUser>,
     block_index_cache: Arc<BlockIndexCache>,
     #[allow(dead_code)]
     kv_persistence: Arc<GridKVPersistence>,
     task_scheduler: GridTaskSchedulerRwLock,
     migration: GridMigration,
+    editor_opener: GridEditorOpener,
 }
 
 impl GridManager {
     pub fn new(
         grid_user: Arc<dyn GridUser>,
-        _rev_web_socket: Arc<dyn RevisionWebSocket>,
+        rev_web_socket: Arc<dyn RevisionWebSocket>,
         database: Arc<dyn GridDatabase>,
     ) -> Self {
         let grid_editors = Arc::new(DashMap::new());
@@ -46,6 +58,15 @@ impl GridManager {
         let block_index_cache = Arc::new(BlockIndexCache::new(database.clone()));
         let task_scheduler = GridTaskScheduler::new();
         let migration = GridMigration::new(grid_user.clone(), database);
+        let (grid_changed_notifier, _) = broadcast::channel(100);
+        let editor_opener = GridEditorOpener {
+            grid_editors: Arc::downgrade(&grid_editors),
+            grid_user: grid_user.clone(),
+            block_index_cache: block_index_cache.clone(),
+            task_scheduler: task_scheduler.clone(),
+            rev_web_socket,
+            grid_changed_notifier,
+        };
         Self {
             grid_editors,
             grid_user,
@@ -53,6 +74,7 @@ impl GridManager {
             block_index_cache,
             task_scheduler,
             migration,
+            editor_opener,
         }
     }
 
@@ -94,7 +116,9 @@ impl GridManager {
     pub async fn close_grid<T: AsRef<str>>(&self, grid_id: T) -> FlowyResult<()> {
         let grid_id = grid_id.as_ref();
         tracing::Span::current().record("grid_id", &grid_id);
-        self.grid_editors.remove(grid_id);
+        if let Some((_, editor)) = self.grid_editors.remove(grid_id) {
+            editor.stop();
+        }
         self.task_scheduler.write().await.unregister_handler(grid_id);
         Ok(())
     }
@@ -103,9 +127,62 @@ impl GridManager {
     pub async fn delete_grid<T: AsRef<str>>(&self, grid_id: T) -> FlowyResult<()> {
         let grid_id = grid_id.as_ref();
         tracing::Span::current().record("grid_id", &grid_id);
-        self.grid_editors.remove(grid_id);
+        if let Some((_, editor)) = self.grid_editors.remove(grid_id) {
+            editor.stop();
+        }
         self.task_scheduler.write().await.unregister_handler(grid_id);
-        Ok(())
+
+        let user_id = self.grid_user.user_id()?;
+        let pool = self.grid_user.db_pool()?;
+        let disk_cache = SQLiteGridRevisionPersistence::new(&user_id, pool.clone());
+        let rev_persistence = Arc::new(RevisionPersistence::new(&user_id, grid_id, disk_cache));
+        let (revisions, _) = RevisionLoader {
+            object_id: grid_id.to_owned(),
+            user_id: user_id.clone(),
+            cloud: None,
+            rev_persistence,
+        }
+        .load()
+        .await?;
+
+        // The blocks of the grid are stored as separate objects, so delete them before the grid itself.
+        if !revisions.is_empty() {
+            let grid_pad = GridRevisionPad::from_revisions(revisions)?;
+            for block_meta_rev in grid_pad.get_block_meta_revs() {
+                let disk_cache = SQLiteGridBlockRevisionPersistence::new(&user_id, pool.clone());
+                let _ = delete_revision_object(&block_meta_rev.block_id, disk_cache, pool.clone())?;
+            }
+        }
+        let disk_cache = SQLiteGridRevisionPersistence::new(&user_id, pool.clone());
+        delete_revision_object(grid_id, disk_cache, pool)
+    }
+
+    /// Subscribe the ids of the grids whose fields or rows were changed, either locally or remotely.
+    pub fn subscribe_grid_changed(&self) -> broadcast::Receiver<String> {
+        self.editor_opener.grid_changed_notifier.subscribe()
+    }
+
+    pub async fn receive_ws_data(&self, data: Bytes) {
+        let result: Result<ServerRevisionWSData, protobuf::ProtobufError> = data.try_into();
+        match result {
+            Ok(data) => {
+                let editor = self
+                    .grid_editors
+                    .iter()
+                    .find(|editor| editor.value().contains_object(&data.object_id))
+                    .map(|editor| editor.value().clone());
+                match editor {
+                    None => tracing::error!("Can't find any source handler for {:?}-{:?}", data.object_id, data.ty),
+                    Some(editor) => match editor.receive_ws_data(data).await {
+                        Ok(_) => {}
+                        Err(e) => tracing::error!("{}", e),
+                    },
+                }
+            }
+            Err(e) => {
+                tracing::error!("Grid ws data parser failed: {:?}", e);
+            }
+        }
     }
 
     // pub fn update_grid_info()
@@ -119,21 +196,61 @@ impl GridManager {
     }
 
     async fn get_or_create_grid_editor(&self, grid_id: &str) -> FlowyResult<Arc<GridRevisionEditor>> {
-        match self.grid_editors.get(grid_id) {
-            None => {
-                tracing::trace!("Create grid editor with id: {}", grid_id);
-                let db_pool = self.grid_user.db_pool()?;
-                let editor = self.make_grid_rev_editor(grid_id, db_pool).await?;
+        self.editor_opener.get_or_create_grid_editor(grid_id).await
+    }
 
-                if self.grid_editors.contains_key(grid_id) {
-                    tracing::warn!("Grid:{} already exists in cache", grid_id);
-                }
-                self.grid_editors.insert(grid_id.to_string(), editor.clone());
-                self.task_scheduler.write().await.register_handler(editor.clone());
-                Ok(editor)
-            }
-            Some(editor) => Ok(editor.clone()),
+    pub fn make_grid_rev_manager(&self, grid_id: &str, pool: Arc<ConnectionPool>) -> FlowyResult<RevisionManager> {
+        make_grid_rev_manager(&self.grid_user, grid_id, pool)
+    }
+
+    fn make_grid_block_rev_manager(&self, block_id: &str, pool: Arc<ConnectionPool>) -> FlowyResult<RevisionManager> {
+        let user_id = self.grid_user.user_id()?;
+        let disk_cache = SQLiteGridBlockRevisionPersistence::new(&user_id, pool.clone());
+        let rev_persistence = RevisionPersistence::new(&user_id, block_id, disk_cache);
+        let rev_compactor = GridBlockRevisionCompactor();
+        let snapshot_persistence = SQLiteRevisionSnapshotPersistence::new(block_id, pool);
+        let rev_manager =
+            RevisionManager::new(&user_id, block_id, rev_persistence, rev_compactor, snapshot_persistence);
+        Ok(rev_manager)
+    }
+}
+
+/// Opens the grid editors and caches them in the [GridManager]. It's also used by the editors to
+/// resolve the other grids, for example, the target grid of the relation field. It holds the cache
+/// weakly, so the cached editors don't keep themselves alive.
+#[derive(Clone)]
+struct GridEditorOpener {
+    grid_editors: Weak<GridEditorMap>,
+    grid_user: Arc<dyn GridUser>,
+    block_index_cache: Arc<BlockIndexCache>,
+    task_scheduler: GridTaskSchedulerRwLock,
+    rev_web_socket: Arc<dyn RevisionWebSocket>,
+    grid_changed_notifier: broadcast::Sender<String>,
+}
+
+impl GridEditorOpener {
+    async fn get_or_create_grid_editor(&self, grid_id: &str) -> FlowyResult<Arc<GridRevisionEditor>> {
+        let grid_editors = self
+            .grid_editors
+            .upgrade()
+            .ok_or_else(|| FlowyError::internal().context("The grid manager was dropped"))?;
+        if let Some(editor) = grid_editors.get(grid_id) {
+            return Ok(editor.clone());
         }
+
+        tracing::trace!("Create grid editor with id: {}", grid_id);
+        let db_pool = self.grid_user.db_pool()?;
+        let editor = self.make_grid_rev_editor(grid_id, db_pool).await?;
+
+        if grid_editors.contains_key(grid_id) {
+            tracing::warn!("Grid:{} already exists in cache", grid_id);
+        }
+        grid_editors.insert(grid_id.to_string(), editor.clone());
+        self.task_scheduler.write().await.register_handler(editor.clone());
+        for rx in editor.subscribe_revisions() {
+            listen_revisions(grid_id, rx, self.grid_changed_notifier.clone());
+        }
+        Ok(editor)
     }
 
     #[tracing::instrument(level = "trace", skip(self, pool), err)]
@@ -143,38 +260,56 @@ impl GridManager {
         pool: Arc<ConnectionPool>,
     ) -> Result<Arc<GridRevisionEditor>, FlowyError> {
         let user = self.grid_user.clone();
-        let rev_manager = self.make_grid_rev_manager(grid_id, pool.clone())?;
+        let rev_manager = make_grid_rev_manager(&user, grid_id, pool)?;
         let grid_editor = GridRevisionEditor::new(
             grid_id,
             user,
             rev_manager,
             self.block_index_cache.clone(),
             self.task_scheduler.clone(),
+            self.rev_web_socket.clone(),
+            Arc::new(self.clone()),
         )
         .await?;
         Ok(grid_editor)
     }
+}
 
-    pub fn make_grid_rev_manager(&self, grid_id: &str, pool: Arc<ConnectionPool>) -> FlowyResult<RevisionManager> {
-        let user_id = self.grid_user.user_id()?;
-        let disk_cache = SQLiteGridRevisionPersistence::new(&user_id, pool.clone());
-        let rev_persistence = RevisionPersistence::new(&user_id, grid_id, disk_cache);
-        let snapshot_persistence = SQLiteRevisionSnapshotPersistence::new(grid_id, pool);
-        let rev_compactor = GridRevisionCompactor();
-        let rev_manager = RevisionManager::new(&user_id, grid_id, rev_persistence, rev_compactor, snapshot_persistence);
-        Ok(rev_manager)
+impl GridRelationResolver for GridEditorOpener {
+    fn get_grid_editor(&self, grid_id: &str) -> BoxFuture<FlowyResult<Arc<GridRevisionEditor>>> {
+        let opener = self.clone();
+        let grid_id = grid_id.to_owned();
+        Box::pin(async move { opener.get_or_create_grid_editor(&grid_id).await })
     }
+}
 
-    fn make_grid_block_rev_manager(&self, block_id: &str, pool: Arc<ConnectionPool>) -> FlowyResult<RevisionManager> {
-        let user_id = self.grid_user.user_id()?;
-        let disk_cache = SQLiteGridBlockRevisionPersistence::new(&user_id, pool.clone());
-        let rev_persistence = RevisionPersistence::new(&user_id, block_id, disk_cache);
-        let rev_compactor = GridBlockRevisionCompactor();
-        let snapshot_persistence = SQLiteRevisionSnapshotPersistence::new(block_id, pool);
-        let rev_manager =
-            RevisionManager::new(&user_id, block_id, rev_persistence, rev_compactor, snapshot_persistence);
-        Ok(rev_manager)
-    }
+fn listen_revisions(grid_id: &str, mut rx: broadcast::Receiver<Revision>, notifier: broadcast::Sender<String>) {
+    let grid_id = grid_id.to_owned();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                // Some revisions were skipped, but the grid was changed anyway.
+                Ok(_) | Err(RecvError::Lagged(_)) => {
+                    let _ = notifier.send(grid_id.clone());
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+fn make_grid_rev_manager(
+    grid_user: &Arc<dyn GridUser>,
+    grid_id: &str,
+    pool: Arc<ConnectionPool>,
+) -> FlowyResult<RevisionManager> {
+    let user_id = grid_user.user_id()?;
+    let disk_cache = SQLiteGridRevisionPersistence::new(&user_id, pool.clone());
+    let rev_persistence = RevisionPersistence::new(&user_id, grid_id, disk_cache);
+    let snapshot_persistence = SQLiteRevisionSnapshotPersistence::new(grid_id, pool);
+    let rev_compactor = GridRevisionCompactor();
+    let rev_manager = RevisionManager::new(&user_id, grid_id, rev_persistence, rev_compactor, snapshot_persistence);
+    Ok(rev_manager)
 }
 
 pub async fn make_grid_view_data(
@@ -209,3 +344,11 @@ pub async fn make_grid_view_data(
 
     Ok(grid_delta_data)
 }
+
+/// Create the grid of the view from the CSV content. The fields are inferred from the columns of
+/// the CSV, see [make_grid_from_csv] for details.
+pub async fn import_grid_from_csv(grid_manager: Arc<GridManager>, view_id: &str, content: &str) -> FlowyResult<Bytes> {
+    let build_context = make_grid_from_csv(content)?;
+    let user_id = grid_manager.grid_user.user_id()?;
+    make_grid_view_data(&user_id, view_id, grid_manager, build_context).await
+}
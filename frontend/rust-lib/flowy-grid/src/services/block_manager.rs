@@ -10,17 +10,20 @@ use flowy_grid_data_model::revision::{
     GridBlockMetaRevision, GridBlockMetaRevisionChangeset, RowMetaChangeset, RowRevision,
 };
 use flowy_revision::disk::SQLiteGridBlockRevisionPersistence;
-use flowy_revision::{RevisionManager, RevisionPersistence, SQLiteRevisionSnapshotPersistence};
+use flowy_revision::{RevisionManager, RevisionPersistence, RevisionWebSocket, SQLiteRevisionSnapshotPersistence};
+use flowy_sync::client_grid::{GridHistoryItem, RowHistoryOperation};
+use flowy_sync::entities::revision::Revision;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::sync::broadcast;
 
 type BlockId = String;
 pub(crate) struct GridBlockManager {
-    #[allow(dead_code)]
     grid_id: String,
     user: Arc<dyn GridUser>,
     persistence: Arc<BlockIndexCache>,
+    web_socket: Arc<dyn RevisionWebSocket>,
     block_editors: DashMap<BlockId, Arc<GridBlockRevisionEditor>>,
 }
 
@@ -30,8 +33,9 @@ impl GridBlockManager {
         user: &Arc<dyn GridUser>,
         block_meta_revs: Vec<Arc<GridBlockMetaRevision>>,
         persistence: Arc<BlockIndexCache>,
+        web_socket: Arc<dyn RevisionWebSocket>,
     ) -> FlowyResult<Self> {
-        let block_editors = make_block_editors(user, block_meta_revs).await?;
+        let block_editors = make_block_editors(user, block_meta_revs, &web_socket).await?;
         let user = user.clone();
         let grid_id = grid_id.to_owned();
         let manager = Self {
@@ -39,6 +43,7 @@ impl GridBlockManager {
             user,
             block_editors,
             persistence,
+            web_socket,
         };
         Ok(manager)
     }
@@ -49,7 +54,7 @@ impl GridBlockManager {
         match self.block_editors.get(block_id) {
             None => {
                 tracing::error!("This is a fatal error, block with id:{} is not exist", block_id);
-                let editor = Arc::new(make_block_editor(&self.user, block_id).await?);
+                let editor = Arc::new(make_block_editor(&self.user, block_id, &self.web_socket).await?);
                 self.block_editors.insert(block_id.to_owned(), editor.clone());
                 Ok(editor)
             }
@@ -57,6 +62,15 @@ impl GridBlockManager {
         }
     }
 
+    pub(crate) fn contains_block(&self, block_id: &str) -> bool {
+        self.block_editors.contains_key(block_id)
+    }
+
+    /// Stop synchronizing the revisions of all the blocks.
+    pub(crate) fn stop(&self) {
+        self.block_editors.iter().for_each(|editor| editor.value().stop());
+    }
+
     async fn get_editor_from_row_id(&self, row_id: &str) -> FlowyResult<Arc<GridBlockRevisionEditor>> {
         let block_id = self.persistence.get_block_id(row_id)?;
         Ok(self.get_editor(&block_id).await?)
@@ -114,8 +128,21 @@ impl GridBlockManager {
     {
         let editor = self.get_editor_from_row_id(&changeset.row_id).await?;
         let _ = editor.update_row(changeset.clone()).await?;
-        match editor.get_row_rev(&changeset.row_id).await? {
-            None => tracing::error!("Internal error: can't find the row with id: {}", changeset.row_id),
+        self.notify_did_update_row(&editor, &changeset.row_id, row_builder)
+            .await
+    }
+
+    async fn notify_did_update_row<F>(
+        &self,
+        editor: &GridBlockRevisionEditor,
+        row_id: &str,
+        row_builder: F,
+    ) -> FlowyResult<()>
+    where
+        F: FnOnce(Arc<RowRevision>) -> Option<GridRowPB>,
+    {
+        match editor.get_row_rev(row_id).await? {
+            None => tracing::error!("Internal error: can't find the row with id: {}", row_id),
             Some(row_rev) => {
                 if let Some(row) = row_builder(row_rev.clone()) {
                     let row_order = UpdatedRowPB::new(&row_rev, row);
@@ -169,14 +196,17 @@ impl GridBlockManager {
     pub(crate) async fn move_row(&self, row_id: &str, from: usize, to: usize) -> FlowyResult<()> {
         let editor = self.get_editor_from_row_id(row_id).await?;
         let _ = editor.move_row(row_id, from, to).await?;
+        self.notify_did_move_row(&editor, row_id, to as i32).await
+    }
 
+    async fn notify_did_move_row(&self, editor: &GridBlockRevisionEditor, row_id: &str, to: i32) -> FlowyResult<()> {
         match editor.get_row_revs(Some(vec![Cow::Borrowed(row_id)])).await?.pop() {
             None => {}
             Some(row_rev) => {
                 let insert_row = InsertedRowPB {
                     block_id: row_rev.block_id.clone(),
                     row_id: row_rev.id.clone(),
-                    index: Some(to as i32),
+                    index: Some(to),
                     height: row_rev.height,
                 };
 
@@ -206,6 +236,143 @@ impl GridBlockManager {
         Ok(())
     }
 
+    /// Update the cell that is computed from the other cells, e.g. the formula cell. The change isn't
+    /// recorded in the history because the cell is recomputed after undoing or redoing.
+    pub async fn update_computed_cell<F>(&self, changeset: CellChangesetPB, row_builder: F) -> FlowyResult<()>
+    where
+        F: FnOnce(Arc<RowRevision>) -> Option<GridRowPB>,
+    {
+        let row_changeset: RowMetaChangeset = changeset.clone().into();
+        let editor = self.get_editor_from_row_id(&row_changeset.row_id).await?;
+        let _ = editor.update_row_without_history(row_changeset).await?;
+        let _ = self
+            .notify_did_update_row(&editor, &changeset.row_id, row_builder)
+            .await?;
+        self.notify_did_update_cell(changeset).await?;
+        Ok(())
+    }
+
+    /// Return the id of the block whose last change is the latest one among the blocks, and the item
+    /// that reverts the change.
+    pub(crate) async fn last_undo(&self) -> Option<(String, GridHistoryItem<RowHistoryOperation>)> {
+        let mut last_undo: Option<(String, GridHistoryItem<RowHistoryOperation>)> = None;
+        for editor in self.get_editors() {
+            if let Some(item) = editor.last_undo().await {
+                if last_undo
+                    .as_ref()
+                    .map(|(_, last_item)| item.seq > last_item.seq)
+                    .unwrap_or(true)
+                {
+                    last_undo = Some((editor.block_id.clone(), item));
+                }
+            }
+        }
+        last_undo
+    }
+
+    /// Return the id of the block whose last reverted change is the latest reverted one among the
+    /// blocks, and the item that reapplies the change. The changes are reverted from the latest
+    /// to the earliest, so the latest reverted change is the earliest one.
+    pub(crate) async fn last_redo(&self) -> Option<(String, GridHistoryItem<RowHistoryOperation>)> {
+        let mut last_redo: Option<(String, GridHistoryItem<RowHistoryOperation>)> = None;
+        for editor in self.get_editors() {
+            if let Some(item) = editor.last_redo().await {
+                if last_redo
+                    .as_ref()
+                    .map(|(_, last_item)| item.seq < last_item.seq)
+                    .unwrap_or(true)
+                {
+                    last_redo = Some((editor.block_id.clone(), item));
+                }
+            }
+        }
+        last_redo
+    }
+
+    /// Revert the last change of the block, the `operation` is the one that reverts the change.
+    /// Return false if the change can't be reverted anymore.
+    pub(crate) async fn undo<F>(
+        &self,
+        block_id: &str,
+        operation: &RowHistoryOperation,
+        row_builder: F,
+    ) -> FlowyResult<bool>
+    where
+        F: Fn(Arc<RowRevision>) -> Option<GridRowPB>,
+    {
+        let editor = self.get_editor(block_id).await?;
+        if !editor.undo().await? {
+            return Ok(false);
+        }
+        let _ = self.notify_did_apply_history(&editor, operation, row_builder).await?;
+        Ok(true)
+    }
+
+    /// Reapply the last reverted change of the block, the `operation` is the one that reapplies the
+    /// change. Return false if the change can't be reapplied anymore.
+    pub(crate) async fn redo<F>(
+        &self,
+        block_id: &str,
+        operation: &RowHistoryOperation,
+        row_builder: F,
+    ) -> FlowyResult<bool>
+    where
+        F: Fn(Arc<RowRevision>) -> Option<GridRowPB>,
+    {
+        let editor = self.get_editor(block_id).await?;
+        if !editor.redo().await? {
+            return Ok(false);
+        }
+        let _ = self.notify_did_apply_history(&editor, operation, row_builder).await?;
+        Ok(true)
+    }
+
+    async fn notify_did_apply_history<F>(
+        &self,
+        editor: &GridBlockRevisionEditor,
+        operation: &RowHistoryOperation,
+        row_builder: F,
+    ) -> FlowyResult<()>
+    where
+        F: Fn(Arc<RowRevision>) -> Option<GridRowPB>,
+    {
+        let block_id = &editor.block_id;
+        match operation {
+            RowHistoryOperation::InsertRows { row_revs } => {
+                let mut inserted_rows = vec![];
+                for (_, row_rev) in row_revs {
+                    let _ = self.persistence.insert(block_id, &row_rev.id)?;
+                    let mut inserted_row = InsertedRowPB::from(row_rev.as_ref());
+                    inserted_row.index = editor.index_of_row(&row_rev.id).await;
+                    inserted_rows.push(inserted_row);
+                }
+                self.notify_did_update_block(block_id, GridBlockChangesetPB::insert(block_id, inserted_rows))
+                    .await
+            }
+            RowHistoryOperation::DeleteRows { row_ids } => {
+                self.notify_did_update_block(block_id, GridBlockChangesetPB::delete(block_id, row_ids.clone()))
+                    .await
+            }
+            RowHistoryOperation::MoveRow { row_id, .. } => match editor.index_of_row(row_id).await {
+                None => Ok(()),
+                Some(index) => self.notify_did_move_row(editor, row_id, index).await,
+            },
+            RowHistoryOperation::UpdateRow { row_id, cells, .. } => {
+                let _ = self.notify_did_update_row(editor, row_id, row_builder).await?;
+                for field_id in cells.keys() {
+                    let changeset = CellChangesetPB {
+                        grid_id: self.grid_id.clone(),
+                        row_id: row_id.clone(),
+                        field_id: field_id.clone(),
+                        content: None,
+                    };
+                    let _ = self.notify_did_update_cell(changeset).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+
     pub async fn get_row_rev(&self, row_id: &str) -> FlowyResult<Option<Arc<RowRevision>>> {
         let editor = self.get_editor_from_row_id(row_id).await?;
         let row_ids = vec![Cow::Borrowed(row_id)];
@@ -217,9 +384,18 @@ impl GridBlockManager {
         }
     }
 
-    pub async fn get_row_orders(&self, block_id: &str) -> FlowyResult<Vec<GridRowPB>> {
-        let editor = self.get_editor(block_id).await?;
-        editor.get_row_infos::<&str>(None).await
+    pub(crate) fn subscribe_revisions(&self) -> Vec<broadcast::Receiver<Revision>> {
+        self.get_editors()
+            .iter()
+            .map(|editor| editor.subscribe_revisions())
+            .collect()
+    }
+
+    fn get_editors(&self) -> Vec<Arc<GridBlockRevisionEditor>> {
+        self.block_editors
+            .iter()
+            .map(|editor| editor.value().clone())
+            .collect::<Vec<Arc<GridBlockRevisionEditor>>>()
     }
 
     pub(crate) async fn get_block_snapshots(
@@ -264,17 +440,22 @@ impl GridBlockManager {
 async fn make_block_editors(
     user: &Arc<dyn GridUser>,
     block_meta_revs: Vec<Arc<GridBlockMetaRevision>>,
+    web_socket: &Arc<dyn RevisionWebSocket>,
 ) -> FlowyResult<DashMap<String, Arc<GridBlockRevisionEditor>>> {
     let editor_map = DashMap::new();
     for block_meta_rev in block_meta_revs {
-        let editor = make_block_editor(user, &block_meta_rev.block_id).await?;
+        let editor = make_block_editor(user, &block_meta_rev.block_id, web_socket).await?;
         editor_map.insert(block_meta_rev.block_id.clone(), Arc::new(editor));
     }
 
     Ok(editor_map)
 }
 
-async fn make_block_editor(user: &Arc<dyn GridUser>, block_id: &str) -> FlowyResult<GridBlockRevisionEditor> {
+async fn make_block_editor(
+    user: &Arc<dyn GridUser>,
+    block_id: &str,
+    web_socket: &Arc<dyn RevisionWebSocket>,
+) -> FlowyResult<GridBlockRevisionEditor> {
     tracing::trace!("Open block:{} meta editor", block_id);
     let token = user.token()?;
     let user_id = user.user_id()?;
@@ -285,5 +466,5 @@ async fn make_block_editor(user: &Arc<dyn GridUser>, block_id: &str) -> FlowyRes
     let rev_compactor = GridBlockRevisionCompactor();
     let snapshot_persistence = SQLiteRevisionSnapshotPersistence::new(block_id, pool);
     let rev_manager = RevisionManager::new(&user_id, block_id, rev_persistence, rev_compactor, snapshot_persistence);
-    GridBlockRevisionEditor::new(&user_id, &token, block_id, rev_manager).await
+    GridBlockRevisionEditor::new(&user_id, &token, block_id, rev_manager, web_socket.clone()).await
 }
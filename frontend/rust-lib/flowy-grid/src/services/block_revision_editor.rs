@@ -2,29 +2,36 @@ use crate::entities::GridRowPB;
 use bytes::Bytes;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_grid_data_model::revision::{CellRevision, GridBlockRevision, RowMetaChangeset, RowRevision};
-use flowy_revision::{RevisionCloudService, RevisionCompactor, RevisionManager, RevisionObjectBuilder};
-use flowy_sync::client_grid::{GridBlockMetaChange, GridBlockRevisionPad};
+use flowy_revision::{
+    RevisionCloudService, RevisionCompactor, RevisionManager, RevisionObjectBuilder, RevisionWebSocket,
+};
+use flowy_sync::client_grid::{GridBlockMetaChange, GridBlockRevisionPad, GridHistoryItem, RowHistoryOperation};
 use flowy_sync::entities::revision::Revision;
+use flowy_sync::entities::ws_data::ServerRevisionWSData;
 use flowy_sync::util::make_delta_from_revisions;
 use lib_infra::future::FutureResult;
 use lib_ot::core::PhantomAttributes;
 use std::borrow::Cow;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 
 pub struct GridBlockRevisionEditor {
     user_id: String,
     pub block_id: String,
     pad: Arc<RwLock<GridBlockRevisionPad>>,
     rev_manager: Arc<RevisionManager>,
+    #[cfg(feature = "sync")]
+    ws_manager: Arc<flowy_revision::RevisionWebSocketManager>,
 }
 
 impl GridBlockRevisionEditor {
+    #[allow(unused_variables)]
     pub async fn new(
         user_id: &str,
         token: &str,
         block_id: &str,
         mut rev_manager: RevisionManager,
+        web_socket: Arc<dyn RevisionWebSocket>,
     ) -> FlowyResult<Self> {
         let cloud = Arc::new(GridBlockRevisionCloudService {
             token: token.to_owned(),
@@ -32,6 +39,17 @@ impl GridBlockRevisionEditor {
         let block_meta_pad = rev_manager.load::<GridBlockMetaPadBuilder>(Some(cloud)).await?;
         let pad = Arc::new(RwLock::new(block_meta_pad));
         let rev_manager = Arc::new(rev_manager);
+
+        #[cfg(feature = "sync")]
+        let ws_manager = crate::services::web_socket::make_grid_block_ws_manager(
+            user_id,
+            block_id,
+            rev_manager.clone(),
+            web_socket,
+            pad.clone(),
+        )
+        .await;
+
         let user_id = user_id.to_owned();
         let block_id = block_id.to_owned();
         Ok(Self {
@@ -39,9 +57,33 @@ impl GridBlockRevisionEditor {
             block_id,
             pad,
             rev_manager,
+            #[cfg(feature = "sync")]
+            ws_manager,
         })
     }
 
+    pub fn subscribe_revisions(&self) -> broadcast::Receiver<Revision> {
+        self.rev_manager.subscribe_revisions()
+    }
+
+    #[cfg(feature = "sync")]
+    pub fn stop(&self) {
+        self.ws_manager.stop();
+    }
+
+    #[cfg(not(feature = "sync"))]
+    pub fn stop(&self) {}
+
+    #[cfg(feature = "sync")]
+    pub async fn receive_ws_data(&self, data: ServerRevisionWSData) -> FlowyResult<()> {
+        self.ws_manager.receive_ws_data(data).await
+    }
+
+    #[cfg(not(feature = "sync"))]
+    pub async fn receive_ws_data(&self, _data: ServerRevisionWSData) -> FlowyResult<()> {
+        Ok(())
+    }
+
     pub async fn duplicate_block(&self, duplicated_block_id: &str) -> GridBlockRevision {
         self.pad.read().await.duplicate_data(duplicated_block_id).await
     }
@@ -93,6 +135,15 @@ impl GridBlockRevisionEditor {
         Ok(())
     }
 
+    /// Update the row without recording the change in the history, see
+    /// [GridBlockRevisionPad::update_row_without_history] for details.
+    pub async fn update_row_without_history(&self, changeset: RowMetaChangeset) -> FlowyResult<()> {
+        let _ = self
+            .modify(|block_pad| Ok(block_pad.update_row_without_history(changeset)?))
+            .await?;
+        Ok(())
+    }
+
     pub async fn move_row(&self, row_id: &str, from: usize, to: usize) -> FlowyResult<()> {
         let _ = self
             .modify(|block_pad| Ok(block_pad.move_row(row_id, from, to)?))
@@ -100,6 +151,49 @@ impl GridBlockRevisionEditor {
         Ok(())
     }
 
+    pub async fn last_undo(&self) -> Option<GridHistoryItem<RowHistoryOperation>> {
+        self.pad.read().await.history().last_undo().cloned()
+    }
+
+    pub async fn last_redo(&self) -> Option<GridHistoryItem<RowHistoryOperation>> {
+        self.pad.read().await.history().last_redo().cloned()
+    }
+
+    /// Revert the last change of the rows. Return false if the change can't be reverted anymore.
+    pub async fn undo(&self) -> FlowyResult<bool> {
+        let mut is_reverted = false;
+        let _ = self
+            .modify(|block_pad| {
+                let change = block_pad.undo()?;
+                is_reverted = change.is_some();
+                Ok(change)
+            })
+            .await?;
+        Ok(is_reverted)
+    }
+
+    /// Reapply the last reverted change of the rows. Return false if the change can't be reapplied
+    /// anymore.
+    pub async fn redo(&self) -> FlowyResult<bool> {
+        let mut is_reapplied = false;
+        let _ = self
+            .modify(|block_pad| {
+                let change = block_pad.redo()?;
+                is_reapplied = change.is_some();
+                Ok(change)
+            })
+            .await?;
+        Ok(is_reapplied)
+    }
+
+    pub async fn number_of_rows(&self) -> i32 {
+        self.pad.read().await.number_of_rows()
+    }
+
+    pub async fn index_of_row(&self, row_id: &str) -> Option<i32> {
+        self.pad.read().await.index_of_row(row_id)
+    }
+
     pub async fn get_row_rev(&self, row_id: &str) -> FlowyResult<Option<Arc<RowRevision>>> {
         let row_ids = vec![Cow::Borrowed(row_id)];
         let row_rev = self.get_row_revs(Some(row_ids)).await?.pop();
@@ -0,0 +1,166 @@
+use crate::entities::{FieldType, GridCalculationType};
+use crate::services::cell::decode_cell_data_to_string;
+use crate::services::field::FormulaValue;
+use crate::services::formula::formula_value_from_cell;
+use flowy_grid_data_model::revision::{FieldRevision, RowRevision};
+use std::collections::HashSet;
+
+/// The decoded cell that is used to calculate the summary of the field.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct CalculationCell {
+    /// The string that is displayed in the cell.
+    pub content: String,
+    pub number: Option<f64>,
+    pub is_checked: bool,
+}
+
+impl CalculationCell {
+    pub fn from_row_rev(row_rev: &RowRevision, field_rev: &FieldRevision) -> Self {
+        let cell_rev = row_rev.cells.get(&field_rev.id);
+        let content = cell_rev
+            .map(|cell_rev| decode_cell_data_to_string(cell_rev.data.clone(), field_rev))
+            .unwrap_or_default();
+
+        let (number, is_checked) = match formula_value_from_cell(field_rev, cell_rev) {
+            FormulaValue::Number(num) => (Some(num), false),
+            FormulaValue::Bool(b) => (None, b),
+            _ => (None, false),
+        };
+
+        // The checkbox cell is displayed as "No" if it's unchecked, but it's counted as empty.
+        let content = if FieldType::from(field_rev.field_type_rev).is_checkbox() && !is_checked {
+            "".to_owned()
+        } else {
+            content
+        };
+
+        Self {
+            content,
+            number,
+            is_checked,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+}
+
+/// Calculate the value of the summary over the cells of the visible rows. Return empty if there
+/// is no cell that can be calculated, e.g. the average of the field that has no number.
+pub(crate) fn calculate<'a, I>(calculation_type: &GridCalculationType, cells: I) -> String
+where
+    I: Iterator<Item = &'a CalculationCell>,
+{
+    let cells = cells.collect::<Vec<&CalculationCell>>();
+    let numbers = || cells.iter().flat_map(|cell| cell.number);
+    let value = match calculation_type {
+        GridCalculationType::Sum => numbers().reduce(|acc, num| acc + num),
+        GridCalculationType::Average => {
+            let count = numbers().count();
+            numbers().reduce(|acc, num| acc + num).map(|sum| sum / count as f64)
+        }
+        GridCalculationType::Min => numbers().reduce(f64::min),
+        GridCalculationType::Max => numbers().reduce(f64::max),
+        GridCalculationType::Count => Some(cells.len() as f64),
+        GridCalculationType::CountEmpty => Some(cells.iter().filter(|cell| cell.is_empty()).count() as f64),
+        GridCalculationType::CountUnique => Some(
+            cells
+                .iter()
+                .filter(|cell| !cell.is_empty())
+                .map(|cell| cell.content.as_str())
+                .collect::<HashSet<&str>>()
+                .len() as f64,
+        ),
+        GridCalculationType::PercentChecked => {
+            if cells.is_empty() {
+                None
+            } else {
+                let checked = cells.iter().filter(|cell| cell.is_checked).count();
+                Some(checked as f64 * 100.0 / cells.len() as f64)
+            }
+        }
+    };
+
+    match value {
+        None => "".to_owned(),
+        Some(value) => {
+            // Keep two decimal places, e.g. the average of 1 and 2 and 2 is 1.67.
+            let value = (value * 100.0).round() / 100.0;
+            let value = FormulaValue::Number(value).to_display_string();
+            if calculation_type == &GridCalculationType::PercentChecked {
+                format!("{}%", value)
+            } else {
+                value
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate, CalculationCell};
+    use crate::entities::GridCalculationType;
+
+    fn number_cell(num: f64) -> CalculationCell {
+        CalculationCell {
+            content: num.to_string(),
+            number: Some(num),
+            is_checked: false,
+        }
+    }
+
+    fn text_cell(s: &str) -> CalculationCell {
+        CalculationCell {
+            content: s.to_owned(),
+            number: None,
+            is_checked: false,
+        }
+    }
+
+    #[test]
+    fn calculate_numbers_test() {
+        let cells = vec![
+            number_cell(1.0),
+            number_cell(2.0),
+            CalculationCell::default(),
+            number_cell(2.0),
+        ];
+        assert_eq!(calculate(&GridCalculationType::Sum, cells.iter()), "5");
+        assert_eq!(calculate(&GridCalculationType::Average, cells.iter()), "1.67");
+        assert_eq!(calculate(&GridCalculationType::Min, cells.iter()), "1");
+        assert_eq!(calculate(&GridCalculationType::Max, cells.iter()), "2");
+        assert_eq!(calculate(&GridCalculationType::Count, cells.iter()), "4");
+    }
+
+    #[test]
+    fn calculate_numbers_without_number_test() {
+        let cells = vec![text_cell("A"), CalculationCell::default()];
+        assert_eq!(calculate(&GridCalculationType::Sum, cells.iter()), "");
+        assert_eq!(calculate(&GridCalculationType::Average, cells.iter()), "");
+        assert_eq!(calculate(&GridCalculationType::PercentChecked, Vec::new().iter()), "");
+    }
+
+    #[test]
+    fn calculate_count_test() {
+        let cells = vec![
+            text_cell("A"),
+            text_cell("B"),
+            text_cell("A"),
+            CalculationCell::default(),
+        ];
+        assert_eq!(calculate(&GridCalculationType::CountEmpty, cells.iter()), "1");
+        assert_eq!(calculate(&GridCalculationType::CountUnique, cells.iter()), "2");
+    }
+
+    #[test]
+    fn calculate_percent_checked_test() {
+        let checked = CalculationCell {
+            content: "Yes".to_owned(),
+            number: None,
+            is_checked: true,
+        };
+        let cells = vec![checked.clone(), CalculationCell::default(), checked];
+        assert_eq!(calculate(&GridCalculationType::PercentChecked, cells.iter()), "66.67%");
+    }
+}
@@ -0,0 +1,219 @@
+use crate::dart_notification::{send_dart_notification, GridNotification};
+use crate::entities::{GridCalculationPB, GridCalculationType, RepeatedGridCalculationPB};
+use crate::services::block_manager::GridBlockManager;
+use crate::services::calculation::{calculate, CalculationCell};
+use crate::services::filter::GridFilterService;
+use flowy_error::FlowyResult;
+use flowy_grid_data_model::revision::{FieldRevision, GridLayoutRevision, RowRevision};
+use flowy_sync::client_grid::GridRevisionPad;
+use flowy_sync::entities::grid::GridSettingChangesetParams;
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Calculates the summary of the fields over the rows that satisfy the filters of the current
+/// layout. The decoded cells are cached, so only the changed rows are decoded again after the
+/// cells were updated.
+pub(crate) struct GridCalculationService {
+    grid_id: String,
+    grid_pad: Arc<RwLock<GridRevisionPad>>,
+    block_manager: Arc<GridBlockManager>,
+    filter_service: Arc<GridFilterService>,
+    cache: RwLock<Option<CalculationCache>>,
+}
+
+impl GridCalculationService {
+    pub async fn new(
+        grid_pad: Arc<RwLock<GridRevisionPad>>,
+        block_manager: Arc<GridBlockManager>,
+        filter_service: Arc<GridFilterService>,
+    ) -> Self {
+        let grid_id = grid_pad.read().await.grid_id();
+        Self {
+            grid_id,
+            grid_pad,
+            block_manager,
+            filter_service,
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Return the calculations of the layout with their values. The layout is the current layout
+    /// if it's None.
+    pub async fn get_calculations(&self, layout: Option<&GridLayoutRevision>) -> FlowyResult<Vec<GridCalculationPB>> {
+        let field_revs = self.calculated_field_revs(layout).await;
+        let mut write_guard = self.cache.write().await;
+        let cache = self.load_cache(&mut write_guard).await?;
+        Ok(field_revs
+            .iter()
+            .map(|(field_rev, calculation_type)| cache.calculate(field_rev, calculation_type))
+            .collect())
+    }
+
+    pub async fn apply_changeset(&self, changeset: GridCalculationChangeset) {
+        if changeset.is_filter_changed {
+            // The visible rows are changed, so the cache is loaded again the next time.
+            *self.cache.write().await = None;
+        }
+
+        if changeset.is_filter_changed || changeset.is_calculation_changed {
+            self.notify().await;
+        }
+    }
+
+    /// Update the cached cells of the row after the row was created or its cells were updated.
+    /// The row is removed from the cache if it doesn't satisfy the filters anymore.
+    pub async fn did_update_row(&self, row_id: &str) {
+        let row_rev = match self.block_manager.get_row_rev(row_id).await {
+            Ok(Some(row_rev)) => row_rev,
+            Ok(None) => return self.did_delete_rows(vec![row_id.to_owned()]).await,
+            Err(e) => {
+                tracing::error!("Get row revision failed: {:?}", e);
+                return;
+            }
+        };
+
+        let is_visible = self.filter_service.is_row_visible(&row_rev).await;
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            if is_visible {
+                cache.insert_row(row_rev);
+            } else {
+                cache.remove_row(row_id);
+            }
+        }
+        self.notify().await;
+    }
+
+    pub async fn did_delete_rows(&self, row_ids: Vec<String>) {
+        if let Some(cache) = self.cache.write().await.as_mut() {
+            for row_id in row_ids.iter() {
+                cache.remove_row(row_id);
+            }
+        }
+        self.notify().await;
+    }
+
+    /// The cache is loaded again after the field was changed, because the formulas and the rollups
+    /// that depend on the field may have changed the cells of all the rows.
+    pub async fn did_update_field(&self) {
+        *self.cache.write().await = None;
+        self.notify().await;
+    }
+
+    async fn calculated_field_revs(
+        &self,
+        layout: Option<&GridLayoutRevision>,
+    ) -> Vec<(Arc<FieldRevision>, GridCalculationType)> {
+        let grid_pad = self.grid_pad.read().await;
+        grid_pad
+            .get_calculations(layout)
+            .into_iter()
+            .flat_map(|calculation_rev| {
+                let (_, field_rev) = grid_pad.get_field_rev(&calculation_rev.field_id)?;
+                Some((field_rev.clone(), calculation_rev.calculation_type.clone().into()))
+            })
+            .collect()
+    }
+
+    async fn load_cache<'a>(&self, cache: &'a mut Option<CalculationCache>) -> FlowyResult<&'a mut CalculationCache> {
+        if cache.is_none() {
+            let mut snapshots = self.block_manager.get_block_snapshots(None).await?;
+            self.filter_service.filter_block_snapshots(&mut snapshots).await;
+            let row_revs = snapshots
+                .into_iter()
+                .flat_map(|snapshot| snapshot.row_revs)
+                .map(|row_rev| (row_rev.id.clone(), row_rev))
+                .collect::<IndexMap<String, Arc<RowRevision>>>();
+            *cache = Some(CalculationCache {
+                row_revs,
+                cells_by_field_id: HashMap::new(),
+                calculations: vec![],
+            });
+        }
+        Ok(cache.as_mut().unwrap())
+    }
+
+    /// Send the calculations of the current layout if any of their values was changed.
+    async fn notify(&self) {
+        let field_revs = self.calculated_field_revs(None).await;
+        let mut write_guard = self.cache.write().await;
+        let cache = match self.load_cache(&mut write_guard).await {
+            Ok(cache) => cache,
+            Err(e) => {
+                tracing::error!("Load calculation cache failed: {:?}", e);
+                return;
+            }
+        };
+
+        let calculations = field_revs
+            .iter()
+            .map(|(field_rev, calculation_type)| cache.calculate(field_rev, calculation_type))
+            .collect::<Vec<GridCalculationPB>>();
+        if calculations == cache.calculations {
+            return;
+        }
+
+        cache.calculations = calculations.clone();
+        send_dart_notification(&self.grid_id, GridNotification::DidUpdateCalculations)
+            .payload(RepeatedGridCalculationPB::from(calculations))
+            .send();
+    }
+}
+
+struct CalculationCache {
+    /// The rows that satisfy the filters of the current layout.
+    row_revs: IndexMap<String, Arc<RowRevision>>,
+    /// The decoded cells of the calculated fields, keyed by the row id. The cells of the field
+    /// are decoded the first time it's calculated.
+    cells_by_field_id: HashMap<String, HashMap<String, CalculationCell>>,
+    /// The last calculations that were sent to the client.
+    calculations: Vec<GridCalculationPB>,
+}
+
+impl CalculationCache {
+    fn insert_row(&mut self, row_rev: Arc<RowRevision>) {
+        // The cells of the row are decoded again when the field is calculated.
+        for cells in self.cells_by_field_id.values_mut() {
+            cells.remove(&row_rev.id);
+        }
+        self.row_revs.insert(row_rev.id.clone(), row_rev);
+    }
+
+    fn remove_row(&mut self, row_id: &str) {
+        for cells in self.cells_by_field_id.values_mut() {
+            cells.remove(row_id);
+        }
+        self.row_revs.remove(row_id);
+    }
+
+    fn calculate(&mut self, field_rev: &FieldRevision, calculation_type: &GridCalculationType) -> GridCalculationPB {
+        let row_revs = &self.row_revs;
+        let cells = self.cells_by_field_id.entry(field_rev.id.clone()).or_default();
+        for (row_id, row_rev) in row_revs.iter() {
+            if !cells.contains_key(row_id) {
+                cells.insert(row_id.clone(), CalculationCell::from_row_rev(row_rev, field_rev));
+            }
+        }
+
+        GridCalculationPB {
+            field_id: field_rev.id.clone(),
+            calculation_type: calculation_type.clone(),
+            value: calculate(calculation_type, cells.values()),
+        }
+    }
+}
+
+pub struct GridCalculationChangeset {
+    is_filter_changed: bool,
+    is_calculation_changed: bool,
+}
+
+impl std::convert::From<&GridSettingChangesetParams> for GridCalculationChangeset {
+    fn from(params: &GridSettingChangesetParams) -> Self {
+        GridCalculationChangeset {
+            is_filter_changed: params.is_filter_changed(),
+            is_calculation_changed: params.is_calculation_changed(),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod calculation;
+mod calculation_service;
+
+pub(crate) use calculation::*;
+pub(crate) use calculation_service::*;
@@ -1,6 +1,8 @@
 use crate::entities::FieldType;
 use crate::services::cell::{AnyCellData, CellBytes};
 use crate::services::field::*;
+use crate::services::group::CellGroup;
+use crate::services::sort::CellSortKey;
 
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
 use flowy_grid_data_model::revision::{CellRevision, FieldRevision, FieldTypeRevision};
@@ -11,6 +13,32 @@ pub trait CellFilterOperation<T> {
     fn apply_filter(&self, any_cell_data: AnyCellData, filter: &T) -> FlowyResult<bool>;
 }
 
+/// This trait is used when sorting the rows of the grid.
+pub trait CellSortOperation {
+    /// Return the key that is used to compare the cells of the same field.
+    /// Return None if the cell is empty, the empty cells are always placed after the others.
+    fn sort_key(&self, any_cell_data: AnyCellData) -> FlowyResult<Option<CellSortKey>>;
+}
+
+/// This trait is used when grouping the rows of the grid, for example, the Board layout.
+pub trait CellGroupOperation {
+    /// Return the groups that are generated by the type option. These groups exist even if there is
+    /// no row in them.
+    fn default_groups(&self) -> Vec<CellGroup>;
+
+    /// Return the groups that the cell belongs to. Return empty if the cell doesn't belong to any group.
+    fn cell_groups(&self, any_cell_data: AnyCellData) -> FlowyResult<Vec<CellGroup>>;
+
+    /// Return the changeset that moves the cell from one group to another. The group id is None if
+    /// the cell is moved from/to the group that contains the cells that don't belong to any group.
+    fn move_group_changeset(
+        &self,
+        any_cell_data: Option<AnyCellData>,
+        from_group_id: Option<&str>,
+        to_group_id: Option<&str>,
+    ) -> FlowyResult<String>;
+}
+
 /// Return object that describes the cell.
 pub trait CellDisplayable<CD> {
     fn display_data(
@@ -64,6 +92,9 @@ pub fn apply_cell_data_changeset<C: ToString, T: AsRef<FieldRevision>>(
         FieldType::MultiSelect => MultiSelectTypeOption::from(field_rev).apply_changeset(changeset.into(), cell_rev),
         FieldType::Checkbox => CheckboxTypeOption::from(field_rev).apply_changeset(changeset.into(), cell_rev),
         FieldType::URL => URLTypeOption::from(field_rev).apply_changeset(changeset.into(), cell_rev),
+        FieldType::Formula => FormulaTypeOption::from(field_rev).apply_changeset(changeset.into(), cell_rev),
+        FieldType::Relation => RelationTypeOption::from(field_rev).apply_changeset(changeset.into(), cell_rev),
+        FieldType::Rollup => RollupTypeOption::from(field_rev).apply_changeset(changeset.into(), cell_rev),
     }?;
 
     Ok(AnyCellData::new(s, field_type).json())
@@ -86,6 +117,41 @@ pub fn decode_any_cell_data<T: TryInto<AnyCellData>>(data: T, field_rev: &FieldR
     }
 }
 
+/// Return the string that is displayed in the cell. For example, the names of the selected options
+/// are joined by the [SELECTION_IDS_SEPARATOR]. Return empty if the cell can't be decoded.
+pub fn decode_cell_data_to_string<T: TryInto<AnyCellData>>(data: T, field_rev: &FieldRevision) -> String {
+    let cell_bytes = decode_any_cell_data(data, field_rev);
+    let field_type: FieldType = field_rev.field_type_rev.into();
+    let result = match field_type {
+        FieldType::DateTime => cell_bytes.with_parser(DateCellDataParser()).map(|cell_data| {
+            let time = cell_data.time.trim();
+            if time.is_empty() {
+                cell_data.date
+            } else {
+                format!("{} {}", cell_data.date, time)
+            }
+        }),
+        FieldType::SingleSelect | FieldType::MultiSelect => {
+            cell_bytes.with_parser(SelectOptionCellDataParser()).map(|cell_data| {
+                cell_data
+                    .select_options
+                    .into_iter()
+                    .map(|option| option.name)
+                    .collect::<Vec<String>>()
+                    .join(SELECTION_IDS_SEPARATOR)
+            })
+        }
+        FieldType::URL => cell_bytes
+            .with_parser(URLCellDataParser())
+            .map(|cell_data| cell_data.content),
+        FieldType::Relation => cell_bytes
+            .with_parser(RelationCellDataParser())
+            .map(|cell_data| cell_data.row_ids.join(RELATION_ROW_IDS_SEPARATOR)),
+        _ => Ok(cell_bytes.to_string()),
+    };
+    result.unwrap_or_default()
+}
+
 pub fn try_decode_cell_data(
     cell_data: CellData<String>,
     field_rev: &FieldRevision,
@@ -117,6 +183,15 @@ pub fn try_decode_cell_data(
             FieldType::URL => field_rev
                 .get_type_option_entry::<URLTypeOption>(field_type)?
                 .decode_cell_data(cell_data.into(), s_field_type, field_rev),
+            FieldType::Formula => field_rev
+                .get_type_option_entry::<FormulaTypeOption>(field_type)?
+                .decode_cell_data(cell_data.into(), s_field_type, field_rev),
+            FieldType::Relation => field_rev
+                .get_type_option_entry::<RelationTypeOption>(field_type)?
+                .decode_cell_data(cell_data.into(), s_field_type, field_rev),
+            FieldType::Rollup => field_rev
+                .get_type_option_entry::<RollupTypeOption>(field_type)?
+                .decode_cell_data(cell_data.into(), s_field_type, field_rev),
         };
         Some(data)
     };
@@ -0,0 +1,25 @@
+use crate::services::cell::decode_cell_data_to_string;
+use crate::services::csv::csv_parser::write_csv;
+use flowy_grid_data_model::revision::{FieldRevision, RowRevision};
+use std::sync::Arc;
+
+/// Export the rows as CSV. The first record contains the names of the fields, and each cell is
+/// written as the string that is displayed in the cell.
+pub(crate) fn export_csv(field_revs: &[Arc<FieldRevision>], row_revs: &[Arc<RowRevision>]) -> String {
+    let mut records = vec![field_revs
+        .iter()
+        .map(|field_rev| field_rev.name.clone())
+        .collect::<Vec<String>>()];
+
+    for row_rev in row_revs {
+        let record = field_revs
+            .iter()
+            .map(|field_rev| match row_rev.cells.get(&field_rev.id) {
+                None => "".to_owned(),
+                Some(cell_rev) => decode_cell_data_to_string(cell_rev.data.clone(), field_rev),
+            })
+            .collect::<Vec<String>>();
+        records.push(record);
+    }
+    write_csv(&records)
+}
@@ -0,0 +1,308 @@
+use crate::services::csv::csv_parser::parse_csv;
+use crate::services::field::*;
+use crate::services::row::RowRevisionBuilder;
+use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
+use flowy_grid_data_model::revision::{BuildGridContext, FieldRevision};
+use flowy_sync::client_grid::GridBuilder;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// The column is imported as the select field only if the number of its distinct values doesn't
+/// exceed this limit, and some of the values are repeated.
+const MAX_SELECT_OPTION_COUNT: usize = 20;
+
+/// The date formats that are tried in order when inferring the date column.
+const DATE_FORMATS: [DateFormat; 3] = [DateFormat::ISO, DateFormat::Local, DateFormat::Friendly];
+
+const TIME_FORMATS: [&str; 2] = ["%H:%M", "%I:%M %p"];
+
+/// Build the grid from the CSV content. The first record contains the names of the fields. The
+/// type of each field is inferred from the values of its column, except the first field which is
+/// always the primary text field.
+pub(crate) fn make_grid_from_csv(content: &str) -> FlowyResult<BuildGridContext> {
+    let mut records = parse_csv(content)?;
+    if records.is_empty() {
+        return Err(FlowyError::new(ErrorCode::InvalidCSV, "The CSV content is empty"));
+    }
+    let header = records.remove(0);
+
+    let mut grid_builder = GridBuilder::new();
+    let mut columns = vec![];
+    for (index, name) in header.iter().enumerate() {
+        let column = if index == 0 {
+            CsvColumn::Text
+        } else {
+            let values = records
+                .iter()
+                .map(|record| cell_value(record, index))
+                .collect::<Vec<&str>>();
+            CsvColumn::infer(&values)
+        };
+
+        let name = match name.trim() {
+            "" => format!("Field {}", index + 1),
+            name => name.to_owned(),
+        };
+        grid_builder.add_field(column.make_field_rev(&name, index == 0));
+        columns.push(column);
+    }
+
+    let block_id = grid_builder.block_id().to_owned();
+    let field_revs = grid_builder.field_revs().clone();
+    for record in records.iter() {
+        let mut row_builder = RowRevisionBuilder::new(&field_revs);
+        for (index, (column, field_rev)) in columns.iter().zip(field_revs.iter()).enumerate() {
+            let value = cell_value(record, index);
+            if !value.is_empty() {
+                let _ = column.insert_cell(&mut row_builder, &field_rev.id, value)?;
+            }
+        }
+        grid_builder.add_row(row_builder.build(&block_id));
+    }
+    Ok(grid_builder.build())
+}
+
+/// The records may have fewer values than the header, the missing values are treated as empty.
+fn cell_value(record: &[String], index: usize) -> &str {
+    record.get(index).map(|value| value.trim()).unwrap_or("")
+}
+
+enum CsvColumn {
+    Text,
+    Number,
+    Checkbox,
+    URL,
+    Date {
+        date_format: DateFormat,
+        include_time: bool,
+    },
+    SingleSelect(Vec<SelectOptionPB>),
+    MultiSelect(Vec<SelectOptionPB>),
+}
+
+impl CsvColumn {
+    /// Infer the type of the column from its non-empty values. The column is imported as text if
+    /// it doesn't match any other type.
+    fn infer(values: &[&str]) -> Self {
+        let values = values
+            .iter()
+            .copied()
+            .filter(|value| !value.is_empty())
+            .collect::<Vec<&str>>();
+        if values.is_empty() {
+            return CsvColumn::Text;
+        }
+
+        if values.iter().all(|value| is_checkbox_value(value)) {
+            return CsvColumn::Checkbox;
+        }
+
+        if values.iter().all(|value| Decimal::from_str(value).is_ok()) {
+            return CsvColumn::Number;
+        }
+
+        for date_format in DATE_FORMATS.iter() {
+            let timestamps = values
+                .iter()
+                .map(|value| parse_date(value, date_format))
+                .collect::<Option<Vec<(i64, bool)>>>();
+            if let Some(timestamps) = timestamps {
+                return CsvColumn::Date {
+                    date_format: *date_format,
+                    include_time: timestamps.iter().any(|(_, has_time)| *has_time),
+                };
+            }
+        }
+
+        if values.iter().all(|value| is_url(value)) {
+            return CsvColumn::URL;
+        }
+
+        let cells = values
+            .iter()
+            .map(|value| split_option_names(value))
+            .collect::<Vec<Vec<&str>>>();
+        let mut option_names: Vec<&str> = vec![];
+        for name in cells.iter().flatten() {
+            if !option_names.contains(name) {
+                option_names.push(*name);
+            }
+        }
+        let value_count: usize = cells.iter().map(|names| names.len()).sum();
+        if option_names.len() <= MAX_SELECT_OPTION_COUNT && value_count > option_names.len() {
+            let options = option_names
+                .into_iter()
+                .map(SelectOptionPB::new)
+                .collect::<Vec<SelectOptionPB>>();
+            return if cells.iter().any(|names| names.len() > 1) {
+                CsvColumn::MultiSelect(options)
+            } else {
+                CsvColumn::SingleSelect(options)
+            };
+        }
+
+        CsvColumn::Text
+    }
+
+    fn make_field_rev(&self, name: &str, is_primary: bool) -> FieldRevision {
+        let builder: BoxTypeOptionBuilder = match self {
+            CsvColumn::Text => RichTextTypeOptionBuilder::default().into(),
+            CsvColumn::Number => NumberTypeOptionBuilder::default().into(),
+            CsvColumn::Checkbox => CheckboxTypeOptionBuilder::default().into(),
+            CsvColumn::URL => URLTypeOptionBuilder::default().into(),
+            CsvColumn::Date {
+                date_format,
+                include_time,
+            } => DateTypeOptionBuilder::default()
+                .date_format(*date_format)
+                .include_time(*include_time)
+                .into(),
+            CsvColumn::SingleSelect(options) => options
+                .iter()
+                .fold(SingleSelectTypeOptionBuilder::default(), |builder, option| {
+                    builder.option(option.clone())
+                })
+                .into(),
+            CsvColumn::MultiSelect(options) => options
+                .iter()
+                .fold(MultiSelectTypeOptionBuilder::default(), |builder, option| {
+                    builder.option(option.clone())
+                })
+                .into(),
+        };
+        FieldBuilder::new(builder)
+            .name(name)
+            .primary(is_primary)
+            .visibility(true)
+            .build()
+    }
+
+    fn insert_cell(&self, row_builder: &mut RowRevisionBuilder, field_id: &str, value: &str) -> FlowyResult<()> {
+        match self {
+            CsvColumn::Text | CsvColumn::Number | CsvColumn::Checkbox | CsvColumn::URL => {
+                row_builder.insert_cell(field_id, value.to_owned())
+            }
+            CsvColumn::Date { date_format, .. } => {
+                let timestamp = parse_date(value, date_format).map(|(timestamp, _)| timestamp);
+                let changeset = DateCellChangesetPB {
+                    date: timestamp.map(|timestamp| timestamp.to_string()),
+                    time: None,
+                };
+                let changeset = serde_json::to_string(&changeset).map_err(internal_error)?;
+                row_builder.insert_cell(field_id, changeset)
+            }
+            CsvColumn::SingleSelect(options) | CsvColumn::MultiSelect(options) => {
+                let option_ids = split_option_names(value)
+                    .into_iter()
+                    .flat_map(|name| options.iter().find(|option| option.name == name))
+                    .map(|option| option.id.clone())
+                    .collect::<Vec<String>>();
+                row_builder.insert_select_option_cells(field_id, option_ids)
+            }
+        }
+    }
+}
+
+fn is_checkbox_value(value: &str) -> bool {
+    matches!(value.to_lowercase().as_str(), "yes" | "no" | "true" | "false")
+}
+
+fn is_url(value: &str) -> bool {
+    match url::Url::parse(value) {
+        Ok(url) => url.scheme() == "http" || url.scheme() == "https",
+        Err(_) => false,
+    }
+}
+
+/// Return the timestamp of the date and whether the value contains the time.
+fn parse_date(value: &str, date_format: &DateFormat) -> Option<(i64, bool)> {
+    let fmt = date_format.format_str();
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, fmt) {
+        return Some((date.and_hms(0, 0, 0).timestamp(), false));
+    }
+
+    TIME_FORMATS.iter().find_map(|time_fmt| {
+        let fmt = format!("{} {}", fmt, time_fmt);
+        chrono::NaiveDateTime::parse_from_str(value, &fmt)
+            .ok()
+            .map(|date_time| (date_time.timestamp(), true))
+    })
+}
+
+/// The names of the selected options are separated by the [SELECTION_IDS_SEPARATOR] when exported.
+fn split_option_names(value: &str) -> Vec<&str> {
+    let mut names = vec![];
+    for name in value.split(SELECTION_IDS_SEPARATOR).map(|name| name.trim()) {
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{make_grid_from_csv, CsvColumn};
+    use crate::entities::FieldType;
+    use crate::services::field::DateFormat;
+
+    #[test]
+    fn infer_column_type_test() {
+        assert!(matches!(CsvColumn::infer(&["Yes", "no", ""]), CsvColumn::Checkbox));
+        assert!(matches!(CsvColumn::infer(&["1", "2.5", "-3"]), CsvColumn::Number));
+        assert!(matches!(
+            CsvColumn::infer(&["2022-03-14", "2022-03-15 10:30"]),
+            CsvColumn::Date {
+                date_format: DateFormat::ISO,
+                include_time: true
+            }
+        ));
+        assert!(matches!(
+            CsvColumn::infer(&["Mar 14,2022"]),
+            CsvColumn::Date {
+                date_format: DateFormat::Friendly,
+                include_time: false
+            }
+        ));
+        assert!(matches!(
+            CsvColumn::infer(&["https://appflowy.io", "http://github.com"]),
+            CsvColumn::URL
+        ));
+        assert!(matches!(CsvColumn::infer(&["A", "B", "A"]), CsvColumn::SingleSelect(options) if options.len() == 2));
+        assert!(matches!(
+            CsvColumn::infer(&["Google,Twitter", "Google"]),
+            CsvColumn::MultiSelect(options) if options.len() == 2
+        ));
+        assert!(matches!(CsvColumn::infer(&["A", "B", "C"]), CsvColumn::Text));
+        assert!(matches!(CsvColumn::infer(&["", ""]), CsvColumn::Text));
+    }
+
+    #[test]
+    fn make_grid_from_csv_test() {
+        let content = "Name,Price,Done,Tags\nA,1,Yes,Google\nB,2,No,\"Google,Twitter\"\nC\n";
+        let build_context = make_grid_from_csv(content).unwrap();
+        let field_types = build_context
+            .field_revs
+            .iter()
+            .map(|field_rev| FieldType::from(field_rev.field_type_rev))
+            .collect::<Vec<FieldType>>();
+        assert_eq!(
+            field_types,
+            vec![
+                FieldType::RichText,
+                FieldType::Number,
+                FieldType::Checkbox,
+                FieldType::MultiSelect
+            ]
+        );
+        assert!(build_context.field_revs[0].is_primary);
+
+        let rows = &build_context.blocks_meta_data[0].rows;
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].cells.len(), 4);
+        // The missing values of the last record are treated as empty.
+        assert_eq!(rows[2].cells.len(), 1);
+
+        assert!(make_grid_from_csv("").is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Parse the CSV content into records. The field can be quoted by the double quotes, and the
+/// double quote inside the quoted field is escaped by another double quote. Both `\n` and `\r\n`
+/// are accepted as the line separator, and the blank lines are skipped.
+pub(crate) fn parse_csv(content: &str) -> FlowyResult<Vec<Vec<String>>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut is_quoted = false;
+    let mut in_quotes = false;
+
+    let mut chars = content.trim_start_matches(BYTE_ORDER_MARK).chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() && !is_quoted => {
+                is_quoted = true;
+                in_quotes = true;
+            }
+            ',' => {
+                record.push(std::mem::take(&mut field));
+                is_quoted = false;
+            }
+            '\r' | '\n' => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                if !record.is_empty() || !field.is_empty() || is_quoted {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                is_quoted = false;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(FlowyError::new(ErrorCode::InvalidCSV, "The quoted field is not closed"));
+    }
+    if !record.is_empty() || !field.is_empty() || is_quoted {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Write the records as CSV. The field is quoted only if it contains the comma, the double quote
+/// or the line separator.
+pub(crate) fn write_csv(records: &[Vec<String>]) -> String {
+    let mut content = String::new();
+    for record in records {
+        let line = record
+            .iter()
+            .map(|field| escape_field(field))
+            .collect::<Vec<String>>()
+            .join(",");
+        content.push_str(&line);
+        content.push('\n');
+    }
+    content
+}
+
+fn escape_field(field: &str) -> String {
+    if field.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_csv, write_csv};
+
+    #[test]
+    fn parse_csv_test() {
+        let records = parse_csv("Name,Price\r\nA,1\n\nB,\n").unwrap();
+        assert_eq!(
+            records,
+            vec![
+                vec!["Name".to_owned(), "Price".to_owned()],
+                vec!["A".to_owned(), "1".to_owned()],
+                vec!["B".to_owned(), "".to_owned()],
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_quoted_csv_test() {
+        let records = parse_csv("\u{feff}\"a, b\",\"say \"\"hi\"\"\",\"line\nbreak\",\"\"").unwrap();
+        assert_eq!(
+            records,
+            vec![vec![
+                "a, b".to_owned(),
+                "say \"hi\"".to_owned(),
+                "line\nbreak".to_owned(),
+                "".to_owned(),
+            ]]
+        );
+
+        assert!(parse_csv("\"abc").is_err());
+    }
+
+    #[test]
+    fn write_csv_round_trip_test() {
+        let records = vec![
+            vec!["Name".to_owned(), "Tags".to_owned()],
+            vec!["A".to_owned(), "Google,Twitter".to_owned()],
+            vec!["say \"hi\"".to_owned(), "".to_owned()],
+        ];
+        let content = write_csv(&records);
+        assert_eq!(content, "Name,Tags\nA,\"Google,Twitter\"\n\"say \"\"hi\"\"\",\n");
+        assert_eq!(parse_csv(&content).unwrap(), records);
+    }
+}
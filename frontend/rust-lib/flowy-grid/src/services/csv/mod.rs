@@ -0,0 +1,6 @@
+mod csv_exporter;
+mod csv_importer;
+mod csv_parser;
+
+pub(crate) use csv_exporter::*;
+pub(crate) use csv_importer::*;
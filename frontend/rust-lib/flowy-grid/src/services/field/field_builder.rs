@@ -97,6 +97,9 @@ pub fn default_type_option_builder_from_type(field_type: &FieldType) -> Box<dyn
         FieldType::MultiSelect => MultiSelectTypeOption::default().into(),
         FieldType::Checkbox => CheckboxTypeOption::default().into(),
         FieldType::URL => URLTypeOption::default().into(),
+        FieldType::Formula => FormulaTypeOption::default().into(),
+        FieldType::Relation => RelationTypeOption::default().into(),
+        FieldType::Rollup => RollupTypeOption::default().into(),
     };
 
     type_option_builder_from_json_str(&s, field_type)
@@ -111,6 +114,9 @@ pub fn type_option_builder_from_json_str(s: &str, field_type: &FieldType) -> Box
         FieldType::MultiSelect => Box::new(MultiSelectTypeOptionBuilder::from_json_str(s)),
         FieldType::Checkbox => Box::new(CheckboxTypeOptionBuilder::from_json_str(s)),
         FieldType::URL => Box::new(URLTypeOptionBuilder::from_json_str(s)),
+        FieldType::Formula => Box::new(FormulaTypeOptionBuilder::from_json_str(s)),
+        FieldType::Relation => Box::new(RelationTypeOptionBuilder::from_json_str(s)),
+        FieldType::Rollup => Box::new(RollupTypeOptionBuilder::from_json_str(s)),
     }
 }
 
@@ -124,5 +130,8 @@ pub fn type_option_builder_from_bytes<T: Into<Bytes>>(bytes: T, field_type: &Fie
         FieldType::MultiSelect => Box::new(MultiSelectTypeOptionBuilder::from_protobuf_bytes(bytes)),
         FieldType::Checkbox => Box::new(CheckboxTypeOptionBuilder::from_protobuf_bytes(bytes)),
         FieldType::URL => Box::new(URLTypeOptionBuilder::from_protobuf_bytes(bytes)),
+        FieldType::Formula => Box::new(FormulaTypeOptionBuilder::from_protobuf_bytes(bytes)),
+        FieldType::Relation => Box::new(RelationTypeOptionBuilder::from_protobuf_bytes(bytes)),
+        FieldType::Rollup => Box::new(RollupTypeOptionBuilder::from_protobuf_bytes(bytes)),
     }
 }
@@ -183,6 +183,11 @@ impl DateTypeOptionBuilder {
         self.0.time_format = time_format;
         self
     }
+
+    pub fn include_time(mut self, include_time: bool) -> Self {
+        self.0.include_time = include_time;
+        self
+    }
 }
 impl TypeOptionBuilder for DateTypeOptionBuilder {
     fn field_type(&self) -> FieldType {
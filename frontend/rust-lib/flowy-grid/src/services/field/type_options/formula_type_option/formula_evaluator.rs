@@ -0,0 +1,179 @@
+use crate::services::field::{FormulaExpr, FormulaFunction, FormulaOperator, FormulaValue, SECONDS_PER_DAY};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Evaluate the expression with the values of the referenced fields. The field that doesn't exist
+/// in the `values` is treated as empty. The error is returned as [FormulaValue::Error], so it can
+/// be displayed in the cell.
+pub fn evaluate_formula(expr: &FormulaExpr, values: &HashMap<String, FormulaValue>) -> FormulaValue {
+    match expr {
+        FormulaExpr::Number(num) => FormulaValue::Number(*num),
+        FormulaExpr::Text(s) => FormulaValue::Text(s.clone()),
+        FormulaExpr::Bool(b) => FormulaValue::Bool(*b),
+        FormulaExpr::Field(field_id) => values.get(field_id).cloned().unwrap_or_default(),
+        FormulaExpr::Negative(expr) => match evaluate_formula(expr, values) {
+            FormulaValue::Error(msg) => FormulaValue::Error(msg),
+            value => match value.as_number() {
+                Some(num) => FormulaValue::Number(-num),
+                None => FormulaValue::error("Can't negate a non-numeric value"),
+            },
+        },
+        FormulaExpr::Binary { op, left, right } => {
+            let left = evaluate_formula(left, values);
+            if left.is_error() {
+                return left;
+            }
+            let right = evaluate_formula(right, values);
+            if right.is_error() {
+                return right;
+            }
+            evaluate_binary(*op, left, right)
+        }
+        FormulaExpr::Call { function, args } => evaluate_call(*function, args, values),
+    }
+}
+
+fn evaluate_binary(op: FormulaOperator, left: FormulaValue, right: FormulaValue) -> FormulaValue {
+    match op {
+        FormulaOperator::Add => match (&left, &right) {
+            (FormulaValue::Date(timestamp), other) | (other, FormulaValue::Date(timestamp)) => {
+                match other.as_number() {
+                    Some(days) => add_days(*timestamp, days),
+                    None => FormulaValue::error("Only the number of days can be added to the date"),
+                }
+            }
+            _ => arithmetic(&left, &right, |a, b| Some(a + b)),
+        },
+        FormulaOperator::Subtract => match (&left, &right) {
+            (FormulaValue::Date(a), FormulaValue::Date(b)) => FormulaValue::Number(days_between(*a, *b)),
+            (FormulaValue::Date(timestamp), other) => match other.as_number() {
+                Some(days) => add_days(*timestamp, -days),
+                None => FormulaValue::error("Only the number of days can be subtracted from the date"),
+            },
+            _ => arithmetic(&left, &right, |a, b| Some(a - b)),
+        },
+        FormulaOperator::Multiply => arithmetic(&left, &right, |a, b| Some(a * b)),
+        FormulaOperator::Divide => match right.as_number() {
+            Some(b) if b == 0.0 => FormulaValue::error("Division by zero"),
+            _ => arithmetic(&left, &right, |a, b| Some(a / b)),
+        },
+        FormulaOperator::Concat => {
+            FormulaValue::Text(format!("{}{}", left.to_display_string(), right.to_display_string()))
+        }
+        FormulaOperator::Equal => FormulaValue::Bool(compare(&left, &right) == Ordering::Equal),
+        FormulaOperator::NotEqual => FormulaValue::Bool(compare(&left, &right) != Ordering::Equal),
+        FormulaOperator::LessThan => FormulaValue::Bool(compare(&left, &right) == Ordering::Less),
+        FormulaOperator::LessThanOrEqual => FormulaValue::Bool(compare(&left, &right) != Ordering::Greater),
+        FormulaOperator::GreaterThan => FormulaValue::Bool(compare(&left, &right) == Ordering::Greater),
+        FormulaOperator::GreaterThanOrEqual => FormulaValue::Bool(compare(&left, &right) != Ordering::Less),
+    }
+}
+
+fn evaluate_call(
+    function: FormulaFunction,
+    args: &[FormulaExpr],
+    values: &HashMap<String, FormulaValue>,
+) -> FormulaValue {
+    // The branches of the if function are evaluated lazily, so the error in the branch that isn't
+    // taken doesn't affect the result.
+    if function == FormulaFunction::If {
+        let condition = evaluate_formula(&args[0], values);
+        if condition.is_error() {
+            return condition;
+        }
+        return if condition.as_bool() {
+            evaluate_formula(&args[1], values)
+        } else {
+            evaluate_formula(&args[2], values)
+        };
+    }
+
+    let args = args
+        .iter()
+        .map(|arg| evaluate_formula(arg, values))
+        .collect::<Vec<FormulaValue>>();
+    if let Some(error) = args.iter().find(|arg| arg.is_error()) {
+        return error.clone();
+    }
+
+    match function {
+        FormulaFunction::If => unreachable!(),
+        FormulaFunction::Concat => FormulaValue::Text(args.iter().map(|arg| arg.to_display_string()).collect()),
+        FormulaFunction::DateAdd => match (args[0].as_timestamp(), args[1].as_number()) {
+            (Some(timestamp), Some(days)) => add_days(timestamp, days),
+            _ if args[0].is_empty() => FormulaValue::Empty,
+            _ => FormulaValue::error("date_add expects a date and a number of days"),
+        },
+        FormulaFunction::DateDiff => match (args[0].as_timestamp(), args[1].as_timestamp()) {
+            (Some(end), Some(start)) => FormulaValue::Number(days_between(end, start)),
+            _ if args[0].is_empty() || args[1].is_empty() => FormulaValue::Empty,
+            _ => FormulaValue::error("date_diff expects two dates"),
+        },
+        FormulaFunction::Count => FormulaValue::Number(args[0].as_list().len() as f64),
+        FormulaFunction::Join => FormulaValue::Text(args[0].as_list().join(&args[1].to_display_string())),
+        FormulaFunction::Contains => {
+            let text = args[1].to_display_string();
+            FormulaValue::Bool(args[0].as_list().iter().any(|item| item == &text))
+        }
+        FormulaFunction::Abs => match args[0].as_number() {
+            Some(num) => FormulaValue::Number(num.abs()),
+            None => FormulaValue::error("abs expects a number"),
+        },
+        FormulaFunction::Round => {
+            let digits = args.get(1).and_then(|arg| arg.as_number()).unwrap_or(0.0) as i32;
+            match args[0].as_number() {
+                Some(num) => {
+                    let factor = 10_f64.powi(digits);
+                    FormulaValue::Number((num * factor).round() / factor)
+                }
+                None => FormulaValue::error("round expects a number"),
+            }
+        }
+    }
+}
+
+fn arithmetic<F>(left: &FormulaValue, right: &FormulaValue, f: F) -> FormulaValue
+where
+    F: Fn(f64, f64) -> Option<f64>,
+{
+    match (left.as_number(), right.as_number()) {
+        (Some(a), Some(b)) => match f(a, b) {
+            Some(num) if num.is_finite() => FormulaValue::Number(num),
+            _ => FormulaValue::error("The result is not a finite number"),
+        },
+        _ => FormulaValue::error(format!(
+            "Can't apply the arithmetic operator to {} and {}",
+            left.to_display_string(),
+            right.to_display_string()
+        )),
+    }
+}
+
+fn add_days(timestamp: i64, days: f64) -> FormulaValue {
+    // Casting a float to an integer saturates, so the seconds out of the range of i64 are
+    // rejected before the cast.
+    let seconds = days * SECONDS_PER_DAY as f64;
+    if seconds.is_finite() && seconds.abs() < i64::MAX as f64 {
+        if let Some(timestamp) = timestamp.checked_add(seconds as i64) {
+            return FormulaValue::Date(timestamp);
+        }
+    }
+    FormulaValue::error("The date is out of range")
+}
+
+fn days_between(end: i64, start: i64) -> f64 {
+    (end - start).div_euclid(SECONDS_PER_DAY) as f64
+}
+
+/// The numbers and the dates are compared numerically, the others are compared as text.
+fn compare(left: &FormulaValue, right: &FormulaValue) -> Ordering {
+    match (left, right) {
+        (FormulaValue::Date(a), FormulaValue::Date(b)) => a.cmp(b),
+        (FormulaValue::Number(_), _) | (_, FormulaValue::Number(_)) => match (left.as_number(), right.as_number()) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            _ => left.to_display_string().cmp(&right.to_display_string()),
+        },
+        (FormulaValue::Bool(a), FormulaValue::Bool(b)) => a.cmp(b),
+        _ => left.to_display_string().cmp(&right.to_display_string()),
+    }
+}
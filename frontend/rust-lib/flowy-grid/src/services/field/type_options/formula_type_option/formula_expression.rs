@@ -0,0 +1,362 @@
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// The syntax tree of the formula expression.
+///
+/// The expression is written like:
+///
+/// * `{field_id}` references the cell of the field in the same row.
+/// * `1.5`, `"text"`, `true` and `false` are the literals.
+/// * `+ - * /` are the arithmetic operators, `&` concatenates the texts.
+/// * `= != < <= > >=` are the comparison operators.
+/// * `name(arg1, arg2, ...)` calls the function, check out the [FormulaFunction] for more information.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaExpr {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    Field(String),
+    Negative(Box<FormulaExpr>),
+    Binary {
+        op: FormulaOperator,
+        left: Box<FormulaExpr>,
+        right: Box<FormulaExpr>,
+    },
+    Call {
+        function: FormulaFunction,
+        args: Vec<FormulaExpr>,
+    },
+}
+
+impl FormulaExpr {
+    pub fn parse(s: &str) -> FlowyResult<FormulaExpr> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser { tokens, position: 0 };
+        let expr = parser.parse_comparison()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(invalid_formula(format!("Unexpected token: {:?}", token))),
+        }
+    }
+
+    /// Return the ids of the fields that are referenced by the expression.
+    pub fn field_ids(&self) -> Vec<String> {
+        let mut field_ids = vec![];
+        self.collect_field_ids(&mut field_ids);
+        field_ids
+    }
+
+    fn collect_field_ids(&self, field_ids: &mut Vec<String>) {
+        match self {
+            FormulaExpr::Field(field_id) => {
+                if !field_ids.contains(field_id) {
+                    field_ids.push(field_id.clone());
+                }
+            }
+            FormulaExpr::Negative(expr) => expr.collect_field_ids(field_ids),
+            FormulaExpr::Binary { left, right, .. } => {
+                left.collect_field_ids(field_ids);
+                right.collect_field_ids(field_ids);
+            }
+            FormulaExpr::Call { args, .. } => args.iter().for_each(|arg| arg.collect_field_ids(field_ids)),
+            FormulaExpr::Number(_) | FormulaExpr::Text(_) | FormulaExpr::Bool(_) => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormulaOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Concat,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormulaFunction {
+    /// if(condition, value_if_true, value_if_false)
+    If,
+    /// concat(text1, text2, ...)
+    Concat,
+    /// date_add(date, days)
+    DateAdd,
+    /// date_diff(end_date, start_date), returns the number of days between the dates.
+    DateDiff,
+    /// count(list), returns the number of the selected options.
+    Count,
+    /// join(list, separator)
+    Join,
+    /// contains(list, text), returns true if one of the selected options is equal to the text.
+    Contains,
+    /// abs(number)
+    Abs,
+    /// round(number) or round(number, digits)
+    Round,
+}
+
+impl FormulaFunction {
+    fn from_name(name: &str) -> Option<Self> {
+        let function = match name.to_lowercase().as_str() {
+            "if" => FormulaFunction::If,
+            "concat" => FormulaFunction::Concat,
+            "date_add" => FormulaFunction::DateAdd,
+            "date_diff" => FormulaFunction::DateDiff,
+            "count" => FormulaFunction::Count,
+            "join" => FormulaFunction::Join,
+            "contains" => FormulaFunction::Contains,
+            "abs" => FormulaFunction::Abs,
+            "round" => FormulaFunction::Round,
+            _ => return None,
+        };
+        Some(function)
+    }
+
+    fn is_valid_arg_count(&self, count: usize) -> bool {
+        match self {
+            FormulaFunction::If => count == 3,
+            FormulaFunction::Concat => count >= 1,
+            FormulaFunction::DateAdd
+            | FormulaFunction::DateDiff
+            | FormulaFunction::Join
+            | FormulaFunction::Contains => count == 2,
+            FormulaFunction::Count | FormulaFunction::Abs => count == 1,
+            FormulaFunction::Round => count == 1 || count == 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Field(String),
+    Ident(String),
+    Operator(FormulaOperator),
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+fn tokenize(s: &str) -> FlowyResult<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            '+' => Token::Operator(FormulaOperator::Add),
+            '-' => Token::Operator(FormulaOperator::Subtract),
+            '*' => Token::Operator(FormulaOperator::Multiply),
+            '/' => Token::Operator(FormulaOperator::Divide),
+            '&' => Token::Operator(FormulaOperator::Concat),
+            '=' => Token::Operator(FormulaOperator::Equal),
+            '!' => {
+                expect_char(&mut chars, '=')?;
+                Token::Operator(FormulaOperator::NotEqual)
+            }
+            '<' => match chars.next_if_eq(&'=') {
+                Some(_) => Token::Operator(FormulaOperator::LessThanOrEqual),
+                None => Token::Operator(FormulaOperator::LessThan),
+            },
+            '>' => match chars.next_if_eq(&'=') {
+                Some(_) => Token::Operator(FormulaOperator::GreaterThanOrEqual),
+                None => Token::Operator(FormulaOperator::GreaterThan),
+            },
+            '{' => {
+                let field_id = take_until(&mut chars, '}')?;
+                if field_id.trim().is_empty() {
+                    return Err(invalid_formula("The field id is empty"));
+                }
+                Token::Field(field_id.trim().to_owned())
+            }
+            '"' => Token::Text(take_until(&mut chars, '"')?),
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut num = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+                    num.push(c);
+                }
+                let num = num
+                    .parse::<f64>()
+                    .map_err(|_| invalid_formula(format!("Invalid number: {}", num)))?;
+                Token::Number(num)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                    ident.push(c);
+                }
+                Token::Ident(ident)
+            }
+            c => return Err(invalid_formula(format!("Unexpected character: {}", c))),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn expect_char(chars: &mut Peekable<Chars>, expected: char) -> FlowyResult<()> {
+    match chars.next() {
+        Some(c) if c == expected => Ok(()),
+        _ => Err(invalid_formula(format!("Expect: {}", expected))),
+    }
+}
+
+fn take_until(chars: &mut Peekable<Chars>, end: char) -> FlowyResult<String> {
+    let mut s = String::new();
+    for c in chars.by_ref() {
+        if c == end {
+            return Ok(s);
+        }
+        s.push(c);
+    }
+    Err(invalid_formula(format!("Missing: {}", end)))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn next_operator(&mut self, operators: &[FormulaOperator]) -> Option<FormulaOperator> {
+        match self.peek() {
+            Some(Token::Operator(op)) if operators.contains(op) => {
+                let op = *op;
+                self.position += 1;
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_binary<F>(&mut self, operators: &[FormulaOperator], parse_operand: F) -> FlowyResult<FormulaExpr>
+    where
+        F: Fn(&mut Self) -> FlowyResult<FormulaExpr>,
+    {
+        let mut left = parse_operand(self)?;
+        while let Some(op) = self.next_operator(operators) {
+            let right = parse_operand(self)?;
+            left = FormulaExpr::Binary {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn parse_comparison(&mut self) -> FlowyResult<FormulaExpr> {
+        let operators = [
+            FormulaOperator::Equal,
+            FormulaOperator::NotEqual,
+            FormulaOperator::LessThan,
+            FormulaOperator::LessThanOrEqual,
+            FormulaOperator::GreaterThan,
+            FormulaOperator::GreaterThanOrEqual,
+        ];
+        self.parse_binary(&operators, Self::parse_concat)
+    }
+
+    fn parse_concat(&mut self) -> FlowyResult<FormulaExpr> {
+        self.parse_binary(&[FormulaOperator::Concat], Self::parse_additive)
+    }
+
+    fn parse_additive(&mut self) -> FlowyResult<FormulaExpr> {
+        self.parse_binary(
+            &[FormulaOperator::Add, FormulaOperator::Subtract],
+            Self::parse_multiplicative,
+        )
+    }
+
+    fn parse_multiplicative(&mut self) -> FlowyResult<FormulaExpr> {
+        self.parse_binary(&[FormulaOperator::Multiply, FormulaOperator::Divide], Self::parse_unary)
+    }
+
+    fn parse_unary(&mut self) -> FlowyResult<FormulaExpr> {
+        if self.next_operator(&[FormulaOperator::Subtract]).is_some() {
+            let expr = self.parse_unary()?;
+            return Ok(FormulaExpr::Negative(Box::new(expr)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> FlowyResult<FormulaExpr> {
+        match self.next() {
+            Some(Token::Number(num)) => Ok(FormulaExpr::Number(num)),
+            Some(Token::Text(s)) => Ok(FormulaExpr::Text(s)),
+            Some(Token::Field(field_id)) => Ok(FormulaExpr::Field(field_id)),
+            Some(Token::LeftParen) => {
+                let expr = self.parse_comparison()?;
+                self.expect(Token::RightParen)?;
+                Ok(expr)
+            }
+            Some(Token::Ident(ident)) => match ident.to_lowercase().as_str() {
+                "true" => Ok(FormulaExpr::Bool(true)),
+                "false" => Ok(FormulaExpr::Bool(false)),
+                _ => self.parse_call(&ident),
+            },
+            Some(token) => Err(invalid_formula(format!("Unexpected token: {:?}", token))),
+            None => Err(invalid_formula("Unexpected end of the expression")),
+        }
+    }
+
+    fn parse_call(&mut self, name: &str) -> FlowyResult<FormulaExpr> {
+        let function =
+            FormulaFunction::from_name(name).ok_or_else(|| invalid_formula(format!("Unknown function: {}", name)))?;
+        self.expect(Token::LeftParen)?;
+
+        let mut args = vec![];
+        if self.peek() == Some(&Token::RightParen) {
+            self.position += 1;
+        } else {
+            loop {
+                args.push(self.parse_comparison()?);
+                match self.next() {
+                    Some(Token::Comma) => continue,
+                    Some(Token::RightParen) => break,
+                    _ => return Err(invalid_formula(format!("Missing ) after the arguments of {}", name))),
+                }
+            }
+        }
+
+        if !function.is_valid_arg_count(args.len()) {
+            return Err(invalid_formula(format!(
+                "Invalid number of arguments for {}: {}",
+                name,
+                args.len()
+            )));
+        }
+        Ok(FormulaExpr::Call { function, args })
+    }
+
+    fn expect(&mut self, expected: Token) -> FlowyResult<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            _ => Err(invalid_formula(format!("Expect: {:?}", expected))),
+        }
+    }
+}
+
+fn invalid_formula<T: ToString>(msg: T) -> FlowyError {
+    FlowyError::new(ErrorCode::InvalidFormula, &msg.to_string())
+}
@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod tests {
+    use crate::entities::FieldType;
+    use crate::services::cell::{CellDataOperation, FromCellString};
+    use crate::services::field::*;
+    use std::collections::HashMap;
+
+    // 2022-03-14 00:00:00 UTC
+    const MONDAY: i64 = 1647216000;
+
+    #[test]
+    fn formula_parse_test() {
+        assert!(FormulaExpr::parse("1 + 2 * 3").is_ok());
+        assert!(FormulaExpr::parse("if({a} > 1, \"big\", \"small\")").is_ok());
+        assert!(FormulaExpr::parse("-({a} - 1) / 2").is_ok());
+        assert!(FormulaExpr::parse("count({tags}) >= 2").is_ok());
+
+        assert!(FormulaExpr::parse("1 +").is_err());
+        assert!(FormulaExpr::parse("(1 + 2").is_err());
+        assert!(FormulaExpr::parse("{}").is_err());
+        assert!(FormulaExpr::parse("\"abc").is_err());
+        assert!(FormulaExpr::parse("unknown(1)").is_err());
+        assert!(FormulaExpr::parse("if(1, 2)").is_err());
+        assert!(FormulaExpr::parse("1 2").is_err());
+    }
+
+    #[test]
+    fn formula_field_ids_test() {
+        let expr = FormulaExpr::parse("{a} + {b} * {a} & concat({c}, \"{d}\")").unwrap();
+        assert_eq!(expr.field_ids(), vec!["a".to_owned(), "b".to_owned(), "c".to_owned()]);
+    }
+
+    #[test]
+    fn formula_arithmetic_test() {
+        let values = values(vec![
+            ("price", FormulaValue::Number(2.5)),
+            ("count", FormulaValue::Number(4.0)),
+        ]);
+        assert_eq!(eval("1 + 2 * 3", &values), FormulaValue::Number(7.0));
+        assert_eq!(eval("(1 + 2) * 3", &values), FormulaValue::Number(9.0));
+        assert_eq!(eval("{price} * {count}", &values), FormulaValue::Number(10.0));
+        assert_eq!(eval("-{price} + 1", &values), FormulaValue::Number(-1.5));
+        assert_eq!(eval("10 - 4 - 3", &values), FormulaValue::Number(3.0));
+        assert_eq!(eval("round({price} / 3, 2)", &values), FormulaValue::Number(0.83));
+        assert_eq!(eval("abs(1 - {count})", &values), FormulaValue::Number(3.0));
+
+        // The empty cell is treated as zero.
+        assert_eq!(eval("{missing} + 1", &values), FormulaValue::Number(1.0));
+        assert!(eval("1 / 0", &values).is_error());
+        assert!(eval("\"abc\" * 2", &values).is_error());
+    }
+
+    #[test]
+    fn formula_text_test() {
+        let values = values(vec![
+            ("name", FormulaValue::Text("AppFlowy".to_owned())),
+            ("price", FormulaValue::Number(3.0)),
+        ]);
+        assert_eq!(
+            eval("{name} & \" costs \" & {price}", &values),
+            FormulaValue::Text("AppFlowy costs 3".to_owned())
+        );
+        assert_eq!(
+            eval("concat({name}, \"-\", {price} * 2)", &values),
+            FormulaValue::Text("AppFlowy-6".to_owned())
+        );
+    }
+
+    #[test]
+    fn formula_if_test() {
+        let values = values(vec![("price", FormulaValue::Number(3.0))]);
+        assert_eq!(
+            eval("if({price} > 2, \"high\", \"low\")", &values),
+            FormulaValue::Text("high".to_owned())
+        );
+        assert_eq!(
+            eval("if({price} <= 2, \"high\", \"low\")", &values),
+            FormulaValue::Text("low".to_owned())
+        );
+        // The branch that isn't taken is not evaluated.
+        assert_eq!(eval("if(true, 1, 1 / 0)", &values), FormulaValue::Number(1.0));
+    }
+
+    #[test]
+    fn formula_date_test() {
+        let values = values(vec![
+            ("start", FormulaValue::Date(MONDAY)),
+            ("end", FormulaValue::Date(MONDAY + 3 * SECONDS_PER_DAY)),
+        ]);
+        assert_eq!(
+            eval("date_add({start}, 2)", &values),
+            FormulaValue::Date(MONDAY + 2 * SECONDS_PER_DAY)
+        );
+        assert_eq!(
+            eval("{start} + 1", &values),
+            FormulaValue::Date(MONDAY + SECONDS_PER_DAY)
+        );
+        assert_eq!(
+            eval("{end} - 1", &values),
+            FormulaValue::Date(MONDAY + 2 * SECONDS_PER_DAY)
+        );
+        assert_eq!(eval("date_diff({end}, {start})", &values), FormulaValue::Number(3.0));
+        assert_eq!(eval("{end} - {start}", &values), FormulaValue::Number(3.0));
+        assert_eq!(eval("{end} > {start}", &values), FormulaValue::Bool(true));
+        assert_eq!(eval("date_add({missing}, 1)", &values), FormulaValue::Empty);
+    }
+
+    #[test]
+    fn formula_date_out_of_range_test() {
+        let values = values(vec![
+            ("start", FormulaValue::Date(MONDAY)),
+            ("max", FormulaValue::Date(i64::MAX)),
+        ]);
+        assert!(eval("date_add({start}, 1000000000000000)", &values).is_error());
+        assert!(eval("{start} - 1000000000000000", &values).is_error());
+        assert!(eval("{max} + 1", &values).is_error());
+    }
+
+    #[test]
+    fn formula_multi_select_test() {
+        let values = values(vec![(
+            "tags",
+            FormulaValue::List(vec!["Google".to_owned(), "Twitter".to_owned()]),
+        )]);
+        assert_eq!(eval("count({tags})", &values), FormulaValue::Number(2.0));
+        assert_eq!(eval("count({missing})", &values), FormulaValue::Number(0.0));
+        assert_eq!(
+            eval("join({tags}, \" / \")", &values),
+            FormulaValue::Text("Google / Twitter".to_owned())
+        );
+        assert_eq!(eval("contains({tags}, \"Google\")", &values), FormulaValue::Bool(true));
+        assert_eq!(
+            eval("contains({tags}, \"Facebook\")", &values),
+            FormulaValue::Bool(false)
+        );
+    }
+
+    #[test]
+    fn formula_result_type_test() {
+        let values = values(vec![("price", FormulaValue::Number(3.0))]);
+        let mut type_option = FormulaTypeOption {
+            expression: "{price} * 2".to_owned(),
+            result_type: FormulaResultType::Text,
+        };
+        assert_eq!(type_option.evaluate(&values), FormulaValue::Text("6".to_owned()));
+
+        type_option.result_type = FormulaResultType::Checkbox;
+        assert_eq!(type_option.evaluate(&values), FormulaValue::Bool(true));
+
+        type_option.expression = "\"abc\"".to_owned();
+        type_option.result_type = FormulaResultType::Number;
+        assert!(type_option.evaluate(&values).is_error());
+
+        type_option.expression = "".to_owned();
+        assert_eq!(type_option.evaluate(&values), FormulaValue::Empty);
+    }
+
+    #[test]
+    fn formula_cell_data_test() {
+        let type_option = FormulaTypeOption::default();
+        let field_rev = FieldBuilder::from_field_type(&FieldType::Formula).build();
+        let value = FormulaValue::Number(12.0);
+        let cell_data = value.to_json().unwrap();
+        assert_eq!(FormulaValue::from_cell_str(&cell_data).unwrap(), value);
+
+        let cell_bytes = type_option
+            .decode_cell_data(cell_data.into(), &FieldType::Formula, &field_rev)
+            .unwrap();
+        assert_eq!(cell_bytes.to_string(), "12");
+
+        // The formula cell can't be edited.
+        assert!(type_option.apply_changeset("1".to_owned().into(), None).is_err());
+    }
+
+    fn eval(s: &str, values: &HashMap<String, FormulaValue>) -> FormulaValue {
+        evaluate_formula(&FormulaExpr::parse(s).unwrap(), values)
+    }
+
+    fn values(values: Vec<(&str, FormulaValue)>) -> HashMap<String, FormulaValue> {
+        values
+            .into_iter()
+            .map(|(field_id, value)| (field_id.to_owned(), value))
+            .collect()
+    }
+}
@@ -0,0 +1,135 @@
+use crate::entities::FieldType;
+use crate::impl_type_option;
+use crate::services::cell::{CellBytes, CellData, CellDataChangeset, CellDataOperation, CellDisplayable};
+use crate::services::field::{
+    evaluate_formula, BoxTypeOptionBuilder, FormulaExpr, FormulaResultType, FormulaValue, TypeOptionBuilder,
+};
+use bytes::Bytes;
+use flowy_derive::ProtoBuf;
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use flowy_grid_data_model::revision::{CellRevision, FieldRevision, TypeOptionDataDeserializer, TypeOptionDataEntry};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Default)]
+pub struct FormulaTypeOptionBuilder(FormulaTypeOption);
+impl_into_box_type_option_builder!(FormulaTypeOptionBuilder);
+impl_builder_from_json_str_and_from_bytes!(FormulaTypeOptionBuilder, FormulaTypeOption);
+
+impl FormulaTypeOptionBuilder {
+    pub fn expression(mut self, expression: &str) -> Self {
+        self.0.expression = expression.to_owned();
+        self
+    }
+
+    pub fn result_type(mut self, result_type: FormulaResultType) -> Self {
+        self.0.result_type = result_type;
+        self
+    }
+}
+
+impl TypeOptionBuilder for FormulaTypeOptionBuilder {
+    fn field_type(&self) -> FieldType {
+        FieldType::Formula
+    }
+
+    fn entry(&self) -> &dyn TypeOptionDataEntry {
+        &self.0
+    }
+}
+
+// Formula
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ProtoBuf)]
+pub struct FormulaTypeOption {
+    #[pb(index = 1)]
+    pub expression: String,
+
+    #[pb(index = 2)]
+    pub result_type: FormulaResultType,
+}
+impl_type_option!(FormulaTypeOption, FieldType::Formula);
+
+impl FormulaTypeOption {
+    /// Return None if the expression is empty.
+    pub fn parse_expression(&self) -> FlowyResult<Option<FormulaExpr>> {
+        if self.expression.trim().is_empty() {
+            return Ok(None);
+        }
+        FormulaExpr::parse(&self.expression).map(Some)
+    }
+
+    /// Return the ids of the fields that are referenced by the expression. Return empty if the
+    /// expression is invalid.
+    pub fn referenced_field_ids(&self) -> Vec<String> {
+        match self.parse_expression() {
+            Ok(Some(expr)) => expr.field_ids(),
+            _ => vec![],
+        }
+    }
+
+    /// Evaluate the expression and convert the result to the `result_type`.
+    pub fn evaluate(&self, values: &HashMap<String, FormulaValue>) -> FormulaValue {
+        match self.parse_expression() {
+            Ok(None) => FormulaValue::Empty,
+            Ok(Some(expr)) => self.convert_to_result_type(evaluate_formula(&expr, values)),
+            Err(e) => FormulaValue::Error(e.msg),
+        }
+    }
+
+    fn convert_to_result_type(&self, value: FormulaValue) -> FormulaValue {
+        if value.is_error() || value == FormulaValue::Empty {
+            return value;
+        }
+
+        match self.result_type {
+            FormulaResultType::Text => FormulaValue::Text(value.to_display_string()),
+            FormulaResultType::Number => match value.as_number() {
+                Some(num) => FormulaValue::Number(num),
+                None => FormulaValue::error(format!("{} is not a number", value.to_display_string())),
+            },
+            FormulaResultType::Date => match value.as_timestamp() {
+                Some(timestamp) => FormulaValue::Date(timestamp),
+                None => FormulaValue::error(format!("{} is not a date", value.to_display_string())),
+            },
+            FormulaResultType::Checkbox => FormulaValue::Bool(value.as_bool()),
+        }
+    }
+}
+
+impl CellDisplayable<FormulaValue> for FormulaTypeOption {
+    fn display_data(
+        &self,
+        cell_data: CellData<FormulaValue>,
+        _decoded_field_type: &FieldType,
+        _field_rev: &FieldRevision,
+    ) -> FlowyResult<CellBytes> {
+        let value: FormulaValue = cell_data.try_into_inner()?;
+        Ok(CellBytes::new(value.to_display_string()))
+    }
+}
+
+impl CellDataOperation<FormulaValue, String> for FormulaTypeOption {
+    fn decode_cell_data(
+        &self,
+        cell_data: CellData<FormulaValue>,
+        decoded_field_type: &FieldType,
+        field_rev: &FieldRevision,
+    ) -> FlowyResult<CellBytes> {
+        if !decoded_field_type.is_formula() {
+            return Ok(CellBytes::default());
+        }
+        self.display_data(cell_data, decoded_field_type, field_rev)
+    }
+
+    fn apply_changeset(
+        &self,
+        _changeset: CellDataChangeset<String>,
+        _cell_rev: Option<CellRevision>,
+    ) -> Result<String, FlowyError> {
+        // The formula cells are computed from the other cells of the row.
+        Err(FlowyError::new(
+            ErrorCode::FieldInvalidOperation,
+            "The cell of the formula field can't be edited",
+        ))
+    }
+}
@@ -0,0 +1,139 @@
+use crate::services::cell::FromCellString;
+use chrono::NaiveDateTime;
+use flowy_derive::ProtoBuf_Enum;
+use flowy_error::{internal_error, FlowyResult};
+use serde::{Deserialize, Serialize};
+
+pub const SECONDS_PER_DAY: i64 = 86400;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ProtoBuf_Enum)]
+pub enum FormulaResultType {
+    Text = 0,
+    Number = 1,
+    Date = 2,
+    Checkbox = 3,
+}
+
+impl std::default::Default for FormulaResultType {
+    fn default() -> Self {
+        FormulaResultType::Text
+    }
+}
+
+/// The value that is produced by evaluating the formula. It's serialized to json and saved as the
+/// data of the formula cell.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum FormulaValue {
+    Empty,
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    /// The timestamp in seconds.
+    Date(i64),
+    /// The names of the selected options.
+    List(Vec<String>),
+    Error(String),
+}
+
+impl std::default::Default for FormulaValue {
+    fn default() -> Self {
+        FormulaValue::Empty
+    }
+}
+
+impl FormulaValue {
+    pub fn error<T: ToString>(msg: T) -> Self {
+        FormulaValue::Error(msg.to_string())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            FormulaValue::Empty => true,
+            FormulaValue::Text(s) => s.is_empty(),
+            FormulaValue::List(items) => items.is_empty(),
+            _ => false,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, FormulaValue::Error(_))
+    }
+
+    /// The empty value is treated as zero, so `{price} + 1` works on the empty cells.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            FormulaValue::Empty => Some(0.0),
+            FormulaValue::Number(num) => Some(*num),
+            FormulaValue::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+            FormulaValue::Text(s) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_timestamp(&self) -> Option<i64> {
+        match self {
+            FormulaValue::Date(timestamp) => Some(*timestamp),
+            FormulaValue::Number(num) => Some(*num as i64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> bool {
+        match self {
+            FormulaValue::Empty | FormulaValue::Error(_) => false,
+            FormulaValue::Number(num) => *num != 0.0,
+            FormulaValue::Text(s) => !s.is_empty(),
+            FormulaValue::Bool(b) => *b,
+            FormulaValue::Date(_) => true,
+            FormulaValue::List(items) => !items.is_empty(),
+        }
+    }
+
+    /// Return the items of the list. The text is treated as a list that contains one item.
+    pub fn as_list(&self) -> Vec<String> {
+        match self {
+            FormulaValue::List(items) => items.clone(),
+            FormulaValue::Empty => vec![],
+            other => vec![other.to_display_string()],
+        }
+    }
+
+    pub fn to_display_string(&self) -> String {
+        match self {
+            FormulaValue::Empty => "".to_owned(),
+            FormulaValue::Number(num) => format_number(*num),
+            FormulaValue::Text(s) => s.clone(),
+            FormulaValue::Bool(b) => b.to_string(),
+            FormulaValue::Date(timestamp) => NaiveDateTime::from_timestamp(*timestamp, 0)
+                .format("%Y/%m/%d")
+                .to_string(),
+            FormulaValue::List(items) => items.join(", "),
+            FormulaValue::Error(msg) => format!("#ERROR: {}", msg),
+        }
+    }
+
+    pub fn to_json(&self) -> FlowyResult<String> {
+        serde_json::to_string(self).map_err(internal_error)
+    }
+}
+
+impl FromCellString for FormulaValue {
+    fn from_cell_str(s: &str) -> FlowyResult<Self>
+    where
+        Self: Sized,
+    {
+        if s.is_empty() {
+            return Ok(FormulaValue::Empty);
+        }
+        serde_json::from_str::<FormulaValue>(s).map_err(internal_error)
+    }
+}
+
+fn format_number(num: f64) -> String {
+    if num.fract() == 0.0 && num.abs() < 1e15 {
+        format!("{}", num as i64)
+    } else {
+        format!("{}", num)
+    }
+}
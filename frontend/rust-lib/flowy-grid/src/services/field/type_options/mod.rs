@@ -1,6 +1,9 @@
 pub mod checkbox_type_option;
 pub mod date_type_option;
+pub mod formula_type_option;
 pub mod number_type_option;
+pub mod relation_type_option;
+pub mod rollup_type_option;
 pub mod selection_type_option;
 pub mod text_type_option;
 pub mod url_type_option;
@@ -8,7 +11,10 @@ mod util;
 
 pub use checkbox_type_option::*;
 pub use date_type_option::*;
+pub use formula_type_option::*;
 pub use number_type_option::*;
+pub use relation_type_option::*;
+pub use rollup_type_option::*;
 pub use selection_type_option::*;
 pub use text_type_option::*;
 pub use url_type_option::*;
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use crate::entities::FieldType;
+    use crate::services::cell::{AnyCellData, CellDataOperation};
+    use crate::services::field::*;
+    use flowy_grid_data_model::revision::CellRevision;
+
+    #[test]
+    fn relation_insert_and_delete_row_ids_test() {
+        let type_option = RelationTypeOption::default();
+        let cell_data = apply(&type_option, RelationCellChangeset::from_insert("row_a"), None);
+        assert_eq!(cell_data, "row_a");
+
+        let changeset = RelationCellChangeset {
+            inserted_row_ids: vec!["row_b".to_owned(), "row_a".to_owned(), "row_c".to_owned()],
+            deleted_row_ids: vec![],
+        };
+        let cell_data = apply(&type_option, changeset, Some(cell_data));
+        assert_eq!(cell_data, "row_a,row_b,row_c");
+
+        let cell_data = apply(
+            &type_option,
+            RelationCellChangeset::from_delete("row_b"),
+            Some(cell_data),
+        );
+        assert_eq!(cell_data, "row_a,row_c");
+    }
+
+    #[test]
+    fn relation_decode_cell_data_test() {
+        let type_option = RelationTypeOption::default();
+        let field_rev = FieldBuilder::from_field_type(&FieldType::Relation).build();
+        let cell_bytes = type_option
+            .decode_cell_data("row_a,row_b".to_owned().into(), &FieldType::Relation, &field_rev)
+            .unwrap();
+        let cell_data = cell_bytes.with_parser(RelationCellDataParser()).unwrap();
+        assert_eq!(cell_data.row_ids, vec!["row_a".to_owned(), "row_b".to_owned()]);
+
+        let cell_bytes = type_option
+            .decode_cell_data("".to_owned().into(), &FieldType::Relation, &field_rev)
+            .unwrap();
+        let cell_data = cell_bytes.with_parser(RelationCellDataParser()).unwrap();
+        assert!(cell_data.row_ids.is_empty());
+    }
+
+    fn apply(type_option: &RelationTypeOption, changeset: RelationCellChangeset, cell_data: Option<String>) -> String {
+        let cell_rev = cell_data.map(|data| CellRevision::new(AnyCellData::new(data, FieldType::Relation).json()));
+        type_option
+            .apply_changeset(changeset.to_str().into(), cell_rev)
+            .unwrap()
+    }
+}
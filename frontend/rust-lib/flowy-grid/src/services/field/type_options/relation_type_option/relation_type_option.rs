@@ -0,0 +1,108 @@
+use crate::entities::FieldType;
+use crate::impl_type_option;
+use crate::services::cell::{CellBytes, CellData, CellDataChangeset, CellDataOperation, CellDisplayable};
+use crate::services::field::type_options::util::get_cell_data;
+use crate::services::field::{
+    BoxTypeOptionBuilder, RelationCellChangeset, RelationCellDataPB, RelationRowIds, TypeOptionBuilder,
+};
+use bytes::Bytes;
+use flowy_derive::ProtoBuf;
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_grid_data_model::revision::{CellRevision, FieldRevision, TypeOptionDataDeserializer, TypeOptionDataEntry};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default)]
+pub struct RelationTypeOptionBuilder(RelationTypeOption);
+impl_into_box_type_option_builder!(RelationTypeOptionBuilder);
+impl_builder_from_json_str_and_from_bytes!(RelationTypeOptionBuilder, RelationTypeOption);
+
+impl RelationTypeOptionBuilder {
+    pub fn target_grid_id(mut self, grid_id: &str) -> Self {
+        self.0.target_grid_id = grid_id.to_owned();
+        self
+    }
+
+    pub fn back_reference_field_id(mut self, field_id: &str) -> Self {
+        self.0.back_reference_field_id = field_id.to_owned();
+        self
+    }
+}
+
+impl TypeOptionBuilder for RelationTypeOptionBuilder {
+    fn field_type(&self) -> FieldType {
+        FieldType::Relation
+    }
+
+    fn entry(&self) -> &dyn TypeOptionDataEntry {
+        &self.0
+    }
+}
+
+// Relation
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ProtoBuf)]
+pub struct RelationTypeOption {
+    /// The id of the grid whose rows are linked by the cells of this field.
+    #[pb(index = 1)]
+    pub target_grid_id: String,
+
+    /// The id of the relation field in the target grid that links the rows back to this grid. It's
+    /// generated when the field is created if it's empty.
+    #[pb(index = 2)]
+    pub back_reference_field_id: String,
+}
+impl_type_option!(RelationTypeOption, FieldType::Relation);
+
+impl RelationTypeOption {
+    /// Return true if the target grid of the relation is chosen.
+    pub fn has_target(&self) -> bool {
+        !self.target_grid_id.is_empty()
+    }
+}
+
+impl CellDisplayable<RelationRowIds> for RelationTypeOption {
+    fn display_data(
+        &self,
+        cell_data: CellData<RelationRowIds>,
+        _decoded_field_type: &FieldType,
+        _field_rev: &FieldRevision,
+    ) -> FlowyResult<CellBytes> {
+        let row_ids: RelationRowIds = cell_data.try_into_inner()?;
+        CellBytes::from(RelationCellDataPB {
+            row_ids: row_ids.into_inner(),
+        })
+    }
+}
+
+impl CellDataOperation<RelationRowIds, RelationCellChangeset> for RelationTypeOption {
+    fn decode_cell_data(
+        &self,
+        cell_data: CellData<RelationRowIds>,
+        decoded_field_type: &FieldType,
+        field_rev: &FieldRevision,
+    ) -> FlowyResult<CellBytes> {
+        if !decoded_field_type.is_relation() {
+            return Ok(CellBytes::default());
+        }
+        self.display_data(cell_data, decoded_field_type, field_rev)
+    }
+
+    fn apply_changeset(
+        &self,
+        changeset: CellDataChangeset<RelationCellChangeset>,
+        cell_rev: Option<CellRevision>,
+    ) -> Result<String, FlowyError> {
+        let changeset = changeset.try_into_inner()?;
+        let mut row_ids = match cell_rev {
+            None => RelationRowIds::default(),
+            Some(cell_rev) => RelationRowIds::from(get_cell_data(&cell_rev)),
+        };
+
+        row_ids.retain(|row_id| !changeset.deleted_row_ids.contains(row_id));
+        for row_id in changeset.inserted_row_ids {
+            if !row_ids.contains(&row_id) {
+                row_ids.push(row_id);
+            }
+        }
+        Ok(row_ids.to_cell_data())
+    }
+}
@@ -0,0 +1,170 @@
+use crate::entities::{CellChangesetPB, GridCellIdPB, GridCellIdParams};
+use crate::services::cell::{CellBytesParser, FromCellChangeset, FromCellString};
+use bytes::Bytes;
+use flowy_derive::ProtoBuf;
+use flowy_error::{internal_error, ErrorCode, FlowyResult};
+use serde::{Deserialize, Serialize};
+
+pub const RELATION_ROW_IDS_SEPARATOR: &str = ",";
+
+/// The ids of the rows in the target grid that are linked by the relation cell.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelationRowIds(Vec<String>);
+
+impl RelationRowIds {
+    pub fn into_inner(self) -> Vec<String> {
+        self.0
+    }
+
+    pub fn to_cell_data(&self) -> String {
+        self.0.join(RELATION_ROW_IDS_SEPARATOR)
+    }
+}
+
+impl FromCellString for RelationRowIds {
+    fn from_cell_str(s: &str) -> FlowyResult<Self>
+    where
+        Self: Sized,
+    {
+        Ok(Self::from(s.to_owned()))
+    }
+}
+
+impl std::convert::From<String> for RelationRowIds {
+    fn from(s: String) -> Self {
+        let row_ids = s
+            .split(RELATION_ROW_IDS_SEPARATOR)
+            .filter(|row_id| !row_id.is_empty())
+            .map(|row_id| row_id.to_owned())
+            .collect::<Vec<String>>();
+        Self(row_ids)
+    }
+}
+
+impl std::convert::From<Vec<String>> for RelationRowIds {
+    fn from(row_ids: Vec<String>) -> Self {
+        Self(row_ids)
+    }
+}
+
+impl std::ops::Deref for RelationRowIds {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for RelationRowIds {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct RelationCellDataPB {
+    #[pb(index = 1)]
+    pub row_ids: Vec<String>,
+}
+
+pub struct RelationCellDataParser();
+impl CellBytesParser for RelationCellDataParser {
+    type Object = RelationCellDataPB;
+
+    fn parse(&self, bytes: &Bytes) -> FlowyResult<Self::Object> {
+        RelationCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RelationCellChangeset {
+    #[serde(default)]
+    pub inserted_row_ids: Vec<String>,
+
+    #[serde(default)]
+    pub deleted_row_ids: Vec<String>,
+}
+
+impl FromCellChangeset for RelationCellChangeset {
+    fn from_changeset(changeset: String) -> FlowyResult<Self>
+    where
+        Self: Sized,
+    {
+        serde_json::from_str::<RelationCellChangeset>(&changeset).map_err(internal_error)
+    }
+}
+
+impl RelationCellChangeset {
+    pub fn from_insert(row_id: &str) -> Self {
+        RelationCellChangeset {
+            inserted_row_ids: vec![row_id.to_owned()],
+            deleted_row_ids: vec![],
+        }
+    }
+
+    pub fn from_delete(row_id: &str) -> Self {
+        RelationCellChangeset {
+            inserted_row_ids: vec![],
+            deleted_row_ids: vec![row_id.to_owned()],
+        }
+    }
+
+    pub fn to_str(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct RelationCellChangesetPayloadPB {
+    #[pb(index = 1)]
+    pub cell_identifier: GridCellIdPB,
+
+    #[pb(index = 2)]
+    pub inserted_row_ids: Vec<String>,
+
+    #[pb(index = 3)]
+    pub deleted_row_ids: Vec<String>,
+}
+
+pub struct RelationCellChangesetParams {
+    pub cell_identifier: GridCellIdParams,
+    pub inserted_row_ids: Vec<String>,
+    pub deleted_row_ids: Vec<String>,
+}
+
+impl std::convert::From<RelationCellChangesetParams> for CellChangesetPB {
+    fn from(params: RelationCellChangesetParams) -> Self {
+        let changeset = RelationCellChangeset {
+            inserted_row_ids: params.inserted_row_ids,
+            deleted_row_ids: params.deleted_row_ids,
+        };
+        CellChangesetPB {
+            grid_id: params.cell_identifier.grid_id,
+            row_id: params.cell_identifier.row_id,
+            field_id: params.cell_identifier.field_id,
+            content: Some(changeset.to_str()),
+        }
+    }
+}
+
+impl TryInto<RelationCellChangesetParams> for RelationCellChangesetPayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<RelationCellChangesetParams, Self::Error> {
+        let cell_identifier: GridCellIdParams = self.cell_identifier.try_into()?;
+        if self
+            .inserted_row_ids
+            .iter()
+            .chain(self.deleted_row_ids.iter())
+            .any(|row_id| row_id.is_empty())
+        {
+            return Err(ErrorCode::RowIdIsEmpty);
+        }
+
+        Ok(RelationCellChangesetParams {
+            cell_identifier,
+            inserted_row_ids: self.inserted_row_ids,
+            deleted_row_ids: self.deleted_row_ids,
+        })
+    }
+}
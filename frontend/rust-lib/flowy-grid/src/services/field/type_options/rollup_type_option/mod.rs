@@ -0,0 +1,7 @@
+#![allow(clippy::module_inception)]
+mod rollup_tests;
+mod rollup_type_option;
+mod rollup_type_option_entities;
+
+pub use rollup_type_option::*;
+pub use rollup_type_option_entities::*;
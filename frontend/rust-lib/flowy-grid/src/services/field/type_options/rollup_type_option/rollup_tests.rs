@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use crate::entities::FieldType;
+    use crate::services::cell::CellDataOperation;
+    use crate::services::field::*;
+
+    // 2022-03-14 00:00:00 UTC
+    const MONDAY: i64 = 1647216000;
+
+    #[test]
+    fn rollup_count_and_sum_test() {
+        let values = vec![
+            FormulaValue::Number(1.5),
+            FormulaValue::Empty,
+            FormulaValue::Text("2".to_owned()),
+            FormulaValue::Text("abc".to_owned()),
+            FormulaValue::error("Division by zero"),
+        ];
+        assert_eq!(
+            calculate(RollupCalculation::Count, values.clone()),
+            FormulaValue::Number(5.0)
+        );
+        assert_eq!(calculate(RollupCalculation::Sum, values), FormulaValue::Number(3.5));
+        assert_eq!(calculate(RollupCalculation::Sum, vec![]), FormulaValue::Number(0.0));
+    }
+
+    #[test]
+    fn rollup_min_max_test() {
+        let values = vec![
+            FormulaValue::Number(3.0),
+            FormulaValue::Empty,
+            FormulaValue::Number(-1.0),
+            FormulaValue::Number(7.0),
+        ];
+        assert_eq!(
+            calculate(RollupCalculation::Min, values.clone()),
+            FormulaValue::Number(-1.0)
+        );
+        assert_eq!(calculate(RollupCalculation::Max, values), FormulaValue::Number(7.0));
+
+        let dates = vec![FormulaValue::Date(MONDAY + 86400), FormulaValue::Date(MONDAY)];
+        assert_eq!(calculate(RollupCalculation::Min, dates), FormulaValue::Date(MONDAY));
+        assert_eq!(
+            calculate(RollupCalculation::Max, vec![FormulaValue::Empty]),
+            FormulaValue::Empty
+        );
+    }
+
+    #[test]
+    fn rollup_unique_values_test() {
+        let values = vec![
+            FormulaValue::List(vec!["Google".to_owned(), "Twitter".to_owned()]),
+            FormulaValue::Text("Google".to_owned()),
+            FormulaValue::Empty,
+            FormulaValue::List(vec!["Facebook".to_owned()]),
+        ];
+        let value = calculate(RollupCalculation::UniqueValues, values);
+        assert_eq!(value.to_display_string(), "Google, Twitter, Facebook");
+        assert_eq!(
+            calculate(RollupCalculation::UniqueValues, vec![FormulaValue::Empty]),
+            FormulaValue::Empty
+        );
+    }
+
+    #[test]
+    fn rollup_cell_data_test() {
+        let type_option = RollupTypeOption::default();
+        let field_rev = FieldBuilder::from_field_type(&FieldType::Rollup).build();
+        let cell_data = FormulaValue::Number(12.0).to_json().unwrap();
+        let cell_bytes = type_option
+            .decode_cell_data(cell_data.into(), &FieldType::Rollup, &field_rev)
+            .unwrap();
+        assert_eq!(cell_bytes.to_string(), "12");
+
+        // The rollup cell can't be edited.
+        assert!(type_option.apply_changeset("1".to_owned().into(), None).is_err());
+    }
+
+    fn calculate(calculation: RollupCalculation, values: Vec<FormulaValue>) -> FormulaValue {
+        let type_option = RollupTypeOption {
+            calculation,
+            ..Default::default()
+        };
+        type_option.calculate(values)
+    }
+}
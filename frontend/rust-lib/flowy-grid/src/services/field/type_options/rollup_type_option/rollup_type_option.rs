@@ -0,0 +1,162 @@
+use crate::entities::FieldType;
+use crate::impl_type_option;
+use crate::services::cell::{CellBytes, CellData, CellDataChangeset, CellDataOperation, CellDisplayable};
+use crate::services::field::{BoxTypeOptionBuilder, FormulaValue, RollupCalculation, TypeOptionBuilder};
+use bytes::Bytes;
+use flowy_derive::ProtoBuf;
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use flowy_grid_data_model::revision::{CellRevision, FieldRevision, TypeOptionDataDeserializer, TypeOptionDataEntry};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default)]
+pub struct RollupTypeOptionBuilder(RollupTypeOption);
+impl_into_box_type_option_builder!(RollupTypeOptionBuilder);
+impl_builder_from_json_str_and_from_bytes!(RollupTypeOptionBuilder, RollupTypeOption);
+
+impl RollupTypeOptionBuilder {
+    pub fn relation_field_id(mut self, field_id: &str) -> Self {
+        self.0.relation_field_id = field_id.to_owned();
+        self
+    }
+
+    pub fn target_field_id(mut self, field_id: &str) -> Self {
+        self.0.target_field_id = field_id.to_owned();
+        self
+    }
+
+    pub fn calculation(mut self, calculation: RollupCalculation) -> Self {
+        self.0.calculation = calculation;
+        self
+    }
+}
+
+impl TypeOptionBuilder for RollupTypeOptionBuilder {
+    fn field_type(&self) -> FieldType {
+        FieldType::Rollup
+    }
+
+    fn entry(&self) -> &dyn TypeOptionDataEntry {
+        &self.0
+    }
+}
+
+// Rollup
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ProtoBuf)]
+pub struct RollupTypeOption {
+    /// The id of the relation field in the same grid that links the rows to aggregate.
+    #[pb(index = 1)]
+    pub relation_field_id: String,
+
+    /// The id of the field in the target grid of the relation. It can be empty if the calculation
+    /// is [RollupCalculation::Count].
+    #[pb(index = 2)]
+    pub target_field_id: String,
+
+    #[pb(index = 3)]
+    pub calculation: RollupCalculation,
+}
+impl_type_option!(RollupTypeOption, FieldType::Rollup);
+
+impl RollupTypeOption {
+    /// Aggregate the values of the target field in the related rows. Each row contributes one value
+    /// to the `values`, the empty cells included.
+    pub fn calculate(&self, values: Vec<FormulaValue>) -> FormulaValue {
+        if self.calculation == RollupCalculation::Count {
+            return FormulaValue::Number(values.len() as f64);
+        }
+
+        // The cells of the formula fields that failed to evaluate are ignored.
+        let values = values
+            .into_iter()
+            .filter(|value| !value.is_error())
+            .collect::<Vec<FormulaValue>>();
+        match self.calculation {
+            RollupCalculation::Count => unreachable!(),
+            RollupCalculation::Sum => FormulaValue::Number(
+                values
+                    .iter()
+                    .filter(|value| !value.is_empty())
+                    .flat_map(|value| value.as_number())
+                    .sum(),
+            ),
+            RollupCalculation::Min => min_or_max(values, true),
+            RollupCalculation::Max => min_or_max(values, false),
+            RollupCalculation::UniqueValues => {
+                let mut unique_values: Vec<String> = vec![];
+                for item in values.iter().flat_map(|value| value.as_list()) {
+                    if !item.is_empty() && !unique_values.contains(&item) {
+                        unique_values.push(item);
+                    }
+                }
+                if unique_values.is_empty() {
+                    FormulaValue::Empty
+                } else {
+                    FormulaValue::List(unique_values)
+                }
+            }
+        }
+    }
+}
+
+/// Return the minimum or the maximum of the numbers and the dates. Return empty if there is no
+/// number or date in the values.
+fn min_or_max(values: Vec<FormulaValue>, is_min: bool) -> FormulaValue {
+    let sort_key = |value: &FormulaValue| match value {
+        FormulaValue::Number(num) => Some(*num),
+        FormulaValue::Date(timestamp) => Some(*timestamp as f64),
+        _ => None,
+    };
+
+    let mut result: Option<(f64, FormulaValue)> = None;
+    for value in values {
+        if let Some(key) = sort_key(&value) {
+            let is_replaced = match &result {
+                None => true,
+                Some((current, _)) if is_min => key < *current,
+                Some((current, _)) => key > *current,
+            };
+            if is_replaced {
+                result = Some((key, value));
+            }
+        }
+    }
+    result.map(|(_, value)| value).unwrap_or_default()
+}
+
+impl CellDisplayable<FormulaValue> for RollupTypeOption {
+    fn display_data(
+        &self,
+        cell_data: CellData<FormulaValue>,
+        _decoded_field_type: &FieldType,
+        _field_rev: &FieldRevision,
+    ) -> FlowyResult<CellBytes> {
+        let value: FormulaValue = cell_data.try_into_inner()?;
+        Ok(CellBytes::new(value.to_display_string()))
+    }
+}
+
+impl CellDataOperation<FormulaValue, String> for RollupTypeOption {
+    fn decode_cell_data(
+        &self,
+        cell_data: CellData<FormulaValue>,
+        decoded_field_type: &FieldType,
+        field_rev: &FieldRevision,
+    ) -> FlowyResult<CellBytes> {
+        if !decoded_field_type.is_rollup() {
+            return Ok(CellBytes::default());
+        }
+        self.display_data(cell_data, decoded_field_type, field_rev)
+    }
+
+    fn apply_changeset(
+        &self,
+        _changeset: CellDataChangeset<String>,
+        _cell_rev: Option<CellRevision>,
+    ) -> Result<String, FlowyError> {
+        // The rollup cells are computed from the related rows.
+        Err(FlowyError::new(
+            ErrorCode::FieldInvalidOperation,
+            "The cell of the rollup field can't be edited",
+        ))
+    }
+}
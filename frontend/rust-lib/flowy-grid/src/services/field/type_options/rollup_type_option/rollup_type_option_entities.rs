@@ -0,0 +1,18 @@
+use flowy_derive::ProtoBuf_Enum;
+use serde::{Deserialize, Serialize};
+
+/// The calculation that aggregates the values of the related rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ProtoBuf_Enum)]
+pub enum RollupCalculation {
+    Count = 0,
+    Sum = 1,
+    Min = 2,
+    Max = 3,
+    UniqueValues = 4,
+}
+
+impl std::default::Default for RollupCalculation {
+    fn default() -> Self {
+        RollupCalculation::Count
+    }
+}
@@ -1,3 +1,6 @@
 #![allow(clippy::module_inception)]
 mod text_type_option;
+mod text_type_option_entities;
+
 pub use text_type_option::*;
+pub use text_type_option_entities::*;
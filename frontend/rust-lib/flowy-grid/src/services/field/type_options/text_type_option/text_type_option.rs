@@ -1,14 +1,20 @@
 use crate::entities::FieldType;
 use crate::impl_type_option;
 use crate::services::cell::{
-    try_decode_cell_data, CellBytes, CellBytesParser, CellData, CellDataChangeset, CellDataOperation, CellDisplayable,
-    FromCellString,
+    try_decode_cell_data, AnyCellData, CellBytes, CellBytesParser, CellData, CellDataChangeset, CellDataOperation,
+    CellDisplayable, FromCellString,
+};
+use crate::services::field::type_options::util::get_cell_data;
+use crate::services::field::{
+    BoxTypeOptionBuilder, RichTextCellChangeset, RichTextCellData, RichTextCellDataPB, TypeOptionBuilder,
 };
-use crate::services::field::{BoxTypeOptionBuilder, TypeOptionBuilder};
 use bytes::Bytes;
 use flowy_derive::ProtoBuf;
-use flowy_error::{FlowyError, FlowyResult};
+use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
 use flowy_grid_data_model::revision::{CellRevision, FieldRevision, TypeOptionDataDeserializer, TypeOptionDataEntry};
+use flowy_sync::client_document::{ClientDocument, PlainDoc};
+use lib_ot::core::Interval;
+use lib_ot::rich_text::{is_block_except_header, AttributeScope, RichTextAttribute, RichTextAttributeKey};
 use serde::{Deserialize, Serialize};
 
 #[derive(Default)]
@@ -33,6 +39,70 @@ pub struct RichTextTypeOption {
 }
 impl_type_option!(RichTextTypeOption, FieldType::RichText);
 
+impl RichTextTypeOption {
+    /// Return the text and the delta of the cell. The cell that was created by other field types is
+    /// treated as the plain text.
+    pub fn rich_text_cell_data(&self, any_cell_data: AnyCellData) -> RichTextCellDataPB {
+        let cell_data: CellData<RichTextCellData> = any_cell_data.data.into();
+        cell_data.try_into_inner().unwrap_or_default().into()
+    }
+
+    /// Apply the changeset to the cell and return the string that is stored in the cell.
+    pub fn apply_rich_text_cell_changeset(
+        &self,
+        changeset: RichTextCellChangeset,
+        cell_rev: Option<CellRevision>,
+    ) -> FlowyResult<String> {
+        let cell_data = match cell_rev {
+            None => RichTextCellData::default(),
+            Some(cell_rev) => RichTextCellData::from_cell_str(&get_cell_data(&cell_rev))?,
+        };
+        let cell_data = self.apply_rich_text_changeset(changeset, cell_data)?;
+        if cell_data.to_plain_text().len() > 10000 {
+            Err(FlowyError::text_too_long().context("The len of the text should not be more than 10000"))
+        } else {
+            Ok(cell_data.to_cell_str())
+        }
+    }
+
+    fn apply_rich_text_changeset(
+        &self,
+        changeset: RichTextCellChangeset,
+        cell_data: RichTextCellData,
+    ) -> FlowyResult<RichTextCellData> {
+        match changeset {
+            RichTextCellChangeset::Text(text) => Ok(RichTextCellData::from_text(&text)),
+            RichTextCellChangeset::Delta(delta) => {
+                let mut document = ClientDocument::from_delta(cell_data.delta);
+                let _ = document.compose_delta(delta).map_err(internal_error)?;
+                Ok(RichTextCellData {
+                    delta: document.delta().clone(),
+                })
+            }
+            RichTextCellChangeset::Format { start, end, attributes } => {
+                let mut document = ClientDocument::from_delta(cell_data.delta);
+                for (key, value) in attributes.iter() {
+                    if key == &RichTextAttributeKey::Header || is_block_except_header(key) {
+                        let msg = format!("The {:?} attribute can't be applied to the cell", key);
+                        return Err(FlowyError::new(ErrorCode::InvalidData, &msg));
+                    }
+                    let attribute = RichTextAttribute {
+                        key: key.clone(),
+                        value: value.clone(),
+                        scope: AttributeScope::Inline,
+                    };
+                    let _ = document
+                        .format(Interval::new(start, end), attribute)
+                        .map_err(internal_error)?;
+                }
+                Ok(RichTextCellData {
+                    delta: document.delta().clone(),
+                })
+            }
+        }
+    }
+}
+
 impl CellDisplayable<String> for RichTextTypeOption {
     fn display_data(
         &self,
@@ -41,11 +111,12 @@ impl CellDisplayable<String> for RichTextTypeOption {
         _field_rev: &FieldRevision,
     ) -> FlowyResult<CellBytes> {
         let cell_str: String = cell_data.try_into_inner()?;
-        Ok(CellBytes::new(cell_str))
+        let cell_data = RichTextCellData::from_cell_str(&cell_str)?;
+        Ok(CellBytes::new(cell_data.to_plain_text()))
     }
 }
 
-impl CellDataOperation<String, String> for RichTextTypeOption {
+impl CellDataOperation<String, RichTextCellChangeset> for RichTextTypeOption {
     fn decode_cell_data(
         &self,
         cell_data: CellData<String>,
@@ -65,15 +136,11 @@ impl CellDataOperation<String, String> for RichTextTypeOption {
 
     fn apply_changeset(
         &self,
-        changeset: CellDataChangeset<String>,
-        _cell_rev: Option<CellRevision>,
+        changeset: CellDataChangeset<RichTextCellChangeset>,
+        cell_rev: Option<CellRevision>,
     ) -> Result<String, FlowyError> {
-        let data = changeset.try_into_inner()?;
-        if data.len() > 10000 {
-            Err(FlowyError::text_too_long().context("The len of the text should not be more than 10000"))
-        } else {
-            Ok(data)
-        }
+        let changeset = changeset.try_into_inner()?;
+        self.apply_rich_text_cell_changeset(changeset, cell_rev)
     }
 }
 
@@ -107,10 +174,12 @@ impl CellBytesParser for TextCellDataParser {
 #[cfg(test)]
 mod tests {
     use crate::entities::FieldType;
-    use crate::services::cell::CellDataOperation;
+    use crate::services::cell::{AnyCellData, CellDataOperation, FromCellString};
 
     use crate::services::field::FieldBuilder;
     use crate::services::field::*;
+    use flowy_grid_data_model::revision::CellRevision;
+    use lib_ot::rich_text::{RichTextAttribute, RichTextAttributes, RichTextDelta};
 
     #[test]
     fn text_description_test() {
@@ -184,4 +253,92 @@ mod tests {
             "$18,443".to_owned()
         );
     }
+
+    #[test]
+    fn text_plain_string_cell_test() {
+        let type_option = RichTextTypeOption::default();
+        let field_rev = FieldBuilder::from_field_type(&FieldType::RichText).build();
+        let cell_data = type_option.apply_changeset("hello".to_owned().into(), None).unwrap();
+        assert_eq!(cell_data, "hello");
+
+        // The cells that were created before the rich text was supported are decoded as plain text.
+        let cell_bytes = type_option
+            .decode_cell_data(cell_data.into(), &FieldType::RichText, &field_rev)
+            .unwrap();
+        assert_eq!(cell_bytes.to_string(), "hello");
+        assert!(RichTextCellData::from_cell_str("hello").unwrap().is_plain());
+    }
+
+    #[test]
+    fn text_json_like_plain_string_cell_test() {
+        let type_option = RichTextTypeOption::default();
+        let field_rev = FieldBuilder::from_field_type(&FieldType::RichText).build();
+        for text in [r#"{"text":"x"}"#, r#"{"delta":[{"insert":"x"}]}"#, "rich_text:{}"] {
+            let cell_data = type_option.apply_changeset(text.to_owned().into(), None).unwrap();
+            let cell_bytes = type_option
+                .decode_cell_data(cell_data.into(), &FieldType::RichText, &field_rev)
+                .unwrap();
+            assert_eq!(cell_bytes.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn text_format_cell_test() {
+        let type_option = RichTextTypeOption::default();
+        let field_rev = FieldBuilder::from_field_type(&FieldType::RichText).build();
+        let cell_rev = CellRevision::new(AnyCellData::new("hello world".to_owned(), FieldType::RichText).json());
+        let changeset = RichTextCellChangeset::Format {
+            start: 0,
+            end: 5,
+            attributes: RichTextAttribute::Bold(true).into(),
+        };
+        let cell_data = type_option
+            .apply_rich_text_cell_changeset(changeset, Some(cell_rev))
+            .unwrap();
+        let rich_text_cell_data = RichTextCellData::from_cell_str(&cell_data).unwrap();
+        assert!(!rich_text_cell_data.is_plain());
+        assert_eq!(rich_text_cell_data.delta.ops.len(), 2);
+
+        let cell_bytes = type_option
+            .decode_cell_data(cell_data.clone().into(), &FieldType::RichText, &field_rev)
+            .unwrap();
+        assert_eq!(cell_bytes.to_string(), "hello world");
+
+        // Replacing the text with the plain string removes the formats.
+        let cell_rev = CellRevision::new(AnyCellData::new(cell_data, FieldType::RichText).json());
+        let cell_data = type_option
+            .apply_changeset("hi".to_owned().into(), Some(cell_rev))
+            .unwrap();
+        assert_eq!(cell_data, "hi");
+    }
+
+    #[test]
+    fn text_compose_delta_cell_test() {
+        let type_option = RichTextTypeOption::default();
+        let cell_rev = CellRevision::new(AnyCellData::new("world".to_owned(), FieldType::RichText).json());
+        let mut delta = RichTextDelta::new();
+        delta.insert("hello ", RichTextAttribute::InlineCode(true).into());
+        delta.retain(5, RichTextAttributes::default());
+        let changeset = RichTextCellChangeset::Delta(delta);
+        let cell_data = type_option
+            .apply_rich_text_cell_changeset(changeset, Some(cell_rev))
+            .unwrap();
+        let rich_text_cell_data = RichTextCellData::from_cell_str(&cell_data).unwrap();
+        assert_eq!(rich_text_cell_data.to_plain_text(), "hello world");
+        assert!(!rich_text_cell_data.is_plain());
+    }
+
+    #[test]
+    fn text_format_block_attribute_error_test() {
+        let type_option = RichTextTypeOption::default();
+        let cell_rev = CellRevision::new(AnyCellData::new("hello".to_owned(), FieldType::RichText).json());
+        let changeset = RichTextCellChangeset::Format {
+            start: 0,
+            end: 5,
+            attributes: RichTextAttribute::Header(1).into(),
+        };
+        assert!(type_option
+            .apply_rich_text_cell_changeset(changeset, Some(cell_rev))
+            .is_err());
+    }
 }
@@ -0,0 +1,171 @@
+use crate::entities::{GridCellIdPB, GridCellIdParams};
+use crate::services::cell::{FromCellChangeset, FromCellString};
+use flowy_derive::ProtoBuf;
+use flowy_error::{internal_error, ErrorCode, FlowyResult};
+use lib_ot::rich_text::{RichTextAttributes, RichTextDelta};
+use serde::{Deserialize, Serialize};
+
+// The prefix of the cells that store the delta. The other cells store the plain text.
+const RICH_TEXT_CELL_PREFIX: &str = "rich_text:";
+
+/// The data of the rich text cell. The cells that were stored as plain strings are loaded as the
+/// deltas that insert the strings, so they don't need to be migrated.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RichTextCellData {
+    pub delta: RichTextDelta,
+}
+
+impl RichTextCellData {
+    pub fn from_text(text: &str) -> Self {
+        let mut delta = RichTextDelta::new();
+        if !text.is_empty() {
+            delta.insert(text, RichTextAttributes::default());
+        }
+        Self { delta }
+    }
+
+    /// Return true if none of the text is formatted.
+    pub fn is_plain(&self) -> bool {
+        self.delta.ops.iter().all(|op| !op.has_attribute())
+    }
+
+    pub fn to_plain_text(&self) -> String {
+        self.delta.content_str().unwrap_or_default()
+    }
+
+    /// Return the string that is stored in the cell. The text without any format is stored as the
+    /// plain string, the same as the cells that were created before the rich text was supported.
+    /// The plain text that starts with the prefix of the rich text cell is stored as the delta, so
+    /// it can't be mistaken for one.
+    pub fn to_cell_str(&self) -> String {
+        let text = self.to_plain_text();
+        if self.is_plain() && !text.starts_with(RICH_TEXT_CELL_PREFIX) {
+            text
+        } else {
+            format!("{}{}", RICH_TEXT_CELL_PREFIX, serde_json::to_string(self).unwrap())
+        }
+    }
+}
+
+impl FromCellString for RichTextCellData {
+    fn from_cell_str(s: &str) -> FlowyResult<Self>
+    where
+        Self: Sized,
+    {
+        match s.strip_prefix(RICH_TEXT_CELL_PREFIX) {
+            None => Ok(RichTextCellData::from_text(s)),
+            Some(json) => serde_json::from_str::<RichTextCellData>(json).map_err(internal_error),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum RichTextCellChangeset {
+    /// Replace the content of the cell with the plain text. It's the changeset of the cells that
+    /// are updated through the `UpdateCell` event.
+    Text(String),
+
+    /// Compose the delta with the delta of the cell.
+    Delta(RichTextDelta),
+
+    /// Format the text in the range with the attributes. Only the inline attributes, e.g. bold,
+    /// link or inline code, are supported.
+    Format {
+        start: usize,
+        end: usize,
+        attributes: RichTextAttributes,
+    },
+}
+
+impl FromCellChangeset for RichTextCellChangeset {
+    fn from_changeset(changeset: String) -> FlowyResult<Self>
+    where
+        Self: Sized,
+    {
+        // The deltas and the formats are sent through the `UpdateRichTextCell` event.
+        Ok(RichTextCellChangeset::Text(changeset))
+    }
+}
+
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct RichTextCellDataPB {
+    /// The text without any format.
+    #[pb(index = 1)]
+    pub text: String,
+
+    /// The delta of the cell in JSON format.
+    #[pb(index = 2)]
+    pub delta: String,
+}
+
+impl std::convert::From<RichTextCellData> for RichTextCellDataPB {
+    fn from(cell_data: RichTextCellData) -> Self {
+        Self {
+            text: cell_data.to_plain_text(),
+            delta: cell_data.delta.to_json_str(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct RichTextFormatPB {
+    #[pb(index = 1)]
+    pub start: i32,
+
+    #[pb(index = 2)]
+    pub end: i32,
+
+    /// The attributes in JSON format, e.g. {"bold":true}.
+    #[pb(index = 3)]
+    pub attributes: String,
+}
+
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct RichTextCellChangesetPayloadPB {
+    #[pb(index = 1)]
+    pub cell_identifier: GridCellIdPB,
+
+    /// The delta in JSON format that is composed with the delta of the cell.
+    #[pb(index = 2, one_of)]
+    pub delta: Option<String>,
+
+    #[pb(index = 3, one_of)]
+    pub format: Option<RichTextFormatPB>,
+}
+
+pub struct RichTextCellChangesetParams {
+    pub cell_identifier: GridCellIdParams,
+    pub changeset: RichTextCellChangeset,
+}
+
+impl TryInto<RichTextCellChangesetParams> for RichTextCellChangesetPayloadPB {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<RichTextCellChangesetParams, Self::Error> {
+        let cell_identifier: GridCellIdParams = self.cell_identifier.try_into()?;
+        let changeset = match (self.delta, self.format) {
+            (Some(delta), None) => {
+                let delta = RichTextDelta::from_json_str(&delta).map_err(|_| ErrorCode::InvalidData)?;
+                RichTextCellChangeset::Delta(delta)
+            }
+            (None, Some(format)) => {
+                if format.start < 0 || format.end < format.start {
+                    return Err(ErrorCode::InvalidData);
+                }
+                let attributes = serde_json::from_str::<RichTextAttributes>(&format.attributes)
+                    .map_err(|_| ErrorCode::InvalidData)?;
+                RichTextCellChangeset::Format {
+                    start: format.start as usize,
+                    end: format.end as usize,
+                    attributes,
+                }
+            }
+            _ => return Err(ErrorCode::InvalidData),
+        };
+
+        Ok(RichTextCellChangesetParams {
+            cell_identifier,
+            changeset,
+        })
+    }
+}
@@ -1,10 +1,10 @@
 use crate::entities::{
     FieldType, GridCheckboxFilter, GridDateFilter, GridNumberFilter, GridSelectOptionFilter, GridTextFilter,
 };
+use crate::services::field::{FormulaResultType, FormulaTypeOption, RollupCalculation, RollupTypeOption};
 use dashmap::DashMap;
-use flowy_grid_data_model::revision::{FieldRevision, RowRevision};
+use flowy_grid_data_model::revision::{GridFilterGroupRevision, GridFilterRevision, RowRevision};
 use flowy_sync::client_grid::GridRevisionPad;
-use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -31,28 +31,22 @@ impl std::ops::Deref for FilterResultCache {
     }
 }
 
-#[derive(Default)]
 pub(crate) struct FilterResult {
     #[allow(dead_code)]
     pub(crate) row_index: i32,
-    pub(crate) visible_by_field_id: HashMap<FilterId, bool>,
+    pub(crate) is_visible: bool,
 }
 
 impl FilterResult {
     pub(crate) fn new(index: i32, _row_rev: &RowRevision) -> Self {
         Self {
             row_index: index,
-            visible_by_field_id: HashMap::new(),
+            is_visible: true,
         }
     }
 
     pub(crate) fn is_visible(&self) -> bool {
-        for visible in self.visible_by_field_id.values() {
-            if visible == &false {
-                return false;
-            }
-        }
-        true
+        self.is_visible
     }
 }
 
@@ -64,6 +58,7 @@ pub(crate) struct FilterCache {
     pub(crate) date_filter: DashMap<FilterId, GridDateFilter>,
     pub(crate) select_option_filter: DashMap<FilterId, GridSelectOptionFilter>,
     pub(crate) checkbox_filter: DashMap<FilterId, GridCheckboxFilter>,
+    pub(crate) filter_group: std::sync::RwLock<Option<Arc<GridFilterGroupRevision>>>,
 }
 
 impl FilterCache {
@@ -73,30 +68,30 @@ impl FilterCache {
         this
     }
 
-    pub(crate) fn remove(&self, filter_id: &FilterId) {
-        let _ = match filter_id.field_type {
-            FieldType::RichText => {
-                let _ = self.text_filter.remove(filter_id);
-            }
-            FieldType::Number => {
-                let _ = self.number_filter.remove(filter_id);
-            }
-            FieldType::DateTime => {
-                let _ = self.date_filter.remove(filter_id);
-            }
-            FieldType::SingleSelect => {
-                let _ = self.select_option_filter.remove(filter_id);
-            }
-            FieldType::MultiSelect => {
-                let _ = self.select_option_filter.remove(filter_id);
-            }
-            FieldType::Checkbox => {
-                let _ = self.checkbox_filter.remove(filter_id);
-            }
-            FieldType::URL => {
-                let _ = self.url_filter.remove(filter_id);
-            }
-        };
+    pub(crate) fn clear(&self) {
+        self.text_filter.clear();
+        self.url_filter.clear();
+        self.number_filter.clear();
+        self.date_filter.clear();
+        self.select_option_filter.clear();
+        self.checkbox_filter.clear();
+        *self.filter_group.write().unwrap() = None;
+    }
+
+    /// Return the ids of all the cached filters.
+    pub(crate) fn filter_ids(&self) -> Vec<FilterId> {
+        let mut filter_ids = vec![];
+        filter_ids.extend(self.text_filter.iter().map(|entry| entry.key().clone()));
+        filter_ids.extend(self.url_filter.iter().map(|entry| entry.key().clone()));
+        filter_ids.extend(self.number_filter.iter().map(|entry| entry.key().clone()));
+        filter_ids.extend(self.date_filter.iter().map(|entry| entry.key().clone()));
+        filter_ids.extend(self.select_option_filter.iter().map(|entry| entry.key().clone()));
+        filter_ids.extend(self.checkbox_filter.iter().map(|entry| entry.key().clone()));
+        filter_ids
+    }
+
+    pub(crate) fn filter_group(&self) -> Option<Arc<GridFilterGroupRevision>> {
+        self.filter_group.read().unwrap().clone()
     }
 }
 
@@ -113,8 +108,8 @@ pub(crate) async fn refresh_filter_cache(
         match grid_pad.get_field_rev(&filter_rev.field_id) {
             None => {}
             Some((_, field_rev)) => {
-                let filter_id = FilterId::from(field_rev);
                 let field_type: FieldType = field_rev.field_type_rev.into();
+                let filter_id = FilterId::new(&filter_rev, field_type.clone());
                 match &field_type {
                     FieldType::RichText => {
                         let _ = cache.text_filter.insert(filter_id, GridTextFilter::from(filter_rev));
@@ -140,22 +135,59 @@ pub(crate) async fn refresh_filter_cache(
                     FieldType::URL => {
                         let _ = cache.url_filter.insert(filter_id, GridTextFilter::from(filter_rev));
                     }
+                    // The filter of the formula field is the filter of its result type.
+                    FieldType::Formula => match FormulaTypeOption::from(field_rev).result_type {
+                        FormulaResultType::Text => {
+                            let _ = cache.text_filter.insert(filter_id, GridTextFilter::from(filter_rev));
+                        }
+                        FormulaResultType::Number => {
+                            let _ = cache
+                                .number_filter
+                                .insert(filter_id, GridNumberFilter::from(filter_rev));
+                        }
+                        FormulaResultType::Date => {
+                            let _ = cache.date_filter.insert(filter_id, GridDateFilter::from(filter_rev));
+                        }
+                        FormulaResultType::Checkbox => {
+                            let _ = cache
+                                .checkbox_filter
+                                .insert(filter_id, GridCheckboxFilter::from(filter_rev));
+                        }
+                    },
+                    FieldType::Rollup => match RollupTypeOption::from(field_rev).calculation {
+                        RollupCalculation::UniqueValues => {
+                            let _ = cache.text_filter.insert(filter_id, GridTextFilter::from(filter_rev));
+                        }
+                        _ => {
+                            let _ = cache
+                                .number_filter
+                                .insert(filter_id, GridNumberFilter::from(filter_rev));
+                        }
+                    },
+                    // The relation field can't be filtered.
+                    FieldType::Relation => {}
                 }
             }
         }
     }
+    *cache.filter_group.write().unwrap() = grid_pad.get_filter_group(None);
 }
-#[derive(Hash, Eq, PartialEq)]
+
+/// Each field may have multiple filters, so the filters are identified by the id of their
+/// GridFilterRevision.
+#[derive(Hash, Eq, PartialEq, Clone)]
 pub(crate) struct FilterId {
+    pub(crate) id: String,
     pub(crate) field_id: String,
     pub(crate) field_type: FieldType,
 }
 
-impl std::convert::From<&Arc<FieldRevision>> for FilterId {
-    fn from(rev: &Arc<FieldRevision>) -> Self {
+impl FilterId {
+    pub(crate) fn new(filter_rev: &GridFilterRevision, field_type: FieldType) -> Self {
         Self {
-            field_id: rev.id.clone(),
-            field_type: rev.field_type_rev.into(),
+            id: filter_rev.id.clone(),
+            field_id: filter_rev.field_id.clone(),
+            field_type,
         }
     }
 }
@@ -0,0 +1,14 @@
+/// The clock is used to evaluate the relative date filters, e.g. today, last 7 days. Replace it
+/// with a fixed clock to get a deterministic result.
+pub trait FilterClock: Send + Sync {
+    /// Return the current unix timestamp in seconds.
+    fn now_timestamp(&self) -> i64;
+}
+
+pub struct SystemClock;
+
+impl FilterClock for SystemClock {
+    fn now_timestamp(&self) -> i64 {
+        chrono::Utc::now().timestamp()
+    }
+}
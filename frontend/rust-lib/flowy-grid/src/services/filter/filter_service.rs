@@ -3,17 +3,20 @@ use crate::entities::{FieldType, GridBlockChangesetPB};
 use crate::services::block_manager::GridBlockManager;
 use crate::services::cell::{AnyCellData, CellFilterOperation};
 use crate::services::field::{
-    CheckboxTypeOption, DateTypeOption, MultiSelectTypeOption, NumberTypeOption, RichTextTypeOption,
-    SingleSelectTypeOptionPB, URLTypeOption,
+    CheckboxTypeOption, DateTypeOption, FormulaResultType, FormulaTypeOption, MultiSelectTypeOption, NumberTypeOption,
+    RichTextTypeOption, RollupCalculation, RollupTypeOption, SingleSelectTypeOptionPB, URLTypeOption,
 };
 use crate::services::filter::filter_cache::{
     refresh_filter_cache, FilterCache, FilterId, FilterResult, FilterResultCache,
 };
+use crate::services::filter::{FilterClock, SystemClock};
 use crate::services::grid_editor_task::GridServiceTaskScheduler;
 use crate::services::row::GridBlockSnapshot;
 use crate::services::tasks::{FilterTaskContext, Task, TaskContent};
 use flowy_error::FlowyResult;
-use flowy_grid_data_model::revision::{CellRevision, FieldId, FieldRevision, RowRevision};
+use flowy_grid_data_model::revision::{
+    FieldId, FieldRevision, GridFilterGroupRevision, GridFilterLogicRevision, RowRevision,
+};
 use flowy_sync::client_grid::GridRevisionPad;
 use flowy_sync::entities::grid::GridSettingChangesetParams;
 use rayon::prelude::*;
@@ -29,6 +32,7 @@ pub(crate) struct GridFilterService {
     block_manager: Arc<GridBlockManager>,
     filter_cache: Arc<FilterCache>,
     filter_result_cache: Arc<FilterResultCache>,
+    clock: RwLock<Arc<dyn FilterClock>>,
 }
 impl GridFilterService {
     pub async fn new<S: GridServiceTaskScheduler>(
@@ -47,9 +51,15 @@ impl GridFilterService {
             scheduler,
             filter_cache,
             filter_result_cache,
+            clock: RwLock::new(Arc::new(SystemClock)),
         }
     }
 
+    /// Replace the clock that is used to evaluate the relative date filters.
+    pub async fn set_clock(&self, clock: Arc<dyn FilterClock>) {
+        *self.clock.write().await = clock;
+    }
+
     pub async fn process(&self, task_context: FilterTaskContext) -> FlowyResult<()> {
         let field_revs = self
             .grid_pad
@@ -60,6 +70,7 @@ impl GridFilterService {
             .map(|field_rev| (field_rev.id.clone(), field_rev))
             .collect::<HashMap<String, Arc<FieldRevision>>>();
 
+        let now_timestamp = self.clock.read().await.now_timestamp();
         let mut changesets = vec![];
         for (index, block) in task_context.blocks.into_iter().enumerate() {
             // The row_ids contains the row that its visibility was changed.
@@ -69,7 +80,14 @@ impl GridFilterService {
                 .flat_map(|row_rev| {
                     let filter_result_cache = self.filter_result_cache.clone();
                     let filter_cache = self.filter_cache.clone();
-                    filter_row(index, row_rev, filter_cache, filter_result_cache, &field_revs)
+                    filter_row(
+                        index,
+                        row_rev,
+                        filter_cache,
+                        filter_result_cache,
+                        &field_revs,
+                        now_timestamp,
+                    )
                 })
                 .collect::<Vec<String>>();
 
@@ -110,14 +128,9 @@ impl GridFilterService {
             return;
         }
 
-        if let Some(filter_id) = &changeset.insert_filter {
-            let field_ids = Some(vec![filter_id.field_id.clone()]);
-            refresh_filter_cache(self.filter_cache.clone(), field_ids, &self.grid_pad).await;
-        }
-
-        if let Some(filter_id) = &changeset.delete_filter {
-            self.filter_cache.remove(filter_id);
-        }
+        // A filter group may reference any filter of the layout, so the whole cache is rebuilt.
+        self.filter_cache.clear();
+        refresh_filter_cache(self.filter_cache.clone(), None, &self.grid_pad).await;
 
         if let Ok(blocks) = self.block_manager.get_block_snapshots(None).await {
             let _task = self.gen_task(blocks).await;
@@ -125,6 +138,57 @@ impl GridFilterService {
         }
     }
 
+    /// The filters of the formula field are cached by the result type of the formula, so the cache
+    /// is rebuilt after the type option of the field was changed.
+    pub async fn did_update_field(&self, field_id: &str) {
+        let is_filtered = self
+            .filter_cache
+            .filter_ids()
+            .iter()
+            .any(|filter_id| filter_id.field_id == field_id);
+        if is_filtered {
+            self.apply_changeset(GridFilterChangeset { is_changed: true }).await;
+        }
+    }
+
+    /// Remove the rows that don't satisfy the filters of the current layout from the snapshots.
+    pub async fn filter_block_snapshots(&self, snapshots: &mut [GridBlockSnapshot]) {
+        let field_revs = match self.grid_pad.read().await.get_field_revs(None) {
+            Ok(field_revs) => field_revs
+                .into_iter()
+                .map(|field_rev| (field_rev.id.clone(), field_rev))
+                .collect::<HashMap<String, Arc<FieldRevision>>>(),
+            Err(e) => {
+                tracing::error!("Get field revisions failed: {:?}", e);
+                return;
+            }
+        };
+
+        let now_timestamp = self.clock.read().await.now_timestamp();
+        for snapshot in snapshots.iter_mut() {
+            snapshot
+                .row_revs
+                .retain(|row_rev| is_row_visible(row_rev, &self.filter_cache, &field_revs, now_timestamp));
+        }
+    }
+
+    /// Return true if the row satisfies the filters of the current layout.
+    pub async fn is_row_visible(&self, row_rev: &RowRevision) -> bool {
+        let field_revs = match self.grid_pad.read().await.get_field_revs(None) {
+            Ok(field_revs) => field_revs
+                .into_iter()
+                .map(|field_rev| (field_rev.id.clone(), field_rev))
+                .collect::<HashMap<String, Arc<FieldRevision>>>(),
+            Err(e) => {
+                tracing::error!("Get field revisions failed: {:?}", e);
+                return true;
+            }
+        };
+
+        let now_timestamp = self.clock.read().await.now_timestamp();
+        is_row_visible(row_rev, &self.filter_cache, &field_revs, now_timestamp)
+    }
+
     async fn gen_task(&self, blocks: Vec<GridBlockSnapshot>) -> Task {
         let task_id = self.scheduler.gen_task_id().await;
         let handler_id = self.grid_pad.read().await.grid_id();
@@ -149,143 +213,200 @@ fn filter_row(
     filter_cache: Arc<FilterCache>,
     filter_result_cache: Arc<FilterResultCache>,
     field_revs: &HashMap<FieldId, Arc<FieldRevision>>,
+    now_timestamp: i64,
 ) -> Option<String> {
+    let is_visible = is_row_visible(row_rev, &filter_cache, field_revs, now_timestamp);
     let mut result = filter_result_cache
         .entry(row_rev.id.clone())
-        .or_insert(FilterResult::new(index as i32, row_rev));
+        .or_insert_with(|| FilterResult::new(index as i32, row_rev));
 
-    for (field_id, cell_rev) in row_rev.cells.iter() {
-        match filter_cell(field_revs, result.value_mut(), &filter_cache, field_id, cell_rev) {
-            None => {}
-            Some(_) => {
-                return Some(row_rev.id.clone());
-            }
+    if result.is_visible != is_visible {
+        result.is_visible = is_visible;
+        Some(row_rev.id.clone())
+    } else {
+        None
+    }
+}
+
+/// Return true if the row satisfies the filters. The filters referenced by the filter group are
+/// combined with the logic of the group, and the rest of the filters are combined with AND.
+fn is_row_visible(
+    row_rev: &RowRevision,
+    filter_cache: &Arc<FilterCache>,
+    field_revs: &HashMap<FieldId, Arc<FieldRevision>>,
+    now_timestamp: i64,
+) -> bool {
+    let is_matched_by_filter_id = filter_cache
+        .filter_ids()
+        .into_iter()
+        .flat_map(|filter_id| {
+            let is_matched = filter_cell(field_revs, filter_cache, &filter_id, row_rev, now_timestamp)?;
+            Some((filter_id.id, is_matched))
+        })
+        .collect::<HashMap<String, bool>>();
+
+    match filter_cache.filter_group() {
+        None => is_matched_by_filter_id.values().all(|is_matched| *is_matched),
+        Some(filter_group_rev) => {
+            let is_group_matched = is_filter_group_matched(&filter_group_rev, &is_matched_by_filter_id).unwrap_or(true);
+            is_group_matched
+                && is_matched_by_filter_id
+                    .iter()
+                    .filter(|(filter_id, _)| !filter_group_rev.contains_filter(filter_id))
+                    .all(|(_, is_matched)| *is_matched)
         }
     }
-    None
 }
 
-// Return None if there is no change in this cell after applying the filter
+// Return None if the group doesn't contain any existing filter.
+fn is_filter_group_matched(
+    filter_group_rev: &GridFilterGroupRevision,
+    is_matched_by_filter_id: &HashMap<String, bool>,
+) -> Option<bool> {
+    let results = filter_group_rev
+        .filter_ids
+        .iter()
+        .flat_map(|filter_id| is_matched_by_filter_id.get(filter_id).cloned())
+        .chain(
+            filter_group_rev
+                .groups
+                .iter()
+                .flat_map(|group| is_filter_group_matched(group, is_matched_by_filter_id)),
+        )
+        .collect::<Vec<bool>>();
+
+    if results.is_empty() {
+        return None;
+    }
+
+    match filter_group_rev.logic {
+        GridFilterLogicRevision::And => Some(results.into_iter().all(|is_matched| is_matched)),
+        GridFilterLogicRevision::Or => Some(results.into_iter().any(|is_matched| is_matched)),
+    }
+}
+
+// Return None if the field of the filter doesn't exist. The cell that doesn't exist in the row is
+// treated as an empty cell.
 fn filter_cell(
     field_revs: &HashMap<FieldId, Arc<FieldRevision>>,
-    filter_result: &mut FilterResult,
     filter_cache: &Arc<FilterCache>,
-    field_id: &str,
-    cell_rev: &CellRevision,
-) -> Option<()> {
-    let field_rev = field_revs.get(field_id)?;
+    filter_id: &FilterId,
+    row_rev: &RowRevision,
+    now_timestamp: i64,
+) -> Option<bool> {
+    let field_rev = field_revs.get(&filter_id.field_id)?;
     let field_type = FieldType::from(field_rev.field_type_rev);
+    if field_type != filter_id.field_type {
+        return None;
+    }
+
     let field_type_rev = field_type.clone().into();
-    let filter_id = FilterId {
-        field_id: field_id.to_owned(),
-        field_type,
-    };
-    let any_cell_data = AnyCellData::try_from(cell_rev).ok()?;
-    let is_visible = match &filter_id.field_type {
-        FieldType::RichText => filter_cache.text_filter.get(&filter_id).and_then(|filter| {
-            Some(
-                field_rev
-                    .get_type_option_entry::<RichTextTypeOption>(field_type_rev)?
-                    .apply_filter(any_cell_data, filter.value())
-                    .ok(),
-            )
+    let any_cell_data = row_rev
+        .cells
+        .get(&filter_id.field_id)
+        .and_then(|cell_rev| AnyCellData::try_from(cell_rev).ok())
+        .unwrap_or_else(|| AnyCellData::new("".to_owned(), field_type.clone()));
+
+    let is_matched = match &filter_id.field_type {
+        FieldType::RichText => filter_cache.text_filter.get(filter_id).and_then(|filter| {
+            field_rev
+                .get_type_option_entry::<RichTextTypeOption>(field_type_rev)?
+                .apply_filter(any_cell_data, filter.value())
+                .ok()
         }),
-        FieldType::Number => filter_cache.number_filter.get(&filter_id).and_then(|filter| {
-            Some(
-                field_rev
-                    .get_type_option_entry::<NumberTypeOption>(field_type_rev)?
-                    .apply_filter(any_cell_data, filter.value())
-                    .ok(),
-            )
+        FieldType::Number => filter_cache.number_filter.get(filter_id).and_then(|filter| {
+            field_rev
+                .get_type_option_entry::<NumberTypeOption>(field_type_rev)?
+                .apply_filter(any_cell_data, filter.value())
+                .ok()
         }),
-        FieldType::DateTime => filter_cache.date_filter.get(&filter_id).and_then(|filter| {
-            Some(
-                field_rev
-                    .get_type_option_entry::<DateTypeOption>(field_type_rev)?
-                    .apply_filter(any_cell_data, filter.value())
-                    .ok(),
-            )
+        FieldType::DateTime => filter_cache.date_filter.get(filter_id).and_then(|filter| {
+            field_rev
+                .get_type_option_entry::<DateTypeOption>(field_type_rev)?
+                .apply_filter_at(any_cell_data, filter.value(), now_timestamp)
+                .ok()
         }),
-        FieldType::SingleSelect => filter_cache.select_option_filter.get(&filter_id).and_then(|filter| {
-            Some(
-                field_rev
-                    .get_type_option_entry::<SingleSelectTypeOptionPB>(field_type_rev)?
-                    .apply_filter(any_cell_data, filter.value())
-                    .ok(),
-            )
+        FieldType::SingleSelect => filter_cache.select_option_filter.get(filter_id).and_then(|filter| {
+            field_rev
+                .get_type_option_entry::<SingleSelectTypeOptionPB>(field_type_rev)?
+                .apply_filter(any_cell_data, filter.value())
+                .ok()
         }),
-        FieldType::MultiSelect => filter_cache.select_option_filter.get(&filter_id).and_then(|filter| {
-            Some(
-                field_rev
-                    .get_type_option_entry::<MultiSelectTypeOption>(field_type_rev)?
-                    .apply_filter(any_cell_data, filter.value())
-                    .ok(),
-            )
+        FieldType::MultiSelect => filter_cache.select_option_filter.get(filter_id).and_then(|filter| {
+            field_rev
+                .get_type_option_entry::<MultiSelectTypeOption>(field_type_rev)?
+                .apply_filter(any_cell_data, filter.value())
+                .ok()
         }),
-        FieldType::Checkbox => filter_cache.checkbox_filter.get(&filter_id).and_then(|filter| {
-            Some(
-                field_rev
-                    .get_type_option_entry::<CheckboxTypeOption>(field_type_rev)?
-                    .apply_filter(any_cell_data, filter.value())
-                    .ok(),
-            )
+        FieldType::Checkbox => filter_cache.checkbox_filter.get(filter_id).and_then(|filter| {
+            field_rev
+                .get_type_option_entry::<CheckboxTypeOption>(field_type_rev)?
+                .apply_filter(any_cell_data, filter.value())
+                .ok()
         }),
-        FieldType::URL => filter_cache.url_filter.get(&filter_id).and_then(|filter| {
-            Some(
-                field_rev
-                    .get_type_option_entry::<URLTypeOption>(field_type_rev)?
-                    .apply_filter(any_cell_data, filter.value())
-                    .ok(),
-            )
+        FieldType::URL => filter_cache.url_filter.get(filter_id).and_then(|filter| {
+            field_rev
+                .get_type_option_entry::<URLTypeOption>(field_type_rev)?
+                .apply_filter(any_cell_data, filter.value())
+                .ok()
         }),
-    }?;
-
-    let is_visible = !is_visible.unwrap_or(true);
-    match filter_result.visible_by_field_id.get(&filter_id) {
-        None => {
-            if is_visible {
-                None
-            } else {
-                filter_result.visible_by_field_id.insert(filter_id, is_visible);
-                Some(())
+        FieldType::Formula => {
+            let type_option = field_rev.get_type_option_entry::<FormulaTypeOption>(field_type_rev)?;
+            match type_option.result_type {
+                FormulaResultType::Text => filter_cache
+                    .text_filter
+                    .get(filter_id)
+                    .and_then(|filter| type_option.apply_filter(any_cell_data, filter.value()).ok()),
+                FormulaResultType::Number => filter_cache
+                    .number_filter
+                    .get(filter_id)
+                    .and_then(|filter| type_option.apply_filter(any_cell_data, filter.value()).ok()),
+                FormulaResultType::Date => filter_cache.date_filter.get(filter_id).and_then(|filter| {
+                    type_option
+                        .apply_filter_at(any_cell_data, filter.value(), now_timestamp)
+                        .ok()
+                }),
+                FormulaResultType::Checkbox => filter_cache
+                    .checkbox_filter
+                    .get(filter_id)
+                    .and_then(|filter| type_option.apply_filter(any_cell_data, filter.value()).ok()),
             }
         }
-        Some(old_is_visible) => {
-            if old_is_visible != &is_visible {
-                filter_result.visible_by_field_id.insert(filter_id, is_visible);
-                Some(())
-            } else {
-                None
+        FieldType::Rollup => {
+            let type_option = field_rev.get_type_option_entry::<RollupTypeOption>(field_type_rev)?;
+            match type_option.calculation {
+                RollupCalculation::UniqueValues => filter_cache
+                    .text_filter
+                    .get(filter_id)
+                    .and_then(|filter| type_option.apply_filter(any_cell_data, filter.value()).ok()),
+                _ => filter_cache
+                    .number_filter
+                    .get(filter_id)
+                    .and_then(|filter| type_option.apply_filter(any_cell_data, filter.value()).ok()),
             }
         }
-    }
+        // The relation field can't be filtered.
+        FieldType::Relation => Some(true),
+    };
+
+    // The cell that can't be parsed doesn't match the filter.
+    Some(is_matched.unwrap_or(false))
 }
 
 pub struct GridFilterChangeset {
-    insert_filter: Option<FilterId>,
-    delete_filter: Option<FilterId>,
+    is_changed: bool,
 }
 
 impl GridFilterChangeset {
     fn is_changed(&self) -> bool {
-        self.insert_filter.is_some() || self.delete_filter.is_some()
+        self.is_changed
     }
 }
 
 impl std::convert::From<&GridSettingChangesetParams> for GridFilterChangeset {
     fn from(params: &GridSettingChangesetParams) -> Self {
-        let insert_filter = params.insert_filter.as_ref().map(|insert_filter_params| FilterId {
-            field_id: insert_filter_params.field_id.clone(),
-            field_type: insert_filter_params.field_type_rev.into(),
-        });
-
-        let delete_filter = params.delete_filter.as_ref().map(|delete_filter_params| FilterId {
-            field_id: delete_filter_params.filter_id.clone(),
-            field_type: delete_filter_params.field_type_rev.into(),
-        });
         GridFilterChangeset {
-            insert_filter,
-            delete_filter,
+            is_changed: params.is_filter_changed(),
         }
     }
 }
@@ -3,12 +3,36 @@ use crate::services::cell::{AnyCellData, CellData, CellFilterOperation};
 use crate::services::field::{DateTimestamp, DateTypeOption};
 use flowy_error::FlowyResult;
 
+const SECONDS_PER_DAY: i64 = 86400;
+
 impl GridDateFilter {
     pub fn is_visible<T: Into<i64>>(&self, cell_timestamp: T) -> bool {
+        self.is_visible_at(cell_timestamp, chrono::Utc::now().timestamp())
+    }
+
+    /// The relative conditions are evaluated against the `now_timestamp`. The days are in UTC as
+    /// the date cells.
+    pub fn is_visible_at<T: Into<i64>>(&self, cell_timestamp: T, now_timestamp: i64) -> bool {
+        let cell_timestamp = cell_timestamp.into();
+        match self.condition {
+            DateFilterCondition::DateIsEmpty => return cell_timestamp == 0_i64,
+            DateFilterCondition::DateIsToday
+            | DateFilterCondition::DateIsYesterday
+            | DateFilterCondition::DateIsThisWeek
+            | DateFilterCondition::DateIsInLastDays
+            | DateFilterCondition::DateIsInNextDays
+            | DateFilterCondition::DateIsOverdue => {
+                if cell_timestamp == 0_i64 {
+                    return false;
+                }
+                return self.is_visible_relative(cell_timestamp, now_timestamp);
+            }
+            _ => {}
+        }
+
         if self.start.is_none() {
             return false;
         }
-        let cell_timestamp = cell_timestamp.into();
         let start_timestamp = *self.start.as_ref().unwrap();
         // We assume that the cell_timestamp doesn't contain hours, just day.
         match self.condition {
@@ -24,26 +48,74 @@ impl GridDateFilter {
                     false
                 }
             }
-            DateFilterCondition::DateIsEmpty => cell_timestamp == 0_i64,
+            _ => false,
+        }
+    }
+
+    fn is_visible_relative(&self, cell_timestamp: i64, now_timestamp: i64) -> bool {
+        let cell_day = cell_timestamp.div_euclid(SECONDS_PER_DAY);
+        let today = now_timestamp.div_euclid(SECONDS_PER_DAY);
+        match self.condition {
+            DateFilterCondition::DateIsToday => cell_day == today,
+            DateFilterCondition::DateIsYesterday => cell_day == today - 1,
+            DateFilterCondition::DateIsThisWeek => {
+                // The week starts on Monday. 1970-01-01 is Thursday, so the days from Monday are
+                // offset by 3.
+                let days_from_monday = (today + 3).rem_euclid(7);
+                let first_day = today - days_from_monday;
+                cell_day >= first_day && cell_day < first_day + 7
+            }
+            // The last N days end with today, and the next N days start with today. The days
+            // that overflow don't match any cell.
+            DateFilterCondition::DateIsInLastDays => match self.days.filter(|days| *days > 0) {
+                Some(days) => match today.checked_sub(days) {
+                    Some(first_day) => cell_day > first_day && cell_day <= today,
+                    None => false,
+                },
+                None => false,
+            },
+            DateFilterCondition::DateIsInNextDays => match self.days.filter(|days| *days > 0) {
+                Some(days) => match today.checked_add(days) {
+                    Some(end_day) => cell_day >= today && cell_day < end_day,
+                    None => false,
+                },
+                None => false,
+            },
+            DateFilterCondition::DateIsOverdue => cell_day < today,
+            _ => false,
         }
     }
 }
 
-impl CellFilterOperation<GridDateFilter> for DateTypeOption {
-    fn apply_filter(&self, any_cell_data: AnyCellData, filter: &GridDateFilter) -> FlowyResult<bool> {
+impl DateTypeOption {
+    pub fn apply_filter_at(
+        &self,
+        any_cell_data: AnyCellData,
+        filter: &GridDateFilter,
+        now_timestamp: i64,
+    ) -> FlowyResult<bool> {
         if !any_cell_data.is_date() {
             return Ok(true);
         }
         let cell_data: CellData<DateTimestamp> = any_cell_data.into();
         let timestamp = cell_data.try_into_inner()?;
-        Ok(filter.is_visible(timestamp))
+        Ok(filter.is_visible_at(timestamp, now_timestamp))
+    }
+}
+
+impl CellFilterOperation<GridDateFilter> for DateTypeOption {
+    fn apply_filter(&self, any_cell_data: AnyCellData, filter: &GridDateFilter) -> FlowyResult<bool> {
+        self.apply_filter_at(any_cell_data, filter, chrono::Utc::now().timestamp())
     }
 }
 
 #[cfg(test)]
 mod tests {
     #![allow(clippy::all)]
-    use crate::entities::{DateFilterCondition, GridDateFilter};
+    use crate::entities::{
+        CreateGridDateFilterParams, CreateGridDateFilterPayload, DateFilterCondition, FieldType, GridDateFilter,
+    };
+    use flowy_error::ErrorCode;
 
     #[test]
     fn date_filter_is_test() {
@@ -51,6 +123,7 @@ mod tests {
             condition: DateFilterCondition::DateIs,
             start: Some(123),
             end: None,
+            days: None,
         };
 
         for (val, visible) in vec![(123, true), (12, false)] {
@@ -63,6 +136,7 @@ mod tests {
             condition: DateFilterCondition::DateBefore,
             start: Some(123),
             end: None,
+            days: None,
         };
 
         for (val, visible) in vec![(123, false), (122, true)] {
@@ -75,6 +149,7 @@ mod tests {
             condition: DateFilterCondition::DateOnOrBefore,
             start: Some(123),
             end: None,
+            days: None,
         };
 
         for (val, visible) in vec![(123, true), (122, true)] {
@@ -87,6 +162,7 @@ mod tests {
             condition: DateFilterCondition::DateAfter,
             start: Some(123),
             end: None,
+            days: None,
         };
 
         for (val, visible) in vec![(1234, true), (122, false), (0, false)] {
@@ -99,10 +175,128 @@ mod tests {
             condition: DateFilterCondition::DateWithIn,
             start: Some(123),
             end: Some(130),
+            days: None,
         };
 
         for (val, visible) in vec![(123, true), (130, true), (132, false)] {
             assert_eq!(filter.is_visible(val as i64), visible);
         }
     }
+
+    // 2022-03-14 12:00:00, Monday
+    const NOW: i64 = 1647259200;
+    const DAY: i64 = 86400;
+
+    fn relative_filter(condition: DateFilterCondition, days: Option<i64>) -> GridDateFilter {
+        GridDateFilter {
+            condition,
+            start: None,
+            end: None,
+            days,
+        }
+    }
+
+    #[test]
+    fn date_filter_today_test() {
+        let filter = relative_filter(DateFilterCondition::DateIsToday, None);
+        for (val, visible) in vec![
+            (NOW, true),
+            (NOW - 12 * 3600, true),
+            (NOW + DAY, false),
+            (NOW - DAY, false),
+            (0, false),
+        ] {
+            assert_eq!(filter.is_visible_at(val, NOW), visible);
+        }
+    }
+
+    #[test]
+    fn date_filter_yesterday_test() {
+        let filter = relative_filter(DateFilterCondition::DateIsYesterday, None);
+        for (val, visible) in vec![(NOW - DAY, true), (NOW, false), (NOW - 2 * DAY, false)] {
+            assert_eq!(filter.is_visible_at(val, NOW), visible);
+        }
+    }
+
+    #[test]
+    fn date_filter_this_week_test() {
+        let filter = relative_filter(DateFilterCondition::DateIsThisWeek, None);
+        for (val, visible) in vec![
+            (NOW, true),
+            (NOW + 6 * DAY, true),
+            (NOW - DAY, false),
+            (NOW + 7 * DAY, false),
+        ] {
+            assert_eq!(filter.is_visible_at(val, NOW), visible);
+        }
+    }
+
+    #[test]
+    fn date_filter_last_days_test() {
+        let filter = relative_filter(DateFilterCondition::DateIsInLastDays, Some(3));
+        for (val, visible) in vec![
+            (NOW, true),
+            (NOW - 2 * DAY, true),
+            (NOW - 3 * DAY, false),
+            (NOW + DAY, false),
+        ] {
+            assert_eq!(filter.is_visible_at(val, NOW), visible);
+        }
+
+        let filter = relative_filter(DateFilterCondition::DateIsInLastDays, None);
+        assert_eq!(filter.is_visible_at(NOW, NOW), false);
+    }
+
+    #[test]
+    fn date_filter_next_days_test() {
+        let filter = relative_filter(DateFilterCondition::DateIsInNextDays, Some(3));
+        for (val, visible) in vec![
+            (NOW, true),
+            (NOW + 2 * DAY, true),
+            (NOW + 3 * DAY, false),
+            (NOW - DAY, false),
+        ] {
+            assert_eq!(filter.is_visible_at(val, NOW), visible);
+        }
+    }
+
+    #[test]
+    fn date_filter_overflow_days_test() {
+        let filter = relative_filter(DateFilterCondition::DateIsInNextDays, Some(i64::MAX));
+        assert_eq!(filter.is_visible_at(NOW, NOW), false);
+
+        // The last days only overflow before 1970.
+        let filter = relative_filter(DateFilterCondition::DateIsInLastDays, Some(i64::MAX));
+        assert_eq!(filter.is_visible_at(NOW, NOW), true);
+        assert_eq!(filter.is_visible_at(-NOW, -NOW), false);
+    }
+
+    #[test]
+    fn date_filter_payload_days_test() {
+        for (days, is_ok) in vec![
+            (Some(i64::MAX), false),
+            (Some(0), false),
+            (Some(36500), true),
+            (None, true),
+        ] {
+            let payload = CreateGridDateFilterPayload {
+                field_id: "field".to_string(),
+                field_type: FieldType::DateTime,
+                condition: DateFilterCondition::DateIsInLastDays,
+                start: None,
+                end: None,
+                days,
+            };
+            let result: Result<CreateGridDateFilterParams, ErrorCode> = payload.try_into();
+            assert_eq!(result.is_ok(), is_ok);
+        }
+    }
+
+    #[test]
+    fn date_filter_overdue_test() {
+        let filter = relative_filter(DateFilterCondition::DateIsOverdue, None);
+        for (val, visible) in vec![(NOW - DAY, true), (NOW - 12 * 3600, false), (NOW, false), (0, false)] {
+            assert_eq!(filter.is_visible_at(val, NOW), visible);
+        }
+    }
 }
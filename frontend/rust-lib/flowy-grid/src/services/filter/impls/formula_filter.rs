@@ -0,0 +1,128 @@
+use crate::entities::{GridCheckboxFilter, GridDateFilter, GridNumberFilter, GridTextFilter};
+use crate::services::cell::{AnyCellData, CellData, CellFilterOperation};
+use crate::services::field::{CheckboxCellData, FormulaTypeOption, FormulaValue, NumberCellData};
+use flowy_error::FlowyResult;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+// The formula cells are filtered by the filters of their result type, so the value of the cell is
+// converted to the cell data of the corresponding field type before applying the filter.
+
+impl FormulaTypeOption {
+    pub fn apply_filter_at(
+        &self,
+        any_cell_data: AnyCellData,
+        filter: &GridDateFilter,
+        now_timestamp: i64,
+    ) -> FlowyResult<bool> {
+        if !any_cell_data.is_formula() {
+            return Ok(true);
+        }
+        let value = formula_value_from(any_cell_data)?;
+        Ok(filter.is_visible_at(value.as_timestamp().unwrap_or(0), now_timestamp))
+    }
+}
+
+impl CellFilterOperation<GridTextFilter> for FormulaTypeOption {
+    fn apply_filter(&self, any_cell_data: AnyCellData, filter: &GridTextFilter) -> FlowyResult<bool> {
+        if !any_cell_data.is_formula() {
+            return Ok(true);
+        }
+        let value = formula_value_from(any_cell_data)?;
+        Ok(filter.is_visible(value.to_display_string()))
+    }
+}
+
+impl CellFilterOperation<GridNumberFilter> for FormulaTypeOption {
+    fn apply_filter(&self, any_cell_data: AnyCellData, filter: &GridNumberFilter) -> FlowyResult<bool> {
+        if !any_cell_data.is_formula() {
+            return Ok(true);
+        }
+        let num_cell_data = match formula_value_from(any_cell_data)? {
+            FormulaValue::Number(num) => Decimal::from_f64(num)
+                .map(NumberCellData::from_decimal)
+                .unwrap_or_default(),
+            _ => NumberCellData::new(),
+        };
+        Ok(filter.is_visible(&num_cell_data))
+    }
+}
+
+impl CellFilterOperation<GridDateFilter> for FormulaTypeOption {
+    fn apply_filter(&self, any_cell_data: AnyCellData, filter: &GridDateFilter) -> FlowyResult<bool> {
+        self.apply_filter_at(any_cell_data, filter, chrono::Utc::now().timestamp())
+    }
+}
+
+impl CellFilterOperation<GridCheckboxFilter> for FormulaTypeOption {
+    fn apply_filter(&self, any_cell_data: AnyCellData, filter: &GridCheckboxFilter) -> FlowyResult<bool> {
+        if !any_cell_data.is_formula() {
+            return Ok(true);
+        }
+        let value = formula_value_from(any_cell_data)?;
+        let checkbox_cell_data = CheckboxCellData::from_str(&value.as_bool().to_string())?;
+        Ok(filter.is_visible(&checkbox_cell_data))
+    }
+}
+
+fn formula_value_from(any_cell_data: AnyCellData) -> FlowyResult<FormulaValue> {
+    let cell_data: CellData<FormulaValue> = any_cell_data.into();
+    cell_data.try_into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::services::cell::{AnyCellData, CellFilterOperation};
+    use crate::services::field::{FormulaTypeOption, FormulaValue};
+
+    #[test]
+    fn formula_number_filter_test() {
+        let type_option = FormulaTypeOption::default();
+        let filter = GridNumberFilter {
+            condition: NumberFilterCondition::GreaterThan,
+            content: Some("5".to_owned()),
+        };
+        for (value, visible) in [
+            (FormulaValue::Number(6.0), true),
+            (FormulaValue::Number(5.0), false),
+            (FormulaValue::Empty, false),
+        ] {
+            assert_eq!(type_option.apply_filter(cell(value), &filter).unwrap(), visible);
+        }
+    }
+
+    #[test]
+    fn formula_text_filter_test() {
+        let type_option = FormulaTypeOption::default();
+        let filter = GridTextFilter {
+            condition: TextFilterCondition::Contains,
+            content: Some("high".to_owned()),
+        };
+        assert!(type_option
+            .apply_filter(cell(FormulaValue::Text("Very High".to_owned())), &filter)
+            .unwrap());
+        assert!(!type_option
+            .apply_filter(cell(FormulaValue::Text("Low".to_owned())), &filter)
+            .unwrap());
+    }
+
+    #[test]
+    fn formula_checkbox_filter_test() {
+        let type_option = FormulaTypeOption::default();
+        let filter = GridCheckboxFilter {
+            condition: CheckboxCondition::IsChecked,
+        };
+        assert!(type_option
+            .apply_filter(cell(FormulaValue::Bool(true)), &filter)
+            .unwrap());
+        assert!(!type_option
+            .apply_filter(cell(FormulaValue::Bool(false)), &filter)
+            .unwrap());
+    }
+
+    fn cell(value: FormulaValue) -> AnyCellData {
+        AnyCellData::new(value.to_json().unwrap(), FieldType::Formula)
+    }
+}
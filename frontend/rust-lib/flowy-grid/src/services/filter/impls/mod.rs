@@ -1,13 +1,17 @@
 mod checkbox_filter;
 mod date_filter;
+mod formula_filter;
 mod number_filter;
+mod rollup_filter;
 mod select_option_filter;
 mod text_filter;
 mod url_filter;
 
 pub use checkbox_filter::*;
 pub use date_filter::*;
+pub use formula_filter::*;
 pub use number_filter::*;
+pub use rollup_filter::*;
 pub use select_option_filter::*;
 pub use text_filter::*;
 pub use url_filter::*;
@@ -0,0 +1,73 @@
+use crate::entities::{GridNumberFilter, GridTextFilter};
+use crate::services::cell::{AnyCellData, CellData, CellFilterOperation};
+use crate::services::field::{FormulaValue, NumberCellData, RollupTypeOption};
+use flowy_error::FlowyResult;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+
+// The unique values of the rollup cells are filtered as text, the others are filtered as numbers.
+
+impl CellFilterOperation<GridTextFilter> for RollupTypeOption {
+    fn apply_filter(&self, any_cell_data: AnyCellData, filter: &GridTextFilter) -> FlowyResult<bool> {
+        if !any_cell_data.is_rollup() {
+            return Ok(true);
+        }
+        let cell_data: CellData<FormulaValue> = any_cell_data.into();
+        Ok(filter.is_visible(cell_data.try_into_inner()?.to_display_string()))
+    }
+}
+
+impl CellFilterOperation<GridNumberFilter> for RollupTypeOption {
+    fn apply_filter(&self, any_cell_data: AnyCellData, filter: &GridNumberFilter) -> FlowyResult<bool> {
+        if !any_cell_data.is_rollup() {
+            return Ok(true);
+        }
+        let cell_data: CellData<FormulaValue> = any_cell_data.into();
+        let num_cell_data = match cell_data.try_into_inner()? {
+            FormulaValue::Number(num) => Decimal::from_f64(num)
+                .map(NumberCellData::from_decimal)
+                .unwrap_or_default(),
+            _ => NumberCellData::new(),
+        };
+        Ok(filter.is_visible(&num_cell_data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::*;
+    use crate::services::cell::{AnyCellData, CellFilterOperation};
+    use crate::services::field::{FormulaValue, RollupTypeOption};
+
+    #[test]
+    fn rollup_number_filter_test() {
+        let type_option = RollupTypeOption::default();
+        let filter = GridNumberFilter {
+            condition: NumberFilterCondition::GreaterThanOrEqualTo,
+            content: Some("2".to_owned()),
+        };
+        for (value, visible) in [
+            (FormulaValue::Number(2.0), true),
+            (FormulaValue::Number(1.0), false),
+            (FormulaValue::Empty, false),
+        ] {
+            assert_eq!(type_option.apply_filter(cell(value), &filter).unwrap(), visible);
+        }
+    }
+
+    #[test]
+    fn rollup_text_filter_test() {
+        let type_option = RollupTypeOption::default();
+        let filter = GridTextFilter {
+            condition: TextFilterCondition::Contains,
+            content: Some("google".to_owned()),
+        };
+        let value = FormulaValue::List(vec!["Google".to_owned(), "Twitter".to_owned()]);
+        assert!(type_option.apply_filter(cell(value), &filter).unwrap());
+        assert!(!type_option.apply_filter(cell(FormulaValue::Empty), &filter).unwrap());
+    }
+
+    fn cell(value: FormulaValue) -> AnyCellData {
+        AnyCellData::new(value.to_json().unwrap(), FieldType::Rollup)
+    }
+}
@@ -1,6 +1,6 @@
 use crate::entities::{GridTextFilter, TextFilterCondition};
 use crate::services::cell::{AnyCellData, CellData, CellFilterOperation};
-use crate::services::field::{RichTextTypeOption, TextCellData};
+use crate::services::field::{RichTextCellData, RichTextTypeOption};
 use flowy_error::FlowyResult;
 
 impl GridTextFilter {
@@ -30,15 +30,19 @@ impl CellFilterOperation<GridTextFilter> for RichTextTypeOption {
             return Ok(true);
         }
 
-        let cell_data: CellData<TextCellData> = any_cell_data.into();
-        let text_cell_data = cell_data.try_into_inner()?;
-        Ok(filter.is_visible(text_cell_data))
+        // The formats of the rich text are ignored, the filter matches against the plain text.
+        let cell_data: CellData<RichTextCellData> = any_cell_data.into();
+        let text = cell_data.try_into_inner()?.to_plain_text();
+        Ok(filter.is_visible(text))
     }
 }
 #[cfg(test)]
 mod tests {
     #![allow(clippy::all)]
-    use crate::entities::{GridTextFilter, TextFilterCondition};
+    use crate::entities::{FieldType, GridTextFilter, TextFilterCondition};
+    use crate::services::cell::{AnyCellData, CellFilterOperation};
+    use crate::services::field::{RichTextCellData, RichTextTypeOption};
+    use lib_ot::rich_text::{RichTextAttribute, RichTextDelta};
 
     #[test]
     fn text_filter_equal_test() {
@@ -98,4 +102,20 @@ mod tests {
         assert_eq!(text_filter.is_visible(""), false);
         assert_eq!(text_filter.is_visible("github"), false);
     }
+
+    #[test]
+    fn text_filter_rich_text_test() {
+        let text_filter = GridTextFilter {
+            condition: TextFilterCondition::Is,
+            content: Some("appflowy".to_owned()),
+        };
+
+        let mut delta = RichTextDelta::new();
+        delta.insert("App", RichTextAttribute::Bold(true).into());
+        delta.insert("Flowy", RichTextAttribute::Link("https://appflowy.io").into());
+        let cell_data = RichTextCellData { delta }.to_cell_str();
+        let any_cell_data = AnyCellData::new(cell_data, FieldType::RichText);
+        let type_option = RichTextTypeOption::default();
+        assert_eq!(type_option.apply_filter(any_cell_data, &text_filter).unwrap(), true);
+    }
 }
@@ -1,5 +1,7 @@
 mod filter_cache;
+mod filter_clock;
 mod filter_service;
 mod impls;
 
+pub use filter_clock::*;
 pub(crate) use filter_service::*;
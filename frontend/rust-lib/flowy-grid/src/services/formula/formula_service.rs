@@ -0,0 +1,392 @@
+use crate::entities::{CellChangesetPB, FieldType};
+use crate::services::block_manager::GridBlockManager;
+use crate::services::cell::{AnyCellData, FromCellString};
+use crate::services::field::{
+    select_option_operation, CheckboxCellData, FormulaTypeOption, FormulaValue, NumberTypeOption, RelationRowIds,
+    RichTextCellData, URLCellDataPB, SELECTION_IDS_SEPARATOR,
+};
+use crate::services::row::make_row_from_row_rev;
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use flowy_grid_data_model::revision::{CellRevision, FieldRevision, RowRevision};
+use flowy_sync::client_grid::GridRevisionPad;
+use rust_decimal::prelude::ToPrimitive;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub(crate) struct GridFormulaService {
+    grid_id: String,
+    grid_pad: Arc<RwLock<GridRevisionPad>>,
+    block_manager: Arc<GridBlockManager>,
+}
+
+impl GridFormulaService {
+    pub async fn new(grid_pad: Arc<RwLock<GridRevisionPad>>, block_manager: Arc<GridBlockManager>) -> Self {
+        let grid_id = grid_pad.read().await.grid_id();
+        Self {
+            grid_id,
+            grid_pad,
+            block_manager,
+        }
+    }
+
+    /// Return error if the expression is invalid, or the formulas would reference each other in a
+    /// cycle after the type option of the field is replaced with the `type_option`.
+    pub async fn validate_formula(&self, field_id: &str, type_option: &FormulaTypeOption) -> FlowyResult<()> {
+        let field_ids = match type_option.parse_expression()? {
+            None => vec![],
+            Some(expr) => expr.field_ids(),
+        };
+
+        let field_revs = self.grid_pad.read().await.get_field_revs(None)?;
+        if let Some(unknown_field_id) = field_ids
+            .iter()
+            .find(|id| id.as_str() != field_id && !field_revs.iter().any(|field_rev| &field_rev.id == *id))
+        {
+            let msg = format!("The field {} referenced by the formula doesn't exist", unknown_field_id);
+            return Err(FlowyError::new(ErrorCode::InvalidFormula, &msg));
+        }
+
+        let mut references = field_revs
+            .iter()
+            .flat_map(|field_rev| {
+                let formula_field = FormulaField::from_field_rev(field_rev)?;
+                Some((formula_field.field_rev.id.clone(), formula_field.field_ids))
+            })
+            .collect::<HashMap<String, Vec<String>>>();
+        references.insert(field_id.to_owned(), field_ids);
+        let _ = sort_formula_fields(&references)?;
+        Ok(())
+    }
+
+    /// Recompute the formula cells of the row that depend on the updated cell. Return the ids of the
+    /// formula fields whose cells were changed.
+    pub async fn did_update_cell(&self, row_id: &str, field_id: &str) -> FlowyResult<Vec<String>> {
+        let formula_fields = self.formula_fields().await?;
+        let affected_ids = dependent_formula_ids(&formula_fields, field_id);
+        if affected_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        match self.block_manager.get_row_rev(row_id).await? {
+            None => Ok(vec![]),
+            Some(row_rev) => self.recompute_row(&row_rev, &formula_fields, &affected_ids).await,
+        }
+    }
+
+    /// Compute the formula cells of the new rows.
+    pub async fn did_create_rows(&self, row_ids: &[String]) -> FlowyResult<()> {
+        let formula_fields = self.formula_fields().await?;
+        if formula_fields.is_empty() {
+            return Ok(());
+        }
+
+        let affected_ids = formula_fields
+            .iter()
+            .map(|formula_field| formula_field.field_rev.id.clone())
+            .collect::<HashSet<String>>();
+        for row_id in row_ids {
+            if let Some(row_rev) = self.block_manager.get_row_rev(row_id).await? {
+                let _ = self.recompute_row(&row_rev, &formula_fields, &affected_ids).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Recompute the cells of the formula fields that depend on the field in all the rows. The
+    /// cells of the field itself are recomputed too if it's a formula field.
+    pub async fn did_update_field(&self, field_id: &str) -> FlowyResult<()> {
+        let formula_fields = self.formula_fields().await?;
+        let mut affected_ids = dependent_formula_ids(&formula_fields, field_id);
+        if formula_fields
+            .iter()
+            .any(|formula_field| formula_field.field_rev.id == field_id)
+        {
+            affected_ids.insert(field_id.to_owned());
+        }
+        if affected_ids.is_empty() {
+            return Ok(());
+        }
+
+        for snapshot in self.block_manager.get_block_snapshots(None).await? {
+            for row_rev in snapshot.row_revs.iter() {
+                let _ = self.recompute_row(row_rev, &formula_fields, &affected_ids).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the formula fields in the order that each formula comes after the formulas it
+    /// references.
+    async fn formula_fields(&self) -> FlowyResult<Vec<FormulaField>> {
+        let field_revs = self.grid_pad.read().await.get_field_revs(None)?;
+        let mut formula_fields = field_revs
+            .iter()
+            .flat_map(FormulaField::from_field_rev)
+            .map(|formula_field| (formula_field.field_rev.id.clone(), formula_field))
+            .collect::<HashMap<String, FormulaField>>();
+
+        let references = formula_fields
+            .iter()
+            .map(|(field_id, formula_field)| (field_id.clone(), formula_field.field_ids.clone()))
+            .collect::<HashMap<String, Vec<String>>>();
+        let formula_field_ids = sort_formula_fields(&references)?;
+
+        let mut sorted_formula_fields = formula_field_ids
+            .iter()
+            .flat_map(|field_id| formula_fields.remove(field_id))
+            .collect::<Vec<FormulaField>>();
+        for formula_field in sorted_formula_fields.iter_mut() {
+            formula_field.referenced_field_revs = formula_field
+                .field_ids
+                .iter()
+                .flat_map(|field_id| field_revs.iter().find(|field_rev| &field_rev.id == field_id).cloned())
+                .collect();
+        }
+        Ok(sorted_formula_fields)
+    }
+
+    async fn recompute_row(
+        &self,
+        row_rev: &RowRevision,
+        formula_fields: &[FormulaField],
+        affected_ids: &HashSet<String>,
+    ) -> FlowyResult<Vec<String>> {
+        let mut updated_field_ids = vec![];
+        for changeset in make_formula_cell_changesets(&self.grid_id, row_rev, formula_fields, affected_ids) {
+            updated_field_ids.push(changeset.field_id.clone());
+            let _ = self
+                .block_manager
+                .update_computed_cell(changeset, make_row_from_row_rev)
+                .await?;
+        }
+        Ok(updated_field_ids)
+    }
+}
+
+struct FormulaField {
+    field_rev: Arc<FieldRevision>,
+    type_option: FormulaTypeOption,
+    /// The ids of the fields that are referenced by the formula.
+    field_ids: Vec<String>,
+    referenced_field_revs: Vec<Arc<FieldRevision>>,
+}
+
+impl FormulaField {
+    fn from_field_rev(field_rev: &Arc<FieldRevision>) -> Option<Self> {
+        if !FieldType::from(field_rev.field_type_rev).is_formula() {
+            return None;
+        }
+        let type_option = FormulaTypeOption::from(field_rev);
+        let field_ids = type_option.referenced_field_ids();
+        Some(Self {
+            field_rev: field_rev.clone(),
+            type_option,
+            field_ids,
+            referenced_field_revs: vec![],
+        })
+    }
+}
+
+/// Return the ids of the formula fields that reference the field directly or through the other
+/// formulas. The `formula_fields` must be sorted by the [sort_formula_fields].
+fn dependent_formula_ids(formula_fields: &[FormulaField], field_id: &str) -> HashSet<String> {
+    let mut dependent_ids = HashSet::new();
+    for formula_field in formula_fields {
+        if formula_field
+            .field_ids
+            .iter()
+            .any(|id| id == field_id || dependent_ids.contains(id))
+        {
+            dependent_ids.insert(formula_field.field_rev.id.clone());
+        }
+    }
+    dependent_ids
+}
+
+/// Sort the formula fields topologically, so each formula comes after the formulas it references.
+/// The `references` maps the id of the formula field to the ids of the fields it references.
+/// Return error if the formulas reference each other in a cycle.
+fn sort_formula_fields(references: &HashMap<String, Vec<String>>) -> FlowyResult<Vec<String>> {
+    fn visit(
+        field_id: &str,
+        references: &HashMap<String, Vec<String>>,
+        visiting: &mut HashSet<String>,
+        sorted: &mut Vec<String>,
+    ) -> FlowyResult<()> {
+        if sorted.iter().any(|id| id == field_id) {
+            return Ok(());
+        }
+        if !visiting.insert(field_id.to_owned()) {
+            let msg = format!("The formula of the field {} references itself", field_id);
+            return Err(FlowyError::new(ErrorCode::FormulaCycleDetected, &msg));
+        }
+
+        for referenced_id in references.get(field_id).into_iter().flatten() {
+            // Only the formula fields can form a cycle.
+            if references.contains_key(referenced_id) {
+                let _ = visit(referenced_id, references, visiting, sorted)?;
+            }
+        }
+        visiting.remove(field_id);
+        sorted.push(field_id.to_owned());
+        Ok(())
+    }
+
+    let mut field_ids = references.keys().collect::<Vec<&String>>();
+    field_ids.sort();
+
+    let mut sorted = vec![];
+    let mut visiting = HashSet::new();
+    for field_id in field_ids {
+        let _ = visit(field_id, references, &mut visiting, &mut sorted)?;
+    }
+    Ok(sorted)
+}
+
+/// Evaluate the affected formulas of the row and return the changesets of the cells whose values
+/// were changed.
+fn make_formula_cell_changesets(
+    grid_id: &str,
+    row_rev: &RowRevision,
+    formula_fields: &[FormulaField],
+    affected_ids: &HashSet<String>,
+) -> Vec<CellChangesetPB> {
+    let mut values: HashMap<String, FormulaValue> = HashMap::new();
+    let mut changesets = vec![];
+    for formula_field in formula_fields {
+        let field_id = &formula_field.field_rev.id;
+        if !affected_ids.contains(field_id) {
+            continue;
+        }
+
+        for field_rev in formula_field.referenced_field_revs.iter() {
+            if !values.contains_key(&field_rev.id) {
+                let value = formula_value_from_cell(field_rev, row_rev.cells.get(&field_rev.id));
+                values.insert(field_rev.id.clone(), value);
+            }
+        }
+
+        let value = formula_field.type_option.evaluate(&values);
+        let content = match value.to_json() {
+            Ok(data) => AnyCellData::new(data, FieldType::Formula).json(),
+            Err(e) => {
+                tracing::error!("Serialize the formula value failed: {:?}", e);
+                continue;
+            }
+        };
+        values.insert(field_id.clone(), value);
+
+        let is_changed = row_rev
+            .cells
+            .get(field_id)
+            .map(|cell_rev| cell_rev.data != content)
+            .unwrap_or(true);
+        if is_changed {
+            changesets.push(CellChangesetPB {
+                grid_id: grid_id.to_owned(),
+                row_id: row_rev.id.clone(),
+                field_id: field_id.clone(),
+                content: Some(content),
+            });
+        }
+    }
+    changesets
+}
+
+/// Convert the cell to the value that is used by the formulas and the rollups. The cell whose data
+/// doesn't match the type of the field is treated as empty.
+pub(crate) fn formula_value_from_cell(field_rev: &FieldRevision, cell_rev: Option<&CellRevision>) -> FormulaValue {
+    let any_cell_data = match cell_rev.and_then(|cell_rev| AnyCellData::try_from(cell_rev).ok()) {
+        None => return FormulaValue::Empty,
+        Some(any_cell_data) => any_cell_data,
+    };
+    let field_type = FieldType::from(field_rev.field_type_rev);
+    if any_cell_data.field_type != field_type {
+        return FormulaValue::Empty;
+    }
+
+    let data = any_cell_data.data;
+    match field_type {
+        FieldType::RichText => {
+            match RichTextCellData::from_cell_str(&data).map(|cell_data| cell_data.to_plain_text()) {
+                Ok(text) if !text.is_empty() => FormulaValue::Text(text),
+                _ => FormulaValue::Empty,
+            }
+        }
+        FieldType::Number => NumberTypeOption::from(field_rev)
+            .format_cell_data(&data)
+            .ok()
+            .and_then(|num_cell_data| num_cell_data.decimal().and_then(|decimal| decimal.to_f64()))
+            .map(FormulaValue::Number)
+            .unwrap_or_default(),
+        FieldType::DateTime => match data.parse::<i64>() {
+            Ok(timestamp) if timestamp != 0 => FormulaValue::Date(timestamp),
+            _ => FormulaValue::Empty,
+        },
+        FieldType::SingleSelect | FieldType::MultiSelect => {
+            let options = select_option_operation(field_rev)
+                .map(|operation| operation.options().clone())
+                .unwrap_or_default();
+            let names = data
+                .split(SELECTION_IDS_SEPARATOR)
+                .flat_map(|option_id| options.iter().find(|option| option.id == option_id))
+                .map(|option| option.name.clone())
+                .collect::<Vec<String>>();
+            if field_type.is_multi_select() {
+                FormulaValue::List(names)
+            } else {
+                names.into_iter().next().map(FormulaValue::Text).unwrap_or_default()
+            }
+        }
+        FieldType::Checkbox => FormulaValue::Bool(
+            CheckboxCellData::from_str(&data)
+                .map(|cell_data| cell_data.is_check())
+                .unwrap_or(false),
+        ),
+        FieldType::URL => match URLCellDataPB::from_cell_str(&data) {
+            Ok(url_cell_data) if !url_cell_data.content.is_empty() => FormulaValue::Text(url_cell_data.content),
+            _ => FormulaValue::Empty,
+        },
+        FieldType::Formula | FieldType::Rollup => FormulaValue::from_cell_str(&data).unwrap_or_default(),
+        // The relation cell is a list of the linked row ids, so `count({relation})` works.
+        FieldType::Relation => FormulaValue::List(RelationRowIds::from(data).into_inner()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sort_formula_fields;
+    use flowy_error::ErrorCode;
+    use std::collections::HashMap;
+
+    #[test]
+    fn sort_formula_fields_test() {
+        let references = make_references(vec![("c", vec!["b", "text"]), ("b", vec!["a"]), ("a", vec!["number"])]);
+        let sorted = sort_formula_fields(&references).unwrap();
+        assert_eq!(sorted, vec!["a".to_owned(), "b".to_owned(), "c".to_owned()]);
+    }
+
+    #[test]
+    fn sort_formula_fields_with_cycle_test() {
+        let references = make_references(vec![("a", vec!["b"]), ("b", vec!["c"]), ("c", vec!["a"])]);
+        let error = sort_formula_fields(&references).unwrap_err();
+        assert_eq!(error.code, ErrorCode::FormulaCycleDetected.value());
+
+        let references = make_references(vec![("a", vec!["a"])]);
+        assert!(sort_formula_fields(&references).is_err());
+    }
+
+    fn make_references(references: Vec<(&str, Vec<&str>)>) -> HashMap<String, Vec<String>> {
+        references
+            .into_iter()
+            .map(|(field_id, field_ids)| {
+                (
+                    field_id.to_owned(),
+                    field_ids.into_iter().map(|id| id.to_owned()).collect(),
+                )
+            })
+            .collect()
+    }
+}
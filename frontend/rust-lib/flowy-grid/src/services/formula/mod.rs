@@ -0,0 +1,3 @@
+mod formula_service;
+
+pub(crate) use formula_service::*;
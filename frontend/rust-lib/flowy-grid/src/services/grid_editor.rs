@@ -3,28 +3,44 @@ use crate::entities::GridCellIdParams;
 use crate::entities::*;
 use crate::manager::{GridTaskSchedulerRwLock, GridUser};
 use crate::services::block_manager::GridBlockManager;
-use crate::services::cell::{apply_cell_data_changeset, decode_any_cell_data, CellBytes};
-use crate::services::field::{default_type_option_builder_from_type, type_option_builder_from_bytes, FieldBuilder};
-use crate::services::filter::{GridFilterChangeset, GridFilterService};
+use crate::services::calculation::{GridCalculationChangeset, GridCalculationService};
+use crate::services::cell::{
+    apply_cell_data_changeset, decode_any_cell_data, decode_cell_data_to_string, AnyCellData, CellBytes,
+};
+use crate::services::csv::export_csv;
+use crate::services::field::{
+    default_type_option_builder_from_type, type_option_builder_from_bytes, FieldBuilder, FormulaTypeOption,
+    FormulaValue, RelationTypeOption, RichTextCellChangesetParams, RichTextTypeOption, RollupTypeOption,
+};
+use crate::services::filter::{FilterClock, GridFilterChangeset, GridFilterService};
+use crate::services::formula::GridFormulaService;
+use crate::services::group::{GridGroupChangeset, GridGroupService};
 use crate::services::persistence::block_index::BlockIndexCache;
+use crate::services::relation::{GridRelationResolver, GridRelationService};
 use crate::services::row::{
     make_grid_blocks, make_row_from_row_rev, make_rows_from_row_revs, GridBlockSnapshot, RowRevisionBuilder,
 };
 use crate::services::setting::make_grid_setting;
+use crate::services::sort::{GridSortChangeset, GridSortService};
 use bytes::Bytes;
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
 use flowy_grid_data_model::revision::*;
-use flowy_revision::{RevisionCloudService, RevisionCompactor, RevisionManager, RevisionObjectBuilder};
-use flowy_sync::client_grid::{GridChangeset, GridRevisionPad, JsonDeserializer};
+use flowy_revision::{
+    RevisionCloudService, RevisionCompactor, RevisionManager, RevisionObjectBuilder, RevisionWebSocket,
+};
+use flowy_sync::client_grid::{
+    FieldHistoryOperation, GridChangeset, GridHistoryItem, GridRevisionPad, JsonDeserializer, RowHistoryOperation,
+};
 use flowy_sync::entities::grid::{FieldChangesetParams, GridSettingChangesetParams};
 use flowy_sync::entities::revision::Revision;
+use flowy_sync::entities::ws_data::ServerRevisionWSData;
 use flowy_sync::errors::CollaborateResult;
 use flowy_sync::util::make_delta_from_revisions;
 use lib_infra::future::FutureResult;
 use lib_ot::core::PhantomAttributes;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 
 pub struct GridRevisionEditor {
     pub(crate) grid_id: String,
@@ -34,6 +50,13 @@ pub struct GridRevisionEditor {
     block_manager: Arc<GridBlockManager>,
     #[allow(dead_code)]
     pub(crate) filter_service: Arc<GridFilterService>,
+    sort_service: Arc<GridSortService>,
+    group_service: Arc<GridGroupService>,
+    formula_service: Arc<GridFormulaService>,
+    relation_service: Arc<GridRelationService>,
+    calculation_service: Arc<GridCalculationService>,
+    #[cfg(feature = "sync")]
+    ws_manager: Arc<flowy_revision::RevisionWebSocketManager>,
 }
 
 impl Drop for GridRevisionEditor {
@@ -49,6 +72,8 @@ impl GridRevisionEditor {
         mut rev_manager: RevisionManager,
         persistence: Arc<BlockIndexCache>,
         task_scheduler: GridTaskSchedulerRwLock,
+        web_socket: Arc<dyn RevisionWebSocket>,
+        relation_resolver: Arc<dyn GridRelationResolver>,
     ) -> FlowyResult<Arc<Self>> {
         let token = user.token()?;
         let cloud = Arc::new(GridRevisionCloudService { token });
@@ -56,9 +81,29 @@ impl GridRevisionEditor {
         let rev_manager = Arc::new(rev_manager);
         let grid_pad = Arc::new(RwLock::new(grid_pad));
         let block_meta_revs = grid_pad.read().await.get_block_meta_revs();
-        let block_manager = Arc::new(GridBlockManager::new(grid_id, &user, block_meta_revs, persistence).await?);
+        let block_manager =
+            Arc::new(GridBlockManager::new(grid_id, &user, block_meta_revs, persistence, web_socket.clone()).await?);
         let filter_service =
             Arc::new(GridFilterService::new(grid_pad.clone(), block_manager.clone(), task_scheduler.clone()).await);
+        let sort_service = Arc::new(GridSortService::new(grid_pad.clone(), block_manager.clone()).await);
+        let group_service = Arc::new(GridGroupService::new(grid_pad.clone(), block_manager.clone()).await);
+        let formula_service = Arc::new(GridFormulaService::new(grid_pad.clone(), block_manager.clone()).await);
+        let relation_service =
+            Arc::new(GridRelationService::new(grid_pad.clone(), block_manager.clone(), relation_resolver).await);
+        let calculation_service = Arc::new(
+            GridCalculationService::new(grid_pad.clone(), block_manager.clone(), filter_service.clone()).await,
+        );
+
+        #[cfg(feature = "sync")]
+        let ws_manager = crate::services::web_socket::make_grid_ws_manager(
+            &user.user_id()?,
+            grid_id,
+            rev_manager.clone(),
+            web_socket,
+            grid_pad.clone(),
+        )
+        .await;
+
         let editor = Arc::new(Self {
             grid_id: grid_id.to_owned(),
             user,
@@ -66,11 +111,52 @@ impl GridRevisionEditor {
             rev_manager,
             block_manager,
             filter_service,
+            sort_service,
+            group_service,
+            formula_service,
+            relation_service,
+            calculation_service,
+            #[cfg(feature = "sync")]
+            ws_manager,
         });
 
         Ok(editor)
     }
 
+    /// The object id of the web socket data is either the id of the grid or the id of one of
+    /// its blocks.
+    pub fn contains_object(&self, object_id: &str) -> bool {
+        self.grid_id == object_id || self.block_manager.contains_block(object_id)
+    }
+
+    pub async fn receive_ws_data(&self, data: ServerRevisionWSData) -> FlowyResult<()> {
+        if data.object_id == self.grid_id {
+            self.receive_grid_ws_data(data).await
+        } else {
+            let block_editor = self.block_manager.get_editor(&data.object_id).await?;
+            block_editor.receive_ws_data(data).await
+        }
+    }
+
+    #[cfg(feature = "sync")]
+    pub fn stop(&self) {
+        self.ws_manager.stop();
+        self.block_manager.stop();
+    }
+
+    #[cfg(not(feature = "sync"))]
+    pub fn stop(&self) {}
+
+    #[cfg(feature = "sync")]
+    async fn receive_grid_ws_data(&self, data: ServerRevisionWSData) -> FlowyResult<()> {
+        self.ws_manager.receive_ws_data(data).await
+    }
+
+    #[cfg(not(feature = "sync"))]
+    async fn receive_grid_ws_data(&self, _data: ServerRevisionWSData) -> FlowyResult<()> {
+        Ok(())
+    }
+
     pub async fn insert_field(&self, params: InsertFieldParams) -> FlowyResult<()> {
         let InsertFieldParams {
             field,
@@ -79,6 +165,10 @@ impl GridRevisionEditor {
             grid_id,
         } = params;
         let field_id = field.id.clone();
+        let field_type = field.field_type.clone();
+        let type_option_data = self
+            .prepare_type_option_data(&field_id, &field_type, type_option_data)
+            .await?;
         if self.contain_field(&field_id).await {
             let _ = self
                 .modify(|grid| {
@@ -110,6 +200,9 @@ impl GridRevisionEditor {
             let _ = self.notify_did_insert_grid_field(&field_id).await?;
         }
 
+        if field_type.is_formula() || field_type.is_relation() || field_type.is_rollup() {
+            let _ = self.did_update_field_type_option(&field_id).await?;
+        }
         Ok(())
     }
 
@@ -125,9 +218,12 @@ impl GridRevisionEditor {
             return Ok(());
         }
         let field_rev = result.unwrap();
+        let field_type: FieldType = field_rev.field_type_rev.into();
+        let type_option_data = self
+            .prepare_type_option_data(field_id, &field_type, type_option_data)
+            .await?;
         let _ = self
             .modify(|grid| {
-                let field_type = field_rev.field_type_rev.into();
                 let deserializer = TypeOptionJsonDeserializer(field_type);
                 let changeset = FieldChangesetParams {
                     field_id: field_id.to_owned(),
@@ -139,6 +235,7 @@ impl GridRevisionEditor {
             })
             .await?;
         let _ = self.notify_did_update_grid_field(field_id).await?;
+        let _ = self.did_update_field_type_option(field_id).await?;
         Ok(())
     }
 
@@ -162,18 +259,33 @@ impl GridRevisionEditor {
         self.grid_pad.read().await.contain_field(field_id)
     }
 
-    pub async fn update_field(&self, params: FieldChangesetParams) -> FlowyResult<()> {
+    pub async fn update_field(&self, mut params: FieldChangesetParams) -> FlowyResult<()> {
         let field_id = params.field_id.clone();
-        let json_deserializer = match self.grid_pad.read().await.get_field_rev(params.field_id.as_str()) {
+        let field_type: FieldType = match self.grid_pad.read().await.get_field_rev(params.field_id.as_str()) {
             None => return Err(ErrorCode::FieldDoesNotExist.into()),
-            Some((_, field_rev)) => TypeOptionJsonDeserializer(field_rev.field_type_rev.into()),
+            Some((_, field_rev)) => field_rev.field_type_rev.into(),
         };
+        let is_type_option_changed = params.type_option_data.is_some() || params.field_type.is_some();
+        if let Some(type_option_data) = params.type_option_data.take() {
+            let field_type = params
+                .field_type
+                .map(FieldType::from)
+                .unwrap_or_else(|| field_type.clone());
+            let type_option_data = self
+                .prepare_type_option_data(&field_id, &field_type, type_option_data)
+                .await?;
+            params.type_option_data = Some(type_option_data);
+        }
 
+        let json_deserializer = TypeOptionJsonDeserializer(field_type);
         let _ = self
             .modify(|grid| Ok(grid.update_field_rev(params, json_deserializer)?))
             .await?;
 
         let _ = self.notify_did_update_grid_field(&field_id).await?;
+        if is_type_option_changed {
+            let _ = self.did_update_field_type_option(&field_id).await?;
+        }
         Ok(())
     }
 
@@ -187,10 +299,24 @@ impl GridRevisionEditor {
     }
 
     pub async fn delete_field(&self, field_id: &str) -> FlowyResult<()> {
+        let field_rev = self.get_field_rev(field_id).await;
         let _ = self.modify(|grid_pad| Ok(grid_pad.delete_field_rev(field_id)?)).await?;
+        self.did_delete_field(field_id, field_rev).await
+    }
+
+    /// The `field_rev` is the revision of the field before it was deleted.
+    async fn did_delete_field(&self, field_id: &str, field_rev: Option<Arc<FieldRevision>>) -> FlowyResult<()> {
         let field_order = GridFieldIdPB::from(field_id);
         let notified_changeset = GridFieldChangesetPB::delete(&self.grid_id, vec![field_order]);
         let _ = self.notify_did_update_grid(notified_changeset).await?;
+
+        if let Some(field_rev) = field_rev.filter(|field_rev| FieldType::from(field_rev.field_type_rev).is_relation()) {
+            let _ = self.relation_service.did_delete_relation_field(&field_rev).await?;
+        }
+
+        // The formulas and the rollups referencing the deleted field treat it as an empty cell.
+        let _ = self.did_update_dependent_fields(field_id).await?;
+        self.calculation_service.did_update_field().await;
         Ok(())
     }
 
@@ -216,6 +342,7 @@ impl GridRevisionEditor {
             .await?;
 
         let _ = self.notify_did_update_grid_field(field_id).await?;
+        let _ = self.did_update_field_type_option(field_id).await?;
 
         Ok(())
     }
@@ -282,6 +409,11 @@ impl GridRevisionEditor {
         // update block row count
         let changeset = GridBlockMetaRevisionChangeset::from_row_count(&block_id, row_count);
         let _ = self.update_block(changeset).await?;
+        let row_ids = vec![row_order.id.clone()];
+        let _ = self.relation_service.did_create_rows(&row_ids).await?;
+        let _ = self.formula_service.did_create_rows(&row_ids).await?;
+        let _ = self.group_service.did_create_rows(&row_ids).await?;
+        self.calculation_service.did_update_row(&row_order.id).await;
         Ok(row_order)
     }
 
@@ -300,6 +432,17 @@ impl GridRevisionEditor {
         for changeset in changesets {
             let _ = self.update_block(changeset).await?;
         }
+
+        let row_ids = row_orders
+            .iter()
+            .map(|row_order| row_order.id.clone())
+            .collect::<Vec<String>>();
+        let _ = self.relation_service.did_create_rows(&row_ids).await?;
+        let _ = self.formula_service.did_create_rows(&row_ids).await?;
+        let _ = self.group_service.did_create_rows(&row_ids).await?;
+        for row_id in row_ids.iter() {
+            self.calculation_service.did_update_row(row_id).await;
+        }
         Ok(row_orders)
     }
 
@@ -310,6 +453,7 @@ impl GridRevisionEditor {
     pub async fn get_rows(&self, block_id: &str) -> FlowyResult<RepeatedRowPB> {
         let block_ids = vec![block_id.to_owned()];
         let mut grid_block_snapshot = self.grid_block_snapshots(Some(block_ids)).await?;
+        self.sort_service.sort_block_snapshots(&mut grid_block_snapshot).await;
 
         // For the moment, we only support one block.
         // We can save the rows into multiple blocks and load them asynchronously in the future.
@@ -331,7 +475,11 @@ impl GridRevisionEditor {
     }
 
     pub async fn delete_row(&self, row_id: &str) -> FlowyResult<()> {
+        let _ = self.relation_service.will_delete_row(row_id).await?;
         let _ = self.block_manager.delete_row(row_id).await?;
+        let row_ids = vec![row_id.to_owned()];
+        self.group_service.did_delete_rows(&row_ids).await;
+        self.calculation_service.did_delete_rows(row_ids).await;
         Ok(())
     }
 
@@ -373,37 +521,90 @@ impl GridRevisionEditor {
             grid_id,
             row_id,
             field_id,
-            mut content,
+            content,
         } = cell_changeset;
 
-        match self.grid_pad.read().await.get_field_rev(&field_id) {
+        match self.get_field_rev(&field_id).await {
             None => {
                 let msg = format!("Field not found with id: {}", &field_id);
                 Err(FlowyError::internal().context(msg))
             }
-            Some((_, field_rev)) => {
+            Some(field_rev) => {
                 tracing::trace!("field changeset: id:{} / value:{:?}", &field_id, content);
 
                 let cell_rev = self.get_cell_rev(&row_id, &field_id).await?;
-                // Update the changeset.data property with the return value.
-                content = Some(apply_cell_data_changeset(content.unwrap(), cell_rev, field_rev)?);
-                let cell_changeset = CellChangesetPB {
-                    grid_id,
-                    row_id,
-                    field_id,
-                    content,
-                };
-                let _ = self
-                    .block_manager
-                    .update_cell(cell_changeset, make_row_from_row_rev)
-                    .await?;
-                Ok(())
+                let content = apply_cell_data_changeset(content.unwrap(), cell_rev.clone(), &field_rev)?;
+                self.save_cell(grid_id, row_id, field_rev, cell_rev, content).await
             }
         }
     }
 
+    /// Update the rich text cell with the delta or the format. The plain text is updated through
+    /// the `update_cell`.
+    pub async fn update_rich_text_cell(&self, params: RichTextCellChangesetParams) -> FlowyResult<()> {
+        let GridCellIdParams {
+            grid_id,
+            field_id,
+            row_id,
+        } = params.cell_identifier;
+        let field_rev = self
+            .get_field_rev(&field_id)
+            .await
+            .ok_or_else(|| FlowyError::internal().context(format!("Field not found with id: {}", &field_id)))?;
+        if FieldType::from(field_rev.field_type_rev) != FieldType::RichText {
+            let msg = format!("The field:{} is not a rich text field", &field_id);
+            return Err(FlowyError::new(ErrorCode::InvalidData, &msg));
+        }
+
+        let cell_rev = self.get_cell_rev(&row_id, &field_id).await?;
+        let cell_data = RichTextTypeOption::from(field_rev.as_ref())
+            .apply_rich_text_cell_changeset(params.changeset, cell_rev.clone())?;
+        let content = AnyCellData::new(cell_data, FieldType::RichText).json();
+        self.save_cell(grid_id, row_id, field_rev, cell_rev, content).await
+    }
+
+    async fn save_cell(
+        &self,
+        grid_id: String,
+        row_id: String,
+        field_rev: Arc<FieldRevision>,
+        old_cell_rev: Option<CellRevision>,
+        content: String,
+    ) -> FlowyResult<()> {
+        let field_id = field_rev.id.clone();
+        let cell_changeset = CellChangesetPB {
+            grid_id,
+            row_id: row_id.clone(),
+            field_id: field_id.clone(),
+            content: Some(content),
+        };
+        let _ = self
+            .block_manager
+            .update_cell(cell_changeset, make_row_from_row_rev)
+            .await?;
+
+        if FieldType::from(field_rev.field_type_rev).is_relation() {
+            let _ = self
+                .relation_service
+                .did_update_relation_cell(&row_id, &field_rev, old_cell_rev.as_ref())
+                .await?;
+        }
+
+        // Recompute the rollups and the formulas before returning, so the cells read afterward
+        // are up to date.
+        let updated_field_ids = self.recompute_row(&row_id, &field_id).await?;
+        let _ = self
+            .relation_service
+            .did_update_cells(&row_id, &updated_field_ids)
+            .await?;
+        self.calculation_service.did_update_row(&row_id).await;
+        self.notify_did_update_row_cells(row_id, updated_field_ids);
+        Ok(())
+    }
+
     pub async fn get_blocks(&self, block_ids: Option<Vec<String>>) -> FlowyResult<RepeatedGridBlockPB> {
-        let block_snapshots = self.grid_block_snapshots(block_ids.clone()).await?;
+        let mut block_snapshots = self.grid_block_snapshots(block_ids.clone()).await?;
+        self.sort_service.sort_block_snapshots(&mut block_snapshots).await;
         make_grid_blocks(block_ids, block_snapshots)
     }
 
@@ -413,10 +614,19 @@ impl GridRevisionEditor {
     }
 
     pub async fn delete_rows(&self, row_orders: Vec<GridRowPB>) -> FlowyResult<()> {
+        for row_order in row_orders.iter() {
+            let _ = self.relation_service.will_delete_row(&row_order.id).await?;
+        }
+        let row_ids = row_orders
+            .iter()
+            .map(|row_order| row_order.id.clone())
+            .collect::<Vec<String>>();
         let changesets = self.block_manager.delete_rows(row_orders).await?;
         for changeset in changesets {
             let _ = self.update_block(changeset).await?;
         }
+        self.group_service.did_delete_rows(&row_ids).await;
+        self.calculation_service.did_delete_rows(row_ids).await;
         Ok(())
     }
 
@@ -427,15 +637,22 @@ impl GridRevisionEditor {
             .iter()
             .map(GridFieldIdPB::from)
             .collect();
-        let mut block_orders = vec![];
-        for block_rev in pad_read_guard.get_block_meta_revs() {
-            let row_orders = self.block_manager.get_row_orders(&block_rev.block_id).await?;
-            let block_order = GridBlockPB {
-                id: block_rev.block_id.clone(),
-                rows: row_orders,
-            };
-            block_orders.push(block_order);
-        }
+        let block_ids = pad_read_guard
+            .get_block_meta_revs()
+            .iter()
+            .map(|block_rev| block_rev.block_id.clone())
+            .collect::<Vec<String>>();
+        drop(pad_read_guard);
+
+        let mut block_snapshots = self.block_manager.get_block_snapshots(Some(block_ids)).await?;
+        self.sort_service.sort_block_snapshots(&mut block_snapshots).await;
+        let block_orders = block_snapshots
+            .into_iter()
+            .map(|snapshot| GridBlockPB {
+                id: snapshot.block_id,
+                rows: snapshot.row_revs.iter().map(GridRowPB::from).collect(),
+            })
+            .collect();
 
         Ok(GridPB {
             id: self.grid_id.clone(),
@@ -464,19 +681,207 @@ impl GridRevisionEditor {
         }
     }
 
+    pub async fn get_grid_sorts(&self, layout_type: &GridLayoutType) -> FlowyResult<Vec<GridSort>> {
+        let layout_rev = layout_type.clone().into();
+        let sorts = self
+            .grid_pad
+            .read()
+            .await
+            .get_sorts(Some(&layout_rev))
+            .iter()
+            .map(|sort_rev| sort_rev.as_ref().into())
+            .collect::<Vec<GridSort>>();
+        Ok(sorts)
+    }
+
+    pub async fn get_grid_calculations(&self, layout_type: &GridLayoutType) -> FlowyResult<Vec<GridCalculationPB>> {
+        let layout_rev = layout_type.clone().into();
+        self.calculation_service.get_calculations(Some(&layout_rev)).await
+    }
+
+    pub async fn get_groups(&self) -> FlowyResult<Vec<GroupPB>> {
+        self.group_service.load_groups().await
+    }
+
+    pub async fn move_group_row(&self, params: MoveGroupRowParams) -> FlowyResult<()> {
+        let cell_changeset = self.group_service.move_row_changeset(params).await?;
+        self.update_cell(cell_changeset).await
+    }
+
     pub async fn update_grid_setting(&self, params: GridSettingChangesetParams) -> FlowyResult<()> {
         let filter_changeset = GridFilterChangeset::from(&params);
+        let sort_changeset = GridSortChangeset::from(&params);
+        let group_changeset = GridGroupChangeset::from(&params);
+        let calculation_changeset = GridCalculationChangeset::from(&params);
         let _ = self
             .modify(|grid_pad| Ok(grid_pad.update_grid_setting_rev(params)?))
             .await?;
 
-        let filter_service = self.filter_service.clone();
+        // Refresh the filters before returning, so the rows read afterward are filtered by the new filters.
+        self.filter_service.apply_changeset(filter_changeset).await;
+        self.calculation_service.apply_changeset(calculation_changeset).await;
+
+        let sort_service = self.sort_service.clone();
         tokio::spawn(async move {
-            filter_service.apply_changeset(filter_changeset).await;
+            sort_service.apply_changeset(sort_changeset).await;
         });
+
+        let group_service = self.group_service.clone();
+        tokio::spawn(async move {
+            group_service.apply_changeset(group_changeset).await;
+        });
+        Ok(())
+    }
+
+    /// Add the `source_row_id` to or remove it from the cells of the back-reference field, which is
+    /// the counterpart of the relation field in the other grid.
+    pub(crate) async fn update_relation_back_references(
+        &self,
+        field_id: &str,
+        source_row_id: &str,
+        inserted_row_ids: Vec<String>,
+        deleted_row_ids: Vec<String>,
+    ) -> FlowyResult<()> {
+        let row_ids = self
+            .relation_service
+            .update_back_references(field_id, source_row_id, inserted_row_ids, deleted_row_ids)
+            .await?;
+        for row_id in row_ids {
+            let updated_field_ids = self.recompute_row(&row_id, field_id).await?;
+            self.calculation_service.did_update_row(&row_id).await;
+            self.notify_did_update_row_cells(row_id, updated_field_ids);
+        }
+        Ok(())
+    }
+
+    /// Refresh the rollups of the rows that link to the rows of the other grid through the
+    /// back-reference field, after the cells of the fields in the other grid were updated.
+    pub(crate) async fn did_update_related_rows(
+        &self,
+        field_id: &str,
+        row_ids: Vec<String>,
+        updated_field_ids: Vec<String>,
+    ) -> FlowyResult<()> {
+        for row_id in row_ids {
+            let rollup_field_ids = self
+                .relation_service
+                .refresh_rollups(&row_id, |_, type_option| {
+                    type_option.relation_field_id == field_id
+                        && updated_field_ids.contains(&type_option.target_field_id)
+                })
+                .await?;
+            if rollup_field_ids.is_empty() {
+                continue;
+            }
+
+            let mut row_updated_field_ids = rollup_field_ids.clone();
+            for rollup_field_id in rollup_field_ids.iter() {
+                for formula_field_id in self.formula_service.did_update_cell(&row_id, rollup_field_id).await? {
+                    if !row_updated_field_ids.contains(&formula_field_id) {
+                        row_updated_field_ids.push(formula_field_id);
+                    }
+                }
+            }
+            self.calculation_service.did_update_row(&row_id).await;
+            self.notify_did_update_row_cells(row_id, row_updated_field_ids);
+        }
         Ok(())
     }
 
+    /// Return the values of the field in the rows, which are aggregated by the rollups of the other grid.
+    pub(crate) async fn get_relation_values(
+        &self,
+        field_id: &str,
+        row_ids: &[String],
+    ) -> FlowyResult<Vec<FormulaValue>> {
+        self.relation_service.get_related_values(field_id, row_ids).await
+    }
+
+    /// Return the type option data that should be saved. Return error if the type option data of the
+    /// formula field contains an invalid expression, or the formula would reference itself through
+    /// the other formulas, or the relation or the rollup is invalid.
+    async fn prepare_type_option_data(
+        &self,
+        field_id: &str,
+        field_type: &FieldType,
+        type_option_data: Vec<u8>,
+    ) -> FlowyResult<Vec<u8>> {
+        match field_type {
+            FieldType::Formula => {
+                let type_option = FormulaTypeOption::from_protobuf_bytes(Bytes::from(type_option_data.clone()));
+                let _ = self.formula_service.validate_formula(field_id, &type_option).await?;
+                Ok(type_option_data)
+            }
+            FieldType::Relation => {
+                let type_option = RelationTypeOption::from_protobuf_bytes(Bytes::from(type_option_data));
+                let type_option = self
+                    .relation_service
+                    .prepare_relation_field(field_id, type_option)
+                    .await?;
+                Ok(type_option.protobuf_bytes().to_vec())
+            }
+            FieldType::Rollup => {
+                let type_option = RollupTypeOption::from_protobuf_bytes(Bytes::from(type_option_data.clone()));
+                let _ = self.relation_service.validate_rollup(&type_option).await?;
+                Ok(type_option_data)
+            }
+            _ => Ok(type_option_data),
+        }
+    }
+
+    async fn did_update_field_type_option(&self, field_id: &str) -> FlowyResult<()> {
+        if let Some(field_rev) = self.get_field_rev(field_id).await {
+            if FieldType::from(field_rev.field_type_rev).is_relation() {
+                let _ = self.relation_service.did_update_relation_field(&field_rev).await?;
+            }
+        }
+        let _ = self.did_update_dependent_fields(field_id).await?;
+        self.filter_service.did_update_field(field_id).await;
+        self.calculation_service.did_update_field().await;
+        Ok(())
+    }
+
+    /// Recompute the cells of the rollups and the formulas that depend on the field in all the rows.
+    async fn did_update_dependent_fields(&self, field_id: &str) -> FlowyResult<()> {
+        let rollup_field_ids = self.relation_service.did_update_field(field_id).await?;
+        let _ = self.formula_service.did_update_field(field_id).await?;
+        for rollup_field_id in rollup_field_ids.iter().filter(|id| id.as_str() != field_id) {
+            let _ = self.formula_service.did_update_field(rollup_field_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Recompute the rollup cells and the formula cells of the row that depend on the updated cell.
+    /// Return the ids of the fields whose cells were changed, including the updated one.
+    async fn recompute_row(&self, row_id: &str, field_id: &str) -> FlowyResult<Vec<String>> {
+        let mut updated_field_ids = vec![field_id.to_owned()];
+        let rollup_field_ids = self
+            .relation_service
+            .refresh_rollups(row_id, |_, type_option| type_option.relation_field_id == field_id)
+            .await?;
+        updated_field_ids.extend(rollup_field_ids);
+
+        for updated_field_id in updated_field_ids.clone() {
+            for formula_field_id in self.formula_service.did_update_cell(row_id, &updated_field_id).await? {
+                if !updated_field_ids.contains(&formula_field_id) {
+                    updated_field_ids.push(formula_field_id);
+                }
+            }
+        }
+        Ok(updated_field_ids)
+    }
+
+    fn notify_did_update_row_cells(&self, row_id: String, field_ids: Vec<String>) {
+        let sort_service = self.sort_service.clone();
+        let group_service = self.group_service.clone();
+        tokio::spawn(async move {
+            for field_id in field_ids {
+                let _ = sort_service.did_update_cell(&field_id).await;
+                let _ = group_service.did_update_cell(&row_id, &field_id).await;
+            }
+        });
+    }
+
     pub async fn grid_block_snapshots(&self, block_ids: Option<Vec<String>>) -> FlowyResult<Vec<GridBlockSnapshot>> {
         let block_ids = match block_ids {
             None => self
@@ -493,6 +898,21 @@ impl GridRevisionEditor {
         Ok(snapshots)
     }
 
+    /// Replace the clock that is used to evaluate the relative date filters, e.g. today.
+    pub async fn set_filter_clock(&self, clock: Arc<dyn FilterClock>) {
+        self.filter_service.set_clock(clock).await;
+    }
+
+    /// Return the snapshots of the blocks that only contain the rows satisfying the filters of the current layout.
+    pub async fn filtered_grid_block_snapshots(
+        &self,
+        block_ids: Option<Vec<String>>,
+    ) -> FlowyResult<Vec<GridBlockSnapshot>> {
+        let mut snapshots = self.grid_block_snapshots(block_ids).await?;
+        self.filter_service.filter_block_snapshots(&mut snapshots).await;
+        Ok(snapshots)
+    }
+
     pub async fn move_item(&self, params: MoveItemParams) -> FlowyResult<()> {
         match params.ty {
             MoveItemTypePB::MoveField => {
@@ -507,6 +927,10 @@ impl GridRevisionEditor {
         let _ = self
             .modify(|grid_pad| Ok(grid_pad.move_field(field_id, from as usize, to as usize)?))
             .await?;
+        self.notify_did_move_grid_field(field_id).await
+    }
+
+    async fn notify_did_move_grid_field(&self, field_id: &str) -> FlowyResult<()> {
         if let Some((index, field_rev)) = self.grid_pad.read().await.get_field_rev(field_id) {
             let delete_field_order = GridFieldIdPB::from(field_id);
             let insert_field = IndexFieldPB::from_field_rev(field_rev, index);
@@ -527,10 +951,270 @@ impl GridRevisionEditor {
         Ok(())
     }
 
+    /// Revert the latest change of the fields, the rows or the cells. The changes that can't be
+    /// reverted anymore are skipped, e.g. the row was deleted by the remote revision.
+    pub async fn undo(&self) -> FlowyResult<GridHistoryStatePB> {
+        while let Some(entry) = self.last_undo_entry().await {
+            if self.apply_history_entry(entry, HistoryAction::Undo).await? {
+                break;
+            }
+        }
+        Ok(self.get_history_state().await)
+    }
+
+    /// Reapply the latest reverted change. The changes that can't be reapplied anymore are skipped.
+    pub async fn redo(&self) -> FlowyResult<GridHistoryStatePB> {
+        while let Some(entry) = self.last_redo_entry().await {
+            if self.apply_history_entry(entry, HistoryAction::Redo).await? {
+                break;
+            }
+        }
+        Ok(self.get_history_state().await)
+    }
+
+    pub async fn get_history_state(&self) -> GridHistoryStatePB {
+        GridHistoryStatePB {
+            can_undo: self.last_undo_entry().await.is_some(),
+            can_redo: self.last_redo_entry().await.is_some(),
+        }
+    }
+
+    /// The changes of the fields are recorded by the grid, and the changes of the rows are recorded
+    /// by the blocks. Return the entry of the latest change among them.
+    async fn last_undo_entry(&self) -> Option<GridHistoryEntry> {
+        let grid_entry = self
+            .grid_pad
+            .read()
+            .await
+            .history()
+            .last_undo()
+            .cloned()
+            .map(GridHistoryEntry::Grid);
+        let block_entry = self
+            .block_manager
+            .last_undo()
+            .await
+            .map(|(block_id, item)| GridHistoryEntry::Block(block_id, item));
+        match (grid_entry, block_entry) {
+            (Some(grid_entry), Some(block_entry)) if block_entry.seq() > grid_entry.seq() => Some(block_entry),
+            (Some(grid_entry), _) => Some(grid_entry),
+            (None, block_entry) => block_entry,
+        }
+    }
+
+    /// Return the entry of the latest reverted change, which is the earliest one among the reverted
+    /// changes. Return None if any change was made after reverting, the reverted changes can't be
+    /// reapplied anymore.
+    async fn last_redo_entry(&self) -> Option<GridHistoryEntry> {
+        let grid_entry = self
+            .grid_pad
+            .read()
+            .await
+            .history()
+            .last_redo()
+            .cloned()
+            .map(GridHistoryEntry::Grid);
+        let block_entry = self
+            .block_manager
+            .last_redo()
+            .await
+            .map(|(block_id, item)| GridHistoryEntry::Block(block_id, item));
+        let redo_entry = match (grid_entry, block_entry) {
+            (Some(grid_entry), Some(block_entry)) if block_entry.seq() < grid_entry.seq() => Some(block_entry),
+            (Some(grid_entry), _) => Some(grid_entry),
+            (None, block_entry) => block_entry,
+        }?;
+
+        match self.last_undo_entry().await {
+            Some(undo_entry) if undo_entry.seq() > redo_entry.seq() => None,
+            _ => Some(redo_entry),
+        }
+    }
+
+    /// Return false if the change can't be applied anymore.
+    async fn apply_history_entry(&self, entry: GridHistoryEntry, action: HistoryAction) -> FlowyResult<bool> {
+        match entry {
+            GridHistoryEntry::Grid(item) => self.apply_field_history(item.operation, action).await,
+            GridHistoryEntry::Block(block_id, item) => self.apply_row_history(&block_id, item.operation, action).await,
+        }
+    }
+
+    async fn apply_field_history(&self, operation: FieldHistoryOperation, action: HistoryAction) -> FlowyResult<bool> {
+        let deleted_field_rev = match &operation {
+            FieldHistoryOperation::DeleteField { field_id } => self.get_field_rev(field_id).await,
+            _ => None,
+        };
+
+        let mut is_applied = false;
+        let _ = self
+            .modify(|grid_pad| {
+                let changeset = match action {
+                    HistoryAction::Undo => grid_pad.undo()?,
+                    HistoryAction::Redo => grid_pad.redo()?,
+                };
+                is_applied = changeset.is_some();
+                Ok(changeset)
+            })
+            .await?;
+        if !is_applied {
+            return Ok(false);
+        }
+
+        match operation {
+            FieldHistoryOperation::InsertField { field_rev, .. } => {
+                let _ = self.notify_did_insert_grid_field(&field_rev.id).await?;
+                let _ = self.did_update_field_type_option(&field_rev.id).await?;
+            }
+            FieldHistoryOperation::DeleteField { field_id } => {
+                let _ = self.did_delete_field(&field_id, deleted_field_rev).await?;
+            }
+            FieldHistoryOperation::ReplaceField { field_rev } => {
+                let _ = self.notify_did_update_grid_field(&field_rev.id).await?;
+                let _ = self.did_update_field_type_option(&field_rev.id).await?;
+            }
+            FieldHistoryOperation::MoveField { field_id, .. } => {
+                let _ = self.notify_did_move_grid_field(&field_id).await?;
+            }
+        }
+        Ok(true)
+    }
+
+    async fn apply_row_history(
+        &self,
+        block_id: &str,
+        operation: RowHistoryOperation,
+        action: HistoryAction,
+    ) -> FlowyResult<bool> {
+        let old_row_rev = match &operation {
+            RowHistoryOperation::DeleteRows { row_ids } => {
+                for row_id in row_ids {
+                    let _ = self.relation_service.will_delete_row(row_id).await?;
+                }
+                None
+            }
+            RowHistoryOperation::UpdateRow { row_id, .. } => self.block_manager.get_row_rev(row_id).await?,
+            _ => None,
+        };
+
+        let is_applied = match action {
+            HistoryAction::Undo => {
+                self.block_manager
+                    .undo(block_id, &operation, make_row_from_row_rev)
+                    .await?
+            }
+            HistoryAction::Redo => {
+                self.block_manager
+                    .redo(block_id, &operation, make_row_from_row_rev)
+                    .await?
+            }
+        };
+        if !is_applied {
+            return Ok(false);
+        }
+
+        match operation {
+            RowHistoryOperation::InsertRows { row_revs } => {
+                let _ = self.did_update_block_row_count(block_id).await?;
+                let row_ids = row_revs
+                    .iter()
+                    .map(|(_, row_rev)| row_rev.id.clone())
+                    .collect::<Vec<String>>();
+                let _ = self.relation_service.did_create_rows(&row_ids).await?;
+                let _ = self.formula_service.did_create_rows(&row_ids).await?;
+                let _ = self.group_service.did_create_rows(&row_ids).await?;
+                for row_id in row_ids.iter() {
+                    self.calculation_service.did_update_row(row_id).await;
+                }
+            }
+            RowHistoryOperation::DeleteRows { row_ids } => {
+                let _ = self.did_update_block_row_count(block_id).await?;
+                self.group_service.did_delete_rows(&row_ids).await;
+                self.calculation_service.did_delete_rows(row_ids).await;
+            }
+            RowHistoryOperation::MoveRow { .. } => {}
+            RowHistoryOperation::UpdateRow { row_id, cells, .. } => {
+                let mut updated_field_ids: Vec<String> = vec![];
+                for field_id in cells.keys() {
+                    if let Some(field_rev) = self.get_field_rev(field_id).await {
+                        if FieldType::from(field_rev.field_type_rev).is_relation() {
+                            let old_cell_rev = old_row_rev.as_ref().and_then(|row_rev| row_rev.cells.get(field_id));
+                            let _ = self
+                                .relation_service
+                                .did_update_relation_cell(&row_id, &field_rev, old_cell_rev)
+                                .await?;
+                        }
+                    }
+
+                    for updated_field_id in self.recompute_row(&row_id, field_id).await? {
+                        if !updated_field_ids.contains(&updated_field_id) {
+                            updated_field_ids.push(updated_field_id);
+                        }
+                    }
+                }
+                let _ = self
+                    .relation_service
+                    .did_update_cells(&row_id, &updated_field_ids)
+                    .await?;
+                self.calculation_service.did_update_row(&row_id).await;
+                self.notify_did_update_row_cells(row_id, updated_field_ids);
+            }
+        }
+        Ok(true)
+    }
+
+    async fn did_update_block_row_count(&self, block_id: &str) -> FlowyResult<()> {
+        let row_count = self.block_manager.get_editor(block_id).await?.number_of_rows().await;
+        let changeset = GridBlockMetaRevisionChangeset::from_row_count(block_id, row_count);
+        self.update_block(changeset).await
+    }
+
     pub async fn delta_bytes(&self) -> Bytes {
         self.grid_pad.read().await.delta_bytes()
     }
 
+    /// Return the text of the cells that is indexed by the search. The cells of each row are
+    /// separated by tabs, and the rows are separated by newlines.
+    pub async fn plain_text(&self) -> FlowyResult<String> {
+        let field_revs = self.get_field_revs(None).await?;
+        let block_snapshots = self.grid_block_snapshots(None).await?;
+        let lines = block_snapshots
+            .iter()
+            .flat_map(|snapshot| snapshot.row_revs.iter())
+            .map(|row_rev| {
+                field_revs
+                    .iter()
+                    .flat_map(|field_rev| {
+                        let cell_rev = row_rev.cells.get(&field_rev.id)?;
+                        let content = decode_cell_data_to_string(cell_rev.data.clone(), field_rev);
+                        Some(content).filter(|content| !content.is_empty())
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\t")
+            })
+            .collect::<Vec<String>>();
+        Ok(lines.join("\n"))
+    }
+
+    /// Subscribe the revisions of the grid and its blocks.
+    pub fn subscribe_revisions(&self) -> Vec<broadcast::Receiver<Revision>> {
+        let mut receivers = vec![self.rev_manager.subscribe_revisions()];
+        receivers.extend(self.block_manager.subscribe_revisions());
+        receivers
+    }
+
+    /// Export the fields and the rows of the grid as CSV. The rows are in the order that they're
+    /// displayed.
+    pub async fn export_csv(&self) -> FlowyResult<String> {
+        let field_revs = self.get_field_revs(None).await?;
+        let mut block_snapshots = self.grid_block_snapshots(None).await?;
+        self.sort_service.sort_block_snapshots(&mut block_snapshots).await;
+        let row_revs = block_snapshots
+            .into_iter()
+            .flat_map(|snapshot| snapshot.row_revs)
+            .collect::<Vec<Arc<RowRevision>>>();
+        Ok(export_csv(&field_revs, &row_revs))
+    }
+
     pub async fn duplicate_grid(&self) -> FlowyResult<BuildGridContext> {
         let grid_pad = self.grid_pad.read().await;
         let original_blocks = grid_pad.get_block_meta_revs();
@@ -632,6 +1316,26 @@ impl GridRevisionEditor {
     }
 }
 
+#[derive(Clone, Copy)]
+enum HistoryAction {
+    Undo,
+    Redo,
+}
+
+enum GridHistoryEntry {
+    Grid(GridHistoryItem<FieldHistoryOperation>),
+    Block(String, GridHistoryItem<RowHistoryOperation>),
+}
+
+impl GridHistoryEntry {
+    fn seq(&self) -> i64 {
+        match self {
+            GridHistoryEntry::Grid(item) => item.seq,
+            GridHistoryEntry::Block(_, item) => item.seq,
+        }
+    }
+}
+
 #[cfg(feature = "flowy_unit_test")]
 impl GridRevisionEditor {
     pub fn rev_manager(&self) -> Arc<RevisionManager> {
@@ -0,0 +1,16 @@
+/// CellGroup describes a group that the cells are bucketed into. The id of the group is generated from
+/// the cell data, so the cells that have the same group id are put into the same group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellGroup {
+    pub id: String,
+    pub desc: String,
+}
+
+impl CellGroup {
+    pub fn new<T: ToString>(id: T, desc: T) -> Self {
+        Self {
+            id: id.to_string(),
+            desc: desc.to_string(),
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use crate::dart_notification::{send_dart_notification, GridNotification};
+use crate::entities::{
+    CellChangesetPB, FieldType, GridRowPB, GroupPB, GroupRowsChangesetPB, MoveGroupRowParams, RepeatedGroupPB,
+};
+use crate::services::block_manager::GridBlockManager;
+use crate::services::cell::{AnyCellData, CellGroupOperation};
+use crate::services::field::{CheckboxTypeOption, DateTypeOption, MultiSelectTypeOption, SingleSelectTypeOptionPB};
+use crate::services::group::CellGroup;
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_grid_data_model::revision::{FieldRevision, GridLayoutRevision, RowRevision};
+use flowy_sync::client_grid::GridRevisionPad;
+use flowy_sync::entities::grid::GridSettingChangesetParams;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub(crate) struct GridGroupService {
+    grid_id: String,
+    grid_pad: Arc<RwLock<GridRevisionPad>>,
+    block_manager: Arc<GridBlockManager>,
+    /// The ids of the groups that each row belongs to. It's used to find out which groups should be
+    /// updated after the cell of the group field was changed.
+    group_ids_by_row_id: RwLock<HashMap<String, Vec<String>>>,
+}
+
+impl GridGroupService {
+    pub async fn new(grid_pad: Arc<RwLock<GridRevisionPad>>, block_manager: Arc<GridBlockManager>) -> Self {
+        let grid_id = grid_pad.read().await.grid_id();
+        let service = Self {
+            grid_id,
+            grid_pad,
+            block_manager,
+            group_ids_by_row_id: RwLock::new(HashMap::new()),
+        };
+        // The groups of the rows are needed to move the rows between the groups after their cells
+        // were changed, even if the Board layout hasn't loaded the groups yet.
+        if let Err(e) = service.load_groups().await {
+            tracing::error!("Load groups failed: {:?}", e);
+        }
+        service
+    }
+
+    /// Bucket the rows of all the blocks into groups. The group that contains the rows that don't
+    /// belong to any group is always the first one.
+    pub async fn load_groups(&self) -> FlowyResult<Vec<GroupPB>> {
+        let group_field = match self.group_field().await {
+            None => return Ok(vec![]),
+            Some(group_field) => group_field,
+        };
+
+        let mut groups = vec![GroupPB {
+            group_id: group_field.field_rev.id.clone(),
+            desc: format!("No {}", group_field.field_rev.name),
+            rows: vec![],
+        }];
+        groups.extend(group_field.operation.default_groups().into_iter().map(|group| GroupPB {
+            group_id: group.id,
+            desc: group.desc,
+            rows: vec![],
+        }));
+        let default_group_count = groups.len();
+
+        let mut group_ids_by_row_id = HashMap::new();
+        for snapshot in self.block_manager.get_block_snapshots(None).await? {
+            for row_rev in snapshot.row_revs.iter() {
+                let mut group_ids = vec![];
+                for cell_group in group_field.cell_groups(row_rev) {
+                    let index = match groups.iter().position(|group| group.group_id == cell_group.id) {
+                        Some(index) => index,
+                        None => {
+                            groups.push(GroupPB {
+                                group_id: cell_group.id.clone(),
+                                desc: cell_group.desc,
+                                rows: vec![],
+                            });
+                            groups.len() - 1
+                        }
+                    };
+                    groups[index].rows.push(GridRowPB::from(row_rev));
+                    group_ids.push(cell_group.id);
+                }
+
+                if group_ids.is_empty() {
+                    groups[0].rows.push(GridRowPB::from(row_rev));
+                    group_ids.push(group_field.field_rev.id.clone());
+                }
+                group_ids_by_row_id.insert(row_rev.id.clone(), group_ids);
+            }
+        }
+
+        // The groups that are generated from the cells, for example, the month of the date, are
+        // ordered by their ids.
+        groups[default_group_count..].sort_by(|left, right| left.group_id.cmp(&right.group_id));
+        *self.group_ids_by_row_id.write().await = group_ids_by_row_id;
+        Ok(groups)
+    }
+
+    /// Return the changeset that moves the row from one group to another by rewriting the cell of
+    /// the group field.
+    pub async fn move_row_changeset(&self, params: MoveGroupRowParams) -> FlowyResult<CellChangesetPB> {
+        let group_field = self
+            .group_field()
+            .await
+            .ok_or_else(|| FlowyError::internal().context("There is no group field in this grid"))?;
+        let field_id = group_field.field_rev.id.clone();
+        let row_rev =
+            self.block_manager.get_row_rev(&params.row_id).await?.ok_or_else(|| {
+                FlowyError::record_not_found().context(format!("Can't find the row: {}", params.row_id))
+            })?;
+
+        let any_cell_data = row_rev
+            .cells
+            .get(&field_id)
+            .and_then(|cell_rev| AnyCellData::try_from(cell_rev).ok());
+        // The id of the group that contains the rows that don't belong to any group is the field id.
+        let group_id = |group_id: &str| -> Option<String> {
+            if group_id == field_id {
+                None
+            } else {
+                Some(group_id.to_owned())
+            }
+        };
+        let from_group_id = group_id(&params.from_group_id);
+        let to_group_id = group_id(&params.to_group_id);
+        let content = group_field.operation.move_group_changeset(
+            any_cell_data,
+            from_group_id.as_deref(),
+            to_group_id.as_deref(),
+        )?;
+
+        Ok(CellChangesetPB {
+            grid_id: params.grid_id,
+            row_id: params.row_id,
+            field_id,
+            content: Some(content),
+        })
+    }
+
+    /// Move the row into its new groups if the updated cell belongs to the group field.
+    pub async fn did_update_cell(&self, row_id: &str, field_id: &str) -> FlowyResult<()> {
+        match self.group_field().await {
+            Some(group_field) if group_field.field_rev.id == field_id => {
+                self.regroup_rows(&group_field, &[row_id.to_owned()]).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Insert the new rows into the groups that their cells belong to.
+    pub async fn did_create_rows(&self, row_ids: &[String]) -> FlowyResult<()> {
+        match self.group_field().await {
+            None => Ok(()),
+            Some(group_field) => self.regroup_rows(&group_field, row_ids).await,
+        }
+    }
+
+    /// Remove the deleted rows from the groups that they belonged to.
+    pub async fn did_delete_rows(&self, row_ids: &[String]) {
+        let mut deleted_row_ids_by_group_id: HashMap<String, Vec<String>> = HashMap::new();
+        let mut group_ids_by_row_id = self.group_ids_by_row_id.write().await;
+        for row_id in row_ids {
+            for group_id in group_ids_by_row_id.remove(row_id).unwrap_or_default() {
+                deleted_row_ids_by_group_id
+                    .entry(group_id)
+                    .or_insert_with(Vec::new)
+                    .push(row_id.clone());
+            }
+        }
+        drop(group_ids_by_row_id);
+
+        for (group_id, deleted_row_ids) in deleted_row_ids_by_group_id {
+            self.notify_did_update_group(GroupRowsChangesetPB::delete(group_id, deleted_row_ids));
+        }
+    }
+
+    async fn regroup_rows(&self, group_field: &GroupField, row_ids: &[String]) -> FlowyResult<()> {
+        for row_id in row_ids {
+            let row_rev = match self.block_manager.get_row_rev(row_id).await? {
+                None => continue,
+                Some(row_rev) => row_rev,
+            };
+
+            let mut group_ids = group_field
+                .cell_groups(&row_rev)
+                .into_iter()
+                .map(|group| group.id)
+                .collect::<Vec<String>>();
+            if group_ids.is_empty() {
+                group_ids.push(group_field.field_rev.id.clone());
+            }
+
+            let old_group_ids = self
+                .group_ids_by_row_id
+                .write()
+                .await
+                .insert(row_id.to_owned(), group_ids.clone())
+                .unwrap_or_default();
+
+            for group_id in old_group_ids.iter() {
+                if !group_ids.contains(group_id) {
+                    let changeset = GroupRowsChangesetPB::delete(group_id.clone(), vec![row_id.to_owned()]);
+                    self.notify_did_update_group(changeset);
+                }
+            }
+
+            for group_id in group_ids {
+                if !old_group_ids.contains(&group_id) {
+                    let changeset = GroupRowsChangesetPB::insert(group_id, vec![GridRowPB::from(&row_rev)]);
+                    self.notify_did_update_group(changeset);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The groups are reloaded if the group setting of the Board layout was changed.
+    pub async fn apply_changeset(&self, changeset: GridGroupChangeset) {
+        if !changeset.is_changed() {
+            return;
+        }
+
+        match self.load_groups().await {
+            Ok(groups) => {
+                send_dart_notification(&self.grid_id, GridNotification::DidUpdateGroups)
+                    .payload(RepeatedGroupPB::from(groups))
+                    .send();
+            }
+            Err(e) => tracing::error!("Load groups failed: {:?}", e),
+        }
+    }
+
+    /// The Board layout uses the first group setting to bucket the rows. If there is no group
+    /// setting, the first single select field is used.
+    async fn group_field(&self) -> Option<GroupField> {
+        let grid_pad = self.grid_pad.read().await;
+        let group_field_rev = grid_pad
+            .get_groups(Some(&GridLayoutRevision::Board))
+            .iter()
+            .flat_map(|group_rev| group_rev.field_id.as_ref())
+            .flat_map(|field_id| grid_pad.get_field_rev(field_id).map(|(_, field_rev)| field_rev.clone()))
+            .find(|field_rev| is_groupable(field_rev))
+            .or_else(|| {
+                grid_pad
+                    .fields()
+                    .iter()
+                    .find(|field_rev| FieldType::from(field_rev.field_type_rev) == FieldType::SingleSelect)
+                    .cloned()
+            })?;
+
+        let operation = make_cell_group_operation(&group_field_rev)?;
+        Some(GroupField {
+            field_rev: group_field_rev,
+            operation,
+        })
+    }
+
+    fn notify_did_update_group(&self, changeset: GroupRowsChangesetPB) {
+        send_dart_notification(&self.grid_id, GridNotification::DidUpdateGroupRows)
+            .payload(changeset)
+            .send();
+    }
+}
+
+struct GroupField {
+    field_rev: Arc<FieldRevision>,
+    operation: Box<dyn CellGroupOperation + Send + Sync>,
+}
+
+impl GroupField {
+    // Return empty if the cell is empty or the cell data was created by another FieldType.
+    fn cell_groups(&self, row_rev: &RowRevision) -> Vec<CellGroup> {
+        let field_type = FieldType::from(self.field_rev.field_type_rev);
+        row_rev
+            .cells
+            .get(&self.field_rev.id)
+            .and_then(|cell_rev| AnyCellData::try_from(cell_rev).ok())
+            .filter(|any_cell_data| any_cell_data.field_type == field_type)
+            .and_then(|any_cell_data| self.operation.cell_groups(any_cell_data).ok())
+            .unwrap_or_default()
+    }
+}
+
+fn is_groupable(field_rev: &FieldRevision) -> bool {
+    matches!(
+        FieldType::from(field_rev.field_type_rev),
+        FieldType::SingleSelect | FieldType::MultiSelect | FieldType::Checkbox | FieldType::DateTime
+    )
+}
+
+fn make_cell_group_operation(field_rev: &FieldRevision) -> Option<Box<dyn CellGroupOperation + Send + Sync>> {
+    let field_type_rev = field_rev.field_type_rev;
+    let operation: Box<dyn CellGroupOperation + Send + Sync> = match FieldType::from(field_type_rev) {
+        FieldType::SingleSelect => {
+            Box::new(field_rev.get_type_option_entry::<SingleSelectTypeOptionPB>(field_type_rev)?)
+        }
+        FieldType::MultiSelect => Box::new(field_rev.get_type_option_entry::<MultiSelectTypeOption>(field_type_rev)?),
+        FieldType::Checkbox => Box::new(field_rev.get_type_option_entry::<CheckboxTypeOption>(field_type_rev)?),
+        FieldType::DateTime => Box::new(field_rev.get_type_option_entry::<DateTypeOption>(field_type_rev)?),
+        _ => return None,
+    };
+    Some(operation)
+}
+
+pub struct GridGroupChangeset {
+    is_changed: bool,
+}
+
+impl GridGroupChangeset {
+    fn is_changed(&self) -> bool {
+        self.is_changed
+    }
+}
+
+impl std::convert::From<&GridSettingChangesetParams> for GridGroupChangeset {
+    fn from(params: &GridSettingChangesetParams) -> Self {
+        GridGroupChangeset {
+            is_changed: params.is_group_changed() && params.layout_type == GridLayoutRevision::Board,
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::services::cell::{AnyCellData, CellData, CellGroupOperation};
+use crate::services::field::{CheckboxCellData, CheckboxTypeOption, CHECK, UNCHECK};
+use crate::services::group::CellGroup;
+use flowy_error::FlowyResult;
+
+impl CellGroupOperation for CheckboxTypeOption {
+    fn default_groups(&self) -> Vec<CellGroup> {
+        vec![CellGroup::new(CHECK, "Checked"), CellGroup::new(UNCHECK, "Unchecked")]
+    }
+
+    fn cell_groups(&self, any_cell_data: AnyCellData) -> FlowyResult<Vec<CellGroup>> {
+        let cell_data: CellData<CheckboxCellData> = any_cell_data.into();
+        let checkbox_cell_data = cell_data.try_into_inner()?;
+        let mut groups = self.default_groups();
+        // The empty cell is treated as unchecked.
+        let group = if checkbox_cell_data.is_check() {
+            groups.remove(0)
+        } else {
+            groups.remove(1)
+        };
+        Ok(vec![group])
+    }
+
+    fn move_group_changeset(
+        &self,
+        _any_cell_data: Option<AnyCellData>,
+        _from_group_id: Option<&str>,
+        to_group_id: Option<&str>,
+    ) -> FlowyResult<String> {
+        Ok(to_group_id.unwrap_or(UNCHECK).to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::FieldType;
+    use crate::services::cell::{AnyCellData, CellGroupOperation};
+    use crate::services::field::{CheckboxTypeOption, CHECK, UNCHECK};
+
+    #[test]
+    fn checkbox_group_test() {
+        let type_option = CheckboxTypeOption::default();
+        let group_id = |s: &str| {
+            let groups = type_option
+                .cell_groups(AnyCellData::new(s.to_owned(), FieldType::Checkbox))
+                .unwrap();
+            assert_eq!(groups.len(), 1);
+            groups[0].id.clone()
+        };
+
+        assert_eq!(group_id(CHECK), CHECK);
+        assert_eq!(group_id(UNCHECK), UNCHECK);
+        assert_eq!(group_id(""), UNCHECK);
+    }
+}
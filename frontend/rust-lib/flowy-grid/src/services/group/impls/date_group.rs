@@ -0,0 +1,84 @@
+use crate::services::cell::{AnyCellData, CellData, CellGroupOperation};
+use crate::services::field::{DateCellChangesetPB, DateTimestamp, DateTypeOption};
+use crate::services::group::CellGroup;
+use chrono::{NaiveDate, NaiveDateTime};
+use flowy_error::{internal_error, FlowyError, FlowyResult};
+
+// The dates are grouped by month, the id of the group looks like 2022/03.
+const DATE_GROUP_ID_FORMAT: &str = "%Y/%m";
+const DATE_GROUP_DESC_FORMAT: &str = "%b %Y";
+
+impl CellGroupOperation for DateTypeOption {
+    fn default_groups(&self) -> Vec<CellGroup> {
+        vec![]
+    }
+
+    fn cell_groups(&self, any_cell_data: AnyCellData) -> FlowyResult<Vec<CellGroup>> {
+        let cell_data: CellData<DateTimestamp> = any_cell_data.into();
+        let timestamp: i64 = cell_data.try_into_inner()?.into();
+        if timestamp == 0 {
+            return Ok(vec![]);
+        }
+
+        let native = NaiveDateTime::from_timestamp(timestamp, 0);
+        let group = CellGroup::new(
+            native.format(DATE_GROUP_ID_FORMAT).to_string(),
+            native.format(DATE_GROUP_DESC_FORMAT).to_string(),
+        );
+        Ok(vec![group])
+    }
+
+    /// Moving the cell to another group will set the date to the first day of the group's month.
+    fn move_group_changeset(
+        &self,
+        _any_cell_data: Option<AnyCellData>,
+        _from_group_id: Option<&str>,
+        to_group_id: Option<&str>,
+    ) -> FlowyResult<String> {
+        let date = match to_group_id {
+            None => None,
+            Some(group_id) => {
+                let first_day = format!("{}/01", group_id);
+                let timestamp = NaiveDate::parse_from_str(&first_day, "%Y/%m/%d")
+                    .map_err(|_| FlowyError::invalid_data().context(format!("Invalid date group: {}", group_id)))?
+                    .and_hms(0, 0, 0)
+                    .timestamp();
+                Some(timestamp.to_string())
+            }
+        };
+        let changeset = DateCellChangesetPB { date, time: None };
+        serde_json::to_string(&changeset).map_err(internal_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::FieldType;
+    use crate::services::cell::{AnyCellData, CellGroupOperation};
+    use crate::services::field::{DateCellChangesetPB, DateTypeOption};
+
+    #[test]
+    fn date_group_by_month_test() {
+        let type_option = DateTypeOption::default();
+        let groups = type_option
+            .cell_groups(AnyCellData::new("1647251762".to_owned(), FieldType::DateTime))
+            .unwrap();
+        assert_eq!(groups[0].id, "2022/03");
+        assert_eq!(groups[0].desc, "Mar 2022");
+
+        let groups = type_option
+            .cell_groups(AnyCellData::new("".to_owned(), FieldType::DateTime))
+            .unwrap();
+        assert!(groups.is_empty());
+    }
+
+    #[test]
+    fn date_group_move_test() {
+        let type_option = DateTypeOption::default();
+        let changeset = type_option.move_group_changeset(None, None, Some("2022/04")).unwrap();
+        let changeset: DateCellChangesetPB = serde_json::from_str(&changeset).unwrap();
+        assert_eq!(changeset.date_timestamp(), Some(1648771200));
+
+        assert!(type_option.move_group_changeset(None, None, Some("abc")).is_err());
+    }
+}
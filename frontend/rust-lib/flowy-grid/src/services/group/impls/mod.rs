@@ -0,0 +1,7 @@
+mod checkbox_group;
+mod date_group;
+mod select_option_group;
+
+pub use checkbox_group::*;
+pub use date_group::*;
+pub use select_option_group::*;
@@ -0,0 +1,133 @@
+use crate::services::cell::{AnyCellData, CellData, CellGroupOperation};
+use crate::services::field::{
+    MultiSelectTypeOption, SelectOptionCellChangeset, SelectOptionIds, SelectOptionOperation, SingleSelectTypeOptionPB,
+};
+use crate::services::group::CellGroup;
+use flowy_error::FlowyResult;
+
+impl CellGroupOperation for SingleSelectTypeOptionPB {
+    fn default_groups(&self) -> Vec<CellGroup> {
+        select_option_groups(self)
+    }
+
+    fn cell_groups(&self, any_cell_data: AnyCellData) -> FlowyResult<Vec<CellGroup>> {
+        let mut groups = select_option_cell_groups(self, any_cell_data.into());
+        groups.truncate(1);
+        Ok(groups)
+    }
+
+    fn move_group_changeset(
+        &self,
+        _any_cell_data: Option<AnyCellData>,
+        _from_group_id: Option<&str>,
+        to_group_id: Option<&str>,
+    ) -> FlowyResult<String> {
+        let changeset = match to_group_id {
+            None => SelectOptionCellChangeset {
+                insert_option_id: None,
+                delete_option_id: None,
+            },
+            Some(option_id) => SelectOptionCellChangeset::from_insert(option_id),
+        };
+        Ok(changeset.to_str())
+    }
+}
+
+impl CellGroupOperation for MultiSelectTypeOption {
+    fn default_groups(&self) -> Vec<CellGroup> {
+        select_option_groups(self)
+    }
+
+    fn cell_groups(&self, any_cell_data: AnyCellData) -> FlowyResult<Vec<CellGroup>> {
+        Ok(select_option_cell_groups(self, any_cell_data.into()))
+    }
+
+    fn move_group_changeset(
+        &self,
+        any_cell_data: Option<AnyCellData>,
+        from_group_id: Option<&str>,
+        to_group_id: Option<&str>,
+    ) -> FlowyResult<String> {
+        let option_ids = match any_cell_data {
+            None => vec![],
+            Some(any_cell_data) => {
+                let cell_data: CellData<SelectOptionIds> = any_cell_data.into();
+                cell_data
+                    .try_into_inner()
+                    .map(|ids| ids.into_inner())
+                    .unwrap_or_default()
+            }
+        };
+
+        // Inserting an option that is already selected will unselect it, so skip the insert if the
+        // cell is already in the target group.
+        let insert_option_id = to_group_id
+            .filter(|option_id| !option_ids.iter().any(|id| id == option_id))
+            .map(|option_id| option_id.to_owned());
+        let changeset = SelectOptionCellChangeset {
+            insert_option_id,
+            delete_option_id: from_group_id.map(|option_id| option_id.to_owned()),
+        };
+        Ok(changeset.to_str())
+    }
+}
+
+fn select_option_groups<T: SelectOptionOperation>(type_option: &T) -> Vec<CellGroup> {
+    type_option
+        .options()
+        .iter()
+        .map(|option| CellGroup::new(&option.id, &option.name))
+        .collect()
+}
+
+// The option that was deleted from the type option is ignored.
+fn select_option_cell_groups<T: SelectOptionOperation>(
+    type_option: &T,
+    cell_data: CellData<SelectOptionIds>,
+) -> Vec<CellGroup> {
+    match cell_data.try_into_inner() {
+        Ok(option_ids) => option_ids
+            .iter()
+            .flat_map(|option_id| type_option.options().iter().find(|option| &option.id == option_id))
+            .map(|option| CellGroup::new(&option.id, &option.name))
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::FieldType;
+    use crate::services::cell::{AnyCellData, CellGroupOperation};
+    use crate::services::field::{
+        MultiSelectTypeOption, SelectOptionCellChangeset, SelectOptionPB, SELECTION_IDS_SEPARATOR,
+    };
+
+    #[test]
+    fn multi_select_group_test() {
+        let option_1 = SelectOptionPB::new("Google");
+        let option_2 = SelectOptionPB::new("Facebook");
+        let type_option = MultiSelectTypeOption {
+            options: vec![option_1.clone(), option_2.clone()],
+            disable_color: false,
+        };
+        assert_eq!(type_option.default_groups().len(), 2);
+
+        let data = vec![option_1.id.as_str(), option_2.id.as_str(), "unknown"].join(SELECTION_IDS_SEPARATOR);
+        let any_cell_data = AnyCellData::new(data.clone(), FieldType::MultiSelect);
+        let group_ids = type_option
+            .cell_groups(any_cell_data)
+            .unwrap()
+            .into_iter()
+            .map(|group| group.id)
+            .collect::<Vec<String>>();
+        assert_eq!(group_ids, vec![option_1.id.clone(), option_2.id.clone()]);
+
+        // The cell is already in the group of option_2, so only option_1 is removed.
+        let any_cell_data = AnyCellData::new(data, FieldType::MultiSelect);
+        let changeset = type_option
+            .move_group_changeset(Some(any_cell_data), Some(&option_1.id), Some(&option_2.id))
+            .unwrap();
+        assert_eq!(changeset, SelectOptionCellChangeset::from_delete(&option_1.id).to_str());
+    }
+}
@@ -0,0 +1,6 @@
+mod cell_group;
+mod group_service;
+mod impls;
+
+pub use cell_group::*;
+pub(crate) use group_service::*;
@@ -2,13 +2,22 @@ mod util;
 
 mod block_manager;
 pub mod block_revision_editor;
+mod calculation;
 pub mod cell;
+mod csv;
 pub mod field;
-mod filter;
+pub mod filter;
+mod formula;
 pub mod grid_editor;
 mod grid_editor_task;
+mod group;
 pub mod persistence;
+pub mod relation;
 pub mod row;
 pub mod setting;
 mod snapshot;
+mod sort;
 pub mod tasks;
+mod web_socket;
+
+pub const GRID_SYNC_INTERVAL_IN_MILLIS: u64 = 1000;
@@ -0,0 +1,5 @@
+mod relation_resolver;
+mod relation_service;
+
+pub use relation_resolver::*;
+pub(crate) use relation_service::*;
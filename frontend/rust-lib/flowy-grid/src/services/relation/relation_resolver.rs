@@ -0,0 +1,10 @@
+use crate::services::grid_editor::GridRevisionEditor;
+use flowy_error::FlowyResult;
+use futures::future::BoxFuture;
+use std::sync::Arc;
+
+/// Resolves the editor of the other grid, for example, the target grid of the relation field. The
+/// grid will be opened if it's not opened yet.
+pub trait GridRelationResolver: Send + Sync {
+    fn get_grid_editor(&self, grid_id: &str) -> BoxFuture<FlowyResult<Arc<GridRevisionEditor>>>;
+}
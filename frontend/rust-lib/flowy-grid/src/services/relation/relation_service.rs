@@ -0,0 +1,498 @@
+use crate::entities::{CellChangesetPB, FieldType, GridFieldPB, InsertFieldParams};
+use crate::services::block_manager::GridBlockManager;
+use crate::services::cell::{apply_cell_data_changeset, AnyCellData};
+use crate::services::field::{
+    FieldBuilder, FormulaValue, RelationCellChangeset, RelationRowIds, RelationTypeOption, RelationTypeOptionBuilder,
+    RollupCalculation, RollupTypeOption, TypeOptionBuilder,
+};
+use crate::services::formula::formula_value_from_cell;
+use crate::services::grid_editor::GridRevisionEditor;
+use crate::services::relation::GridRelationResolver;
+use crate::services::row::make_row_from_row_rev;
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use flowy_grid_data_model::revision::{gen_field_id, CellRevision, FieldRevision, RowRevision, TypeOptionDataEntry};
+use flowy_sync::client_grid::GridRevisionPad;
+use futures::future::BoxFuture;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Keeps the relation cells of the linked grids in sync and computes the rollup cells.
+///
+/// Each relation field has a back-reference field in its target grid, the cell of the back-reference
+/// field contains the ids of the rows that link to the row. The rollups are refreshed when the
+/// relation cells or the cells of the related rows are updated. The rollup of a rollup field isn't
+/// supported, so the refreshing doesn't propagate further than the directly related grid.
+pub(crate) struct GridRelationService {
+    grid_id: String,
+    grid_pad: Arc<RwLock<GridRevisionPad>>,
+    block_manager: Arc<GridBlockManager>,
+    resolver: Arc<dyn GridRelationResolver>,
+}
+
+impl GridRelationService {
+    pub async fn new(
+        grid_pad: Arc<RwLock<GridRevisionPad>>,
+        block_manager: Arc<GridBlockManager>,
+        resolver: Arc<dyn GridRelationResolver>,
+    ) -> Self {
+        let grid_id = grid_pad.read().await.grid_id();
+        Self {
+            grid_id,
+            grid_pad,
+            block_manager,
+            resolver,
+        }
+    }
+
+    /// Validate the type option of the relation field and return the type option that should be
+    /// saved. The id of the back-reference field is generated if the target grid is chosen for the
+    /// first time. The target grid can't be changed once it's chosen.
+    pub async fn prepare_relation_field(
+        &self,
+        field_id: &str,
+        mut type_option: RelationTypeOption,
+    ) -> FlowyResult<RelationTypeOption> {
+        if !type_option.has_target() {
+            return Ok(type_option);
+        }
+
+        let current_type_option = self
+            .get_field_rev(field_id)
+            .await
+            .and_then(|field_rev| field_rev.get_type_option_entry::<RelationTypeOption>(FieldType::Relation.into()))
+            .filter(|current_type_option| current_type_option.has_target());
+        match current_type_option {
+            Some(current_type_option) => {
+                if current_type_option.target_grid_id != type_option.target_grid_id {
+                    return Err(FlowyError::new(
+                        ErrorCode::InvalidRelation,
+                        "The target grid of the relation field can't be changed",
+                    ));
+                }
+                type_option.back_reference_field_id = current_type_option.back_reference_field_id;
+            }
+            None => {
+                let _ = self.get_target_editor(&type_option.target_grid_id).await?;
+                if type_option.back_reference_field_id.is_empty() {
+                    type_option.back_reference_field_id = gen_field_id();
+                }
+            }
+        }
+        Ok(type_option)
+    }
+
+    /// Return error if the relation field or the target field of the rollup doesn't exist. The
+    /// target field can't be a rollup field.
+    pub async fn validate_rollup(&self, type_option: &RollupTypeOption) -> FlowyResult<()> {
+        if type_option.relation_field_id.is_empty() {
+            return Ok(());
+        }
+
+        let relation_type_option = self
+            .get_field_rev(&type_option.relation_field_id)
+            .await
+            .filter(|field_rev| FieldType::from(field_rev.field_type_rev).is_relation())
+            .map(|field_rev| RelationTypeOption::from(&field_rev))
+            .ok_or_else(|| invalid_rollup("The relation field of the rollup doesn't exist"))?;
+        if type_option.target_field_id.is_empty() {
+            return Ok(());
+        }
+        if !relation_type_option.has_target() {
+            return Err(invalid_rollup("The target grid of the relation field is not chosen"));
+        }
+
+        let target_editor = self.get_target_editor(&relation_type_option.target_grid_id).await?;
+        match target_editor.get_field_rev(&type_option.target_field_id).await {
+            None => Err(invalid_rollup("The target field of the rollup doesn't exist")),
+            Some(field_rev) if FieldType::from(field_rev.field_type_rev).is_rollup() => {
+                Err(invalid_rollup("The target field of the rollup can't be a rollup field"))
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Create the back-reference field in the target grid if it doesn't exist.
+    pub async fn did_update_relation_field(&self, field_rev: &FieldRevision) -> FlowyResult<()> {
+        let type_option = RelationTypeOption::from(field_rev);
+        if !type_option.has_target() {
+            return Ok(());
+        }
+
+        let target_editor = self.get_target_editor(&type_option.target_grid_id).await?;
+        if target_editor.contain_field(&type_option.back_reference_field_id).await {
+            return Ok(());
+        }
+
+        let builder = RelationTypeOptionBuilder::default()
+            .target_grid_id(&self.grid_id)
+            .back_reference_field_id(&field_rev.id);
+        let type_option_data = builder.entry().protobuf_bytes().to_vec();
+        let mut back_reference_field_rev = FieldBuilder::new(builder).name(&field_rev.name).build();
+        back_reference_field_rev.id = type_option.back_reference_field_id.clone();
+        let params = InsertFieldParams {
+            grid_id: type_option.target_grid_id.clone(),
+            field: GridFieldPB::from(back_reference_field_rev),
+            type_option_data,
+            start_field_id: None,
+        };
+        // The target editor calls back into this grid while inserting the field, so the future is
+        // boxed to break the cycle of the async types.
+        let fut: BoxFuture<FlowyResult<()>> = Box::pin(async move { target_editor.insert_field(params).await });
+        fut.await
+    }
+
+    /// Delete the back-reference field of the deleted relation field.
+    pub async fn did_delete_relation_field(&self, field_rev: &FieldRevision) -> FlowyResult<()> {
+        let type_option = RelationTypeOption::from(field_rev);
+        if !type_option.has_target() {
+            return Ok(());
+        }
+
+        let target_editor = self.get_target_editor(&type_option.target_grid_id).await?;
+        if target_editor.contain_field(&type_option.back_reference_field_id).await {
+            let field_id = type_option.back_reference_field_id;
+            let fut: BoxFuture<FlowyResult<()>> = Box::pin(async move { target_editor.delete_field(&field_id).await });
+            let _ = fut.await?;
+        }
+        Ok(())
+    }
+
+    /// Add the row to or remove the row from the back-reference cells of the rows that are linked or
+    /// unlinked by the relation cell.
+    pub async fn did_update_relation_cell(
+        &self,
+        row_id: &str,
+        field_rev: &FieldRevision,
+        old_cell_rev: Option<&CellRevision>,
+    ) -> FlowyResult<()> {
+        let type_option = RelationTypeOption::from(field_rev);
+        if !type_option.has_target() {
+            return Ok(());
+        }
+
+        let old_row_ids = relation_row_ids_from_cell(old_cell_rev);
+        let new_row_ids = match self.block_manager.get_row_rev(row_id).await? {
+            None => RelationRowIds::default(),
+            Some(row_rev) => relation_row_ids_from_cell(row_rev.cells.get(&field_rev.id)),
+        };
+        let inserted_row_ids = new_row_ids
+            .iter()
+            .filter(|row_id| !old_row_ids.contains(*row_id))
+            .cloned()
+            .collect::<Vec<String>>();
+        let deleted_row_ids = old_row_ids
+            .iter()
+            .filter(|row_id| !new_row_ids.contains(*row_id))
+            .cloned()
+            .collect::<Vec<String>>();
+        if inserted_row_ids.is_empty() && deleted_row_ids.is_empty() {
+            return Ok(());
+        }
+
+        let target_editor = self.get_target_editor(&type_option.target_grid_id).await?;
+        target_editor
+            .update_relation_back_references(
+                &type_option.back_reference_field_id,
+                row_id,
+                inserted_row_ids,
+                deleted_row_ids,
+            )
+            .await
+    }
+
+    /// Link the new rows to the rows of the target grids if their relation cells are not empty, then
+    /// compute their rollup cells.
+    pub async fn did_create_rows(&self, row_ids: &[String]) -> FlowyResult<()> {
+        let relation_fields = self.relation_fields().await?;
+        for row_id in row_ids {
+            let row_rev = match self.block_manager.get_row_rev(row_id).await? {
+                None => continue,
+                Some(row_rev) => row_rev,
+            };
+            for (field_rev, type_option) in relation_fields.iter() {
+                let linked_row_ids = relation_row_ids_from_cell(row_rev.cells.get(&field_rev.id));
+                if linked_row_ids.is_empty() {
+                    continue;
+                }
+                let target_editor = self.get_target_editor(&type_option.target_grid_id).await?;
+                let _ = target_editor
+                    .update_relation_back_references(
+                        &type_option.back_reference_field_id,
+                        row_id,
+                        linked_row_ids.into_inner(),
+                        vec![],
+                    )
+                    .await?;
+            }
+            let _ = self.refresh_rollups(row_id, |_, _| true).await?;
+        }
+        Ok(())
+    }
+
+    /// Recompute the cells of the rollup field, or the rollup fields that depend on the relation
+    /// field, in all the rows. Return the ids of the rollup fields whose cells were changed.
+    pub async fn did_update_field(&self, field_id: &str) -> FlowyResult<Vec<String>> {
+        let predicate = |field_rev: &FieldRevision, type_option: &RollupTypeOption| {
+            field_rev.id == field_id || type_option.relation_field_id == field_id
+        };
+        let mut updated_field_ids = vec![];
+        for snapshot in self.block_manager.get_block_snapshots(None).await? {
+            for row_rev in snapshot.row_revs.iter() {
+                for updated_field_id in self.refresh_rollups(&row_rev.id, predicate).await? {
+                    if !updated_field_ids.contains(&updated_field_id) {
+                        updated_field_ids.push(updated_field_id);
+                    }
+                }
+            }
+        }
+        Ok(updated_field_ids)
+    }
+
+    /// Remove the row from the back-reference cells of all the rows that it links to.
+    pub async fn will_delete_row(&self, row_id: &str) -> FlowyResult<()> {
+        let row_rev = match self.block_manager.get_row_rev(row_id).await? {
+            None => return Ok(()),
+            Some(row_rev) => row_rev,
+        };
+
+        for (field_rev, type_option) in self.relation_fields().await? {
+            let row_ids = relation_row_ids_from_cell(row_rev.cells.get(&field_rev.id));
+            if row_ids.is_empty() {
+                continue;
+            }
+            let target_editor = self.get_target_editor(&type_option.target_grid_id).await?;
+            let _ = target_editor
+                .update_relation_back_references(
+                    &type_option.back_reference_field_id,
+                    row_id,
+                    vec![],
+                    row_ids.into_inner(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Notify the grids that link to the row that the cells of the fields were updated, so they can
+    /// refresh their rollups.
+    pub async fn did_update_cells(&self, row_id: &str, field_ids: &[String]) -> FlowyResult<()> {
+        let row_rev = match self.block_manager.get_row_rev(row_id).await? {
+            None => return Ok(()),
+            Some(row_rev) => row_rev,
+        };
+
+        for (field_rev, type_option) in self.relation_fields().await? {
+            let row_ids = relation_row_ids_from_cell(row_rev.cells.get(&field_rev.id));
+            if row_ids.is_empty() {
+                continue;
+            }
+            let target_editor = self.get_target_editor(&type_option.target_grid_id).await?;
+            let _ = target_editor
+                .did_update_related_rows(
+                    &type_option.back_reference_field_id,
+                    row_ids.into_inner(),
+                    field_ids.to_vec(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Add the `source_row_id` to or remove it from the cells of the back-reference field. Return
+    /// the ids of the rows whose cells were updated.
+    pub async fn update_back_references(
+        &self,
+        field_id: &str,
+        source_row_id: &str,
+        inserted_row_ids: Vec<String>,
+        deleted_row_ids: Vec<String>,
+    ) -> FlowyResult<Vec<String>> {
+        let field_rev = match self.get_field_rev(field_id).await {
+            None => return Ok(vec![]),
+            Some(field_rev) => field_rev,
+        };
+
+        let changesets = inserted_row_ids
+            .into_iter()
+            .map(|row_id| (row_id, RelationCellChangeset::from_insert(source_row_id)))
+            .chain(
+                deleted_row_ids
+                    .into_iter()
+                    .map(|row_id| (row_id, RelationCellChangeset::from_delete(source_row_id))),
+            );
+
+        let mut updated_row_ids = vec![];
+        for (row_id, changeset) in changesets {
+            let row_rev = match self.block_manager.get_row_rev(&row_id).await? {
+                None => continue,
+                Some(row_rev) => row_rev,
+            };
+            let cell_rev = row_rev.cells.get(field_id).cloned();
+            let content = apply_cell_data_changeset(changeset.to_str(), cell_rev, &field_rev)?;
+            let cell_changeset = CellChangesetPB {
+                grid_id: self.grid_id.clone(),
+                row_id: row_id.clone(),
+                field_id: field_id.to_owned(),
+                content: Some(content),
+            };
+            let _ = self
+                .block_manager
+                .update_computed_cell(cell_changeset, make_row_from_row_rev)
+                .await?;
+            updated_row_ids.push(row_id);
+        }
+        Ok(updated_row_ids)
+    }
+
+    /// Return the values of the field in the rows. The rows that don't exist are skipped.
+    pub async fn get_related_values(&self, field_id: &str, row_ids: &[String]) -> FlowyResult<Vec<FormulaValue>> {
+        let field_rev = self.get_field_rev(field_id).await;
+        let mut values = vec![];
+        for row_id in row_ids {
+            if let Some(row_rev) = self.block_manager.get_row_rev(row_id).await? {
+                let value = match field_rev.as_ref() {
+                    None => FormulaValue::Empty,
+                    Some(field_rev) => formula_value_from_cell(field_rev, row_rev.cells.get(field_id)),
+                };
+                values.push(value);
+            }
+        }
+        Ok(values)
+    }
+
+    /// Recompute the rollup cells of the row that satisfy the `predicate`. Return the ids of the
+    /// rollup fields whose cells were changed.
+    pub async fn refresh_rollups<P>(&self, row_id: &str, predicate: P) -> FlowyResult<Vec<String>>
+    where
+        P: Fn(&FieldRevision, &RollupTypeOption) -> bool,
+    {
+        let field_revs = self.grid_pad.read().await.get_field_revs(None)?;
+        let rollup_fields = field_revs
+            .iter()
+            .filter(|field_rev| FieldType::from(field_rev.field_type_rev).is_rollup())
+            .map(|field_rev| (field_rev.clone(), RollupTypeOption::from(field_rev)))
+            .filter(|(field_rev, type_option)| predicate(field_rev.as_ref(), type_option))
+            .collect::<Vec<(Arc<FieldRevision>, RollupTypeOption)>>();
+        if rollup_fields.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let row_rev = match self.block_manager.get_row_rev(row_id).await? {
+            None => return Ok(vec![]),
+            Some(row_rev) => row_rev,
+        };
+
+        let mut updated_field_ids = vec![];
+        for (field_rev, type_option) in rollup_fields {
+            let value = self.calculate_rollup(&row_rev, &field_revs, &type_option).await?;
+            let content = AnyCellData::new(value.to_json()?, FieldType::Rollup).json();
+            let is_changed = match row_rev.cells.get(&field_rev.id) {
+                None => value != FormulaValue::Empty,
+                Some(cell_rev) => cell_rev.data != content,
+            };
+            if is_changed {
+                let changeset = CellChangesetPB {
+                    grid_id: self.grid_id.clone(),
+                    row_id: row_id.to_owned(),
+                    field_id: field_rev.id.clone(),
+                    content: Some(content),
+                };
+                let _ = self
+                    .block_manager
+                    .update_computed_cell(changeset, make_row_from_row_rev)
+                    .await?;
+                updated_field_ids.push(field_rev.id.clone());
+            }
+        }
+        Ok(updated_field_ids)
+    }
+
+    async fn calculate_rollup(
+        &self,
+        row_rev: &RowRevision,
+        field_revs: &[Arc<FieldRevision>],
+        type_option: &RollupTypeOption,
+    ) -> FlowyResult<FormulaValue> {
+        let relation_field_rev = field_revs.iter().find(|field_rev| {
+            field_rev.id == type_option.relation_field_id && FieldType::from(field_rev.field_type_rev).is_relation()
+        });
+        let relation_field_rev = match relation_field_rev {
+            None => return Ok(FormulaValue::Empty),
+            Some(field_rev) => field_rev,
+        };
+
+        let row_ids = relation_row_ids_from_cell(row_rev.cells.get(&relation_field_rev.id));
+        let relation_type_option = RelationTypeOption::from(relation_field_rev);
+        let values = if row_ids.is_empty() || !relation_type_option.has_target() {
+            vec![]
+        } else if type_option.target_field_id.is_empty() && type_option.calculation != RollupCalculation::Count {
+            return Ok(FormulaValue::Empty);
+        } else {
+            let target_editor = self.get_target_editor(&relation_type_option.target_grid_id).await?;
+            target_editor
+                .get_relation_values(&type_option.target_field_id, &row_ids)
+                .await?
+        };
+        Ok(type_option.calculate(values))
+    }
+
+    async fn relation_fields(&self) -> FlowyResult<Vec<(Arc<FieldRevision>, RelationTypeOption)>> {
+        let field_revs = self.grid_pad.read().await.get_field_revs(None)?;
+        let relation_fields = field_revs
+            .into_iter()
+            .filter(|field_rev| FieldType::from(field_rev.field_type_rev).is_relation())
+            .map(|field_rev| {
+                let type_option = RelationTypeOption::from(&field_rev);
+                (field_rev, type_option)
+            })
+            .filter(|(_, type_option)| type_option.has_target())
+            .collect();
+        Ok(relation_fields)
+    }
+
+    async fn get_field_rev(&self, field_id: &str) -> Option<Arc<FieldRevision>> {
+        let field_rev = self.grid_pad.read().await.get_field_rev(field_id)?.1.clone();
+        Some(field_rev)
+    }
+
+    async fn get_target_editor(&self, grid_id: &str) -> FlowyResult<Arc<GridRevisionEditor>> {
+        self.resolver.get_grid_editor(grid_id).await.map_err(|e| {
+            let msg = format!("Can't open the target grid {} of the relation: {}", grid_id, e.msg);
+            FlowyError::new(ErrorCode::InvalidRelation, &msg)
+        })
+    }
+}
+
+fn relation_row_ids_from_cell(cell_rev: Option<&CellRevision>) -> RelationRowIds {
+    cell_rev
+        .and_then(|cell_rev| AnyCellData::try_from(cell_rev).ok())
+        .filter(|any_cell_data| any_cell_data.is_relation())
+        .map(|any_cell_data| RelationRowIds::from(any_cell_data.data))
+        .unwrap_or_default()
+}
+
+fn invalid_rollup(msg: &str) -> FlowyError {
+    FlowyError::new(ErrorCode::InvalidRollup, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::relation_row_ids_from_cell;
+    use crate::entities::FieldType;
+    use crate::services::cell::AnyCellData;
+    use flowy_grid_data_model::revision::CellRevision;
+
+    #[test]
+    fn relation_row_ids_from_cell_test() {
+        let cell_rev = CellRevision::new(AnyCellData::new("a,b".to_owned(), FieldType::Relation).json());
+        assert_eq!(
+            relation_row_ids_from_cell(Some(&cell_rev)).into_inner(),
+            vec!["a".to_owned(), "b".to_owned()]
+        );
+
+        // The cell of the other field type is treated as empty.
+        let cell_rev = CellRevision::new(AnyCellData::new("a,b".to_owned(), FieldType::RichText).json());
+        assert!(relation_row_ids_from_cell(Some(&cell_rev)).is_empty());
+        assert!(relation_row_ids_from_cell(None).is_empty());
+    }
+}
@@ -59,6 +59,28 @@ impl<'a> RowRevisionBuilder<'a> {
         }
     }
 
+    /// Insert the cell that selects all the options of the `option_ids`.
+    pub fn insert_select_option_cells(&mut self, field_id: &str, option_ids: Vec<String>) -> FlowyResult<()> {
+        match self.field_rev_map.get(&field_id.to_owned()) {
+            None => {
+                let msg = format!("Invalid field_id: {}", field_id);
+                Err(FlowyError::internal().context(msg))
+            }
+            Some(field_rev) => {
+                let mut cell = None;
+                for option_id in option_ids {
+                    let cell_data = SelectOptionCellChangeset::from_insert(&option_id).to_str();
+                    let data = apply_cell_data_changeset(cell_data, cell, field_rev)?;
+                    cell = Some(CellRevision::new(data));
+                }
+                if let Some(cell) = cell {
+                    self.payload.cell_by_field_id.insert(field_id.to_owned(), cell);
+                }
+                Ok(())
+            }
+        }
+    }
+
     #[allow(dead_code)]
     pub fn height(mut self, height: i32) -> Self {
         self.payload.height = height;
@@ -2,7 +2,10 @@ use crate::entities::{
     GridLayoutPB, GridLayoutType, GridSettingPB, RepeatedGridFilterPB, RepeatedGridGroupPB, RepeatedGridSortPB,
 };
 use flowy_grid_data_model::revision::{FieldRevision, GridSettingRevision};
-use flowy_sync::entities::grid::{CreateGridFilterParams, DeleteFilterParams, GridSettingChangesetParams};
+use flowy_sync::entities::grid::{
+    CreateGridCalculationParams, CreateGridFilterGroupParams, CreateGridFilterParams, CreateGridGroupParams,
+    CreateGridSortParams, DeleteFilterParams, GridSettingChangesetParams,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -21,6 +24,10 @@ impl GridSettingChangesetBuilder {
             delete_group: None,
             insert_sort: None,
             delete_sort: None,
+            insert_filter_group: None,
+            delete_filter_group: None,
+            insert_calculation: None,
+            delete_calculation: None,
         };
         Self { params }
     }
@@ -35,6 +42,46 @@ impl GridSettingChangesetBuilder {
         self
     }
 
+    pub fn insert_group(mut self, params: CreateGridGroupParams) -> Self {
+        self.params.insert_group = Some(params);
+        self
+    }
+
+    pub fn delete_group(mut self, group_id: &str) -> Self {
+        self.params.delete_group = Some(group_id.to_owned());
+        self
+    }
+
+    pub fn insert_sort(mut self, params: CreateGridSortParams) -> Self {
+        self.params.insert_sort = Some(params);
+        self
+    }
+
+    pub fn delete_sort(mut self, sort_id: &str) -> Self {
+        self.params.delete_sort = Some(sort_id.to_owned());
+        self
+    }
+
+    pub fn insert_filter_group(mut self, params: CreateGridFilterGroupParams) -> Self {
+        self.params.insert_filter_group = Some(params);
+        self
+    }
+
+    pub fn delete_filter_group(mut self, filter_group_id: &str) -> Self {
+        self.params.delete_filter_group = Some(filter_group_id.to_owned());
+        self
+    }
+
+    pub fn insert_calculation(mut self, params: CreateGridCalculationParams) -> Self {
+        self.params.insert_calculation = Some(params);
+        self
+    }
+
+    pub fn delete_calculation(mut self, field_id: &str) -> Self {
+        self.params.delete_calculation = Some(field_id.to_owned());
+        self
+    }
+
     pub fn build(self) -> GridSettingChangesetParams {
         self.params
     }
@@ -52,7 +99,7 @@ pub fn make_grid_setting(grid_setting_rev: &GridSettingRevision, field_revs: &[A
         })
         .unwrap_or_default();
     let groups_by_field_id = grid_setting_rev
-        .get_all_group()
+        .get_all_group(field_revs)
         .map(|groups_by_field_id| {
             groups_by_field_id
                 .into_iter()
@@ -61,7 +108,7 @@ pub fn make_grid_setting(grid_setting_rev: &GridSettingRevision, field_revs: &[A
         })
         .unwrap_or_default();
     let sorts_by_field_id = grid_setting_rev
-        .get_all_sort()
+        .get_all_sort(field_revs)
         .map(|sorts_by_field_id| {
             sorts_by_field_id
                 .into_iter()
@@ -69,6 +116,9 @@ pub fn make_grid_setting(grid_setting_rev: &GridSettingRevision, field_revs: &[A
                 .collect::<HashMap<String, RepeatedGridSortPB>>()
         })
         .unwrap_or_default();
+    let filter_group = grid_setting_rev
+        .get_filter_group(&grid_setting_rev.layout)
+        .map(|filter_group_rev| filter_group_rev.as_ref().into());
 
     GridSettingPB {
         layouts: GridLayoutPB::all(),
@@ -76,5 +126,6 @@ pub fn make_grid_setting(grid_setting_rev: &GridSettingRevision, field_revs: &[A
         filters_by_field_id,
         groups_by_field_id,
         sorts_by_field_id,
+        filter_group,
     }
 }
@@ -0,0 +1,13 @@
+use crate::services::cell::{AnyCellData, CellData, CellSortOperation};
+use crate::services::field::{CheckboxCellData, CheckboxTypeOption};
+use crate::services::sort::CellSortKey;
+use flowy_error::FlowyResult;
+
+impl CellSortOperation for CheckboxTypeOption {
+    fn sort_key(&self, any_cell_data: AnyCellData) -> FlowyResult<Option<CellSortKey>> {
+        // An empty checkbox cell is the same as the unchecked one, so it never returns None.
+        let cell_data: CellData<CheckboxCellData> = any_cell_data.into();
+        let checkbox_cell_data = cell_data.try_into_inner()?;
+        Ok(Some(CellSortKey::Checkbox(checkbox_cell_data.is_check())))
+    }
+}
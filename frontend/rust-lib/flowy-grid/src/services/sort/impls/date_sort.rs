@@ -0,0 +1,15 @@
+use crate::services::cell::{AnyCellData, CellData, CellSortOperation};
+use crate::services::field::{DateTimestamp, DateTypeOption};
+use crate::services::sort::CellSortKey;
+use flowy_error::FlowyResult;
+
+impl CellSortOperation for DateTypeOption {
+    fn sort_key(&self, any_cell_data: AnyCellData) -> FlowyResult<Option<CellSortKey>> {
+        let cell_data: CellData<DateTimestamp> = any_cell_data.into();
+        let timestamp: i64 = cell_data.try_into_inner()?.into();
+        if timestamp == 0 {
+            return Ok(None);
+        }
+        Ok(Some(CellSortKey::Timestamp(timestamp)))
+    }
+}
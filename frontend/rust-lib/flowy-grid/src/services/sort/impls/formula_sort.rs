@@ -0,0 +1,47 @@
+use crate::services::cell::{AnyCellData, CellData, CellSortOperation};
+use crate::services::field::{FormulaTypeOption, FormulaValue};
+use crate::services::sort::CellSortKey;
+use flowy_error::FlowyResult;
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::Decimal;
+
+impl CellSortOperation for FormulaTypeOption {
+    fn sort_key(&self, any_cell_data: AnyCellData) -> FlowyResult<Option<CellSortKey>> {
+        let cell_data: CellData<FormulaValue> = any_cell_data.into();
+        Ok(formula_value_sort_key(cell_data.try_into_inner()?))
+    }
+}
+
+/// Return the sort key of the computed value. The empty values and the errors are placed after the
+/// others.
+pub(crate) fn formula_value_sort_key(value: FormulaValue) -> Option<CellSortKey> {
+    match value {
+        FormulaValue::Empty | FormulaValue::Error(_) => None,
+        FormulaValue::Number(num) => Decimal::from_f64(num).map(CellSortKey::Number),
+        FormulaValue::Date(timestamp) => Some(CellSortKey::Timestamp(timestamp)),
+        FormulaValue::Bool(b) => Some(CellSortKey::Checkbox(b)),
+        value => Some(CellSortKey::from_text(value.to_display_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::FieldType;
+    use crate::services::cell::{AnyCellData, CellSortOperation};
+    use crate::services::field::{FormulaTypeOption, FormulaValue};
+
+    #[test]
+    fn formula_sort_test() {
+        let type_option = FormulaTypeOption::default();
+        let sort_key = |value: FormulaValue| {
+            type_option
+                .sort_key(AnyCellData::new(value.to_json().unwrap(), FieldType::Formula))
+                .unwrap()
+        };
+
+        assert!(sort_key(FormulaValue::Number(9.0)) < sort_key(FormulaValue::Number(10.5)));
+        assert!(sort_key(FormulaValue::Text("a".to_owned())) < sort_key(FormulaValue::Text("B".to_owned())));
+        assert_eq!(sort_key(FormulaValue::Empty), None);
+        assert_eq!(sort_key(FormulaValue::error("Division by zero")), None);
+    }
+}
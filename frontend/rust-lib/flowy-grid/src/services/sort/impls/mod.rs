@@ -0,0 +1,19 @@
+mod checkbox_sort;
+mod date_sort;
+mod formula_sort;
+mod number_sort;
+mod relation_sort;
+mod rollup_sort;
+mod select_option_sort;
+mod text_sort;
+mod url_sort;
+
+pub use checkbox_sort::*;
+pub use date_sort::*;
+pub use formula_sort::*;
+pub use number_sort::*;
+pub use relation_sort::*;
+pub use rollup_sort::*;
+pub use select_option_sort::*;
+pub use text_sort::*;
+pub use url_sort::*;
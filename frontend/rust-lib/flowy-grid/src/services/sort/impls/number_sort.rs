@@ -0,0 +1,34 @@
+use crate::services::cell::{AnyCellData, CellSortOperation};
+use crate::services::field::NumberTypeOption;
+use crate::services::sort::CellSortKey;
+use flowy_error::FlowyResult;
+
+impl CellSortOperation for NumberTypeOption {
+    fn sort_key(&self, any_cell_data: AnyCellData) -> FlowyResult<Option<CellSortKey>> {
+        let num_cell_data = self.format_cell_data(&any_cell_data.data)?;
+        Ok((*num_cell_data.decimal()).map(CellSortKey::Number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::FieldType;
+    use crate::services::cell::{AnyCellData, CellSortOperation};
+    use crate::services::field::{NumberFormat, NumberTypeOption};
+
+    #[test]
+    fn number_sort_test() {
+        let mut type_option = NumberTypeOption::default();
+        type_option.set_format(NumberFormat::USD);
+        let sort_key = |s: &str| {
+            type_option
+                .sort_key(AnyCellData::new(s.to_owned(), FieldType::Number))
+                .unwrap()
+        };
+
+        assert!(sort_key("9") < sort_key("10"));
+        assert!(sort_key("$9") < sort_key("$10.5"));
+        assert_eq!(sort_key("10"), sort_key("$10"));
+        assert_eq!(sort_key(""), None);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::services::cell::{AnyCellData, CellData, CellSortOperation};
+use crate::services::field::{RelationRowIds, RelationTypeOption};
+use crate::services::sort::CellSortKey;
+use flowy_error::FlowyResult;
+use rust_decimal::Decimal;
+
+/// The relation cells are sorted by the number of the linked rows.
+impl CellSortOperation for RelationTypeOption {
+    fn sort_key(&self, any_cell_data: AnyCellData) -> FlowyResult<Option<CellSortKey>> {
+        let cell_data: CellData<RelationRowIds> = any_cell_data.into();
+        let row_ids = cell_data.try_into_inner()?;
+        if row_ids.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(CellSortKey::Number(Decimal::from(row_ids.len()))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::FieldType;
+    use crate::services::cell::{AnyCellData, CellSortOperation};
+    use crate::services::field::RelationTypeOption;
+
+    #[test]
+    fn relation_sort_test() {
+        let type_option = RelationTypeOption::default();
+        let sort_key = |s: &str| {
+            type_option
+                .sort_key(AnyCellData::new(s.to_owned(), FieldType::Relation))
+                .unwrap()
+        };
+
+        assert!(sort_key("a") < sort_key("a,b"));
+        assert_eq!(sort_key(""), None);
+    }
+}
@@ -0,0 +1,12 @@
+use crate::services::cell::{AnyCellData, CellData, CellSortOperation};
+use crate::services::field::{FormulaValue, RollupTypeOption};
+use crate::services::sort::impls::formula_sort::formula_value_sort_key;
+use crate::services::sort::CellSortKey;
+use flowy_error::FlowyResult;
+
+impl CellSortOperation for RollupTypeOption {
+    fn sort_key(&self, any_cell_data: AnyCellData) -> FlowyResult<Option<CellSortKey>> {
+        let cell_data: CellData<FormulaValue> = any_cell_data.into();
+        Ok(formula_value_sort_key(cell_data.try_into_inner()?))
+    }
+}
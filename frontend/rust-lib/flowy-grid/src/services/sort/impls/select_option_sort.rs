@@ -0,0 +1,70 @@
+use crate::services::cell::{AnyCellData, CellData, CellSortOperation};
+use crate::services::field::{MultiSelectTypeOption, SelectOptionIds, SelectOptionOperation, SingleSelectTypeOptionPB};
+use crate::services::sort::CellSortKey;
+use flowy_error::FlowyResult;
+
+impl CellSortOperation for SingleSelectTypeOptionPB {
+    fn sort_key(&self, any_cell_data: AnyCellData) -> FlowyResult<Option<CellSortKey>> {
+        let mut option_indexes = select_option_indexes(self, any_cell_data.into());
+        option_indexes.truncate(1);
+        Ok(option_indexes_sort_key(option_indexes))
+    }
+}
+
+impl CellSortOperation for MultiSelectTypeOption {
+    fn sort_key(&self, any_cell_data: AnyCellData) -> FlowyResult<Option<CellSortKey>> {
+        let mut option_indexes = select_option_indexes(self, any_cell_data.into());
+        option_indexes.sort_unstable();
+        Ok(option_indexes_sort_key(option_indexes))
+    }
+}
+
+/// The select options are ordered by their position in the type option instead of their names.
+fn select_option_indexes<T: SelectOptionOperation>(
+    type_option: &T,
+    cell_data: CellData<SelectOptionIds>,
+) -> Vec<usize> {
+    match cell_data.try_into_inner() {
+        Ok(option_ids) => option_ids
+            .iter()
+            .flat_map(|option_id| type_option.options().iter().position(|option| &option.id == option_id))
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+fn option_indexes_sort_key(option_indexes: Vec<usize>) -> Option<CellSortKey> {
+    if option_indexes.is_empty() {
+        None
+    } else {
+        Some(CellSortKey::OptionIndexes(option_indexes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::FieldType;
+    use crate::services::cell::{AnyCellData, CellSortOperation};
+    use crate::services::field::{MultiSelectTypeOption, SelectOptionPB, SELECTION_IDS_SEPARATOR};
+
+    #[test]
+    fn multi_select_sort_by_option_position_test() {
+        let option_1 = SelectOptionPB::new("Z");
+        let option_2 = SelectOptionPB::new("A");
+        let type_option = MultiSelectTypeOption {
+            options: vec![option_1.clone(), option_2.clone()],
+            disable_color: false,
+        };
+        let sort_key = |ids: Vec<&str>| {
+            let data = ids.join(SELECTION_IDS_SEPARATOR);
+            type_option
+                .sort_key(AnyCellData::new(data, FieldType::MultiSelect))
+                .unwrap()
+        };
+
+        assert!(sort_key(vec![&option_1.id]) < sort_key(vec![&option_2.id]));
+        assert!(sort_key(vec![&option_2.id, &option_1.id]) < sort_key(vec![&option_2.id]));
+        assert_eq!(sort_key(vec![]), None);
+        assert_eq!(sort_key(vec!["unknown"]), None);
+    }
+}
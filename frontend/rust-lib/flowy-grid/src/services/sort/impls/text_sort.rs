@@ -0,0 +1,37 @@
+use crate::services::cell::{AnyCellData, CellData, CellSortOperation};
+use crate::services::field::{RichTextCellData, RichTextTypeOption};
+use crate::services::sort::CellSortKey;
+use flowy_error::FlowyResult;
+
+impl CellSortOperation for RichTextTypeOption {
+    fn sort_key(&self, any_cell_data: AnyCellData) -> FlowyResult<Option<CellSortKey>> {
+        let cell_data: CellData<RichTextCellData> = any_cell_data.into();
+        let text = cell_data.try_into_inner()?.to_plain_text();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(CellSortKey::from_text(text)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::FieldType;
+    use crate::services::cell::{AnyCellData, CellSortOperation};
+    use crate::services::field::RichTextTypeOption;
+
+    #[test]
+    fn text_sort_ignore_case_test() {
+        let type_option = RichTextTypeOption::default();
+        let sort_key = |s: &str| {
+            type_option
+                .sort_key(AnyCellData::new(s.to_owned(), FieldType::RichText))
+                .unwrap()
+        };
+
+        assert!(sort_key("apple") < sort_key("Banana"));
+        assert!(sort_key("Apple") < sort_key("banana"));
+        assert!(sort_key("Apple") < sort_key("apple"));
+        assert_eq!(sort_key(""), None);
+    }
+}
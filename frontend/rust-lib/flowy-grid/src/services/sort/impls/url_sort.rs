@@ -0,0 +1,20 @@
+use crate::services::cell::{AnyCellData, CellData, CellSortOperation};
+use crate::services::field::{URLCellDataPB, URLTypeOption};
+use crate::services::sort::CellSortKey;
+use flowy_error::FlowyResult;
+
+impl CellSortOperation for URLTypeOption {
+    fn sort_key(&self, any_cell_data: AnyCellData) -> FlowyResult<Option<CellSortKey>> {
+        let cell_data: CellData<URLCellDataPB> = any_cell_data.into();
+        let url_cell_data = cell_data.try_into_inner()?;
+        let text = if url_cell_data.content.is_empty() {
+            &url_cell_data.url
+        } else {
+            &url_cell_data.content
+        };
+        if text.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(CellSortKey::from_text(text)))
+    }
+}
@@ -0,0 +1,6 @@
+mod impls;
+mod sort_key;
+mod sort_service;
+
+pub use sort_key::*;
+pub(crate) use sort_service::*;
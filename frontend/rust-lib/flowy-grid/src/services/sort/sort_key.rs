@@ -0,0 +1,64 @@
+use rust_decimal::Decimal;
+use std::cmp::Ordering;
+
+/// CellSortKey is the value that is extracted from the cell data in order to compare the cells of the
+/// same field. Each FieldType produces its own variant, so the keys of different variants are never
+/// compared with each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CellSortKey {
+    /// The text is compared case-insensitively first, the original text is used to break the tie.
+    Text {
+        collation: String,
+        text: String,
+    },
+    Number(Decimal),
+    Timestamp(i64),
+    /// The indexes of the selected options in the type option's options.
+    OptionIndexes(Vec<usize>),
+    Checkbox(bool),
+}
+
+impl CellSortKey {
+    pub fn from_text<T: AsRef<str>>(text: T) -> Self {
+        let text = text.as_ref();
+        CellSortKey::Text {
+            collation: text.to_lowercase(),
+            text: text.to_owned(),
+        }
+    }
+
+    fn variant_index(&self) -> u8 {
+        match self {
+            CellSortKey::Text { .. } => 0,
+            CellSortKey::Number(_) => 1,
+            CellSortKey::Timestamp(_) => 2,
+            CellSortKey::OptionIndexes(_) => 3,
+            CellSortKey::Checkbox(_) => 4,
+        }
+    }
+}
+
+impl PartialOrd for CellSortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CellSortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (
+                CellSortKey::Text { collation, text },
+                CellSortKey::Text {
+                    collation: other_collation,
+                    text: other_text,
+                },
+            ) => collation.cmp(other_collation).then_with(|| text.cmp(other_text)),
+            (CellSortKey::Number(left), CellSortKey::Number(right)) => left.cmp(right),
+            (CellSortKey::Timestamp(left), CellSortKey::Timestamp(right)) => left.cmp(right),
+            (CellSortKey::OptionIndexes(left), CellSortKey::OptionIndexes(right)) => left.cmp(right),
+            (CellSortKey::Checkbox(left), CellSortKey::Checkbox(right)) => left.cmp(right),
+            (left, right) => left.variant_index().cmp(&right.variant_index()),
+        }
+    }
+}
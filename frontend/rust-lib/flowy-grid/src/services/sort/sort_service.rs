@@ -0,0 +1,220 @@
+use crate::dart_notification::{send_dart_notification, GridNotification};
+use crate::entities::{FieldType, GridRowOrderChangesetPB, GridSortCondition};
+use crate::services::block_manager::GridBlockManager;
+use crate::services::cell::{AnyCellData, CellSortOperation};
+use crate::services::field::{
+    CheckboxTypeOption, DateTypeOption, FormulaTypeOption, MultiSelectTypeOption, NumberTypeOption, RelationTypeOption,
+    RichTextTypeOption, RollupTypeOption, SingleSelectTypeOptionPB, URLTypeOption,
+};
+use crate::services::row::GridBlockSnapshot;
+use crate::services::sort::CellSortKey;
+use flowy_error::FlowyResult;
+use flowy_grid_data_model::revision::{FieldRevision, RowRevision};
+use flowy_sync::client_grid::GridRevisionPad;
+use flowy_sync::entities::grid::GridSettingChangesetParams;
+use std::cmp::Ordering;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub(crate) struct GridSortService {
+    grid_id: String,
+    grid_pad: Arc<RwLock<GridRevisionPad>>,
+    block_manager: Arc<GridBlockManager>,
+}
+
+impl GridSortService {
+    pub async fn new(grid_pad: Arc<RwLock<GridRevisionPad>>, block_manager: Arc<GridBlockManager>) -> Self {
+        let grid_id = grid_pad.read().await.grid_id();
+        Self {
+            grid_id,
+            grid_pad,
+            block_manager,
+        }
+    }
+
+    /// Sort the rows of each block by the sorts of the current layout. The rows keep their
+    /// original order if there is no sort.
+    pub async fn sort_block_snapshots(&self, snapshots: &mut [GridBlockSnapshot]) {
+        let cell_sorts = self.make_cell_sorts().await;
+        if cell_sorts.is_empty() {
+            return;
+        }
+
+        for snapshot in snapshots.iter_mut() {
+            sort_row_revs(&mut snapshot.row_revs, &cell_sorts);
+        }
+    }
+
+    pub async fn apply_changeset(&self, changeset: GridSortChangeset) {
+        if !changeset.is_changed() {
+            return;
+        }
+
+        match self.block_manager.get_block_snapshots(None).await {
+            Ok(mut snapshots) => {
+                self.sort_block_snapshots(&mut snapshots).await;
+                self.notify(snapshots).await;
+            }
+            Err(e) => tracing::error!("Get block snapshots failed: {:?}", e),
+        }
+    }
+
+    /// Reorder the rows if the updated cell belongs to one of the sorted fields.
+    pub async fn did_update_cell(&self, field_id: &str) -> FlowyResult<()> {
+        let is_sorted_field = self
+            .grid_pad
+            .read()
+            .await
+            .get_sorts(None)
+            .iter()
+            .any(|sort_rev| sort_rev.field_id == field_id);
+
+        if is_sorted_field {
+            let changeset = GridSortChangeset { is_changed: true };
+            self.apply_changeset(changeset).await;
+        }
+        Ok(())
+    }
+
+    async fn make_cell_sorts(&self) -> Vec<CellSort> {
+        let grid_pad = self.grid_pad.read().await;
+        grid_pad
+            .get_sorts(None)
+            .into_iter()
+            .flat_map(|sort_rev| {
+                let (_, field_rev) = grid_pad.get_field_rev(&sort_rev.field_id)?;
+                Some(CellSort {
+                    field_id: field_rev.id.clone(),
+                    field_type: field_rev.field_type_rev.into(),
+                    condition: sort_rev.condition.clone().into(),
+                    operation: make_cell_sort_operation(field_rev)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn notify(&self, snapshots: Vec<GridBlockSnapshot>) {
+        for snapshot in snapshots {
+            let changeset = GridRowOrderChangesetPB {
+                block_id: snapshot.block_id,
+                row_ids: snapshot.row_revs.iter().map(|row_rev| row_rev.id.clone()).collect(),
+            };
+            send_dart_notification(&self.grid_id, GridNotification::DidReorderRows)
+                .payload(changeset)
+                .send();
+        }
+    }
+}
+
+struct CellSort {
+    field_id: String,
+    field_type: FieldType,
+    condition: GridSortCondition,
+    operation: Box<dyn CellSortOperation + Send + Sync>,
+}
+
+impl CellSort {
+    // Return None if the cell is empty or the cell data was created by another FieldType.
+    fn sort_key(&self, row_rev: &RowRevision) -> Option<CellSortKey> {
+        let cell_rev = row_rev.cells.get(&self.field_id)?;
+        let any_cell_data = AnyCellData::try_from(cell_rev).ok()?;
+        if any_cell_data.field_type != self.field_type {
+            return None;
+        }
+        self.operation.sort_key(any_cell_data).ok()?
+    }
+}
+
+fn make_cell_sort_operation(field_rev: &FieldRevision) -> Option<Box<dyn CellSortOperation + Send + Sync>> {
+    let field_type_rev = field_rev.field_type_rev;
+    let operation: Box<dyn CellSortOperation + Send + Sync> = match FieldType::from(field_type_rev) {
+        FieldType::RichText => Box::new(field_rev.get_type_option_entry::<RichTextTypeOption>(field_type_rev)?),
+        FieldType::Number => Box::new(field_rev.get_type_option_entry::<NumberTypeOption>(field_type_rev)?),
+        FieldType::DateTime => Box::new(field_rev.get_type_option_entry::<DateTypeOption>(field_type_rev)?),
+        FieldType::SingleSelect => {
+            Box::new(field_rev.get_type_option_entry::<SingleSelectTypeOptionPB>(field_type_rev)?)
+        }
+        FieldType::MultiSelect => Box::new(field_rev.get_type_option_entry::<MultiSelectTypeOption>(field_type_rev)?),
+        FieldType::Checkbox => Box::new(field_rev.get_type_option_entry::<CheckboxTypeOption>(field_type_rev)?),
+        FieldType::URL => Box::new(field_rev.get_type_option_entry::<URLTypeOption>(field_type_rev)?),
+        FieldType::Formula => Box::new(field_rev.get_type_option_entry::<FormulaTypeOption>(field_type_rev)?),
+        FieldType::Relation => Box::new(field_rev.get_type_option_entry::<RelationTypeOption>(field_type_rev)?),
+        FieldType::Rollup => Box::new(field_rev.get_type_option_entry::<RollupTypeOption>(field_type_rev)?),
+    };
+    Some(operation)
+}
+
+/// The sort keys of each row are computed once before sorting. The sort is stable, so the rows
+/// with the same keys keep their original order.
+fn sort_row_revs(row_revs: &mut Vec<Arc<RowRevision>>, cell_sorts: &[CellSort]) {
+    let mut rows_with_keys = row_revs
+        .drain(..)
+        .map(|row_rev| {
+            let sort_keys = cell_sorts
+                .iter()
+                .map(|cell_sort| cell_sort.sort_key(&row_rev))
+                .collect::<Vec<Option<CellSortKey>>>();
+            (row_rev, sort_keys)
+        })
+        .collect::<Vec<_>>();
+
+    rows_with_keys.sort_by(|(_, left_keys), (_, right_keys)| {
+        cell_sorts
+            .iter()
+            .zip(left_keys.iter().zip(right_keys.iter()))
+            .map(|(cell_sort, (left, right))| cmp_sort_key(left, right, &cell_sort.condition))
+            .find(|ordering| ordering != &Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+
+    row_revs.extend(rows_with_keys.into_iter().map(|(row_rev, _)| row_rev));
+}
+
+// The empty cells are always placed after the others no matter what the condition is.
+fn cmp_sort_key(left: &Option<CellSortKey>, right: &Option<CellSortKey>, condition: &GridSortCondition) -> Ordering {
+    match (left, right) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(left), Some(right)) => match condition {
+            GridSortCondition::Ascending => left.cmp(right),
+            GridSortCondition::Descending => right.cmp(left),
+        },
+    }
+}
+
+pub struct GridSortChangeset {
+    is_changed: bool,
+}
+
+impl GridSortChangeset {
+    fn is_changed(&self) -> bool {
+        self.is_changed
+    }
+}
+
+impl std::convert::From<&GridSettingChangesetParams> for GridSortChangeset {
+    fn from(params: &GridSettingChangesetParams) -> Self {
+        GridSortChangeset {
+            is_changed: params.is_sort_changed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cmp_sort_key;
+    use crate::entities::GridSortCondition;
+    use crate::services::sort::CellSortKey;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn empty_cell_is_placed_last_test() {
+        let empty = None;
+        let text = Some(CellSortKey::from_text("A"));
+        for condition in [GridSortCondition::Ascending, GridSortCondition::Descending] {
+            assert_eq!(cmp_sort_key(&empty, &text, &condition), Ordering::Greater);
+            assert_eq!(cmp_sort_key(&text, &empty, &condition), Ordering::Less);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crate::services::GRID_SYNC_INTERVAL_IN_MILLIS;
+use bytes::Bytes;
+use flowy_error::FlowyError;
+use flowy_revision::*;
+use flowy_sync::{
+    client_grid::{GridBlockRevisionPad, GridRevisionPad},
+    entities::{
+        revision::RevisionRange,
+        ws_data::{ClientRevisionWSData, NewDocumentUser, ServerRevisionWSDataType},
+    },
+};
+use lib_infra::future::{BoxResultFuture, FutureResult};
+use lib_ot::core::{OperationTransformable, PhantomAttributes, PlainTextDelta};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::RwLock;
+
+#[allow(dead_code)]
+pub(crate) async fn make_grid_ws_manager(
+    user_id: &str,
+    grid_id: &str,
+    rev_manager: Arc<RevisionManager>,
+    web_socket: Arc<dyn RevisionWebSocket>,
+    grid_pad: Arc<RwLock<GridRevisionPad>>,
+) -> Arc<RevisionWebSocketManager> {
+    let resolver = Arc::new(GridConflictResolver { grid_pad });
+    make_ws_manager("Grid", user_id, grid_id, rev_manager, web_socket, resolver)
+}
+
+#[allow(dead_code)]
+pub(crate) async fn make_grid_block_ws_manager(
+    user_id: &str,
+    block_id: &str,
+    rev_manager: Arc<RevisionManager>,
+    web_socket: Arc<dyn RevisionWebSocket>,
+    block_pad: Arc<RwLock<GridBlockRevisionPad>>,
+) -> Arc<RevisionWebSocketManager> {
+    let resolver = Arc::new(GridBlockConflictResolver { block_pad });
+    make_ws_manager("GridBlock", user_id, block_id, rev_manager, web_socket, resolver)
+}
+
+fn make_ws_manager(
+    object_name: &str,
+    user_id: &str,
+    object_id: &str,
+    rev_manager: Arc<RevisionManager>,
+    web_socket: Arc<dyn RevisionWebSocket>,
+    resolver: Arc<dyn ConflictResolver<PhantomAttributes> + Send + Sync>,
+) -> Arc<RevisionWebSocketManager> {
+    let ws_data_provider = Arc::new(WSDataProvider::new(object_id, Arc::new(rev_manager.clone())));
+    let conflict_controller = ConflictController::<PhantomAttributes>::new(
+        user_id,
+        resolver,
+        Arc::new(ws_data_provider.clone()),
+        rev_manager,
+    );
+    let ws_data_stream = Arc::new(GridRevisionWSDataStream::new(conflict_controller));
+    let ws_data_sink = Arc::new(GridWSDataSink(ws_data_provider));
+    let ping_duration = Duration::from_millis(GRID_SYNC_INTERVAL_IN_MILLIS);
+    Arc::new(RevisionWebSocketManager::new(
+        object_name,
+        object_id,
+        web_socket,
+        ws_data_sink,
+        ws_data_stream,
+        ping_duration,
+    ))
+}
+
+pub(crate) struct GridWSDataSink(Arc<WSDataProvider>);
+impl RevisionWebSocketSink for GridWSDataSink {
+    fn next(&self) -> FutureResult<Option<ClientRevisionWSData>, FlowyError> {
+        let sink_provider = self.0.clone();
+        FutureResult::new(async move { sink_provider.next().await })
+    }
+}
+
+struct GridConflictResolver {
+    grid_pad: Arc<RwLock<GridRevisionPad>>,
+}
+
+impl ConflictResolver<PhantomAttributes> for GridConflictResolver {
+    fn compose_delta(&self, delta: PlainTextDelta) -> BoxResultFuture<DeltaMD5, FlowyError> {
+        let grid_pad = self.grid_pad.clone();
+        Box::pin(async move {
+            let md5 = grid_pad.write().await.compose_remote_delta(delta)?;
+            Ok(md5)
+        })
+    }
+
+    fn transform_delta(
+        &self,
+        delta: PlainTextDelta,
+    ) -> BoxResultFuture<TransformDeltas<PhantomAttributes>, FlowyError> {
+        let grid_pad = self.grid_pad.clone();
+        Box::pin(async move {
+            let read_guard = grid_pad.read().await;
+            if read_guard.delta().utf16_target_len == delta.utf16_base_len {
+                // The remote delta is based on the current delta, so it can be composed directly
+                // and there is nothing to send back to the server.
+                return Ok(TransformDeltas {
+                    client_prime: delta,
+                    server_prime: Some(PlainTextDelta::default()),
+                });
+            }
+
+            let (server_prime, client_prime) = read_guard.delta().transform(&delta)?;
+            Ok(TransformDeltas {
+                client_prime,
+                server_prime: Some(server_prime),
+            })
+        })
+    }
+
+    fn reset_delta(&self, delta: PlainTextDelta) -> BoxResultFuture<DeltaMD5, FlowyError> {
+        let grid_pad = self.grid_pad.clone();
+        Box::pin(async move {
+            let md5 = grid_pad.write().await.reset_grid(delta)?;
+            Ok(md5)
+        })
+    }
+}
+
+struct GridBlockConflictResolver {
+    block_pad: Arc<RwLock<GridBlockRevisionPad>>,
+}
+
+impl ConflictResolver<PhantomAttributes> for GridBlockConflictResolver {
+    fn compose_delta(&self, delta: PlainTextDelta) -> BoxResultFuture<DeltaMD5, FlowyError> {
+        let block_pad = self.block_pad.clone();
+        Box::pin(async move {
+            let md5 = block_pad.write().await.compose_remote_delta(delta)?;
+            Ok(md5)
+        })
+    }
+
+    fn transform_delta(
+        &self,
+        delta: PlainTextDelta,
+    ) -> BoxResultFuture<TransformDeltas<PhantomAttributes>, FlowyError> {
+        let block_pad = self.block_pad.clone();
+        Box::pin(async move {
+            let read_guard = block_pad.read().await;
+            if read_guard.delta().utf16_target_len == delta.utf16_base_len {
+                // The remote delta is based on the current delta, so it can be composed directly
+                // and there is nothing to send back to the server.
+                return Ok(TransformDeltas {
+                    client_prime: delta,
+                    server_prime: Some(PlainTextDelta::default()),
+                });
+            }
+
+            let (server_prime, client_prime) = read_guard.delta().transform(&delta)?;
+            Ok(TransformDeltas {
+                client_prime,
+                server_prime: Some(server_prime),
+            })
+        })
+    }
+
+    fn reset_delta(&self, delta: PlainTextDelta) -> BoxResultFuture<DeltaMD5, FlowyError> {
+        let block_pad = self.block_pad.clone();
+        Box::pin(async move {
+            let md5 = block_pad.write().await.reset_block(delta)?;
+            Ok(md5)
+        })
+    }
+}
+
+struct GridRevisionWSDataStream {
+    conflict_controller: Arc<PlainTextConflictController>,
+}
+
+impl GridRevisionWSDataStream {
+    pub fn new(conflict_controller: PlainTextConflictController) -> Self {
+        Self {
+            conflict_controller: Arc::new(conflict_controller),
+        }
+    }
+}
+
+impl RevisionWSDataStream for GridRevisionWSDataStream {
+    fn receive_push_revision(&self, bytes: Bytes) -> BoxResultFuture<(), FlowyError> {
+        let resolver = self.conflict_controller.clone();
+        Box::pin(async move { resolver.receive_bytes(bytes).await })
+    }
+
+    fn receive_ack(&self, id: String, ty: ServerRevisionWSDataType) -> BoxResultFuture<(), FlowyError> {
+        let resolver = self.conflict_controller.clone();
+        Box::pin(async move { resolver.ack_revision(id, ty).await })
+    }
+
+    fn receive_new_user_connect(&self, _new_user: NewDocumentUser) -> BoxResultFuture<(), FlowyError> {
+        // Do nothing by now, just a placeholder for future extension.
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn pull_revisions_in_range(&self, range: RevisionRange) -> BoxResultFuture<(), FlowyError> {
+        let resolver = self.conflict_controller.clone();
+        Box::pin(async move { resolver.send_revisions(range).await })
+    }
+}
@@ -244,6 +244,20 @@ impl GridRowTest {
                 assert_eq!(cell_data.content, expected);
                 // assert_eq!(cell_data.url, expected);
             }
+            FieldType::Formula | FieldType::Rollup => {
+                let cell_data = self.editor.get_cell_bytes(&cell_id).await.unwrap();
+                assert_eq!(cell_data.to_string(), expected);
+            }
+            FieldType::Relation => {
+                let cell_data = self
+                    .editor
+                    .get_cell_bytes(&cell_id)
+                    .await
+                    .unwrap()
+                    .with_parser(RelationCellDataParser())
+                    .unwrap();
+                assert_eq!(cell_data.row_ids.join(","), expected);
+            }
         }
     }
 }
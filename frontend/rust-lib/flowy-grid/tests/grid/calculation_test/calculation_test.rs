@@ -0,0 +1,176 @@
+use crate::grid::calculation_test::script::CalculationScript::*;
+use crate::grid::calculation_test::script::*;
+use flowy_grid::entities::{
+    CellChangesetPB, CheckboxCondition, CreateGridFilterPayloadPB, FieldType, GridCalculationType,
+};
+
+#[tokio::test]
+async fn grid_calculation_create_and_delete_test() {
+    let mut test = GridCalculationTest::new().await;
+    let field_id = test.get_field_rev(FieldType::Number).id.clone();
+    let scripts = vec![
+        UpdateCalculation {
+            field_id: field_id.clone(),
+            calculation_type: Some(GridCalculationType::Sum),
+        },
+        AssertCalculationCount { count: 1 },
+        // The calculation of the same field is replaced.
+        UpdateCalculation {
+            field_id: field_id.clone(),
+            calculation_type: Some(GridCalculationType::Max),
+        },
+        AssertCalculationCount { count: 1 },
+        AssertCalculationValue {
+            field_id: field_id.clone(),
+            expected: "5".to_owned(),
+        },
+        UpdateCalculation {
+            field_id,
+            calculation_type: None,
+        },
+        AssertCalculationCount { count: 0 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_calculation_number_test() {
+    let mut test = GridCalculationTest::new().await;
+    let field_id = test.get_field_rev(FieldType::Number).id.clone();
+    for (calculation_type, expected) in [
+        (GridCalculationType::Sum, "15"),
+        (GridCalculationType::Average, "3"),
+        (GridCalculationType::Min, "1"),
+        (GridCalculationType::Max, "5"),
+        (GridCalculationType::Count, "5"),
+    ] {
+        let scripts = vec![
+            UpdateCalculation {
+                field_id: field_id.clone(),
+                calculation_type: Some(calculation_type),
+            },
+            AssertCalculationValue {
+                field_id: field_id.clone(),
+                expected: expected.to_owned(),
+            },
+        ];
+        test.run_scripts(scripts).await;
+    }
+}
+
+#[tokio::test]
+async fn grid_calculation_count_empty_and_unique_test() {
+    let mut test = GridCalculationTest::new().await;
+    let text_field_id = test.get_field_rev(FieldType::RichText).id.clone();
+    let select_field_id = test.get_field_rev(FieldType::SingleSelect).id.clone();
+    let scripts = vec![
+        UpdateCalculation {
+            field_id: text_field_id.clone(),
+            calculation_type: Some(GridCalculationType::CountEmpty),
+        },
+        UpdateCalculation {
+            field_id: select_field_id.clone(),
+            calculation_type: Some(GridCalculationType::CountUnique),
+        },
+        AssertCalculationValue {
+            field_id: text_field_id.clone(),
+            expected: "0".to_owned(),
+        },
+        AssertCalculationValue {
+            field_id: select_field_id.clone(),
+            expected: "3".to_owned(),
+        },
+        CreateEmptyRow,
+        AssertCalculationValue {
+            field_id: text_field_id,
+            expected: "1".to_owned(),
+        },
+        AssertCalculationValue {
+            field_id: select_field_id,
+            expected: "3".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_calculation_percent_checked_test() {
+    let mut test = GridCalculationTest::new().await;
+    let field_id = test.get_field_rev(FieldType::Checkbox).id.clone();
+    let scripts = vec![
+        UpdateCalculation {
+            field_id: field_id.clone(),
+            calculation_type: Some(GridCalculationType::PercentChecked),
+        },
+        AssertCalculationValue {
+            field_id: field_id.clone(),
+            expected: "40%".to_owned(),
+        },
+        DeleteRow { row_index: 0 },
+        AssertCalculationValue {
+            field_id,
+            expected: "25%".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_calculation_update_after_cell_changed_test() {
+    let mut test = GridCalculationTest::new().await;
+    let field_id = test.get_field_rev(FieldType::Number).id.clone();
+    let changeset = CellChangesetPB {
+        grid_id: test.grid_id.clone(),
+        row_id: test.row_revs[0].id.clone(),
+        field_id: field_id.clone(),
+        content: Some("10".to_owned()),
+    };
+    let scripts = vec![
+        UpdateCalculation {
+            field_id: field_id.clone(),
+            calculation_type: Some(GridCalculationType::Sum),
+        },
+        AssertCalculationValue {
+            field_id: field_id.clone(),
+            expected: "15".to_owned(),
+        },
+        UpdateCell { changeset },
+        AssertCalculationValue {
+            field_id,
+            expected: "24".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_calculation_over_filtered_rows_test() {
+    let mut test = GridCalculationTest::new().await;
+    let number_field_id = test.get_field_rev(FieldType::Number).id.clone();
+    let checkbox_field_rev = test.get_field_rev(FieldType::Checkbox).clone();
+    let payload = CreateGridFilterPayloadPB::new(&checkbox_field_rev, CheckboxCondition::IsChecked, None);
+    let changeset = CellChangesetPB {
+        grid_id: test.grid_id.clone(),
+        row_id: test.row_revs[2].id.clone(),
+        field_id: checkbox_field_rev.id.clone(),
+        content: Some("true".to_owned()),
+    };
+    let scripts = vec![
+        UpdateCalculation {
+            field_id: number_field_id.clone(),
+            calculation_type: Some(GridCalculationType::Sum),
+        },
+        InsertGridTableFilter { payload },
+        AssertCalculationValue {
+            field_id: number_field_id.clone(),
+            expected: "3".to_owned(),
+        },
+        // The third row satisfies the filter after it's checked.
+        UpdateCell { changeset },
+        AssertCalculationValue {
+            field_id: number_field_id,
+            expected: "6".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
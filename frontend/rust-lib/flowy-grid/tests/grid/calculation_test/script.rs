@@ -0,0 +1,118 @@
+#![cfg_attr(rustfmt, rustfmt::skip)]
+#![allow(clippy::all)]
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use flowy_grid::entities::{CellChangesetPB, CreateGridFilterPayloadPB, GridCalculationChangesetPayloadPB, GridCalculationPB, GridCalculationType, GridLayoutType};
+use flowy_grid::services::setting::GridSettingChangesetBuilder;
+use flowy_sync::entities::grid::{CreateGridFilterParams, GridSettingChangesetParams};
+use crate::grid::grid_editor::GridEditorTest;
+
+pub enum CalculationScript {
+    UpdateCalculation {
+        field_id: String,
+        calculation_type: Option<GridCalculationType>,
+    },
+    InsertGridTableFilter {
+        payload: CreateGridFilterPayloadPB,
+    },
+    UpdateCell {
+        changeset: CellChangesetPB,
+    },
+    CreateEmptyRow,
+    DeleteRow {
+        row_index: usize,
+    },
+    AssertCalculationCount {
+        count: usize,
+    },
+    AssertCalculationValue {
+        field_id: String,
+        expected: String,
+    },
+}
+
+pub struct GridCalculationTest {
+    inner: GridEditorTest,
+}
+
+impl GridCalculationTest {
+    pub async fn new() -> Self {
+        let editor_test = GridEditorTest::new().await;
+        Self {
+            inner: editor_test
+        }
+    }
+
+    pub async fn run_scripts(&mut self, scripts: Vec<CalculationScript>) {
+        for script in scripts {
+            self.run_script(script).await;
+        }
+    }
+
+    pub async fn grid_calculations(&self) -> Vec<GridCalculationPB> {
+        let layout_type = GridLayoutType::Table;
+        self.editor.get_grid_calculations(&layout_type).await.unwrap()
+    }
+
+    pub async fn run_script(&mut self, script: CalculationScript) {
+        match script {
+            CalculationScript::UpdateCalculation { field_id, calculation_type } => {
+                let payload = GridCalculationChangesetPayloadPB {
+                    grid_id: self.grid_id.clone(),
+                    layout_type: GridLayoutType::Table,
+                    field_id,
+                    calculation_type,
+                };
+                let params: GridSettingChangesetParams = payload.try_into().unwrap();
+                let _ = self.editor.update_grid_setting(params).await.unwrap();
+            }
+            CalculationScript::InsertGridTableFilter { payload } => {
+                let params: CreateGridFilterParams = payload.try_into().unwrap();
+                let layout_type = GridLayoutType::Table;
+                let params = GridSettingChangesetBuilder::new(&self.grid_id, &layout_type)
+                    .insert_filter(params)
+                    .build();
+                let _ = self.editor.update_grid_setting(params).await.unwrap();
+            }
+            CalculationScript::UpdateCell { changeset } => {
+                let _ = self.editor.update_cell(changeset).await.unwrap();
+            }
+            CalculationScript::CreateEmptyRow => {
+                let _ = self.editor.create_row(None).await.unwrap();
+                self.row_revs = self.get_row_revs().await;
+            }
+            CalculationScript::DeleteRow { row_index } => {
+                let row_id = self.row_revs[row_index].id.clone();
+                let _ = self.editor.delete_row(&row_id).await.unwrap();
+                self.row_revs = self.get_row_revs().await;
+            }
+            CalculationScript::AssertCalculationCount { count } => {
+                assert_eq!(count, self.grid_calculations().await.len());
+            }
+            CalculationScript::AssertCalculationValue { field_id, expected } => {
+                let calculation = self
+                    .grid_calculations()
+                    .await
+                    .into_iter()
+                    .find(|calculation| calculation.field_id == field_id)
+                    .unwrap();
+                assert_eq!(calculation.value, expected);
+            }
+        }
+    }
+}
+
+impl std::ops::Deref for GridCalculationTest {
+    type Target = GridEditorTest;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl std::ops::DerefMut for GridCalculationTest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
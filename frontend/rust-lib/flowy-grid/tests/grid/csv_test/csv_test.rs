@@ -0,0 +1,77 @@
+use crate::grid::csv_test::script::CsvScript::*;
+use crate::grid::csv_test::script::*;
+use flowy_grid::entities::FieldType;
+
+#[tokio::test]
+async fn grid_export_csv_test() {
+    let mut test = GridCsvTest::new().await;
+    let header = test
+        .field_revs
+        .iter()
+        .map(|field_rev| field_rev.name.clone())
+        .collect::<Vec<String>>()
+        .join(",");
+    let scripts = vec![AssertExportedRecords { records: vec![header] }];
+    test.run_scripts(scripts).await;
+
+    let content = test.editor.export_csv().await.unwrap();
+    let records = content.lines().skip(1).collect::<Vec<&str>>();
+    assert_eq!(records.len(), test.row_revs.len());
+    assert!(records[0].starts_with("A,"));
+    assert!(records[4].starts_with("E,"));
+}
+
+#[tokio::test]
+async fn grid_import_csv_test() {
+    let mut test = GridCsvTest::new().await;
+    let content = "Name,Price,Done,Date,Link,Status,Tags,Note\n\
+                   A,1,Yes,2022-03-14,https://appflowy.io,Planned,\"Google,Twitter\",hello\n\
+                   B,2.5,No,2022-03-15,https://github.com,Completed,Google,world\n\
+                   C,3,No,2022-03-16,,Planned,,\n";
+    let scripts = vec![
+        ImportGrid {
+            content: content.to_owned(),
+            is_err: false,
+        },
+        AssertImportedFieldTypes {
+            field_types: vec![
+                FieldType::RichText,
+                FieldType::Number,
+                FieldType::Checkbox,
+                FieldType::DateTime,
+                FieldType::URL,
+                FieldType::SingleSelect,
+                FieldType::MultiSelect,
+                FieldType::RichText,
+            ],
+        },
+        AssertImportedRowCount { expected: 3 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_import_export_csv_round_trip_test() {
+    let mut test = GridCsvTest::new().await;
+    let content = "Name,Status,Link\nA,Planned,https://appflowy.io\nB,Completed,\nC,Planned,https://github.com\n";
+    let scripts = vec![
+        ImportGrid {
+            content: content.to_owned(),
+            is_err: false,
+        },
+        AssertImportedGridExport {
+            content: content.to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_import_empty_csv_test() {
+    let mut test = GridCsvTest::new().await;
+    let scripts = vec![ImportGrid {
+        content: "".to_owned(),
+        is_err: true,
+    }];
+    test.run_scripts(scripts).await;
+}
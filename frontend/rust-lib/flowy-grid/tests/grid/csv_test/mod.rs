@@ -0,0 +1,2 @@
+mod csv_test;
+mod script;
@@ -0,0 +1,109 @@
+#![cfg_attr(rustfmt, rustfmt::skip)]
+#![allow(clippy::all)]
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use flowy_grid::entities::*;
+use flowy_grid::manager::import_grid_from_csv;
+use flowy_grid::services::grid_editor::GridRevisionEditor;
+use flowy_grid_data_model::revision::*;
+use std::sync::Arc;
+use crate::grid::grid_editor::GridEditorTest;
+
+pub enum CsvScript {
+    /// Assert the records of the exported CSV, each record is a line of the CSV.
+    AssertExportedRecords {
+        records: Vec<String>,
+    },
+    ImportGrid {
+        content: String,
+        is_err: bool,
+    },
+    AssertImportedFieldTypes {
+        field_types: Vec<FieldType>,
+    },
+    AssertImportedRowCount {
+        expected: usize,
+    },
+    /// Assert the CSV that is exported from the imported grid.
+    AssertImportedGridExport {
+        content: String,
+    },
+}
+
+pub struct GridCsvTest {
+    inner: GridEditorTest,
+    pub imported_editor: Option<Arc<GridRevisionEditor>>,
+}
+
+impl GridCsvTest {
+    pub async fn new() -> Self {
+        let editor_test = GridEditorTest::new().await;
+        Self {
+            inner: editor_test,
+            imported_editor: None,
+        }
+    }
+
+    pub async fn run_scripts(&mut self, scripts: Vec<CsvScript>) {
+        for script in scripts {
+            self.run_script(script).await;
+        }
+    }
+
+    pub async fn run_script(&mut self, script: CsvScript) {
+        match script {
+            CsvScript::AssertExportedRecords { records } => {
+                let content = self.editor.export_csv().await.unwrap();
+                let lines = content.lines().collect::<Vec<&str>>();
+                for (index, record) in records.iter().enumerate() {
+                    assert_eq!(lines[index], record);
+                }
+            }
+            CsvScript::ImportGrid { content, is_err } => {
+                let view_id = gen_grid_id();
+                let result = import_grid_from_csv(self.sdk.grid_manager.clone(), &view_id, &content).await;
+                assert_eq!(is_err, result.is_err());
+                if !is_err {
+                    let editor = self.sdk.grid_manager.open_grid(&view_id).await.unwrap();
+                    self.imported_editor = Some(editor);
+                }
+            }
+            CsvScript::AssertImportedFieldTypes { field_types } => {
+                let field_revs = self.imported_editor().get_field_revs(None).await.unwrap();
+                let imported_field_types = field_revs
+                    .iter()
+                    .map(|field_rev| FieldType::from(field_rev.field_type_rev))
+                    .collect::<Vec<FieldType>>();
+                assert_eq!(imported_field_types, field_types);
+            }
+            CsvScript::AssertImportedRowCount { expected } => {
+                let grid = self.imported_editor().get_grid_data().await.unwrap();
+                let row_count: usize = grid.blocks.iter().map(|block| block.rows.len()).sum();
+                assert_eq!(row_count, expected);
+            }
+            CsvScript::AssertImportedGridExport { content } => {
+                let exported_content = self.imported_editor().export_csv().await.unwrap();
+                assert_eq!(exported_content, content);
+            }
+        }
+    }
+
+    fn imported_editor(&self) -> Arc<GridRevisionEditor> {
+        self.imported_editor.clone().unwrap()
+    }
+}
+
+impl std::ops::Deref for GridCsvTest {
+    type Target = GridEditorTest;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl std::ops::DerefMut for GridCsvTest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
@@ -0,0 +1,108 @@
+use crate::grid::filter_test::script::FilterScript::*;
+use crate::grid::filter_test::script::*;
+use flowy_grid::entities::{CreateGridFilterPayloadPB, DateFilterCondition, DateRange, FieldType};
+
+// The date of all the rows in the test grid is 2022-03-14.
+const MARCH_14: i64 = 1647259200;
+const DAY: i64 = 86400;
+
+#[tokio::test]
+async fn grid_filter_date_is_today_test() {
+    let mut test = GridFilterTest::new().await;
+    let payload = create_date_filter(&test, DateFilterCondition::DateIsToday, None);
+    let scripts = vec![
+        InsertGridTableFilter { payload },
+        SetFilterClock {
+            now_timestamp: MARCH_14,
+        },
+        AssertVisibleRowCount { count: 5 },
+        // The same filter hides the rows on the next day.
+        SetFilterClock {
+            now_timestamp: MARCH_14 + DAY,
+        },
+        AssertVisibleRowCount { count: 0 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_date_is_yesterday_test() {
+    let mut test = GridFilterTest::new().await;
+    let payload = create_date_filter(&test, DateFilterCondition::DateIsYesterday, None);
+    let scripts = vec![
+        InsertGridTableFilter { payload },
+        SetFilterClock {
+            now_timestamp: MARCH_14 + DAY,
+        },
+        AssertVisibleRowCount { count: 5 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_date_in_last_days_test() {
+    let mut test = GridFilterTest::new().await;
+    let payload = create_date_filter(&test, DateFilterCondition::DateIsInLastDays, Some(3));
+    let scripts = vec![
+        InsertGridTableFilter { payload },
+        SetFilterClock {
+            now_timestamp: MARCH_14 + 2 * DAY,
+        },
+        AssertVisibleRowCount { count: 5 },
+        SetFilterClock {
+            now_timestamp: MARCH_14 + 3 * DAY,
+        },
+        AssertVisibleRowCount { count: 0 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_date_in_next_days_test() {
+    let mut test = GridFilterTest::new().await;
+    let payload = create_date_filter(&test, DateFilterCondition::DateIsInNextDays, Some(7));
+    let scripts = vec![
+        InsertGridTableFilter { payload },
+        SetFilterClock {
+            now_timestamp: MARCH_14 - 6 * DAY,
+        },
+        AssertVisibleRowCount { count: 5 },
+        SetFilterClock {
+            now_timestamp: MARCH_14 + DAY,
+        },
+        AssertVisibleRowCount { count: 0 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_date_is_overdue_test() {
+    let mut test = GridFilterTest::new().await;
+    let payload = create_date_filter(&test, DateFilterCondition::DateIsOverdue, None);
+    let scripts = vec![
+        InsertGridTableFilter { payload },
+        SetFilterClock {
+            now_timestamp: MARCH_14,
+        },
+        AssertVisibleRowCount { count: 0 },
+        SetFilterClock {
+            now_timestamp: MARCH_14 + DAY,
+        },
+        AssertVisibleRowCount { count: 5 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+fn create_date_filter(
+    test: &GridFilterTest,
+    condition: DateFilterCondition,
+    days: Option<i64>,
+) -> CreateGridFilterPayloadPB {
+    let field_rev = test.get_field_rev(FieldType::DateTime);
+    let content = DateRange {
+        start: None,
+        end: None,
+        days,
+    };
+    CreateGridFilterPayloadPB::new(field_rev, condition, Some(content.to_string()))
+}
@@ -0,0 +1,122 @@
+use crate::grid::filter_test::script::FilterScript::*;
+use crate::grid::filter_test::script::*;
+use flowy_grid::entities::{
+    CheckboxCondition, CreateGridFilterGroupPayloadPB, CreateGridFilterPayloadPB, FieldType, GridFilterLogic,
+    TextFilterCondition,
+};
+
+#[tokio::test]
+async fn grid_filter_without_group_combine_filters_with_and_test() {
+    let mut test = GridFilterTest::new().await;
+    let _ = insert_text_filter(&mut test, "a").await;
+    let _ = insert_checked_filter(&mut test).await;
+    test.run_scripts(vec![AssertVisibleRowCount { count: 1 }]).await;
+
+    let _ = insert_text_filter(&mut test, "c").await;
+    test.run_scripts(vec![AssertVisibleRowCount { count: 0 }]).await;
+}
+
+#[tokio::test]
+async fn grid_filter_group_or_test() {
+    let mut test = GridFilterTest::new().await;
+    let filter_a = insert_text_filter(&mut test, "a").await;
+    let filter_c = insert_text_filter(&mut test, "c").await;
+    let payload = CreateGridFilterGroupPayloadPB::new(GridFilterLogic::Or, vec![filter_a, filter_c], vec![]);
+    let scripts = vec![
+        InsertGridTableFilterGroup { payload },
+        AssertVisibleRowCount { count: 2 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_nested_group_test() {
+    let mut test = GridFilterTest::new().await;
+    let filter_a = insert_text_filter(&mut test, "a").await;
+    let filter_c = insert_text_filter(&mut test, "c").await;
+    let filter_checked = insert_checked_filter(&mut test).await;
+
+    // (Name is a OR Name is c) AND is urgent
+    let or_group = CreateGridFilterGroupPayloadPB::new(GridFilterLogic::Or, vec![filter_a, filter_c], vec![]);
+    let payload = CreateGridFilterGroupPayloadPB::new(GridFilterLogic::And, vec![filter_checked], vec![or_group]);
+    let scripts = vec![
+        InsertGridTableFilterGroup { payload },
+        AssertVisibleRowCount { count: 1 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_group_and_ungrouped_filter_test() {
+    let mut test = GridFilterTest::new().await;
+    let filter_a = insert_text_filter(&mut test, "a").await;
+    let filter_c = insert_text_filter(&mut test, "c").await;
+    let _ = insert_checked_filter(&mut test).await;
+
+    // The checkbox filter isn't in the group, so it's combined with the group by AND.
+    let payload = CreateGridFilterGroupPayloadPB::new(GridFilterLogic::Or, vec![filter_a, filter_c], vec![]);
+    let scripts = vec![
+        InsertGridTableFilterGroup { payload },
+        AssertVisibleRowCount { count: 1 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_group_delete_filter_test() {
+    let mut test = GridFilterTest::new().await;
+    let filter_a = insert_text_filter(&mut test, "a").await;
+    let filter_c = insert_text_filter(&mut test, "c").await;
+    let payload =
+        CreateGridFilterGroupPayloadPB::new(GridFilterLogic::Or, vec![filter_a.clone(), filter_c.clone()], vec![]);
+    test.run_scripts(vec![InsertGridTableFilterGroup { payload }]).await;
+
+    // The deleted filter is removed from the group too.
+    let field_rev = test.get_field_rev(FieldType::RichText).as_ref().clone();
+    let mut expected = test.filter_group().await.unwrap();
+    expected.filter_ids = vec![filter_a];
+    let scripts = vec![
+        DeleteGridTableFilter {
+            filter_id: filter_c,
+            field_rev,
+        },
+        AssertTableFilterGroup {
+            expected: Some(expected),
+        },
+        AssertVisibleRowCount { count: 1 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_group_delete_test() {
+    let mut test = GridFilterTest::new().await;
+    let filter_a = insert_text_filter(&mut test, "a").await;
+    let filter_c = insert_text_filter(&mut test, "c").await;
+    let payload = CreateGridFilterGroupPayloadPB::new(GridFilterLogic::Or, vec![filter_a, filter_c], vec![]);
+    test.run_scripts(vec![InsertGridTableFilterGroup { payload }]).await;
+
+    let filter_group = test.filter_group().await.unwrap();
+    let scripts = vec![
+        DeleteGridTableFilterGroup {
+            filter_group_id: filter_group.id,
+        },
+        AssertTableFilterGroup { expected: None },
+        AssertVisibleRowCount { count: 0 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+async fn insert_text_filter(test: &mut GridFilterTest, content: &str) -> String {
+    let field_rev = test.get_field_rev(FieldType::RichText).clone();
+    let payload = CreateGridFilterPayloadPB::new(&field_rev, TextFilterCondition::Is, Some(content.to_owned()));
+    test.run_scripts(vec![InsertGridTableFilter { payload }]).await;
+    test.grid_filters().await.pop().unwrap().id
+}
+
+async fn insert_checked_filter(test: &mut GridFilterTest) -> String {
+    let field_rev = test.get_field_rev(FieldType::Checkbox).clone();
+    let payload = CreateGridFilterPayloadPB::new(&field_rev, CheckboxCondition::IsChecked, None);
+    test.run_scripts(vec![InsertGridTableFilter { payload }]).await;
+    test.grid_filters().await.pop().unwrap().id
+}
@@ -1,2 +1,4 @@
+mod date_filter_test;
+mod filter_group_test;
 mod script;
 mod text_filter_test;
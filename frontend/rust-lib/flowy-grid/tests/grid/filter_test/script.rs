@@ -3,10 +3,12 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
-use flowy_grid::entities::{CreateGridFilterPayloadPB, GridLayoutType, GridSettingPB};
+use flowy_grid::entities::{CreateGridFilterGroupPayloadPB, CreateGridFilterPayloadPB, GridFilterGroup, GridLayoutType, GridSettingPB};
+use flowy_grid::services::filter::FilterClock;
 use flowy_grid::services::setting::GridSettingChangesetBuilder;
+use std::sync::Arc;
 use flowy_grid_data_model::revision::{FieldRevision, FieldTypeRevision};
-use flowy_sync::entities::grid::{CreateGridFilterParams, DeleteFilterParams, GridSettingChangesetParams};
+use flowy_sync::entities::grid::{CreateGridFilterGroupParams, CreateGridFilterParams, DeleteFilterParams, GridSettingChangesetParams};
 use crate::grid::grid_editor::GridEditorTest;
 
 pub enum FilterScript {
@@ -24,6 +26,21 @@ pub enum FilterScript {
         filter_id: String,
         field_rev: FieldRevision,
     },
+    InsertGridTableFilterGroup {
+        payload: CreateGridFilterGroupPayloadPB,
+    },
+    DeleteGridTableFilterGroup {
+        filter_group_id: String,
+    },
+    AssertTableFilterGroup {
+        expected: Option<GridFilterGroup>,
+    },
+    AssertVisibleRowCount {
+        count: usize,
+    },
+    SetFilterClock {
+        now_timestamp: i64,
+    },
     #[allow(dead_code)]
     AssertGridSetting {
         expected_setting: GridSettingPB,
@@ -73,6 +90,33 @@ impl GridFilterTest {
                     .build();
                 let _ = self.editor.update_grid_setting(params).await.unwrap();
             }
+            FilterScript::InsertGridTableFilterGroup { payload } => {
+                let params: CreateGridFilterGroupParams = payload.try_into().unwrap();
+                let layout_type = GridLayoutType::Table;
+                let params = GridSettingChangesetBuilder::new(&self.grid_id, &layout_type)
+                    .insert_filter_group(params)
+                    .build();
+                let _ = self.editor.update_grid_setting(params).await.unwrap();
+            }
+            FilterScript::DeleteGridTableFilterGroup { filter_group_id } => {
+                let layout_type = GridLayoutType::Table;
+                let params = GridSettingChangesetBuilder::new(&self.grid_id, &layout_type)
+                    .delete_filter_group(&filter_group_id)
+                    .build();
+                let _ = self.editor.update_grid_setting(params).await.unwrap();
+            }
+            FilterScript::AssertTableFilterGroup { expected } => {
+                let filter_group = self.editor.get_grid_setting().await.unwrap().filter_group;
+                assert_eq!(expected, filter_group);
+            }
+            FilterScript::AssertVisibleRowCount { count } => {
+                let snapshots = self.editor.filtered_grid_block_snapshots(None).await.unwrap();
+                let row_count: usize = snapshots.iter().map(|snapshot| snapshot.row_revs.len()).sum();
+                assert_eq!(count, row_count);
+            }
+            FilterScript::SetFilterClock { now_timestamp } => {
+                self.editor.set_filter_clock(Arc::new(FixedClock(now_timestamp))).await;
+            }
             FilterScript::AssertGridSetting { expected_setting } => {
                 let setting = self.editor.get_grid_setting().await.unwrap();
                 assert_eq!(expected_setting, setting);
@@ -82,6 +126,20 @@ impl GridFilterTest {
 }
 
 
+struct FixedClock(i64);
+
+impl FilterClock for FixedClock {
+    fn now_timestamp(&self) -> i64 {
+        self.0
+    }
+}
+
+impl GridFilterTest {
+    pub async fn filter_group(&self) -> Option<GridFilterGroup> {
+        self.editor.get_grid_setting().await.unwrap().filter_group
+    }
+}
+
 impl std::ops::Deref for GridFilterTest {
     type Target = GridEditorTest;
 
@@ -0,0 +1,270 @@
+use crate::grid::formula_test::script::FormulaScript::*;
+use crate::grid::formula_test::script::*;
+use flowy_grid::entities::{CheckboxCondition, CreateGridFilterPayloadPB, FieldType, NumberFilterCondition};
+use flowy_grid::services::field::FormulaResultType;
+
+#[tokio::test]
+async fn grid_formula_compute_on_insert_test() {
+    let mut test = GridFormulaTest::new().await;
+    let price_field_id = test.field_id(FieldType::Number);
+    let field_rev = make_formula_field(
+        "Double price",
+        &format!("{{{}}} * 2", price_field_id),
+        FormulaResultType::Number,
+    );
+    let field_id = field_rev.id.clone();
+    let scripts = vec![
+        InsertFormulaField {
+            field_rev,
+            is_err: false,
+        },
+        AssertCellContent {
+            row_index: 0,
+            field_id: field_id.clone(),
+            expected: "2".to_owned(),
+        },
+        AssertCellContent {
+            row_index: 4,
+            field_id,
+            expected: "10".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_recompute_after_cell_changed_test() {
+    let mut test = GridFormulaTest::new().await;
+    let price_field_id = test.field_id(FieldType::Number);
+    let double_field = make_formula_field(
+        "Double price",
+        &format!("{{{}}} * 2", price_field_id),
+        FormulaResultType::Number,
+    );
+    let double_field_id = double_field.id.clone();
+    // The formula that references the other formula is recomputed as well.
+    let plus_one_field = make_formula_field(
+        "Double price plus one",
+        &format!("{{{}}} + 1", double_field_id),
+        FormulaResultType::Number,
+    );
+    let plus_one_field_id = plus_one_field.id.clone();
+    let scripts = vec![
+        InsertFormulaField {
+            field_rev: double_field,
+            is_err: false,
+        },
+        InsertFormulaField {
+            field_rev: plus_one_field,
+            is_err: false,
+        },
+        AssertCellContent {
+            row_index: 0,
+            field_id: plus_one_field_id.clone(),
+            expected: "3".to_owned(),
+        },
+        UpdateCell {
+            row_index: 0,
+            field_id: price_field_id,
+            content: "10".to_owned(),
+            is_err: false,
+        },
+        AssertCellContent {
+            row_index: 0,
+            field_id: double_field_id,
+            expected: "20".to_owned(),
+        },
+        AssertCellContent {
+            row_index: 0,
+            field_id: plus_one_field_id,
+            expected: "21".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_text_and_checkbox_test() {
+    let mut test = GridFormulaTest::new().await;
+    let name_field_id = test.field_id(FieldType::RichText);
+    let checkbox_field_id = test.field_id(FieldType::Checkbox);
+    let field_rev = make_formula_field(
+        "Label",
+        &format!("if({{{}}}, {{{}}} & \"!\", \"-\")", checkbox_field_id, name_field_id),
+        FormulaResultType::Text,
+    );
+    let field_id = field_rev.id.clone();
+    let scripts = vec![
+        InsertFormulaField {
+            field_rev,
+            is_err: false,
+        },
+        AssertCellContent {
+            row_index: 0,
+            field_id: field_id.clone(),
+            expected: "A!".to_owned(),
+        },
+        AssertCellContent {
+            row_index: 2,
+            field_id,
+            expected: "-".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_compute_new_row_test() {
+    let mut test = GridFormulaTest::new().await;
+    let price_field_id = test.field_id(FieldType::Number);
+    let field_rev = make_formula_field(
+        "Price plus one",
+        &format!("{{{}}} + 1", price_field_id),
+        FormulaResultType::Number,
+    );
+    let field_id = field_rev.id.clone();
+    let scripts = vec![
+        InsertFormulaField {
+            field_rev,
+            is_err: false,
+        },
+        CreateEmptyRow,
+        // The empty number cell is treated as zero.
+        AssertCellContent {
+            row_index: 5,
+            field_id,
+            expected: "1".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_cell_is_read_only_test() {
+    let mut test = GridFormulaTest::new().await;
+    let field_rev = make_formula_field("Constant", "1 + 2", FormulaResultType::Number);
+    let field_id = field_rev.id.clone();
+    let scripts = vec![
+        InsertFormulaField {
+            field_rev,
+            is_err: false,
+        },
+        UpdateCell {
+            row_index: 0,
+            field_id: field_id.clone(),
+            content: "10".to_owned(),
+            is_err: true,
+        },
+        AssertCellContent {
+            row_index: 0,
+            field_id,
+            expected: "3".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_invalid_expression_test() {
+    let mut test = GridFormulaTest::new().await;
+    let scripts = vec![
+        InsertFormulaField {
+            field_rev: make_formula_field("Invalid", "1 +", FormulaResultType::Number),
+            is_err: true,
+        },
+        InsertFormulaField {
+            field_rev: make_formula_field("Unknown field", "{unknown} + 1", FormulaResultType::Number),
+            is_err: true,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_cycle_detected_test() {
+    let mut test = GridFormulaTest::new().await;
+    let price_field_id = test.field_id(FieldType::Number);
+    let first_field = make_formula_field(
+        "First",
+        &format!("{{{}}} + 1", price_field_id),
+        FormulaResultType::Number,
+    );
+    let first_field_id = first_field.id.clone();
+    let second_field = make_formula_field(
+        "Second",
+        &format!("{{{}}} * 2", first_field_id),
+        FormulaResultType::Number,
+    );
+    let second_field_id = second_field.id.clone();
+    let scripts = vec![
+        InsertFormulaField {
+            field_rev: first_field,
+            is_err: false,
+        },
+        InsertFormulaField {
+            field_rev: second_field,
+            is_err: false,
+        },
+        UpdateFormulaExpression {
+            field_id: first_field_id.clone(),
+            expression: format!("{{{}}} + 1", second_field_id),
+            result_type: FormulaResultType::Number,
+            is_err: true,
+        },
+        UpdateFormulaExpression {
+            field_id: first_field_id.clone(),
+            expression: format!("{{{}}} + 1", first_field_id),
+            result_type: FormulaResultType::Number,
+            is_err: true,
+        },
+        // The rejected expressions are not saved.
+        AssertCellContent {
+            row_index: 0,
+            field_id: second_field_id,
+            expected: "4".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_number_filter_test() {
+    let mut test = GridFormulaTest::new().await;
+    let price_field_id = test.field_id(FieldType::Number);
+    let field_rev = make_formula_field(
+        "Double price",
+        &format!("{{{}}} * 2", price_field_id),
+        FormulaResultType::Number,
+    );
+    let payload = CreateGridFilterPayloadPB::new(&field_rev, NumberFilterCondition::GreaterThan, Some("5".to_owned()));
+    let scripts = vec![
+        InsertFormulaField {
+            field_rev,
+            is_err: false,
+        },
+        InsertGridTableFilter { payload },
+        AssertVisibleRowCount { count: 3 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_formula_checkbox_filter_test() {
+    let mut test = GridFormulaTest::new().await;
+    let price_field_id = test.field_id(FieldType::Number);
+    let field_rev = make_formula_field(
+        "Expensive",
+        &format!("{{{}}} >= 4", price_field_id),
+        FormulaResultType::Checkbox,
+    );
+    let payload = CreateGridFilterPayloadPB::new(&field_rev, CheckboxCondition::IsChecked, None);
+    let scripts = vec![
+        InsertFormulaField {
+            field_rev,
+            is_err: false,
+        },
+        InsertGridTableFilter { payload },
+        AssertVisibleRowCount { count: 2 },
+    ];
+    test.run_scripts(scripts).await;
+}
@@ -0,0 +1,2 @@
+mod formula_test;
+mod script;
@@ -0,0 +1,165 @@
+#![cfg_attr(rustfmt, rustfmt::skip)]
+#![allow(clippy::all)]
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use flowy_grid::entities::*;
+use flowy_grid::services::field::*;
+use flowy_grid::services::setting::GridSettingChangesetBuilder;
+use flowy_grid_data_model::revision::*;
+use flowy_sync::entities::grid::CreateGridFilterParams;
+use crate::grid::grid_editor::GridEditorTest;
+
+pub enum FormulaScript {
+    InsertFormulaField {
+        field_rev: FieldRevision,
+        is_err: bool,
+    },
+    UpdateFormulaExpression {
+        field_id: String,
+        expression: String,
+        result_type: FormulaResultType,
+        is_err: bool,
+    },
+    UpdateCell {
+        row_index: usize,
+        field_id: String,
+        content: String,
+        is_err: bool,
+    },
+    CreateEmptyRow,
+    AssertCellContent {
+        row_index: usize,
+        field_id: String,
+        expected: String,
+    },
+    InsertGridTableFilter {
+        payload: CreateGridFilterPayloadPB,
+    },
+    AssertVisibleRowCount {
+        count: usize,
+    },
+}
+
+pub struct GridFormulaTest {
+    inner: GridEditorTest,
+}
+
+impl GridFormulaTest {
+    pub async fn new() -> Self {
+        let editor_test = GridEditorTest::new().await;
+        Self {
+            inner: editor_test
+        }
+    }
+
+    pub fn field_id(&self, field_type: FieldType) -> String {
+        self.get_field_rev(field_type).id.clone()
+    }
+
+    pub async fn run_scripts(&mut self, scripts: Vec<FormulaScript>) {
+        for script in scripts {
+            self.run_script(script).await;
+        }
+    }
+
+    pub async fn run_script(&mut self, script: FormulaScript) {
+        match script {
+            FormulaScript::InsertFormulaField { field_rev, is_err } => {
+                let params = make_insert_field_params(&self.grid_id, field_rev);
+                let result = self.editor.insert_field(params).await;
+                assert_eq!(is_err, result.is_err());
+            }
+            FormulaScript::UpdateFormulaExpression { field_id, expression, result_type, is_err } => {
+                let type_option = FormulaTypeOption { expression, result_type };
+                let type_option_data = type_option.protobuf_bytes().to_vec();
+                let result = self.editor.update_field_type_option(&self.grid_id, &field_id, type_option_data).await;
+                assert_eq!(is_err, result.is_err());
+            }
+            FormulaScript::UpdateCell { row_index, field_id, content, is_err } => {
+                let row_rev = self.get_row_revs().await[row_index].clone();
+                let changeset = CellChangesetPB {
+                    grid_id: self.grid_id.clone(),
+                    row_id: row_rev.id.clone(),
+                    field_id,
+                    content: Some(content),
+                };
+                let result = self.editor.update_cell(changeset).await;
+                assert_eq!(is_err, result.is_err());
+            }
+            FormulaScript::CreateEmptyRow => {
+                let _ = self.editor.create_row(None).await.unwrap();
+            }
+            FormulaScript::AssertCellContent { row_index, field_id, expected } => {
+                let row_rev = self.get_row_revs().await[row_index].clone();
+                let params = GridCellIdParams {
+                    grid_id: self.grid_id.clone(),
+                    field_id,
+                    row_id: row_rev.id.clone(),
+                };
+                let content = self.editor.get_cell_bytes(&params).await.map(|bytes| bytes.to_string()).unwrap_or_default();
+                assert_eq!(expected, content);
+            }
+            FormulaScript::InsertGridTableFilter { payload } => {
+                let params: CreateGridFilterParams = payload.try_into().unwrap();
+                let layout_type = GridLayoutType::Table;
+                let params = GridSettingChangesetBuilder::new(&self.grid_id, &layout_type)
+                    .insert_filter(params)
+                    .build();
+                let _ = self.editor.update_grid_setting(params).await.unwrap();
+            }
+            FormulaScript::AssertVisibleRowCount { count } => {
+                let snapshots = self.editor.filtered_grid_block_snapshots(None).await.unwrap();
+                let row_count: usize = snapshots.iter().map(|snapshot| snapshot.row_revs.len()).sum();
+                assert_eq!(count, row_count);
+            }
+        }
+    }
+}
+
+pub fn make_formula_field(name: &str, expression: &str, result_type: FormulaResultType) -> FieldRevision {
+    let builder = FormulaTypeOptionBuilder::default()
+        .expression(expression)
+        .result_type(result_type);
+    FieldBuilder::new(builder).name(name).visibility(true).build()
+}
+
+fn make_insert_field_params(grid_id: &str, field_rev: FieldRevision) -> InsertFieldParams {
+    let type_option_data = field_rev
+        .get_type_option_entry::<FormulaTypeOption>(field_rev.field_type_rev)
+        .unwrap()
+        .protobuf_bytes()
+        .to_vec();
+
+    let field = GridFieldPB {
+        id: field_rev.id,
+        name: field_rev.name,
+        desc: field_rev.desc,
+        field_type: field_rev.field_type_rev.into(),
+        frozen: field_rev.frozen,
+        visibility: field_rev.visibility,
+        width: field_rev.width,
+        is_primary: false,
+    };
+
+    InsertFieldParams {
+        grid_id: grid_id.to_owned(),
+        field,
+        type_option_data,
+        start_field_id: None,
+    }
+}
+
+impl std::ops::Deref for GridFormulaTest {
+    type Target = GridEditorTest;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl std::ops::DerefMut for GridFormulaTest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
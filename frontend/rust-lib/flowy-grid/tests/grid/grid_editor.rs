@@ -106,7 +106,7 @@ pub const PLANNED: &str = "Planned";
 pub const PAUSED: &str = "Paused";
 
 // This grid is assumed to contain all the Fields.
-fn make_test_grid() -> BuildGridContext {
+pub fn make_test_grid() -> BuildGridContext {
     let mut grid_builder = GridBuilder::new();
     // Iterate through the FieldType to create the corresponding Field.
     for field_type in FieldType::iter() {
@@ -168,6 +168,24 @@ fn make_test_grid() -> BuildGridContext {
                 let url_field = FieldBuilder::new(url).name("link").visibility(true).build();
                 grid_builder.add_field(url_field);
             }
+            FieldType::Formula => {
+                // Formula
+                let formula = FormulaTypeOptionBuilder::default();
+                let formula_field = FieldBuilder::new(formula).name("Formula").visibility(true).build();
+                grid_builder.add_field(formula_field);
+            }
+            FieldType::Relation => {
+                // Relation
+                let relation = RelationTypeOptionBuilder::default();
+                let relation_field = FieldBuilder::new(relation).name("Relation").visibility(true).build();
+                grid_builder.add_field(relation_field);
+            }
+            FieldType::Rollup => {
+                // Rollup
+                let rollup = RollupTypeOptionBuilder::default();
+                let rollup_field = FieldBuilder::new(rollup).name("Rollup").visibility(true).build();
+                grid_builder.add_field(rollup_field);
+            }
         }
     }
 
@@ -0,0 +1,157 @@
+use crate::grid::grid_editor::GridEditorTest;
+use bytes::Bytes;
+use dart_notify::sink::{subscribe_notifications, NotificationFilter, NotificationReceiver};
+use flowy_grid::dart_notification::{GridNotification, OBSERVABLE_CATEGORY};
+use flowy_grid::entities::{GridLayoutType, GroupPB, GroupRowsChangesetPB, MoveGroupRowParams};
+use flowy_grid::services::setting::GridSettingChangesetBuilder;
+use flowy_sync::entities::grid::CreateGridGroupParams;
+use flowy_test::helper::receive_notification;
+use std::convert::TryFrom;
+
+pub enum GroupScript {
+    InsertBoardGroup {
+        field_id: String,
+    },
+    AssertGroupCount {
+        count: usize,
+    },
+    AssertGroup {
+        group_index: usize,
+        row_count: usize,
+    },
+    /// The row_index is the index of the row in the order they were created.
+    AssertGroupRow {
+        group_index: usize,
+        row_index_in_group: usize,
+        row_index: usize,
+    },
+    MoveRow {
+        row_index: usize,
+        from_group_index: usize,
+        to_group_index: usize,
+    },
+    CreateRow,
+    /// The deleted row is kept in the row_revs, so it can still be referred by its index.
+    DeleteRow {
+        row_index: usize,
+    },
+    AssertRowInserted {
+        group_index: usize,
+        row_index: usize,
+    },
+    AssertRowDeleted {
+        group_index: usize,
+        row_index: usize,
+    },
+}
+
+pub struct GridGroupTest {
+    inner: GridEditorTest,
+    receiver: NotificationReceiver,
+}
+
+impl GridGroupTest {
+    pub async fn new() -> Self {
+        let inner = GridEditorTest::new().await;
+        let filter = NotificationFilter::default()
+            .source(OBSERVABLE_CATEGORY)
+            .id(&inner.grid_id);
+        let receiver = subscribe_notifications(filter);
+        Self { inner, receiver }
+    }
+
+    async fn receive_group_changeset(&mut self) -> GroupRowsChangesetPB {
+        let subject = receive_notification(&mut self.receiver, GridNotification::DidUpdateGroupRows).await;
+        GroupRowsChangesetPB::try_from(Bytes::from(subject.payload.unwrap())).unwrap()
+    }
+
+    pub async fn run_scripts(&mut self, scripts: Vec<GroupScript>) {
+        for script in scripts {
+            self.run_script(script).await;
+        }
+    }
+
+    pub async fn groups(&self) -> Vec<GroupPB> {
+        self.editor.get_groups().await.unwrap()
+    }
+
+    pub async fn run_script(&mut self, script: GroupScript) {
+        match script {
+            GroupScript::InsertBoardGroup { field_id } => {
+                let params = CreateGridGroupParams {
+                    field_id: Some(field_id),
+                    sub_field_id: None,
+                };
+                let params = GridSettingChangesetBuilder::new(&self.grid_id, &GridLayoutType::Board)
+                    .insert_group(params)
+                    .build();
+                let _ = self.editor.update_grid_setting(params).await.unwrap();
+            }
+            GroupScript::AssertGroupCount { count } => {
+                assert_eq!(count, self.groups().await.len());
+            }
+            GroupScript::AssertGroup { group_index, row_count } => {
+                let group = self.groups().await.remove(group_index);
+                assert_eq!(row_count, group.rows.len());
+            }
+            GroupScript::AssertGroupRow {
+                group_index,
+                row_index_in_group,
+                row_index,
+            } => {
+                let group = self.groups().await.remove(group_index);
+                assert_eq!(group.rows[row_index_in_group].id, self.row_revs[row_index].id);
+            }
+            GroupScript::MoveRow {
+                row_index,
+                from_group_index,
+                to_group_index,
+            } => {
+                let groups = self.groups().await;
+                let params = MoveGroupRowParams {
+                    grid_id: self.grid_id.clone(),
+                    row_id: self.row_revs[row_index].id.clone(),
+                    from_group_id: groups[from_group_index].group_id.clone(),
+                    to_group_id: groups[to_group_index].group_id.clone(),
+                };
+                let _ = self.editor.move_group_row(params).await.unwrap();
+                self.row_revs = self.get_row_revs().await;
+            }
+            GroupScript::CreateRow => {
+                let _ = self.editor.create_row(None).await.unwrap();
+                self.row_revs = self.get_row_revs().await;
+            }
+            GroupScript::DeleteRow { row_index } => {
+                let row_id = self.row_revs[row_index].id.clone();
+                let _ = self.editor.delete_row(&row_id).await.unwrap();
+            }
+            GroupScript::AssertRowInserted { group_index, row_index } => {
+                let changeset = self.receive_group_changeset().await;
+                let group = self.groups().await.remove(group_index);
+                assert_eq!(changeset.group_id, group.group_id);
+                assert_eq!(changeset.inserted_rows.len(), 1);
+                assert_eq!(changeset.inserted_rows[0].id, self.row_revs[row_index].id);
+            }
+            GroupScript::AssertRowDeleted { group_index, row_index } => {
+                let changeset = self.receive_group_changeset().await;
+                let group = self.groups().await.remove(group_index);
+                assert_eq!(changeset.group_id, group.group_id);
+                assert_eq!(changeset.deleted_rows, vec![self.row_revs[row_index].id.clone()]);
+            }
+        }
+    }
+}
+
+impl std::ops::Deref for GridGroupTest {
+    type Target = GridEditorTest;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl std::ops::DerefMut for GridGroupTest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
@@ -0,0 +1,192 @@
+use crate::grid::group_test::script::GroupScript::*;
+use crate::grid::group_test::script::*;
+use flowy_grid::entities::FieldType;
+
+#[tokio::test]
+async fn group_by_single_select_by_default_test() {
+    let mut test = GridGroupTest::new().await;
+    // The first group contains the rows that don't belong to any group. The others are generated
+    // from the options: Completed, Planned and Paused.
+    let scripts = vec![
+        AssertGroupCount { count: 4 },
+        AssertGroup {
+            group_index: 0,
+            row_count: 0,
+        },
+        AssertGroup {
+            group_index: 1,
+            row_count: 2,
+        },
+        AssertGroup {
+            group_index: 2,
+            row_count: 2,
+        },
+        AssertGroup {
+            group_index: 3,
+            row_count: 1,
+        },
+        AssertGroupRow {
+            group_index: 3,
+            row_index_in_group: 0,
+            row_index: 4,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_by_checkbox_test() {
+    let mut test = GridGroupTest::new().await;
+    let field_id = test.get_field_rev(FieldType::Checkbox).id.clone();
+    let scripts = vec![
+        InsertBoardGroup { field_id },
+        AssertGroupCount { count: 3 },
+        AssertGroup {
+            group_index: 1,
+            row_count: 2,
+        },
+        AssertGroup {
+            group_index: 2,
+            row_count: 3,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_by_date_test() {
+    let mut test = GridGroupTest::new().await;
+    let field_id = test.get_field_rev(FieldType::DateTime).id.clone();
+    let scripts = vec![
+        InsertBoardGroup { field_id },
+        AssertGroupCount { count: 2 },
+        AssertGroup {
+            group_index: 1,
+            row_count: 5,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_move_row_to_other_group_test() {
+    let mut test = GridGroupTest::new().await;
+    let scripts = vec![
+        MoveRow {
+            row_index: 2,
+            from_group_index: 2,
+            to_group_index: 1,
+        },
+        AssertGroup {
+            group_index: 1,
+            row_count: 3,
+        },
+        AssertGroup {
+            group_index: 2,
+            row_count: 1,
+        },
+        AssertGroupRow {
+            group_index: 1,
+            row_index_in_group: 2,
+            row_index: 2,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_move_row_to_no_status_group_test() {
+    let mut test = GridGroupTest::new().await;
+    let scripts = vec![
+        MoveRow {
+            row_index: 0,
+            from_group_index: 1,
+            to_group_index: 0,
+        },
+        AssertGroup {
+            group_index: 0,
+            row_count: 1,
+        },
+        AssertGroup {
+            group_index: 1,
+            row_count: 1,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_move_row_between_checkbox_groups_test() {
+    let mut test = GridGroupTest::new().await;
+    let field_id = test.get_field_rev(FieldType::Checkbox).id.clone();
+    let scripts = vec![
+        InsertBoardGroup { field_id },
+        MoveRow {
+            row_index: 4,
+            from_group_index: 2,
+            to_group_index: 1,
+        },
+        AssertGroup {
+            group_index: 1,
+            row_count: 3,
+        },
+        AssertGroup {
+            group_index: 2,
+            row_count: 2,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_create_row_test() {
+    let mut test = GridGroupTest::new().await;
+    // The new row doesn't have any option, so it's inserted into the first group. Moving it to
+    // another group removes it from the first one.
+    let scripts = vec![
+        CreateRow,
+        AssertRowInserted {
+            group_index: 0,
+            row_index: 5,
+        },
+        AssertGroup {
+            group_index: 0,
+            row_count: 1,
+        },
+        MoveRow {
+            row_index: 5,
+            from_group_index: 0,
+            to_group_index: 1,
+        },
+        AssertRowDeleted {
+            group_index: 0,
+            row_index: 5,
+        },
+        AssertRowInserted {
+            group_index: 1,
+            row_index: 5,
+        },
+        AssertGroup {
+            group_index: 1,
+            row_count: 3,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn group_delete_row_test() {
+    let mut test = GridGroupTest::new().await;
+    let scripts = vec![
+        DeleteRow { row_index: 0 },
+        AssertRowDeleted {
+            group_index: 1,
+            row_index: 0,
+        },
+        AssertGroup {
+            group_index: 1,
+            row_count: 1,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
@@ -0,0 +1,122 @@
+use crate::grid::field_test::util::create_text_field;
+use crate::grid::history_test::script::GridHistoryTest;
+use crate::grid::history_test::script::HistoryScript::*;
+use flowy_grid::entities::FieldType;
+
+#[tokio::test]
+async fn grid_undo_delete_field_test() {
+    let mut test = GridHistoryTest::new().await;
+    let field_count = test.field_count;
+    let field_id = test.get_field_rev(FieldType::RichText).id.clone();
+    let scripts = vec![
+        DeleteField {
+            field_id: field_id.clone(),
+        },
+        AssertFieldCount(field_count - 1),
+        AssertHistoryState {
+            can_undo: true,
+            can_redo: false,
+        },
+        Undo,
+        AssertFieldCount(field_count),
+        AssertHistoryState {
+            can_undo: false,
+            can_redo: true,
+        },
+        Redo,
+        AssertFieldCount(field_count - 1),
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_undo_update_cell_test() {
+    let mut test = GridHistoryTest::new().await;
+    let field_id = test.get_field_rev(FieldType::RichText).id.clone();
+    let row_id = test.row_revs[0].id.clone();
+    let scripts = vec![
+        UpdateCell {
+            row_id: row_id.clone(),
+            field_id: field_id.clone(),
+            content: "hello".to_owned(),
+        },
+        UpdateCell {
+            row_id: row_id.clone(),
+            field_id: field_id.clone(),
+            content: "world".to_owned(),
+        },
+        Undo,
+        AssertCellContent {
+            row_id: row_id.clone(),
+            field_id: field_id.clone(),
+            expected: "hello".to_owned(),
+        },
+        Redo,
+        AssertCellContent {
+            row_id,
+            field_id,
+            expected: "world".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_undo_delete_row_test() {
+    let mut test = GridHistoryTest::new().await;
+    let row_count = test.row_revs.len();
+    let row_id = test.row_revs[0].id.clone();
+    let scripts = vec![
+        DeleteRow { row_id },
+        AssertRowCount(row_count - 1),
+        Undo,
+        AssertRowCount(row_count),
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_undo_create_row_test() {
+    let mut test = GridHistoryTest::new().await;
+    let row_count = test.row_revs.len();
+    let scripts = vec![
+        CreateEmptyRow,
+        AssertRowCount(row_count + 1),
+        Undo,
+        AssertRowCount(row_count),
+        Redo,
+        AssertRowCount(row_count + 1),
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_undo_in_the_order_of_changes_test() {
+    let mut test = GridHistoryTest::new().await;
+    let field_count = test.field_count;
+    let (params, field_rev) = create_text_field(&test.grid_id);
+    let row_id = test.row_revs[0].id.clone();
+    let scripts = vec![
+        CreateField { params },
+        UpdateCell {
+            row_id: row_id.clone(),
+            field_id: field_rev.id.clone(),
+            content: "hello".to_owned(),
+        },
+        // The change of the cell is reverted before the creation of the field.
+        Undo,
+        AssertFieldCount(field_count + 1),
+        AssertCellContent {
+            row_id,
+            field_id: field_rev.id.clone(),
+            expected: "".to_owned(),
+        },
+        Undo,
+        AssertFieldCount(field_count),
+        AssertHistoryState {
+            can_undo: false,
+            can_redo: true,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
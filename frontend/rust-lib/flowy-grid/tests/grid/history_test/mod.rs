@@ -0,0 +1,2 @@
+mod history_test;
+mod script;
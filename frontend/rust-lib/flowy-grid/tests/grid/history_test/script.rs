@@ -0,0 +1,126 @@
+#![cfg_attr(rustfmt, rustfmt::skip)]
+#![allow(clippy::all)]
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use flowy_grid::entities::*;
+use flowy_grid::services::cell::decode_cell_data_to_string;
+use flowy_sync::entities::grid::InsertFieldParams;
+use crate::grid::grid_editor::GridEditorTest;
+
+pub enum HistoryScript {
+    CreateField {
+        params: InsertFieldParams,
+    },
+    DeleteField {
+        field_id: String,
+    },
+    CreateEmptyRow,
+    DeleteRow {
+        row_id: String,
+    },
+    UpdateCell {
+        row_id: String,
+        field_id: String,
+        content: String,
+    },
+    Undo,
+    Redo,
+    AssertFieldCount(usize),
+    AssertRowCount(usize),
+    AssertCellContent {
+        row_id: String,
+        field_id: String,
+        expected: String,
+    },
+    AssertHistoryState {
+        can_undo: bool,
+        can_redo: bool,
+    },
+}
+
+pub struct GridHistoryTest {
+    inner: GridEditorTest,
+}
+
+impl GridHistoryTest {
+    pub async fn new() -> Self {
+        let editor_test = GridEditorTest::new().await;
+        Self {
+            inner: editor_test
+        }
+    }
+
+    pub async fn run_scripts(&mut self, scripts: Vec<HistoryScript>) {
+        for script in scripts {
+            self.run_script(script).await;
+        }
+    }
+
+    pub async fn run_script(&mut self, script: HistoryScript) {
+        match script {
+            HistoryScript::CreateField { params } => {
+                self.editor.insert_field(params).await.unwrap();
+            }
+            HistoryScript::DeleteField { field_id } => {
+                self.editor.delete_field(&field_id).await.unwrap();
+            }
+            HistoryScript::CreateEmptyRow => {
+                let _ = self.editor.create_row(None).await.unwrap();
+            }
+            HistoryScript::DeleteRow { row_id } => {
+                self.editor.delete_row(&row_id).await.unwrap();
+            }
+            HistoryScript::UpdateCell { row_id, field_id, content } => {
+                let changeset = CellChangesetPB {
+                    grid_id: self.grid_id.clone(),
+                    row_id,
+                    field_id,
+                    content: Some(content),
+                };
+                self.editor.update_cell(changeset).await.unwrap();
+            }
+            HistoryScript::Undo => {
+                let _ = self.editor.undo().await.unwrap();
+            }
+            HistoryScript::Redo => {
+                let _ = self.editor.redo().await.unwrap();
+            }
+            HistoryScript::AssertFieldCount(count) => {
+                let field_revs = self.editor.get_field_revs(None).await.unwrap();
+                assert_eq!(field_revs.len(), count);
+            }
+            HistoryScript::AssertRowCount(count) => {
+                let row_revs = self.get_row_revs().await;
+                assert_eq!(row_revs.len(), count);
+            }
+            HistoryScript::AssertCellContent { row_id, field_id, expected } => {
+                let field_rev = self.editor.get_field_rev(&field_id).await.unwrap();
+                let content = match self.editor.get_cell_rev(&row_id, &field_id).await.unwrap() {
+                    None => "".to_owned(),
+                    Some(cell_rev) => decode_cell_data_to_string(cell_rev, &field_rev),
+                };
+                assert_eq!(content, expected);
+            }
+            HistoryScript::AssertHistoryState { can_undo, can_redo } => {
+                let history_state = self.editor.get_history_state().await;
+                assert_eq!(history_state.can_undo, can_undo);
+                assert_eq!(history_state.can_redo, can_redo);
+            }
+        }
+    }
+}
+
+impl std::ops::Deref for GridHistoryTest {
+    type Target = GridEditorTest;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl std::ops::DerefMut for GridHistoryTest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
@@ -1,5 +1,14 @@
 mod block_test;
+mod calculation_test;
 mod cell_test;
+mod csv_test;
 mod field_test;
 mod filter_test;
+mod formula_test;
 mod grid_editor;
+mod group_test;
+mod history_test;
+mod relation_test;
+mod sort_test;
+#[cfg(feature = "sync")]
+mod sync_test;
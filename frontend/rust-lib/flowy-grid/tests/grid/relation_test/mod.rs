@@ -0,0 +1,2 @@
+mod relation_test;
+mod script;
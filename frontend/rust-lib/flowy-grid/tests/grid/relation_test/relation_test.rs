@@ -0,0 +1,237 @@
+use crate::grid::relation_test::script::RelationScript::*;
+use crate::grid::relation_test::script::*;
+use flowy_grid::entities::FieldType;
+use flowy_grid::services::field::RollupCalculation;
+
+#[tokio::test]
+async fn grid_relation_create_back_reference_field_test() {
+    let mut test = GridRelationTest::new().await;
+    let field_rev = make_relation_field("Projects");
+    let field_id = field_rev.id.clone();
+    let scripts = vec![InsertRelationField {
+        field_rev,
+        target_grid_id: test.target_grid_id.clone(),
+        is_err: false,
+    }];
+    test.run_scripts(scripts).await;
+
+    let back_reference_field_id = test.back_reference_field_id(&field_id).await;
+    assert!(!back_reference_field_id.is_empty());
+    let scripts = vec![
+        AssertTargetFieldExists {
+            field_id: back_reference_field_id.clone(),
+            exists: true,
+        },
+        DeleteField { field_id },
+        AssertTargetFieldExists {
+            field_id: back_reference_field_id,
+            exists: false,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_relation_link_rows_test() {
+    let mut test = GridRelationTest::new().await;
+    let field_rev = make_relation_field("Projects");
+    let field_id = field_rev.id.clone();
+    let scripts = vec![
+        InsertRelationField {
+            field_rev,
+            target_grid_id: test.target_grid_id.clone(),
+            is_err: false,
+        },
+        LinkRows {
+            row_index: 0,
+            field_id: field_id.clone(),
+            target_row_indexes: vec![0, 1],
+        },
+        LinkRows {
+            row_index: 1,
+            field_id: field_id.clone(),
+            target_row_indexes: vec![1],
+        },
+    ];
+    test.run_scripts(scripts).await;
+
+    let row_ids = vec![test.row_id(0).await, test.row_id(1).await];
+    let target_row_ids = vec![test.target_row_id(0).await, test.target_row_id(1).await];
+    let back_reference_field_id = test.back_reference_field_id(&field_id).await;
+    let scripts = vec![
+        AssertLinkedRows {
+            row_index: 0,
+            field_id: field_id.clone(),
+            target_row_ids: target_row_ids.clone(),
+        },
+        AssertBackReferences {
+            target_row_index: 0,
+            field_id: back_reference_field_id.clone(),
+            row_ids: vec![row_ids[0].clone()],
+        },
+        AssertBackReferences {
+            target_row_index: 1,
+            field_id: back_reference_field_id.clone(),
+            row_ids: row_ids.clone(),
+        },
+        UnlinkRows {
+            row_index: 0,
+            field_id: field_id.clone(),
+            target_row_indexes: vec![1],
+        },
+        AssertLinkedRows {
+            row_index: 0,
+            field_id,
+            target_row_ids: vec![target_row_ids[0].clone()],
+        },
+        AssertBackReferences {
+            target_row_index: 1,
+            field_id: back_reference_field_id,
+            row_ids: vec![row_ids[1].clone()],
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_rollup_count_and_sum_test() {
+    let mut test = GridRelationTest::new().await;
+    let relation_field_rev = make_relation_field("Projects");
+    let relation_field_id = relation_field_rev.id.clone();
+    let target_price_field_id = test.target_field_id(FieldType::Number).await;
+    let count_field_rev = make_rollup_field("Project count");
+    let count_field_id = count_field_rev.id.clone();
+    let sum_field_rev = make_rollup_field("Total price");
+    let sum_field_id = sum_field_rev.id.clone();
+    let scripts = vec![
+        InsertRelationField {
+            field_rev: relation_field_rev,
+            target_grid_id: test.target_grid_id.clone(),
+            is_err: false,
+        },
+        InsertRollupField {
+            field_rev: count_field_rev,
+            type_option: make_rollup_type_option(&relation_field_id, "", RollupCalculation::Count),
+            is_err: false,
+        },
+        InsertRollupField {
+            field_rev: sum_field_rev,
+            type_option: make_rollup_type_option(&relation_field_id, &target_price_field_id, RollupCalculation::Sum),
+            is_err: false,
+        },
+        LinkRows {
+            row_index: 0,
+            field_id: relation_field_id.clone(),
+            target_row_indexes: vec![0, 1, 2],
+        },
+        AssertCellContent {
+            row_index: 0,
+            field_id: count_field_id.clone(),
+            expected: "3".to_owned(),
+        },
+        AssertCellContent {
+            row_index: 0,
+            field_id: sum_field_id.clone(),
+            expected: "6".to_owned(),
+        },
+        // The rollup is refreshed after the cell of the related row is updated.
+        UpdateTargetCell {
+            row_index: 0,
+            field_id: target_price_field_id,
+            content: "10".to_owned(),
+        },
+        AssertCellContent {
+            row_index: 0,
+            field_id: sum_field_id,
+            expected: "15".to_owned(),
+        },
+        UnlinkRows {
+            row_index: 0,
+            field_id: relation_field_id,
+            target_row_indexes: vec![2],
+        },
+        AssertCellContent {
+            row_index: 0,
+            field_id: count_field_id,
+            expected: "2".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_rollup_delete_related_row_test() {
+    let mut test = GridRelationTest::new().await;
+    let relation_field_rev = make_relation_field("Projects");
+    let relation_field_id = relation_field_rev.id.clone();
+    let count_field_rev = make_rollup_field("Project count");
+    let count_field_id = count_field_rev.id.clone();
+    let scripts = vec![
+        InsertRelationField {
+            field_rev: relation_field_rev,
+            target_grid_id: test.target_grid_id.clone(),
+            is_err: false,
+        },
+        InsertRollupField {
+            field_rev: count_field_rev,
+            type_option: make_rollup_type_option(&relation_field_id, "", RollupCalculation::Count),
+            is_err: false,
+        },
+        LinkRows {
+            row_index: 0,
+            field_id: relation_field_id.clone(),
+            target_row_indexes: vec![0, 1],
+        },
+    ];
+    test.run_scripts(scripts).await;
+
+    let remaining_target_row_id = test.target_row_id(1).await;
+    let scripts = vec![
+        DeleteTargetRow { row_index: 0 },
+        AssertLinkedRows {
+            row_index: 0,
+            field_id: relation_field_id,
+            target_row_ids: vec![remaining_target_row_id],
+        },
+        AssertCellContent {
+            row_index: 0,
+            field_id: count_field_id,
+            expected: "1".to_owned(),
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_rollup_invalid_target_field_test() {
+    let mut test = GridRelationTest::new().await;
+    let relation_field_rev = make_relation_field("Projects");
+    let relation_field_id = relation_field_rev.id.clone();
+    let target_rollup_field_id = test.target_field_id(FieldType::Rollup).await;
+    let scripts = vec![
+        InsertRelationField {
+            field_rev: relation_field_rev,
+            target_grid_id: test.target_grid_id.clone(),
+            is_err: false,
+        },
+        // The target field doesn't exist in the target grid.
+        InsertRollupField {
+            field_rev: make_rollup_field("Unknown"),
+            type_option: make_rollup_type_option(&relation_field_id, "unknown", RollupCalculation::Sum),
+            is_err: true,
+        },
+        // The rollup of a rollup field is not supported.
+        InsertRollupField {
+            field_rev: make_rollup_field("Nested"),
+            type_option: make_rollup_type_option(&relation_field_id, &target_rollup_field_id, RollupCalculation::Sum),
+            is_err: true,
+        },
+        // The relation field doesn't exist.
+        InsertRollupField {
+            field_rev: make_rollup_field("No relation"),
+            type_option: make_rollup_type_option("unknown", "", RollupCalculation::Count),
+            is_err: true,
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
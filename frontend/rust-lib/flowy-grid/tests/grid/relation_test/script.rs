@@ -0,0 +1,268 @@
+#![cfg_attr(rustfmt, rustfmt::skip)]
+#![allow(clippy::all)]
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use bytes::Bytes;
+use flowy_grid::entities::*;
+use flowy_grid::services::field::*;
+use flowy_grid::services::grid_editor::GridRevisionEditor;
+use flowy_grid_data_model::revision::*;
+use flowy_test::helper::ViewTest;
+use std::sync::Arc;
+use crate::grid::grid_editor::{make_test_grid, GridEditorTest};
+
+pub enum RelationScript {
+    InsertRelationField {
+        field_rev: FieldRevision,
+        target_grid_id: String,
+        is_err: bool,
+    },
+    InsertRollupField {
+        field_rev: FieldRevision,
+        type_option: RollupTypeOption,
+        is_err: bool,
+    },
+    DeleteField {
+        field_id: String,
+    },
+    LinkRows {
+        row_index: usize,
+        field_id: String,
+        target_row_indexes: Vec<usize>,
+    },
+    UnlinkRows {
+        row_index: usize,
+        field_id: String,
+        target_row_indexes: Vec<usize>,
+    },
+    UpdateTargetCell {
+        row_index: usize,
+        field_id: String,
+        content: String,
+    },
+    DeleteTargetRow {
+        row_index: usize,
+    },
+    AssertTargetFieldExists {
+        field_id: String,
+        exists: bool,
+    },
+    /// Assert the ids of the target rows that are linked by the relation cell.
+    AssertLinkedRows {
+        row_index: usize,
+        field_id: String,
+        target_row_ids: Vec<String>,
+    },
+    /// Assert the ids of the rows that link to the target row through the back-reference field.
+    AssertBackReferences {
+        target_row_index: usize,
+        field_id: String,
+        row_ids: Vec<String>,
+    },
+    AssertCellContent {
+        row_index: usize,
+        field_id: String,
+        expected: String,
+    },
+}
+
+pub struct GridRelationTest {
+    inner: GridEditorTest,
+    pub target_grid_id: String,
+    pub target_editor: Arc<GridRevisionEditor>,
+}
+
+impl GridRelationTest {
+    pub async fn new() -> Self {
+        let editor_test = GridEditorTest::new().await;
+        let view_data: Bytes = make_test_grid().into();
+        let target_view = ViewTest::new_grid_view(&editor_test.sdk, view_data.to_vec()).await;
+        let target_editor = editor_test.sdk.grid_manager.open_grid(&target_view.view.id).await.unwrap();
+        Self {
+            inner: editor_test,
+            target_grid_id: target_view.view.id,
+            target_editor,
+        }
+    }
+
+    pub fn field_id(&self, field_type: FieldType) -> String {
+        self.get_field_rev(field_type).id.clone()
+    }
+
+    pub async fn target_field_id(&self, field_type: FieldType) -> String {
+        let field_type_rev: FieldTypeRevision = field_type.into();
+        self.target_editor
+            .get_field_revs(None)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|field_rev| field_rev.field_type_rev == field_type_rev)
+            .unwrap()
+            .id
+            .clone()
+    }
+
+    pub async fn row_id(&self, row_index: usize) -> String {
+        self.get_row_revs().await[row_index].id.clone()
+    }
+
+    pub async fn target_row_id(&self, row_index: usize) -> String {
+        self.target_row_revs().await[row_index].id.clone()
+    }
+
+    /// Return the id of the field that is created in the target grid for the relation field.
+    pub async fn back_reference_field_id(&self, field_id: &str) -> String {
+        let field_rev = self.editor.get_field_rev(field_id).await.unwrap();
+        RelationTypeOption::from(&field_rev).back_reference_field_id
+    }
+
+    pub async fn run_scripts(&mut self, scripts: Vec<RelationScript>) {
+        for script in scripts {
+            self.run_script(script).await;
+        }
+    }
+
+    pub async fn run_script(&mut self, script: RelationScript) {
+        match script {
+            RelationScript::InsertRelationField { field_rev, target_grid_id, is_err } => {
+                let type_option = RelationTypeOption {
+                    target_grid_id,
+                    back_reference_field_id: "".to_owned(),
+                };
+                let params = make_insert_field_params(&self.grid_id, field_rev, type_option.protobuf_bytes().to_vec());
+                let result = self.editor.insert_field(params).await;
+                assert_eq!(is_err, result.is_err());
+            }
+            RelationScript::InsertRollupField { field_rev, type_option, is_err } => {
+                let params = make_insert_field_params(&self.grid_id, field_rev, type_option.protobuf_bytes().to_vec());
+                let result = self.editor.insert_field(params).await;
+                assert_eq!(is_err, result.is_err());
+            }
+            RelationScript::DeleteField { field_id } => {
+                let _ = self.editor.delete_field(&field_id).await.unwrap();
+            }
+            RelationScript::LinkRows { row_index, field_id, target_row_indexes } => {
+                let mut changeset = RelationCellChangeset::default();
+                for target_row_index in target_row_indexes {
+                    changeset.inserted_row_ids.push(self.target_row_id(target_row_index).await);
+                }
+                self.update_relation_cell(row_index, field_id, changeset).await;
+            }
+            RelationScript::UnlinkRows { row_index, field_id, target_row_indexes } => {
+                let mut changeset = RelationCellChangeset::default();
+                for target_row_index in target_row_indexes {
+                    changeset.deleted_row_ids.push(self.target_row_id(target_row_index).await);
+                }
+                self.update_relation_cell(row_index, field_id, changeset).await;
+            }
+            RelationScript::UpdateTargetCell { row_index, field_id, content } => {
+                let changeset = CellChangesetPB {
+                    grid_id: self.target_grid_id.clone(),
+                    row_id: self.target_row_id(row_index).await,
+                    field_id,
+                    content: Some(content),
+                };
+                let _ = self.target_editor.update_cell(changeset).await.unwrap();
+            }
+            RelationScript::DeleteTargetRow { row_index } => {
+                let row_id = self.target_row_id(row_index).await;
+                let _ = self.target_editor.delete_row(&row_id).await.unwrap();
+            }
+            RelationScript::AssertTargetFieldExists { field_id, exists } => {
+                assert_eq!(exists, self.target_editor.contain_field(&field_id).await);
+            }
+            RelationScript::AssertLinkedRows { row_index, field_id, target_row_ids } => {
+                let row_id = self.row_id(row_index).await;
+                let row_ids = relation_row_ids(&self.editor, &self.grid_id, &row_id, &field_id).await;
+                assert_eq!(target_row_ids, row_ids);
+            }
+            RelationScript::AssertBackReferences { target_row_index, field_id, row_ids } => {
+                let target_row_id = self.target_row_id(target_row_index).await;
+                let back_references =
+                    relation_row_ids(&self.target_editor, &self.target_grid_id, &target_row_id, &field_id).await;
+                assert_eq!(row_ids, back_references);
+            }
+            RelationScript::AssertCellContent { row_index, field_id, expected } => {
+                let params = GridCellIdParams {
+                    grid_id: self.grid_id.clone(),
+                    field_id,
+                    row_id: self.row_id(row_index).await,
+                };
+                let content = self.editor.get_cell_bytes(&params).await.map(|bytes| bytes.to_string()).unwrap_or_default();
+                assert_eq!(expected, content);
+            }
+        }
+    }
+
+    async fn update_relation_cell(&self, row_index: usize, field_id: String, changeset: RelationCellChangeset) {
+        let changeset = CellChangesetPB {
+            grid_id: self.grid_id.clone(),
+            row_id: self.row_id(row_index).await,
+            field_id,
+            content: Some(changeset.to_str()),
+        };
+        let _ = self.editor.update_cell(changeset).await.unwrap();
+    }
+
+    async fn target_row_revs(&self) -> Vec<Arc<RowRevision>> {
+        self.target_editor
+            .grid_block_snapshots(None)
+            .await
+            .unwrap()
+            .pop()
+            .unwrap()
+            .row_revs
+    }
+}
+
+pub fn make_relation_field(name: &str) -> FieldRevision {
+    FieldBuilder::new(RelationTypeOptionBuilder::default()).name(name).visibility(true).build()
+}
+
+pub fn make_rollup_field(name: &str) -> FieldRevision {
+    FieldBuilder::new(RollupTypeOptionBuilder::default()).name(name).visibility(true).build()
+}
+
+pub fn make_rollup_type_option(relation_field_id: &str, target_field_id: &str, calculation: RollupCalculation) -> RollupTypeOption {
+    RollupTypeOption {
+        relation_field_id: relation_field_id.to_owned(),
+        target_field_id: target_field_id.to_owned(),
+        calculation,
+    }
+}
+
+async fn relation_row_ids(editor: &Arc<GridRevisionEditor>, grid_id: &str, row_id: &str, field_id: &str) -> Vec<String> {
+    let params = GridCellIdParams {
+        grid_id: grid_id.to_owned(),
+        field_id: field_id.to_owned(),
+        row_id: row_id.to_owned(),
+    };
+    match editor.get_cell_bytes(&params).await {
+        None => vec![],
+        Some(cell_bytes) => cell_bytes.with_parser(RelationCellDataParser()).unwrap().row_ids,
+    }
+}
+
+fn make_insert_field_params(grid_id: &str, field_rev: FieldRevision, type_option_data: Vec<u8>) -> InsertFieldParams {
+    InsertFieldParams {
+        grid_id: grid_id.to_owned(),
+        field: GridFieldPB::from(field_rev),
+        type_option_data,
+        start_field_id: None,
+    }
+}
+
+impl std::ops::Deref for GridRelationTest {
+    type Target = GridEditorTest;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl std::ops::DerefMut for GridRelationTest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
@@ -0,0 +1,2 @@
+mod row_sort_test;
+mod script;
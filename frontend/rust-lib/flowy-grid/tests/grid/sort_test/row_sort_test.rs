@@ -0,0 +1,116 @@
+use crate::grid::sort_test::script::SortScript::*;
+use crate::grid::sort_test::script::*;
+use flowy_grid::entities::{CreateGridSortPayloadPB, FieldType, GridSortCondition};
+
+#[tokio::test]
+async fn grid_sort_create_test() {
+    let mut test = GridSortTest::new().await;
+    let field_rev = test.get_field_rev(FieldType::RichText);
+    let payload = CreateGridSortPayloadPB::new(field_rev, GridSortCondition::Ascending);
+    let scripts = vec![
+        InsertGridTableSort { payload },
+        AssertTableSortCount { count: 1 },
+        AssertSettingSortCount { count: 1 },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_sort_replace_sort_of_the_same_field_test() {
+    let mut test = GridSortTest::new().await;
+    let field_rev = test.get_field_rev(FieldType::Number).clone();
+    let scripts = vec![
+        InsertGridTableSort {
+            payload: CreateGridSortPayloadPB::new(&field_rev, GridSortCondition::Ascending),
+        },
+        InsertGridTableSort {
+            payload: CreateGridSortPayloadPB::new(&field_rev, GridSortCondition::Descending),
+        },
+        AssertTableSortCount { count: 1 },
+        AssertRowOrder {
+            expected_row_indexes: vec![4, 3, 2, 1, 0],
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_sort_delete_test() {
+    let mut test = GridSortTest::new().await;
+    let field_rev = test.get_field_rev(FieldType::RichText).clone();
+    let payload = CreateGridSortPayloadPB::new(&field_rev, GridSortCondition::Descending);
+    test.run_scripts(vec![InsertGridTableSort { payload }, AssertTableSortCount { count: 1 }])
+        .await;
+
+    let sort = test.grid_sorts().await.pop().unwrap();
+    let scripts = vec![
+        DeleteGridTableSort { sort_id: sort.id },
+        AssertTableSortCount { count: 0 },
+        AssertRowOrder {
+            expected_row_indexes: vec![0, 1, 2, 3, 4],
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_sort_text_descending_test() {
+    let mut test = GridSortTest::new().await;
+    let field_rev = test.get_field_rev(FieldType::RichText);
+    let payload = CreateGridSortPayloadPB::new(field_rev, GridSortCondition::Descending);
+    let scripts = vec![
+        InsertGridTableSort { payload },
+        AssertRowOrder {
+            expected_row_indexes: vec![4, 3, 2, 1, 0],
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_sort_single_select_descending_test() {
+    let mut test = GridSortTest::new().await;
+    let field_rev = test.get_field_rev(FieldType::SingleSelect);
+    let payload = CreateGridSortPayloadPB::new(field_rev, GridSortCondition::Descending);
+    let scripts = vec![
+        InsertGridTableSort { payload },
+        AssertRowOrder {
+            expected_row_indexes: vec![4, 2, 3, 0, 1],
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_sort_checkbox_ascending_test() {
+    let mut test = GridSortTest::new().await;
+    let field_rev = test.get_field_rev(FieldType::Checkbox);
+    let payload = CreateGridSortPayloadPB::new(field_rev, GridSortCondition::Ascending);
+    let scripts = vec![
+        InsertGridTableSort { payload },
+        AssertRowOrder {
+            expected_row_indexes: vec![2, 3, 4, 0, 1],
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_sort_multiple_sorts_test() {
+    let mut test = GridSortTest::new().await;
+    let checkbox_field_rev = test.get_field_rev(FieldType::Checkbox).clone();
+    let number_field_rev = test.get_field_rev(FieldType::Number).clone();
+    let scripts = vec![
+        InsertGridTableSort {
+            payload: CreateGridSortPayloadPB::new(&checkbox_field_rev, GridSortCondition::Descending),
+        },
+        InsertGridTableSort {
+            payload: CreateGridSortPayloadPB::new(&number_field_rev, GridSortCondition::Descending),
+        },
+        AssertTableSortCount { count: 2 },
+        AssertRowOrder {
+            expected_row_indexes: vec![1, 0, 4, 3, 2],
+        },
+    ];
+    test.run_scripts(scripts).await;
+}
@@ -0,0 +1,109 @@
+#![cfg_attr(rustfmt, rustfmt::skip)]
+#![allow(clippy::all)]
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use flowy_grid::entities::{CreateGridSortPayloadPB, GridLayoutType, GridSort};
+use flowy_grid::services::setting::GridSettingChangesetBuilder;
+use flowy_sync::entities::grid::CreateGridSortParams;
+use crate::grid::grid_editor::GridEditorTest;
+
+pub enum SortScript {
+    InsertGridTableSort {
+        payload: CreateGridSortPayloadPB,
+    },
+    DeleteGridTableSort {
+        sort_id: String,
+    },
+    AssertTableSortCount {
+        count: i32,
+    },
+    AssertSettingSortCount {
+        count: i32,
+    },
+    /// The indexes of the rows in the order they were created.
+    AssertRowOrder {
+        expected_row_indexes: Vec<usize>,
+    },
+}
+
+pub struct GridSortTest {
+    inner: GridEditorTest,
+}
+
+impl GridSortTest {
+    pub async fn new() -> Self {
+        let editor_test = GridEditorTest::new().await;
+        Self {
+            inner: editor_test
+        }
+    }
+
+    pub async fn run_scripts(&mut self, scripts: Vec<SortScript>) {
+        for script in scripts {
+            self.run_script(script).await;
+        }
+    }
+
+    pub async fn grid_sorts(&self) -> Vec<GridSort> {
+        let layout_type = GridLayoutType::Table;
+        self.editor.get_grid_sorts(&layout_type).await.unwrap()
+    }
+
+    pub async fn run_script(&mut self, script: SortScript) {
+        match script {
+            SortScript::InsertGridTableSort { payload } => {
+                let params: CreateGridSortParams = payload.try_into().unwrap();
+                let layout_type = GridLayoutType::Table;
+                let params = GridSettingChangesetBuilder::new(&self.grid_id, &layout_type)
+                    .insert_sort(params)
+                    .build();
+                let _ = self.editor.update_grid_setting(params).await.unwrap();
+            }
+            SortScript::DeleteGridTableSort { sort_id } => {
+                let layout_type = GridLayoutType::Table;
+                let params = GridSettingChangesetBuilder::new(&self.grid_id, &layout_type)
+                    .delete_sort(&sort_id)
+                    .build();
+                let _ = self.editor.update_grid_setting(params).await.unwrap();
+            }
+            SortScript::AssertTableSortCount { count } => {
+                assert_eq!(count as usize, self.grid_sorts().await.len());
+            }
+            SortScript::AssertSettingSortCount { count } => {
+                let setting = self.editor.get_grid_setting().await.unwrap();
+                let sort_count: usize = setting.sorts_by_field_id.values().map(|sorts| sorts.items.len()).sum();
+                assert_eq!(count as usize, sort_count);
+            }
+            SortScript::AssertRowOrder { expected_row_indexes } => {
+                let expected_row_ids = expected_row_indexes
+                    .into_iter()
+                    .map(|index| self.row_revs[index].id.clone())
+                    .collect::<Vec<String>>();
+
+                let grid = self.editor.get_grid_data().await.unwrap();
+                let row_ids = grid.blocks[0].rows.iter().map(|row| row.id.clone()).collect::<Vec<String>>();
+                assert_eq!(expected_row_ids, row_ids);
+
+                let block_id = self.block_id().to_owned();
+                let rows = self.editor.get_rows(&block_id).await.unwrap();
+                let row_ids = rows.items.iter().map(|row| row.id.clone()).collect::<Vec<String>>();
+                assert_eq!(expected_row_ids, row_ids);
+            }
+        }
+    }
+}
+
+impl std::ops::Deref for GridSortTest {
+    type Target = GridEditorTest;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl std::ops::DerefMut for GridSortTest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
@@ -0,0 +1,73 @@
+use crate::grid::sync_test::script::Client::*;
+use crate::grid::sync_test::script::GridSyncTest;
+use crate::grid::sync_test::script::SyncScript::*;
+
+#[tokio::test]
+async fn grid_sync_update_cell_test() {
+    let mut test = GridSyncTest::new().await;
+    let scripts = vec![
+        UpdateTextCell {
+            client: First,
+            row_index: 0,
+            content: "hello world".to_owned(),
+        },
+        WaitForSync,
+        AssertTextCell {
+            client: Second,
+            row_index: 0,
+            content: "hello world".to_owned(),
+        },
+        AssertConverged,
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_sync_create_row_test() {
+    let mut test = GridSyncTest::new().await;
+    let scripts = vec![
+        CreateEmptyRow { client: Second },
+        WaitForSync,
+        AssertRowCount {
+            client: First,
+            count: 6,
+        },
+        AssertRowCount {
+            client: Second,
+            count: 6,
+        },
+        AssertConverged,
+    ];
+    test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_sync_update_cell_from_both_clients_test() {
+    let mut test = GridSyncTest::new().await;
+    let scripts = vec![
+        UpdateTextCell {
+            client: First,
+            row_index: 0,
+            content: "first".to_owned(),
+        },
+        WaitForSync,
+        UpdateTextCell {
+            client: Second,
+            row_index: 1,
+            content: "second".to_owned(),
+        },
+        WaitForSync,
+        AssertTextCell {
+            client: First,
+            row_index: 1,
+            content: "second".to_owned(),
+        },
+        AssertTextCell {
+            client: Second,
+            row_index: 0,
+            content: "first".to_owned(),
+        },
+        AssertConverged,
+    ];
+    test.run_scripts(scripts).await;
+}
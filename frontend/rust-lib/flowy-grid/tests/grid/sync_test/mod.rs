@@ -0,0 +1,2 @@
+mod grid_sync_test;
+mod script;
@@ -0,0 +1,179 @@
+use crate::grid::grid_editor::make_test_grid;
+use bytes::Bytes;
+use flowy_grid::entities::{CellChangesetPB, FieldType};
+use flowy_grid::manager::make_grid_view_data;
+use flowy_grid::services::cell::decode_any_cell_data;
+use flowy_grid::services::grid_editor::GridRevisionEditor;
+use flowy_grid::services::GRID_SYNC_INTERVAL_IN_MILLIS;
+use flowy_grid_data_model::revision::{BuildGridContext, RowRevision};
+use flowy_test::helper::ViewTest;
+use flowy_test::FlowySDKTest;
+use std::convert::TryFrom;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[derive(Clone, Copy)]
+pub enum Client {
+    First,
+    Second,
+}
+
+pub enum SyncScript {
+    UpdateTextCell {
+        client: Client,
+        row_index: usize,
+        content: String,
+    },
+    CreateEmptyRow {
+        client: Client,
+    },
+    /// Wait until the clients pushed their revisions and pulled the revisions of the others.
+    WaitForSync,
+    AssertRowCount {
+        client: Client,
+        count: usize,
+    },
+    AssertTextCell {
+        client: Client,
+        row_index: usize,
+        content: String,
+    },
+    AssertConverged,
+}
+
+struct GridSyncClient {
+    #[allow(dead_code)]
+    sdk: FlowySDKTest,
+    editor: Arc<GridRevisionEditor>,
+}
+
+pub struct GridSyncTest {
+    grid_id: String,
+    first: GridSyncClient,
+    second: GridSyncClient,
+}
+
+impl GridSyncTest {
+    pub async fn new() -> Self {
+        let first_sdk = FlowySDKTest::default();
+        let _ = first_sdk.init_user().await;
+        let view_data: Bytes = make_test_grid().into();
+        let test = ViewTest::new_grid_view(&first_sdk, view_data.to_vec()).await;
+        let grid_id = test.view.id;
+        let first_editor = first_sdk.grid_manager.open_grid(&grid_id).await.unwrap();
+
+        // The second client creates the same grid and then synchronizes it with the first client
+        // through the local server of the first client.
+        let second_sdk = first_sdk.connect();
+        let user_profile = second_sdk.init_user().await;
+        let build_context = BuildGridContext::try_from(view_data).unwrap();
+        let _ = make_grid_view_data(
+            &user_profile.id,
+            &grid_id,
+            second_sdk.grid_manager.clone(),
+            build_context,
+        )
+        .await
+        .unwrap();
+        let second_editor = second_sdk.grid_manager.open_grid(&grid_id).await.unwrap();
+
+        Self {
+            grid_id,
+            first: GridSyncClient {
+                sdk: first_sdk,
+                editor: first_editor,
+            },
+            second: GridSyncClient {
+                sdk: second_sdk,
+                editor: second_editor,
+            },
+        }
+    }
+
+    pub async fn run_scripts(&mut self, scripts: Vec<SyncScript>) {
+        for script in scripts {
+            self.run_script(script).await;
+        }
+    }
+
+    pub async fn run_script(&mut self, script: SyncScript) {
+        match script {
+            SyncScript::UpdateTextCell {
+                client,
+                row_index,
+                content,
+            } => {
+                let editor = self.editor(client);
+                let row_rev = self.row_revs(client).await.remove(row_index);
+                let field_id = self.text_field_id(client).await;
+                let changeset = CellChangesetPB {
+                    grid_id: self.grid_id.clone(),
+                    row_id: row_rev.id.clone(),
+                    field_id,
+                    content: Some(content),
+                };
+                let _ = editor.update_cell(changeset).await.unwrap();
+            }
+            SyncScript::CreateEmptyRow { client } => {
+                let _ = self.editor(client).create_row(None).await.unwrap();
+            }
+            SyncScript::WaitForSync => {
+                sleep(Duration::from_millis(GRID_SYNC_INTERVAL_IN_MILLIS * 3)).await;
+            }
+            SyncScript::AssertRowCount { client, count } => {
+                assert_eq!(self.row_revs(client).await.len(), count);
+            }
+            SyncScript::AssertTextCell {
+                client,
+                row_index,
+                content,
+            } => {
+                let row_rev = self.row_revs(client).await.remove(row_index);
+                let field_id = self.text_field_id(client).await;
+                let field_rev = self.editor(client).get_field_rev(&field_id).await.unwrap();
+                let cell_rev = row_rev.cells.get(&field_id).unwrap().clone();
+                let cell_bytes = decode_any_cell_data(cell_rev.data, &field_rev);
+                assert_eq!(cell_bytes.to_string(), content);
+            }
+            SyncScript::AssertConverged => {
+                let first_grid = self.first.editor.delta_bytes().await;
+                let second_grid = self.second.editor.delta_bytes().await;
+                assert_eq!(first_grid, second_grid);
+
+                let first_rows = serde_json::to_string(&self.row_revs(Client::First).await).unwrap();
+                let second_rows = serde_json::to_string(&self.row_revs(Client::Second).await).unwrap();
+                assert_eq!(first_rows, second_rows);
+            }
+        }
+    }
+
+    fn editor(&self, client: Client) -> Arc<GridRevisionEditor> {
+        match client {
+            Client::First => self.first.editor.clone(),
+            Client::Second => self.second.editor.clone(),
+        }
+    }
+
+    async fn row_revs(&self, client: Client) -> Vec<Arc<RowRevision>> {
+        self.editor(client)
+            .grid_block_snapshots(None)
+            .await
+            .unwrap()
+            .pop()
+            .unwrap()
+            .row_revs
+    }
+
+    async fn text_field_id(&self, client: Client) -> String {
+        self.editor(client)
+            .get_field_revs(None)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|field_rev| FieldType::from(field_rev.field_type_rev) == FieldType::RichText)
+            .unwrap()
+            .id
+            .clone()
+    }
+}
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+CREATE TABLE server_rev_table (
+     id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+     object_id TEXT NOT NULL DEFAULT '',
+     base_rev_id BIGINT NOT NULL DEFAULT 0,
+     rev_id BIGINT NOT NULL DEFAULT 0,
+     data BLOB NOT NULL DEFAULT (x''),
+     md5 TEXT NOT NULL DEFAULT '',
+     user_id TEXT NOT NULL DEFAULT ''
+);
+CREATE UNIQUE INDEX server_rev_table_object_id_rev_id ON server_rev_table (object_id, rev_id);
@@ -10,6 +10,11 @@ pub struct ClientServerConfiguration {
     pub host: String,
     pub http_scheme: String,
     pub ws_scheme: String,
+
+    /// The directory that the local server stores the revisions in, so they survive the restart
+    /// of the server. The revisions are kept in memory if it's not set.
+    #[serde(default)]
+    pub local_server_storage_dir: Option<String>,
 }
 
 pub fn get_client_server_configuration() -> Result<ClientServerConfiguration, config::ConfigError> {
@@ -37,6 +42,10 @@ impl ClientServerConfiguration {
         self.port = port;
     }
 
+    pub fn set_local_server_storage_dir(&mut self, storage_dir: &str) {
+        self.local_server_storage_dir = Some(storage_dir.to_owned());
+    }
+
     pub fn base_url(&self) -> String {
         format!("{}://{}:{}", self.http_scheme, self.host, self.port)
     }
@@ -8,4 +8,11 @@ pub mod protobuf;
 mod request;
 pub mod ws;
 
-pub use crate::configuration::{get_client_server_configuration, ClientServerConfiguration};
+pub use crate::configuration::{get_client_server_configuration, ClientServerConfiguration, HEADER_TOKEN};
+
+#[macro_use]
+extern crate diesel;
+#[macro_use]
+extern crate diesel_derives;
+#[macro_use]
+extern crate diesel_migrations;
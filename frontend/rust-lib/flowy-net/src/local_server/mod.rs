@@ -1,11 +1,17 @@
 use crate::configuration::ClientServerConfiguration;
+use flowy_sync::errors::CollaborateError;
+use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc};
 
 mod persistence;
+mod schema;
 mod server;
+mod sqlite_storage;
 mod ws;
 
+pub use persistence::{MemoryDocumentCloudStorage, RevisionCloudStorage};
 pub use server::*;
+pub use sqlite_storage::*;
 pub use ws::*;
 
 pub struct LocalServerContext {
@@ -13,7 +19,7 @@ pub struct LocalServerContext {
     pub local_server: LocalServer,
 }
 
-pub fn build_server(_config: &ClientServerConfiguration) -> LocalServerContext {
+pub fn build_server(config: &ClientServerConfiguration) -> Result<LocalServerContext, CollaborateError> {
     let (client_ws_sender, server_ws_receiver) = mpsc::unbounded_channel();
     let (server_ws_sender, _) = broadcast::channel(16);
 
@@ -21,7 +27,18 @@ pub fn build_server(_config: &ClientServerConfiguration) -> LocalServerContext {
     // server_ws_receiver <- client_ws_sender
     let local_ws = LocalWebSocket::new(server_ws_receiver, server_ws_sender.clone());
     let client_ws_receiver = server_ws_sender;
-    let local_server = LocalServer::new(client_ws_sender, client_ws_receiver);
+    let storage = mk_storage(config)?;
+    let local_server = LocalServer::new(client_ws_sender, client_ws_receiver, storage);
 
-    LocalServerContext { local_ws, local_server }
+    Ok(LocalServerContext { local_ws, local_server })
+}
+
+fn mk_storage(config: &ClientServerConfiguration) -> Result<Arc<dyn RevisionCloudStorage>, CollaborateError> {
+    match config.local_server_storage_dir.as_ref() {
+        None => Ok(Arc::new(MemoryDocumentCloudStorage::default())),
+        Some(storage_dir) => {
+            let storage = SQLiteRevisionCloudStorage::new(storage_dir)?;
+            Ok(Arc::new(storage))
+        }
+    }
 }
@@ -1,10 +1,12 @@
-use flowy_sync::entities::revision::{RepeatedRevision, Revision};
+use dashmap::DashMap;
+use flowy_sync::entities::revision::{RepeatedRevision, Revision, RevisionRange};
 use flowy_sync::{
-    entities::{folder::FolderInfo, text_block::DocumentPB},
+    entities::{folder::FolderInfo, grid::GridInfo, text_block::DocumentPB},
     errors::CollaborateError,
     server_document::*,
     server_folder::FolderCloudPersistence,
-    util::{make_document_from_revision_pbs, make_folder_from_revisions_pb},
+    server_grid::GridCloudPersistence,
+    util::{make_document_from_revision_pbs, make_folder_from_revisions_pb, make_grid_from_revisions_pb},
 };
 use lib_infra::future::BoxResultFuture;
 use std::{
@@ -12,8 +14,9 @@ use std::{
     sync::Arc,
 };
 
-// For the moment, we use memory to cache the data, it will be implemented with
-// other storage. Like the Firestore,Dropbox.etc.
+// The revisions are kept in memory by default, or in the SQLite database if the
+// `local_server_storage_dir` of the `ClientServerConfiguration` is set. It could be
+// implemented with other storage. Like the Firestore,Dropbox.etc.
 pub trait RevisionCloudStorage: Send + Sync {
     fn set_revisions(&self, repeated_revision: RepeatedRevision) -> BoxResultFuture<(), CollaborateError>;
     fn get_revisions(
@@ -22,6 +25,15 @@ pub trait RevisionCloudStorage: Send + Sync {
         rev_ids: Option<Vec<i64>>,
     ) -> BoxResultFuture<RepeatedRevision, CollaborateError>;
 
+    /// Return the revisions whose ids are in the range, including the start and the end.
+    fn get_revisions_with_range(
+        &self,
+        object_id: &str,
+        range: RevisionRange,
+    ) -> BoxResultFuture<RepeatedRevision, CollaborateError> {
+        self.get_revisions(object_id, Some(range.to_rev_ids()))
+    }
+
     fn reset_object(
         &self,
         object_id: &str,
@@ -39,11 +51,9 @@ impl Debug for LocalTextBlockCloudPersistence {
     }
 }
 
-impl std::default::Default for LocalTextBlockCloudPersistence {
-    fn default() -> Self {
-        LocalTextBlockCloudPersistence {
-            storage: Arc::new(MemoryDocumentCloudStorage::default()),
-        }
+impl LocalTextBlockCloudPersistence {
+    pub(crate) fn new(storage: Arc<dyn RevisionCloudStorage>) -> Self {
+        LocalTextBlockCloudPersistence { storage }
     }
 }
 
@@ -109,6 +119,68 @@ impl FolderCloudPersistence for LocalTextBlockCloudPersistence {
     }
 }
 
+impl GridCloudPersistence for LocalTextBlockCloudPersistence {
+    fn read_grid(&self, _user_id: &str, object_id: &str) -> BoxResultFuture<GridInfo, CollaborateError> {
+        let storage = self.storage.clone();
+        let object_id = object_id.to_owned();
+        Box::pin(async move {
+            let repeated_revision = storage.get_revisions(&object_id, None).await?;
+            match make_grid_from_revisions_pb(&object_id, repeated_revision)? {
+                Some(grid_info) => Ok(grid_info),
+                None => Err(CollaborateError::record_not_found()),
+            }
+        })
+    }
+
+    fn create_grid(
+        &self,
+        _user_id: &str,
+        object_id: &str,
+        repeated_revision: RepeatedRevision,
+    ) -> BoxResultFuture<Option<GridInfo>, CollaborateError> {
+        let object_id = object_id.to_owned();
+        let storage = self.storage.clone();
+        Box::pin(async move {
+            let _ = storage.set_revisions(repeated_revision.clone()).await?;
+            make_grid_from_revisions_pb(&object_id, repeated_revision)
+        })
+    }
+
+    fn save_grid_revisions(&self, repeated_revision: RepeatedRevision) -> BoxResultFuture<(), CollaborateError> {
+        let storage = self.storage.clone();
+        Box::pin(async move {
+            let _ = storage.set_revisions(repeated_revision).await?;
+            Ok(())
+        })
+    }
+
+    fn read_grid_revisions(
+        &self,
+        object_id: &str,
+        rev_ids: Option<Vec<i64>>,
+    ) -> BoxResultFuture<Vec<Revision>, CollaborateError> {
+        let object_id = object_id.to_owned();
+        let storage = self.storage.clone();
+        Box::pin(async move {
+            let repeated_revision = storage.get_revisions(&object_id, rev_ids).await?;
+            Ok(repeated_revision.into_inner())
+        })
+    }
+
+    fn reset_grid(
+        &self,
+        object_id: &str,
+        repeated_revision: RepeatedRevision,
+    ) -> BoxResultFuture<(), CollaborateError> {
+        let storage = self.storage.clone();
+        let object_id = object_id.to_owned();
+        Box::pin(async move {
+            let _ = storage.reset_object(&object_id, repeated_revision).await?;
+            Ok(())
+        })
+    }
+}
+
 impl TextBlockCloudPersistence for LocalTextBlockCloudPersistence {
     fn read_text_block(&self, doc_id: &str) -> BoxResultFuture<DocumentPB, CollaborateError> {
         let storage = self.storage.clone();
@@ -166,29 +238,62 @@ impl TextBlockCloudPersistence for LocalTextBlockCloudPersistence {
     }
 }
 
+/// Keeps the revisions of each object in memory, so the clients that connect to the same local
+/// server can pull the revisions that were pushed by the others.
 #[derive(Default)]
-struct MemoryDocumentCloudStorage {}
+pub struct MemoryDocumentCloudStorage {
+    revisions_by_object_id: DashMap<String, Vec<Revision>>,
+}
+
+impl MemoryDocumentCloudStorage {
+    fn insert_revisions(&self, revisions: Vec<Revision>) {
+        for revision in revisions {
+            let mut object_revisions = self
+                .revisions_by_object_id
+                .entry(revision.object_id.clone())
+                .or_insert_with(Vec::new);
+            match object_revisions.binary_search_by_key(&revision.rev_id, |revision| revision.rev_id) {
+                Ok(index) => object_revisions[index] = revision,
+                Err(index) => object_revisions.insert(index, revision),
+            }
+        }
+    }
+}
+
 impl RevisionCloudStorage for MemoryDocumentCloudStorage {
-    fn set_revisions(&self, _repeated_revision: RepeatedRevision) -> BoxResultFuture<(), CollaborateError> {
+    fn set_revisions(&self, repeated_revision: RepeatedRevision) -> BoxResultFuture<(), CollaborateError> {
+        self.insert_revisions(repeated_revision.into_inner());
         Box::pin(async move { Ok(()) })
     }
 
     fn get_revisions(
         &self,
-        _doc_id: &str,
-        _rev_ids: Option<Vec<i64>>,
+        doc_id: &str,
+        rev_ids: Option<Vec<i64>>,
     ) -> BoxResultFuture<RepeatedRevision, CollaborateError> {
+        let revisions = self
+            .revisions_by_object_id
+            .get(doc_id)
+            .map(|revisions| {
+                revisions
+                    .iter()
+                    .filter(|revision| match rev_ids.as_ref() {
+                        None => true,
+                        Some(rev_ids) => rev_ids.contains(&revision.rev_id),
+                    })
+                    .cloned()
+                    .collect::<Vec<Revision>>()
+            })
+            .unwrap_or_default();
         Box::pin(async move {
-            let repeated_revisions = RepeatedRevision::default();
+            let repeated_revisions = RepeatedRevision::new(revisions);
             Ok(repeated_revisions)
         })
     }
 
-    fn reset_object(
-        &self,
-        _doc_id: &str,
-        _repeated_revision: RepeatedRevision,
-    ) -> BoxResultFuture<(), CollaborateError> {
+    fn reset_object(&self, doc_id: &str, repeated_revision: RepeatedRevision) -> BoxResultFuture<(), CollaborateError> {
+        self.revisions_by_object_id.remove(doc_id);
+        self.insert_revisions(repeated_revision.into_inner());
         Box::pin(async move { Ok(()) })
     }
 }
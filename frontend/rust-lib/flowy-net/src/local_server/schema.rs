@@ -0,0 +1,11 @@
+table! {
+    server_rev_table (id) {
+        id -> Integer,
+        object_id -> Text,
+        base_rev_id -> BigInt,
+        rev_id -> BigInt,
+        data -> Binary,
+        md5 -> Text,
+        user_id -> Text,
+    }
+}
@@ -1,4 +1,5 @@
-use crate::local_server::persistence::LocalTextBlockCloudPersistence;
+use crate::local_server::persistence::{LocalTextBlockCloudPersistence, RevisionCloudStorage};
+use crate::local_server::LocalWebSocket;
 use async_stream::stream;
 use bytes::Bytes;
 use flowy_error::{internal_error, FlowyError};
@@ -6,6 +7,7 @@ use flowy_folder::event_map::FolderCouldServiceV1;
 use flowy_sync::{
     client_document::default::initial_quill_delta_string,
     entities::{
+        revision::RepeatedRevision,
         text_block::{CreateTextBlockParams, DocumentPB, ResetTextBlockParams, TextBlockIdPB},
         ws_data::{ClientRevisionWSData, ClientRevisionWSDataType},
     },
@@ -13,23 +15,28 @@ use flowy_sync::{
     protobuf::ClientRevisionWSData as ClientRevisionWSDataPB,
     server_document::ServerDocumentManager,
     server_folder::ServerFolderManager,
+    server_grid::ServerGridManager,
     synchronizer::{RevisionSyncResponse, RevisionUser},
 };
 use futures_util::stream::StreamExt;
 use lib_ws::{WSChannel, WebSocketRawMessage};
 use nanoid::nanoid;
-use parking_lot::RwLock;
 use std::{
     convert::{TryFrom, TryInto},
     fmt::Debug,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 use tokio::sync::{broadcast, mpsc, mpsc::UnboundedSender};
 
 pub struct LocalServer {
     doc_manager: Arc<ServerDocumentManager>,
     folder_manager: Arc<ServerFolderManager>,
-    stop_tx: RwLock<Option<mpsc::Sender<()>>>,
+    grid_manager: Arc<ServerGridManager>,
+    stop_tx: broadcast::Sender<()>,
+    is_running: AtomicBool,
     client_ws_sender: mpsc::UnboundedSender<WebSocketRawMessage>,
     client_ws_receiver: broadcast::Sender<WebSocketRawMessage>,
 }
@@ -38,45 +45,96 @@ impl LocalServer {
     pub fn new(
         client_ws_sender: mpsc::UnboundedSender<WebSocketRawMessage>,
         client_ws_receiver: broadcast::Sender<WebSocketRawMessage>,
+        storage: Arc<dyn RevisionCloudStorage>,
     ) -> Self {
-        let persistence = Arc::new(LocalTextBlockCloudPersistence::default());
+        let persistence = Arc::new(LocalTextBlockCloudPersistence::new(storage));
         let doc_manager = Arc::new(ServerDocumentManager::new(persistence.clone()));
-        let folder_manager = Arc::new(ServerFolderManager::new(persistence));
-        let stop_tx = RwLock::new(None);
+        let folder_manager = Arc::new(ServerFolderManager::new(persistence.clone()));
+        let grid_manager = Arc::new(ServerGridManager::new(persistence));
+        let (stop_tx, _) = broadcast::channel(1);
 
         LocalServer {
             doc_manager,
             folder_manager,
+            grid_manager,
             stop_tx,
+            is_running: AtomicBool::new(false),
             client_ws_sender,
             client_ws_receiver,
         }
     }
 
     pub async fn stop(&self) {
-        if let Some(stop_tx) = self.stop_tx.read().clone() {
-            let _ = stop_tx.send(()).await;
-        }
+        let _ = self.stop_tx.send(());
     }
 
+    /// Start handling the messages of the client that this server was built with. It does nothing
+    /// if the server is already running.
     pub fn run(&self) {
-        let (stop_tx, stop_rx) = mpsc::channel(1);
-        *self.stop_tx.write() = Some(stop_tx);
+        if self.is_running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.spawn_runner("", self.client_ws_sender.clone(), self.client_ws_receiver.subscribe());
+    }
+
+    /// Connect another client to this server. The messages of each client are handled by their
+    /// own runner, so the responses are only sent back to the client that the messages came from,
+    /// while the revisions are shared by all the clients.
+    pub fn connect(&self) -> LocalWebSocket {
+        let (client_ws_sender, server_ws_receiver) = mpsc::unbounded_channel();
+        let (server_ws_sender, client_ws_receiver) = broadcast::channel(16);
+        let local_ws = LocalWebSocket::new(server_ws_receiver, server_ws_sender);
+        self.spawn_runner("", client_ws_sender, client_ws_receiver);
+        local_ws
+    }
+
+    /// Connect a client whose messages are delivered through the channels instead of the
+    /// `LocalWebSocket`, e.g. the client that connects to the sync server over the network. The
+    /// responses are sent back through the `client_ws_sender`.
+    pub fn connect_with_channel(
+        &self,
+        user_id: &str,
+        client_ws_sender: mpsc::UnboundedSender<WebSocketRawMessage>,
+        client_ws_receiver: broadcast::Receiver<WebSocketRawMessage>,
+    ) {
+        self.spawn_runner(user_id, client_ws_sender, client_ws_receiver);
+    }
+
+    /// Replace the revisions of the document that is opened by the connected clients.
+    pub async fn reset_document(&self, doc_id: &str, repeated_revision: RepeatedRevision) -> Result<(), FlowyError> {
+        let _ = self
+            .doc_manager
+            .handle_document_reset(doc_id, repeated_revision)
+            .await
+            .map_err(internal_error)?;
+        Ok(())
+    }
+
+    fn spawn_runner(
+        &self,
+        user_id: &str,
+        client_ws_sender: mpsc::UnboundedSender<WebSocketRawMessage>,
+        client_ws_receiver: broadcast::Receiver<WebSocketRawMessage>,
+    ) {
         let runner = LocalWebSocketRunner {
+            user_id: user_id.to_owned(),
             doc_manager: self.doc_manager.clone(),
             folder_manager: self.folder_manager.clone(),
-            stop_rx: Some(stop_rx),
-            client_ws_sender: self.client_ws_sender.clone(),
-            client_ws_receiver: Some(self.client_ws_receiver.subscribe()),
+            grid_manager: self.grid_manager.clone(),
+            stop_rx: Some(self.stop_tx.subscribe()),
+            client_ws_sender,
+            client_ws_receiver: Some(client_ws_receiver),
         };
         tokio::spawn(runner.run());
     }
 }
 
 struct LocalWebSocketRunner {
+    user_id: String,
     doc_manager: Arc<ServerDocumentManager>,
     folder_manager: Arc<ServerFolderManager>,
-    stop_rx: Option<mpsc::Receiver<()>>,
+    grid_manager: Arc<ServerGridManager>,
+    stop_rx: Option<broadcast::Receiver<()>>,
     client_ws_sender: mpsc::UnboundedSender<WebSocketRawMessage>,
     client_ws_receiver: Option<broadcast::Receiver<WebSocketRawMessage>>,
 }
@@ -91,6 +149,10 @@ impl LocalWebSocketRunner {
                     result = client_ws_receiver.recv() => {
                         match result {
                             Ok(msg) => yield msg,
+                            Err(broadcast::error::RecvError::Closed) => {
+                                tracing::trace!("[LocalWebSocketRunner] client disconnected");
+                                break
+                            },
                             Err(_e) => {},
                         }
                     },
@@ -116,15 +178,20 @@ impl LocalWebSocketRunner {
         let client_data = ClientRevisionWSData::try_from(bytes).map_err(internal_error)?;
         match message.channel {
             WSChannel::Document => {
-                let _ = self.handle_document_client_data(client_data, "".to_owned()).await?;
+                let _ = self
+                    .handle_document_client_data(client_data, self.user_id.clone())
+                    .await?;
                 Ok(())
             }
             WSChannel::Folder => {
-                let _ = self.handle_folder_client_data(client_data, "".to_owned()).await?;
+                let _ = self
+                    .handle_folder_client_data(client_data, self.user_id.clone())
+                    .await?;
                 Ok(())
             }
             WSChannel::Grid => {
-                todo!("Implement grid web socket channel")
+                let _ = self.handle_grid_client_data(client_data, self.user_id.clone()).await?;
+                Ok(())
             }
         }
     }
@@ -165,6 +232,39 @@ impl LocalWebSocketRunner {
         Ok(())
     }
 
+    pub async fn handle_grid_client_data(
+        &self,
+        client_data: ClientRevisionWSData,
+        user_id: String,
+    ) -> Result<(), CollaborateError> {
+        tracing::trace!(
+            "[LocalGridServer] receive: {}:{}-{:?} ",
+            client_data.object_id,
+            client_data.id(),
+            client_data.ty,
+        );
+        let client_ws_sender = self.client_ws_sender.clone();
+        let user = Arc::new(LocalRevisionUser {
+            user_id,
+            client_ws_sender,
+            channel: WSChannel::Grid,
+        });
+        let ty = client_data.ty.clone();
+        let grid_client_data: ClientRevisionWSDataPB = client_data.try_into()?;
+        match ty {
+            ClientRevisionWSDataType::ClientPushRev => {
+                let _ = self
+                    .grid_manager
+                    .handle_client_revisions(user, grid_client_data)
+                    .await?;
+            }
+            ClientRevisionWSDataType::ClientPing => {
+                let _ = self.grid_manager.handle_client_ping(user, grid_client_data).await?;
+            }
+        }
+        Ok(())
+    }
+
     pub async fn handle_document_client_data(
         &self,
         client_data: ClientRevisionWSData,
@@ -0,0 +1,233 @@
+use crate::local_server::persistence::RevisionCloudStorage;
+use crate::local_server::schema::{server_rev_table, server_rev_table::dsl};
+use bytes::Bytes;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection};
+use flowy_sync::{
+    entities::revision::{RepeatedRevision, Revision, RevisionRange},
+    errors::CollaborateError,
+};
+use lib_infra::future::BoxResultFuture;
+use lib_sqlite::{ConnectionPool, Database, PoolConfig};
+use std::sync::Arc;
+
+embed_migrations!("../flowy-net/migrations/");
+const DB_NAME: &str = "flowy-local-server.db";
+
+type Error = diesel::result::Error;
+
+/// Keeps the revisions of each object in a SQLite database, so the local server doesn't lose them
+/// when it restarts. The database is opened in the `storage_dir` and only contains the server's
+/// tables, which are migrated separately from the client's database.
+pub struct SQLiteRevisionCloudStorage {
+    pool: Arc<ConnectionPool>,
+}
+
+impl SQLiteRevisionCloudStorage {
+    pub fn new(storage_dir: &str) -> Result<Self, CollaborateError> {
+        let database = Database::new(storage_dir, DB_NAME, PoolConfig::default()).map_err(internal_error)?;
+        let conn = database.get_connection().map_err(internal_error)?;
+        let _ = embedded_migrations::run(&*conn).map_err(internal_error)?;
+        Ok(Self {
+            pool: database.get_pool(),
+        })
+    }
+}
+
+impl RevisionCloudStorage for SQLiteRevisionCloudStorage {
+    fn set_revisions(&self, repeated_revision: RepeatedRevision) -> BoxResultFuture<(), CollaborateError> {
+        let pool = self.pool.clone();
+        Box::pin(async move {
+            let conn = pool.get().map_err(internal_error)?;
+            let _ = ServerRevisionSql::create(repeated_revision.into_inner(), &*conn).map_err(internal_error)?;
+            Ok(())
+        })
+    }
+
+    fn get_revisions(
+        &self,
+        object_id: &str,
+        rev_ids: Option<Vec<i64>>,
+    ) -> BoxResultFuture<RepeatedRevision, CollaborateError> {
+        let pool = self.pool.clone();
+        let object_id = object_id.to_owned();
+        Box::pin(async move {
+            let conn = pool.get().map_err(internal_error)?;
+            let revisions = ServerRevisionSql::read(&object_id, rev_ids, &*conn).map_err(internal_error)?;
+            Ok(RepeatedRevision::new(revisions))
+        })
+    }
+
+    fn get_revisions_with_range(
+        &self,
+        object_id: &str,
+        range: RevisionRange,
+    ) -> BoxResultFuture<RepeatedRevision, CollaborateError> {
+        let pool = self.pool.clone();
+        let object_id = object_id.to_owned();
+        Box::pin(async move {
+            let conn = pool.get().map_err(internal_error)?;
+            let revisions = ServerRevisionSql::read_with_range(&object_id, range, &*conn).map_err(internal_error)?;
+            Ok(RepeatedRevision::new(revisions))
+        })
+    }
+
+    fn reset_object(
+        &self,
+        object_id: &str,
+        repeated_revision: RepeatedRevision,
+    ) -> BoxResultFuture<(), CollaborateError> {
+        let pool = self.pool.clone();
+        let object_id = object_id.to_owned();
+        Box::pin(async move {
+            let conn = pool.get().map_err(internal_error)?;
+            conn.immediate_transaction::<_, Error, _>(|| {
+                let _ = ServerRevisionSql::delete(&object_id, &*conn)?;
+                let _ = ServerRevisionSql::create(repeated_revision.into_inner(), &*conn)?;
+                Ok(())
+            })
+            .map_err(internal_error)
+        })
+    }
+}
+
+struct ServerRevisionSql();
+impl ServerRevisionSql {
+    /// Insert the revisions, the one that has the same object id and revision id as an existing
+    /// one replaces it.
+    fn create(revisions: Vec<Revision>, conn: &SqliteConnection) -> Result<(), Error> {
+        let records = revisions
+            .into_iter()
+            .map(|revision| {
+                (
+                    dsl::object_id.eq(revision.object_id),
+                    dsl::base_rev_id.eq(revision.base_rev_id),
+                    dsl::rev_id.eq(revision.rev_id),
+                    dsl::data.eq(revision.delta_data),
+                    dsl::md5.eq(revision.md5),
+                    dsl::user_id.eq(revision.user_id),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let _ = diesel::replace_into(dsl::server_rev_table)
+            .values(&records)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    fn read(object_id: &str, rev_ids: Option<Vec<i64>>, conn: &SqliteConnection) -> Result<Vec<Revision>, Error> {
+        let mut sql = dsl::server_rev_table.filter(dsl::object_id.eq(object_id)).into_boxed();
+        if let Some(rev_ids) = rev_ids {
+            sql = sql.filter(dsl::rev_id.eq_any(rev_ids));
+        }
+        let rows = sql.order(dsl::rev_id.asc()).load::<ServerRevisionTable>(conn)?;
+        Ok(rows.into_iter().map(|row| row.into()).collect())
+    }
+
+    fn read_with_range(object_id: &str, range: RevisionRange, conn: &SqliteConnection) -> Result<Vec<Revision>, Error> {
+        let rows = dsl::server_rev_table
+            .filter(dsl::rev_id.ge(range.start))
+            .filter(dsl::rev_id.le(range.end))
+            .filter(dsl::object_id.eq(object_id))
+            .order(dsl::rev_id.asc())
+            .load::<ServerRevisionTable>(conn)?;
+        Ok(rows.into_iter().map(|row| row.into()).collect())
+    }
+
+    fn delete(object_id: &str, conn: &SqliteConnection) -> Result<(), Error> {
+        let affected_row = diesel::delete(dsl::server_rev_table.filter(dsl::object_id.eq(object_id))).execute(conn)?;
+        tracing::trace!("[ServerRevisionSql] Delete {} rows of {}", affected_row, object_id);
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Clone, Debug, Queryable, Identifiable)]
+#[table_name = "server_rev_table"]
+struct ServerRevisionTable {
+    id: i32,
+    object_id: String,
+    base_rev_id: i64,
+    rev_id: i64,
+    data: Vec<u8>,
+    md5: String,
+    user_id: String,
+}
+
+impl std::convert::From<ServerRevisionTable> for Revision {
+    fn from(table: ServerRevisionTable) -> Self {
+        Revision::new(
+            &table.object_id,
+            table.base_rev_id,
+            table.rev_id,
+            Bytes::from(table.data),
+            &table.user_id,
+            table.md5,
+        )
+    }
+}
+
+fn internal_error<T: std::fmt::Debug>(e: T) -> CollaborateError {
+    CollaborateError::internal().context(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flowy_sync::util::md5;
+    use nanoid::nanoid;
+
+    fn mk_revision(object_id: &str, rev_id: i64, data: &str) -> Revision {
+        Revision::new(
+            object_id,
+            rev_id - 1,
+            rev_id,
+            Bytes::from(data.to_owned()),
+            "user",
+            md5(data),
+        )
+    }
+
+    fn rev_ids(repeated_revision: RepeatedRevision) -> Vec<i64> {
+        repeated_revision
+            .into_inner()
+            .iter()
+            .map(|revision| revision.rev_id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn sqlite_storage_keeps_revisions_after_reopen_test() {
+        let storage_dir = std::env::temp_dir().join(nanoid!(6));
+        let storage_dir = storage_dir.to_str().unwrap();
+        let object_id = "object";
+        let revisions = (1..=5).map(|rev_id| mk_revision(object_id, rev_id, "hello")).collect();
+        {
+            let storage = SQLiteRevisionCloudStorage::new(storage_dir).unwrap();
+            storage.set_revisions(RepeatedRevision::new(revisions)).await.unwrap();
+            storage
+                .set_revisions(RepeatedRevision::new(vec![mk_revision("other", 1, "world")]))
+                .await
+                .unwrap();
+        }
+
+        let storage = SQLiteRevisionCloudStorage::new(storage_dir).unwrap();
+        let all = storage.get_revisions(object_id, None).await.unwrap();
+        assert_eq!(rev_ids(all), vec![1, 2, 3, 4, 5]);
+
+        let some = storage.get_revisions(object_id, Some(vec![2, 4])).await.unwrap();
+        assert_eq!(rev_ids(some), vec![2, 4]);
+
+        let range = RevisionRange { start: 2, end: 4 };
+        let in_range = storage.get_revisions_with_range(object_id, range).await.unwrap();
+        assert_eq!(rev_ids(in_range), vec![2, 3, 4]);
+
+        let reset = RepeatedRevision::new(vec![mk_revision(object_id, 1, "reset")]);
+        storage.reset_object(object_id, reset).await.unwrap();
+        let revisions = storage.get_revisions(object_id, None).await.unwrap().into_inner();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].delta_data, b"reset".to_vec());
+
+        let other = storage.get_revisions("other", None).await.unwrap();
+        assert_eq!(rev_ids(other), vec![1]);
+    }
+}
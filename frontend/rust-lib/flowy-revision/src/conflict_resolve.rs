@@ -125,6 +125,11 @@ where
                 for revision in &revisions {
                     let _ = self.rev_manager.add_remote_revision(revision).await?;
                 }
+                if server_prime.is_empty() {
+                    // The remote revisions were composed without any transformation, so there is
+                    // no need to generate a new revision.
+                    return Ok(None);
+                }
                 let (client_revision, server_revision) = make_client_and_server_revision(
                     &self.user_id,
                     &self.rev_manager,
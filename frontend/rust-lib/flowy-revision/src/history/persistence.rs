@@ -5,7 +5,7 @@ use flowy_database::{
     ConnectionPool,
 };
 use flowy_error::{internal_error, FlowyResult};
-use flowy_sync::entities::revision::Revision;
+use flowy_sync::{entities::revision::Revision, util::md5};
 use std::sync::Arc;
 
 pub struct SQLiteRevisionHistoryPersistence {
@@ -21,12 +21,14 @@ impl SQLiteRevisionHistoryPersistence {
 }
 
 impl RevisionHistoryDiskCache for SQLiteRevisionHistoryPersistence {
-    fn write_history(&self, revision: Revision) -> FlowyResult<()> {
+    fn write_history(&self, history: RevisionHistory) -> FlowyResult<()> {
         let record = (
-            dsl::object_id.eq(revision.object_id),
-            dsl::start_rev_id.eq(revision.base_rev_id),
-            dsl::end_rev_id.eq(revision.rev_id),
-            dsl::data.eq(revision.delta_data),
+            dsl::object_id.eq(history.object_id),
+            dsl::start_rev_id.eq(history.start_rev_id),
+            dsl::end_rev_id.eq(history.end_rev_id),
+            dsl::data.eq(history.data),
+            dsl::user_id.eq(history.user_id),
+            dsl::timestamp.eq(history.timestamp),
         );
         let conn = self.pool.get().map_err(internal_error)?;
 
@@ -38,41 +40,73 @@ impl RevisionHistoryDiskCache for SQLiteRevisionHistoryPersistence {
 
     fn read_histories(&self) -> FlowyResult<Vec<RevisionHistory>> {
         let conn = self.pool.get().map_err(internal_error)?;
-        let records: Vec<RevisionRecord> = dsl::rev_history
+        let records: Vec<RevisionHistoryRecord> = dsl::rev_history
             .filter(dsl::object_id.eq(&self.object_id))
-            .load::<RevisionRecord>(&*conn)?;
+            .order(dsl::end_rev_id.asc())
+            .load::<RevisionHistoryRecord>(&*conn)?;
 
         Ok(records
             .into_iter()
             .map(|record| record.into())
             .collect::<Vec<RevisionHistory>>())
     }
+
+    fn delete_histories(&self) -> FlowyResult<()> {
+        let conn = self.pool.get().map_err(internal_error)?;
+        let _ = diesel::delete(dsl::rev_history.filter(dsl::object_id.eq(&self.object_id))).execute(&*conn)?;
+        Ok(())
+    }
 }
 
 #[derive(PartialEq, Clone, Debug, Queryable, Identifiable, Insertable, Associations)]
 #[table_name = "rev_history"]
-struct RevisionRecord {
+struct RevisionHistoryRecord {
     id: i32,
     object_id: String,
     start_rev_id: i64,
     end_rev_id: i64,
     data: Vec<u8>,
+    user_id: String,
+    timestamp: i64,
 }
 
+/// The compaction of the revisions from `start_rev_id` to `end_rev_id`, both inclusive.
+#[derive(Clone, Debug)]
 pub struct RevisionHistory {
     pub object_id: String,
     pub start_rev_id: i64,
     pub end_rev_id: i64,
     pub data: Vec<u8>,
+    /// The user who made the revisions of this history.
+    pub user_id: String,
+    /// The time, in seconds, the history was written.
+    pub timestamp: i64,
+}
+
+impl RevisionHistory {
+    /// The revision that applies the changes of this history.
+    pub fn to_revision(&self) -> Revision {
+        let md5 = md5(&self.data);
+        Revision::new(
+            &self.object_id,
+            self.start_rev_id - 1,
+            self.end_rev_id,
+            self.data.clone().into(),
+            &self.user_id,
+            md5,
+        )
+    }
 }
 
-impl std::convert::From<RevisionRecord> for RevisionHistory {
-    fn from(record: RevisionRecord) -> Self {
+impl std::convert::From<RevisionHistoryRecord> for RevisionHistory {
+    fn from(record: RevisionHistoryRecord) -> Self {
         RevisionHistory {
             object_id: record.object_id,
             start_rev_id: record.start_rev_id,
             end_rev_id: record.end_rev_id,
             data: record.data,
+            user_id: record.user_id,
+            timestamp: record.timestamp,
         }
     }
 }
@@ -1,6 +1,6 @@
 mod cache;
 mod conflict_resolve;
-// mod history;
+mod history;
 mod rev_manager;
 mod rev_persistence;
 mod snapshot;
@@ -8,7 +8,7 @@ mod ws_manager;
 
 pub use cache::*;
 pub use conflict_resolve::*;
-// pub use history::*;
+pub use history::*;
 pub use rev_manager::*;
 pub use rev_persistence::*;
 pub use snapshot::*;
@@ -1,5 +1,8 @@
 use crate::disk::RevisionState;
-use crate::{RevisionPersistence, RevisionSnapshotDiskCache, RevisionSnapshotManager, WSDataProviderDataSource};
+use crate::{
+    RevisionHistory, RevisionHistoryConfig, RevisionHistoryDiskCache, RevisionHistoryManager, RevisionPersistence,
+    RevisionSnapshotDiskCache, RevisionSnapshotInfo, RevisionSnapshotManager, WSDataProviderDataSource,
+};
 use bytes::Bytes;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_sync::{
@@ -8,6 +11,7 @@ use flowy_sync::{
 };
 use lib_infra::future::FutureResult;
 use std::sync::Arc;
+use tokio::sync::broadcast;
 
 pub trait RevisionCloudService: Send + Sync {
     fn fetch_object(&self, user_id: &str, object_id: &str) -> FutureResult<Vec<Revision>, FlowyError>;
@@ -45,11 +49,14 @@ pub struct RevisionManager {
     user_id: String,
     rev_id_counter: RevIdCounter,
     rev_persistence: Arc<RevisionPersistence>,
-    #[allow(dead_code)]
+    rev_history: Option<Arc<RevisionHistoryManager>>,
     rev_snapshot: Arc<RevisionSnapshotManager>,
     rev_compactor: Arc<dyn RevisionCompactor>,
+    rev_notifier: broadcast::Sender<Revision>,
     #[cfg(feature = "flowy_unit_test")]
     rev_ack_notifier: tokio::sync::broadcast::Sender<i64>,
+    #[cfg(feature = "flowy_unit_test")]
+    loaded_revision_count: usize,
 }
 
 impl RevisionManager {
@@ -70,6 +77,7 @@ impl RevisionManager {
         let rev_persistence = Arc::new(rev_persistence);
 
         let rev_snapshot = Arc::new(RevisionSnapshotManager::new(user_id, object_id, snapshot_persistence));
+        let (rev_notifier, _) = broadcast::channel(100);
         #[cfg(feature = "flowy_unit_test")]
         let (revision_ack_notifier, _) = tokio::sync::broadcast::channel(1);
 
@@ -78,27 +86,59 @@ impl RevisionManager {
             user_id: user_id.to_owned(),
             rev_id_counter,
             rev_persistence,
+            rev_history: None,
             rev_snapshot,
             rev_compactor,
+            rev_notifier,
             #[cfg(feature = "flowy_unit_test")]
             rev_ack_notifier: revision_ack_notifier,
+            #[cfg(feature = "flowy_unit_test")]
+            loaded_revision_count: 0,
         }
     }
 
+    /// Record the revisions of the object as the histories, so that the object can be browsed and
+    /// restored at any of its histories.
+    pub fn with_history<HP>(mut self, history_persistence: HP) -> Self
+    where
+        HP: 'static + RevisionHistoryDiskCache,
+    {
+        self.rev_history = Some(Arc::new(RevisionHistoryManager::new(
+            &self.user_id,
+            &self.object_id,
+            RevisionHistoryConfig::default(),
+            Arc::new(history_persistence),
+            self.rev_compactor.clone(),
+        )));
+        self
+    }
+
     #[tracing::instrument(level = "debug", skip_all, fields(object_id) err)]
     pub async fn load<B>(&mut self, cloud: Option<Arc<dyn RevisionCloudService>>) -> FlowyResult<B::Output>
     where
         B: RevisionObjectBuilder,
     {
+        let snapshot = match self.rev_snapshot.read_last_snapshot() {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                tracing::error!("Read the snapshot of {} failed: {:?}", self.object_id, e);
+                None
+            }
+        };
         let (revisions, rev_id) = RevisionLoader {
             object_id: self.object_id.clone(),
             user_id: self.user_id.clone(),
             cloud,
             rev_persistence: self.rev_persistence.clone(),
         }
-        .load()
+        .load_with_snapshot(snapshot)
         .await?;
         self.rev_id_counter.set(rev_id);
+        self.write_snapshot_if_need(rev_id);
+        #[cfg(feature = "flowy_unit_test")]
+        {
+            self.loaded_revision_count = revisions.len();
+        }
         tracing::Span::current().record("object_id", &self.object_id.as_str());
         B::build_object(&self.object_id, revisions)
     }
@@ -106,8 +146,13 @@ impl RevisionManager {
     #[tracing::instrument(level = "debug", skip(self, revisions), err)]
     pub async fn reset_object(&self, revisions: RepeatedRevision) -> FlowyResult<()> {
         let rev_id = pair_rev_id_from_revisions(&revisions).1;
-        let _ = self.rev_persistence.reset(revisions.into_inner()).await?;
+        let revisions = revisions.into_inner();
+        let _ = self.rev_snapshot.reset()?;
+        let _ = self.rev_persistence.reset(revisions.clone()).await?;
         self.rev_id_counter.set(rev_id);
+        for revision in revisions {
+            self.notify(revision);
+        }
         Ok(())
     }
 
@@ -118,8 +163,12 @@ impl RevisionManager {
         }
 
         let _ = self.rev_persistence.add_ack_revision(revision).await?;
-        // self.rev_history.add_revision(revision).await;
+        if let Some(rev_history) = self.rev_history.as_ref() {
+            rev_history.add_revision(revision).await;
+        }
         self.rev_id_counter.set(revision.rev_id);
+        self.notify(revision.clone());
+        self.write_snapshot_if_need(revision.rev_id);
         Ok(())
     }
 
@@ -132,14 +181,18 @@ impl RevisionManager {
             .rev_persistence
             .add_sync_revision(revision, &self.rev_compactor)
             .await?;
-        // self.rev_history.add_revision(revision).await;
+        if let Some(rev_history) = self.rev_history.as_ref() {
+            rev_history.add_revision(revision).await;
+        }
         self.rev_id_counter.set(rev_id);
+        self.notify(revision.clone());
         Ok(())
     }
 
     #[tracing::instrument(level = "debug", skip(self), err)]
     pub async fn ack_revision(&self, rev_id: i64) -> Result<(), FlowyError> {
         if self.rev_persistence.ack_revision(rev_id).await.is_ok() {
+            self.write_snapshot_if_need(rev_id);
             #[cfg(feature = "flowy_unit_test")]
             let _ = self.rev_ack_notifier.send(rev_id);
         }
@@ -161,6 +214,87 @@ impl RevisionManager {
         Ok(revisions)
     }
 
+    /// Returns the revisions that compose the object at the `rev_id`, ordered by the rev_id. The
+    /// object is rebuilt from the last snapshot if it's not after the `rev_id`, otherwise from the
+    /// beginning. The revisions that were compacted or removed are replaced by the histories.
+    pub async fn revisions_at(&self, rev_id: i64) -> FlowyResult<Vec<Revision>> {
+        let not_found = || FlowyError::record_not_found().context(format!("The revision {} is unreachable", rev_id));
+        if rev_id < 0 || rev_id > self.rev_id() {
+            return Err(not_found());
+        }
+
+        let histories = match self.rev_history.as_ref() {
+            None => vec![],
+            Some(rev_history) => rev_history.read_revision_histories().await?,
+        };
+        if let Some(snapshot) = self.rev_snapshot.read_last_snapshot()? {
+            if snapshot.rev_id <= rev_id {
+                let start = snapshot.rev_id + 1;
+                let snapshot_revision = self.rev_snapshot.revision_from_snapshot(snapshot);
+                if let Some(revisions) = self.collect_revisions(start, rev_id, &histories).await? {
+                    let mut all_revisions = vec![snapshot_revision];
+                    all_revisions.extend(revisions);
+                    return Ok(all_revisions);
+                }
+            }
+        }
+
+        match self.collect_revisions(0, rev_id, &histories).await? {
+            None => Err(not_found()),
+            Some(revisions) => Ok(revisions),
+        }
+    }
+
+    /// Collect the revisions from `start` to `end`, both inclusive. Each rev_id is read from the
+    /// revision table, or from the history that starts at it. Returns None if any of them is missing.
+    async fn collect_revisions(
+        &self,
+        start: i64,
+        end: i64,
+        histories: &[RevisionHistory],
+    ) -> FlowyResult<Option<Vec<Revision>>> {
+        let range = RevisionRange { start, end };
+        let mut stored_revisions = self
+            .rev_persistence
+            .read_revisions_with_range(range)
+            .await?
+            .into_iter()
+            .peekable();
+
+        let mut revisions = vec![];
+        let mut next_rev_id = start;
+        while next_rev_id <= end {
+            while stored_revisions
+                .next_if(|revision| revision.rev_id < next_rev_id)
+                .is_some()
+            {}
+            if let Some(revision) = stored_revisions.next_if(|revision| revision.rev_id == next_rev_id) {
+                revisions.push(revision);
+                next_rev_id += 1;
+                continue;
+            }
+
+            match histories
+                .iter()
+                .find(|history| history.start_rev_id == next_rev_id && history.end_rev_id <= end)
+            {
+                None => return Ok(None),
+                Some(history) => {
+                    revisions.push(history.to_revision());
+                    next_rev_id = history.end_rev_id + 1;
+                }
+            }
+        }
+        Ok(Some(revisions))
+    }
+
+    pub async fn read_revision_histories(&self) -> FlowyResult<Vec<RevisionHistory>> {
+        match self.rev_history.as_ref() {
+            None => Err(FlowyError::internal().context(format!("The history of {} isn't recorded", self.object_id))),
+            Some(rev_history) => rev_history.read_revision_histories().await,
+        }
+    }
+
     pub async fn next_sync_revision(&self) -> FlowyResult<Option<Revision>> {
         Ok(self.rev_persistence.next_sync_revision().await?)
     }
@@ -168,6 +302,68 @@ impl RevisionManager {
     pub async fn get_revision(&self, rev_id: i64) -> Option<Revision> {
         self.rev_persistence.get(rev_id).await.map(|record| record.revision)
     }
+
+    /// Subscribe the revisions that are applied to the object, including the local revisions and
+    /// the remote revisions. The stream is closed after the [RevisionManager] was dropped.
+    pub fn subscribe_revisions(&self) -> broadcast::Receiver<Revision> {
+        self.rev_notifier.subscribe()
+    }
+
+    /// Write the snapshot at the `rev_id` in the background if there are enough revisions after the
+    /// last snapshot.
+    fn write_snapshot_if_need(&self, rev_id: i64) {
+        let last_rev_id = match self.rev_snapshot.try_begin_snapshot(rev_id) {
+            None => return,
+            Some(last_rev_id) => last_rev_id,
+        };
+
+        let rev_snapshot = self.rev_snapshot.clone();
+        let rev_persistence = self.rev_persistence.clone();
+        let rev_compactor = self.rev_compactor.clone();
+        tokio::spawn(async move {
+            let result = async {
+                // The snapshot only contains the revisions that were acked, the unacked ones may be
+                // changed while they conflict with the remote revisions.
+                if let Some(sync_rev_id) = rev_persistence.next_sync_rev_id().await {
+                    if sync_rev_id <= rev_id {
+                        return Ok(false);
+                    }
+                }
+
+                let mut revisions = vec![];
+                let mut start = 0;
+                if let Some(snapshot) = rev_snapshot.read_last_snapshot()? {
+                    if snapshot.rev_id >= rev_id {
+                        return Ok(true);
+                    }
+                    start = snapshot.rev_id + 1;
+                    revisions.push(rev_snapshot.revision_from_snapshot(snapshot));
+                }
+                let range = RevisionRange { start, end: rev_id };
+                revisions.extend(rev_persistence.read_revisions_with_range(range).await?);
+                let data = rev_compactor.bytes_from_revisions(revisions)?;
+                let _ = rev_snapshot.write_snapshot(rev_id, data.to_vec())?;
+                Ok::<bool, FlowyError>(true)
+            }
+            .await;
+
+            match result {
+                Ok(true) => {}
+                Ok(false) => rev_snapshot.cancel_snapshot(rev_id, last_rev_id),
+                Err(e) => {
+                    tracing::error!("Write snapshot at {} failed: {:?}", rev_id, e);
+                    rev_snapshot.cancel_snapshot(rev_id, last_rev_id);
+                }
+            }
+        });
+    }
+
+    fn notify(&self, revision: Revision) {
+        // The send fails if there is no subscriber, which is fine.
+        if self.rev_notifier.receiver_count() > 0 {
+            let _ = self.rev_notifier.send(revision);
+        }
+    }
 }
 
 impl WSDataProviderDataSource for Arc<RevisionManager> {
@@ -191,6 +387,12 @@ impl RevisionManager {
     pub async fn revision_cache(&self) -> Arc<RevisionPersistence> {
         self.rev_persistence.clone()
     }
+    pub fn revision_snapshot(&self) -> Arc<RevisionSnapshotManager> {
+        self.rev_snapshot.clone()
+    }
+    pub fn loaded_revision_count(&self) -> usize {
+        self.loaded_revision_count
+    }
     pub fn ack_notify(&self) -> tokio::sync::broadcast::Receiver<i64> {
         self.rev_ack_notifier.subscribe()
     }
@@ -205,10 +407,22 @@ pub struct RevisionLoader {
 
 impl RevisionLoader {
     pub async fn load(&self) -> Result<(Vec<Revision>, i64), FlowyError> {
-        let records = self.rev_persistence.batch_get(&self.object_id)?;
+        self.load_with_snapshot(None).await
+    }
+
+    /// Load the revisions after the snapshot. The snapshot is returned as the first revision, so
+    /// composing the returned revisions builds the whole object.
+    pub async fn load_with_snapshot(
+        &self,
+        snapshot: Option<RevisionSnapshotInfo>,
+    ) -> Result<(Vec<Revision>, i64), FlowyError> {
+        let records = match &snapshot {
+            None => self.rev_persistence.batch_get(&self.object_id)?,
+            Some(snapshot) => self.rev_persistence.batch_get_after(snapshot.rev_id)?,
+        };
         let revisions: Vec<Revision>;
         let mut rev_id = 0;
-        if records.is_empty() && self.cloud.is_some() {
+        if records.is_empty() && snapshot.is_none() && self.cloud.is_some() {
             let remote_revisions = self
                 .cloud
                 .as_ref()
@@ -228,7 +442,15 @@ impl RevisionLoader {
                     let _ = self.rev_persistence.sync_revision(&record.revision).await?;
                 }
             }
-            revisions = records.into_iter().map(|record| record.revision).collect::<_>();
+            let mut loaded_revisions = vec![];
+            if let Some(snapshot) = snapshot {
+                if records.is_empty() {
+                    rev_id = snapshot.rev_id;
+                }
+                loaded_revisions.push(snapshot.into_revision(&self.object_id, &self.user_id));
+            }
+            loaded_revisions.extend(records.into_iter().map(|record| record.revision));
+            revisions = loaded_revisions;
         }
 
         if let Some(revision) = revisions.last() {
@@ -4,11 +4,14 @@ use crate::cache::{
 };
 use crate::disk::{RevisionRecord, RevisionState, SQLiteGridBlockRevisionPersistence};
 use crate::memory::RevisionMemoryCache;
-use crate::RevisionCompactor;
+use crate::{
+    RevisionCompactor, RevisionHistoryDiskCache, RevisionSnapshotDiskCache, SQLiteRevisionHistoryPersistence,
+    SQLiteRevisionSnapshotPersistence,
+};
 use flowy_database::ConnectionPool;
 use flowy_error::{internal_error, FlowyError, FlowyResult};
 use flowy_sync::entities::revision::{Revision, RevisionRange};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::{borrow::Cow, sync::Arc};
 use tokio::sync::RwLock;
 use tokio::task::spawn_blocking;
@@ -108,6 +111,10 @@ impl RevisionPersistence {
         Ok(())
     }
 
+    pub(crate) async fn next_sync_rev_id(&self) -> Option<i64> {
+        self.sync_seq.read().await.next_rev_id()
+    }
+
     pub(crate) async fn next_sync_revision(&self) -> FlowyResult<Option<Revision>> {
         match self.sync_seq.read().await.next_rev_id() {
             None => Ok(None),
@@ -186,6 +193,16 @@ impl RevisionPersistence {
         self.disk_cache.read_revision_records(doc_id, None)
     }
 
+    /// Read the records which rev_id > rev_id from the disk.
+    pub fn batch_get_after(&self, rev_id: i64) -> FlowyResult<Vec<RevisionRecord>> {
+        let range = RevisionRange {
+            start: rev_id + 1,
+            end: i64::MAX,
+        };
+        self.disk_cache
+            .read_revision_records_with_range(&self.object_id, &range)
+    }
+
     // Read the revision which rev_id >= range.start && rev_id <= range.end
     pub async fn revisions_in_range(&self, range: &RevisionRange) -> FlowyResult<Vec<Revision>> {
         let range = range.clone();
@@ -212,6 +229,27 @@ impl RevisionPersistence {
             .map(|record| record.revision)
             .collect::<Vec<Revision>>())
     }
+
+    /// Read the revisions which rev_id >= range.start && rev_id <= range.end from both the disk and
+    /// the memory cache. Unlike [revisions_in_range], the rev_ids in the range are not required to be
+    /// continuous.
+    pub(crate) async fn read_revisions_with_range(&self, range: RevisionRange) -> FlowyResult<Vec<Revision>> {
+        let disk_cache = self.disk_cache.clone();
+        let object_id = self.object_id.clone();
+        let disk_range = range.clone();
+        let disk_records = spawn_blocking(move || disk_cache.read_revision_records_with_range(&object_id, &disk_range))
+            .await
+            .map_err(internal_error)??;
+
+        let mut revisions = BTreeMap::new();
+        for record in disk_records {
+            revisions.insert(record.revision.rev_id, record.revision);
+        }
+        for record in self.memory_cache.get_with_range(&range).await? {
+            revisions.insert(record.revision.rev_id, record.revision);
+        }
+        Ok(revisions.into_values().collect())
+    }
 }
 
 pub fn mk_text_block_revision_disk_cache(
@@ -228,6 +266,17 @@ pub fn mk_grid_block_revision_disk_cache(
     Arc::new(SQLiteGridBlockRevisionPersistence::new(user_id, pool))
 }
 
+/// Delete the revisions, the histories and the snapshots of the object from the disk.
+pub fn delete_revision_object<C>(object_id: &str, disk_cache: C, pool: Arc<ConnectionPool>) -> FlowyResult<()>
+where
+    C: RevisionDiskCache<Error = FlowyError>,
+{
+    let _ = disk_cache.delete_revision_records(object_id, None)?;
+    let _ = SQLiteRevisionHistoryPersistence::new(object_id, pool.clone()).delete_histories()?;
+    let _ = SQLiteRevisionSnapshotPersistence::new(object_id, pool).delete_snapshots(object_id)?;
+    Ok(())
+}
+
 impl RevisionMemoryCacheDelegate for Arc<dyn RevisionDiskCache<Error = FlowyError>> {
     fn checkpoint_tick(&self, mut records: Vec<RevisionRecord>) -> FlowyResult<()> {
         records.retain(|record| record.write_to_disk);
@@ -1,12 +1,15 @@
-#![allow(clippy::all)]
-#![allow(dead_code)]
-#![allow(unused_variables)]
 use crate::{RevisionSnapshotDiskCache, RevisionSnapshotInfo};
-use flowy_database::ConnectionPool;
-use flowy_error::FlowyResult;
+use diesel::OptionalExtension;
+use flowy_database::{
+    prelude::*,
+    schema::{rev_snapshot, rev_snapshot::dsl},
+    ConnectionPool,
+};
+use flowy_error::{internal_error, FlowyError, FlowyResult};
 use std::sync::Arc;
 
 pub struct SQLiteRevisionSnapshotPersistence {
+    #[allow(dead_code)]
     object_id: String,
     pool: Arc<ConnectionPool>,
 }
@@ -22,10 +25,62 @@ impl SQLiteRevisionSnapshotPersistence {
 
 impl RevisionSnapshotDiskCache for SQLiteRevisionSnapshotPersistence {
     fn write_snapshot(&self, object_id: &str, rev_id: i64, data: Vec<u8>) -> FlowyResult<()> {
-        todo!()
+        let conn = self.pool.get().map_err(internal_error)?;
+        conn.immediate_transaction::<_, FlowyError, _>(|| {
+            let record = (dsl::object_id.eq(object_id), dsl::rev_id.eq(rev_id), dsl::data.eq(data));
+            let _ = diesel::insert_into(dsl::rev_snapshot).values(record).execute(&*conn)?;
+
+            // Only the latest snapshot is used to load the object, so the older ones are removed.
+            let _ = diesel::delete(
+                dsl::rev_snapshot
+                    .filter(dsl::object_id.eq(object_id))
+                    .filter(dsl::rev_id.lt(rev_id)),
+            )
+            .execute(&*conn)?;
+            Ok(())
+        })
     }
 
     fn read_snapshot(&self, object_id: &str, rev_id: i64) -> FlowyResult<RevisionSnapshotInfo> {
-        todo!()
+        let conn = self.pool.get().map_err(internal_error)?;
+        let record = dsl::rev_snapshot
+            .filter(dsl::object_id.eq(object_id))
+            .filter(dsl::rev_id.eq(rev_id))
+            .first::<RevisionSnapshotRecord>(&*conn)?;
+        Ok(record.into())
+    }
+
+    fn read_last_snapshot(&self, object_id: &str) -> FlowyResult<Option<RevisionSnapshotInfo>> {
+        let conn = self.pool.get().map_err(internal_error)?;
+        let record = dsl::rev_snapshot
+            .filter(dsl::object_id.eq(object_id))
+            .order(dsl::rev_id.desc())
+            .first::<RevisionSnapshotRecord>(&*conn)
+            .optional()?;
+        Ok(record.map(|record| record.into()))
+    }
+
+    fn delete_snapshots(&self, object_id: &str) -> FlowyResult<()> {
+        let conn = self.pool.get().map_err(internal_error)?;
+        let _ = diesel::delete(dsl::rev_snapshot.filter(dsl::object_id.eq(object_id))).execute(&*conn)?;
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Clone, Debug, Queryable, Identifiable, Insertable, Associations)]
+#[table_name = "rev_snapshot"]
+struct RevisionSnapshotRecord {
+    id: i32,
+    object_id: String,
+    rev_id: i64,
+    data: Vec<u8>,
+}
+
+impl std::convert::From<RevisionSnapshotRecord> for RevisionSnapshotInfo {
+    fn from(record: RevisionSnapshotRecord) -> Self {
+        RevisionSnapshotInfo {
+            rev_id: record.rev_id,
+            data: record.data,
+        }
     }
 }
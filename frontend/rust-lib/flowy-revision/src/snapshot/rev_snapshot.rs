@@ -1,18 +1,24 @@
-#![allow(clippy::all)]
-#![allow(dead_code)]
-#![allow(unused_variables)]
 use flowy_error::FlowyResult;
+use flowy_sync::entities::revision::Revision;
+use flowy_sync::util::md5;
+use std::sync::atomic::{AtomicI64, Ordering::SeqCst};
 use std::sync::Arc;
 
 pub trait RevisionSnapshotDiskCache: Send + Sync {
     fn write_snapshot(&self, object_id: &str, rev_id: i64, data: Vec<u8>) -> FlowyResult<()>;
     fn read_snapshot(&self, object_id: &str, rev_id: i64) -> FlowyResult<RevisionSnapshotInfo>;
+    fn read_last_snapshot(&self, object_id: &str) -> FlowyResult<Option<RevisionSnapshotInfo>>;
+    fn delete_snapshots(&self, object_id: &str) -> FlowyResult<()>;
 }
 
+/// The snapshot is the object composed from the revisions until the rev_id. Loading the object from
+/// the latest snapshot only needs to compose the revisions after it.
 pub struct RevisionSnapshotManager {
     user_id: String,
     object_id: String,
+    config: RevisionSnapshotConfig,
     disk_cache: Arc<dyn RevisionSnapshotDiskCache>,
+    last_snapshot_rev_id: AtomicI64,
 }
 
 impl RevisionSnapshotManager {
@@ -24,9 +30,84 @@ impl RevisionSnapshotManager {
         Self {
             user_id: user_id.to_string(),
             object_id: object_id.to_string(),
+            config: RevisionSnapshotConfig::default(),
             disk_cache,
+            last_snapshot_rev_id: AtomicI64::new(0),
         }
     }
+
+    pub fn read_last_snapshot(&self) -> FlowyResult<Option<RevisionSnapshotInfo>> {
+        let snapshot = self.disk_cache.read_last_snapshot(&self.object_id)?;
+        if let Some(snapshot) = &snapshot {
+            self.last_snapshot_rev_id.fetch_max(snapshot.rev_id, SeqCst);
+        }
+        Ok(snapshot)
+    }
+
+    /// Returns the last snapshot's rev_id if there are enough revisions after the last snapshot. The
+    /// `rev_id` is marked as the last snapshot's rev_id, so the following calls return None until the
+    /// next interval or the snapshot is cancelled.
+    pub fn try_begin_snapshot(&self, rev_id: i64) -> Option<i64> {
+        let interval = self.config.interval;
+        self.last_snapshot_rev_id
+            .fetch_update(SeqCst, SeqCst, |last_rev_id| {
+                if rev_id - last_rev_id >= interval {
+                    Some(rev_id)
+                } else {
+                    None
+                }
+            })
+            .ok()
+    }
+
+    /// Restore the mark set by [try_begin_snapshot] if the snapshot at `rev_id` wasn't written, so
+    /// that the snapshot can be retried by the next revision.
+    pub fn cancel_snapshot(&self, rev_id: i64, last_rev_id: i64) {
+        let _ = self
+            .last_snapshot_rev_id
+            .compare_exchange(rev_id, last_rev_id, SeqCst, SeqCst);
+    }
+
+    pub fn write_snapshot(&self, rev_id: i64, data: Vec<u8>) -> FlowyResult<()> {
+        let _ = self.disk_cache.write_snapshot(&self.object_id, rev_id, data)?;
+        self.last_snapshot_rev_id.fetch_max(rev_id, SeqCst);
+        tracing::trace!("{} write snapshot at {}", self.object_id, rev_id);
+        Ok(())
+    }
+
+    /// Remove the snapshots, e.g. the revisions of the object were replaced by the remote ones.
+    pub fn reset(&self) -> FlowyResult<()> {
+        let _ = self.disk_cache.delete_snapshots(&self.object_id)?;
+        self.last_snapshot_rev_id.store(0, SeqCst);
+        Ok(())
+    }
+
+    pub fn revision_from_snapshot(&self, snapshot: RevisionSnapshotInfo) -> Revision {
+        snapshot.into_revision(&self.object_id, &self.user_id)
+    }
+}
+
+pub struct RevisionSnapshotConfig {
+    /// The number of revisions between two snapshots.
+    interval: i64,
 }
 
-pub struct RevisionSnapshotInfo {}
+impl std::default::Default for RevisionSnapshotConfig {
+    fn default() -> Self {
+        Self { interval: 100 }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RevisionSnapshotInfo {
+    pub rev_id: i64,
+    pub data: Vec<u8>,
+}
+
+impl RevisionSnapshotInfo {
+    /// The revision that contains the whole object at the snapshot's rev_id.
+    pub fn into_revision(self, object_id: &str, user_id: &str) -> Revision {
+        let md5 = md5(&self.data);
+        Revision::new(object_id, 0, self.rev_id, self.data.into(), user_id, md5)
+    }
+}
@@ -6,6 +6,7 @@ use flowy_folder::{
     errors::{internal_error, FlowyError},
     event_map::{FolderCouldServiceV1, WorkspaceDatabase, WorkspaceUser},
     manager::FolderManager,
+    services::TrashRetention,
 };
 use flowy_grid::manager::{make_grid_view_data, GridManager};
 use flowy_grid::util::make_default_grid;
@@ -26,6 +27,7 @@ use lib_ws::{WSChannel, WSMessageReceiver, WebSocketRawMessage};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::{convert::TryInto, sync::Arc};
+use tokio::sync::broadcast;
 
 pub struct FolderDepsResolver();
 impl FolderDepsResolver {
@@ -36,6 +38,7 @@ impl FolderDepsResolver {
         ws_conn: &Arc<FlowyWebSocketConnect>,
         text_block_manager: &Arc<TextBlockManager>,
         grid_manager: &Arc<GridManager>,
+        trash_retention: TrashRetention,
     ) -> Arc<FolderManager> {
         let user: Arc<dyn WorkspaceUser> = Arc::new(WorkspaceUserImpl(user_session.clone()));
         let database: Arc<dyn WorkspaceDatabase> = Arc::new(WorkspaceDatabaseImpl(user_session));
@@ -46,8 +49,17 @@ impl FolderDepsResolver {
         };
 
         let view_data_processor = make_view_data_processor(text_block_manager.clone(), grid_manager.clone());
-        let folder_manager =
-            Arc::new(FolderManager::new(user.clone(), cloud_service, database, view_data_processor, web_socket).await);
+        let folder_manager = Arc::new(
+            FolderManager::new(
+                user.clone(),
+                cloud_service,
+                database,
+                view_data_processor,
+                web_socket,
+                trash_retention,
+            )
+            .await,
+        );
 
         if let (Ok(user_id), Ok(token)) = (user.user_id(), user.token()) {
             match folder_manager.initialize(&user_id, &token).await {
@@ -180,6 +192,19 @@ impl ViewDataProcessor for TextBlockViewDataProcessor {
         })
     }
 
+    fn get_plain_text(&self, view_id: &str) -> FutureResult<String, FlowyError> {
+        let view_id = view_id.to_string();
+        let manager = self.0.clone();
+        FutureResult::new(async move {
+            let editor = manager.open_block(view_id).await?;
+            editor.plain_text().await
+        })
+    }
+
+    fn subscribe_data_changed(&self) -> broadcast::Receiver<String> {
+        self.0.subscribe_block_changed()
+    }
+
     fn create_default_view(&self, user_id: &str, view_id: &str) -> FutureResult<Bytes, FlowyError> {
         let user_id = user_id.to_string();
         let view_id = view_id.to_string();
@@ -252,6 +277,19 @@ impl ViewDataProcessor for GridViewDataProcessor {
         })
     }
 
+    fn get_plain_text(&self, view_id: &str) -> FutureResult<String, FlowyError> {
+        let view_id = view_id.to_string();
+        let grid_manager = self.0.clone();
+        FutureResult::new(async move {
+            let editor = grid_manager.open_grid(view_id).await?;
+            editor.plain_text().await
+        })
+    }
+
+    fn subscribe_data_changed(&self) -> broadcast::Receiver<String> {
+        self.0.subscribe_grid_changed()
+    }
+
     fn create_default_view(&self, user_id: &str, view_id: &str) -> FutureResult<Bytes, FlowyError> {
         let build_context = make_default_grid();
         let user_id = user_id.to_string();
@@ -273,7 +311,9 @@ impl ViewDataProcessor for GridViewDataProcessor {
 
         FutureResult::new(async move {
             let bytes = Bytes::from(data);
-            let build_context = BuildGridContext::try_from(bytes)?;
+            let mut build_context = BuildGridContext::try_from(bytes)?;
+            // The data may come from a template that creates more than one grid.
+            build_context.regenerate_ids();
             make_grid_view_data(&user_id, &view_id, grid_manager, build_context).await
         })
     }
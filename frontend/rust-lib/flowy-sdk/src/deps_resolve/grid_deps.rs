@@ -9,7 +9,7 @@ use flowy_sync::entities::ws_data::ClientRevisionWSData;
 use flowy_user::services::UserSession;
 use futures_core::future::BoxFuture;
 use lib_infra::future::BoxResultFuture;
-use lib_ws::{WSChannel, WebSocketRawMessage};
+use lib_ws::{WSChannel, WSMessageReceiver, WebSocketRawMessage};
 use std::convert::TryInto;
 use std::sync::Arc;
 
@@ -18,7 +18,7 @@ pub struct GridDepsResolver();
 impl GridDepsResolver {
     pub async fn resolve(ws_conn: Arc<FlowyWebSocketConnect>, user_session: Arc<UserSession>) -> Arc<GridManager> {
         let user = Arc::new(GridUserImpl(user_session.clone()));
-        let rev_web_socket = Arc::new(GridWebSocket(ws_conn));
+        let rev_web_socket = Arc::new(GridWebSocket(ws_conn.clone()));
         let grid_manager = Arc::new(GridManager::new(
             user.clone(),
             rev_web_socket,
@@ -32,6 +32,8 @@ impl GridDepsResolver {
             }
         }
 
+        let receiver = Arc::new(GridWSMessageReceiverImpl(grid_manager.clone()));
+        ws_conn.add_ws_message_receiver(receiver).unwrap();
         grid_manager
     }
 }
@@ -84,3 +86,16 @@ impl RevisionWebSocket for GridWebSocket {
         Box::pin(async move { ws_conn.subscribe_websocket_state().await })
     }
 }
+
+struct GridWSMessageReceiverImpl(Arc<GridManager>);
+impl WSMessageReceiver for GridWSMessageReceiverImpl {
+    fn source(&self) -> WSChannel {
+        WSChannel::Grid
+    }
+    fn receive_message(&self, msg: WebSocketRawMessage) {
+        let handler = self.0.clone();
+        tokio::spawn(async move {
+            handler.receive_ws_data(Bytes::from(msg.data)).await;
+        });
+    }
+}
@@ -3,7 +3,7 @@ pub mod module;
 pub use flowy_net::get_client_server_configuration;
 
 use crate::deps_resolve::*;
-use flowy_folder::{errors::FlowyError, manager::FolderManager};
+use flowy_folder::{errors::FlowyError, manager::FolderManager, services::TrashRetention};
 use flowy_grid::manager::GridManager;
 use flowy_net::ClientServerConfiguration;
 use flowy_net::{
@@ -34,6 +34,8 @@ pub struct FlowySDKConfig {
     root: String,
     log_filter: String,
     server_config: ClientServerConfiguration,
+    local_server: Option<Arc<LocalServer>>,
+    trash_retention: TrashRetention,
 }
 
 impl fmt::Debug for FlowySDKConfig {
@@ -52,6 +54,8 @@ impl FlowySDKConfig {
             root: root.to_owned(),
             log_filter: crate_log_filter("info".to_owned()),
             server_config,
+            local_server: None,
+            trash_retention: TrashRetention::default(),
         }
     }
 
@@ -59,6 +63,19 @@ impl FlowySDKConfig {
         self.log_filter = crate_log_filter(level.to_owned());
         self
     }
+
+    /// Connect to an existing local server instead of building a new one, so that several
+    /// clients can collaborate through the same server.
+    pub fn local_server(mut self, local_server: Arc<LocalServer>) -> Self {
+        self.local_server = Some(local_server);
+        self
+    }
+
+    /// Set how long the items are kept in the trash before they get purged.
+    pub fn trash_retention(mut self, trash_retention: TrashRetention) -> Self {
+        self.trash_retention = trash_retention;
+        self
+    }
 }
 
 fn crate_log_filter(level: String) -> String {
@@ -102,7 +119,11 @@ impl FlowySDK {
         init_kv(&config.root);
         tracing::debug!("🔥 {:?}", config);
         let runtime = tokio_default_runtime().unwrap();
-        let (local_server, ws_conn) = mk_local_server(&config.server_config);
+        let (local_server, ws_conn) = {
+            // Connecting to an existing local server spawns the task that handles this client.
+            let _guard = runtime.enter();
+            mk_local_server(&config)
+        };
         let (user_session, text_block_manager, folder_manager, local_server, grid_manager) = runtime.block_on(async {
             let user_session = mk_user_session(&config, &local_server, &config.server_config);
             let text_block_manager = TextBlockDepsResolver::resolve(
@@ -121,6 +142,7 @@ impl FlowySDK {
                 &ws_conn,
                 &text_block_manager,
                 &grid_manager,
+                config.trash_retention.clone(),
             )
             .await;
 
@@ -198,15 +220,31 @@ fn _start_listening(
     });
 }
 
-fn mk_local_server(
-    server_config: &ClientServerConfiguration,
-) -> (Option<Arc<LocalServer>>, Arc<FlowyWebSocketConnect>) {
+fn mk_local_server(config: &FlowySDKConfig) -> (Option<Arc<LocalServer>>, Arc<FlowyWebSocketConnect>) {
+    let server_config = &config.server_config;
     let ws_addr = server_config.ws_addr();
     if cfg!(feature = "http_sync") {
         let ws_conn = Arc::new(FlowyWebSocketConnect::new(ws_addr));
         (None, ws_conn)
+    } else if let Some(local_server) = config.local_server.clone() {
+        let local_ws = Arc::new(local_server.connect());
+        let ws_conn = Arc::new(FlowyWebSocketConnect::from_local(ws_addr, local_ws));
+        (Some(local_server), ws_conn)
     } else {
-        let context = flowy_net::local_server::build_server(server_config);
+        let context = match flowy_net::local_server::build_server(server_config) {
+            Ok(context) => context,
+            Err(e) => {
+                // The storage dir comes from the configuration, so keep the revisions in memory
+                // instead of failing to start if it can't be opened.
+                tracing::error!(
+                    "Open the local server storage failed: {:?}, keep the revisions in memory",
+                    e
+                );
+                let mut server_config = server_config.clone();
+                server_config.local_server_storage_dir = None;
+                flowy_net::local_server::build_server(&server_config).unwrap()
+            }
+        };
         let local_ws = Arc::new(context.local_ws);
         let ws_conn = Arc::new(FlowyWebSocketConnect::from_local(ws_addr, local_ws));
         (Some(Arc::new(context.local_server)), ws_conn)
@@ -0,0 +1,131 @@
+use crate::{
+    router::{empty, internal_error, parse_params, success, unauthorized},
+    server::SyncServer,
+};
+use bytes::Bytes;
+use flowy_error::FlowyResult;
+use flowy_sync::{
+    entities::text_block::{CreateTextBlockParams, ResetTextBlockParams, TextBlockIdPB},
+    util::make_document_from_revision_pbs,
+};
+use http_flowy::{errors::ServerError, response::FlowyResponse};
+use parking_lot::RwLock;
+use std::{collections::HashMap, path::PathBuf};
+
+const DOCUMENT_STORE_FILE: &str = "documents.json";
+
+/// Keeps the id of the user that created each document in a JSON file of the storage directory.
+/// The documents are only read and reset by the users that created them.
+pub(crate) struct DocumentStore {
+    path: PathBuf,
+    owners: RwLock<HashMap<String, String>>,
+}
+
+impl DocumentStore {
+    pub(crate) fn new(storage_dir: &str) -> FlowyResult<Self> {
+        let path = PathBuf::from(storage_dir).join(DOCUMENT_STORE_FILE);
+        let owners = if path.exists() {
+            serde_json::from_slice(&std::fs::read(&path)?)?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self {
+            path,
+            owners: RwLock::new(owners),
+        })
+    }
+
+    /// Record the user as the owner of the document if it has no owner yet. Return false if the
+    /// document belongs to another user.
+    fn add_owner(&self, doc_id: &str, user_id: &str) -> Result<bool, ServerError> {
+        let mut owners = self.owners.write();
+        match owners.get(doc_id) {
+            Some(owner) => Ok(owner == user_id),
+            None => {
+                owners.insert(doc_id.to_owned(), user_id.to_owned());
+                let _ = self.save(&owners)?;
+                Ok(true)
+            }
+        }
+    }
+
+    /// The documents of the other users are reported as not found, like the items of the folder.
+    fn check_owner(&self, doc_id: &str, user_id: &str) -> Result<(), ServerError> {
+        match self.owners.read().get(doc_id) {
+            Some(owner) if owner == user_id => Ok(()),
+            _ => Err(ServerError::record_not_found().context(format!("Can't find the document {}", doc_id))),
+        }
+    }
+
+    fn save(&self, owners: &HashMap<String, String>) -> Result<(), ServerError> {
+        let bytes = serde_json::to_vec(owners).map_err(internal_error)?;
+        std::fs::write(&self.path, bytes).map_err(internal_error)?;
+        Ok(())
+    }
+}
+
+/// Save the initial revisions of the document. The document that already exists is kept as it
+/// is, its revisions are pushed through the WebSocket endpoint.
+pub(crate) async fn create_document_handler(
+    server: &SyncServer,
+    user_id: &str,
+    body: Bytes,
+) -> Result<FlowyResponse, ServerError> {
+    let params: CreateTextBlockParams = parse_params(body)?;
+    if !server.document_store.add_owner(&params.id, user_id)? {
+        return Err(unauthorized("The document belongs to another user"));
+    }
+    let revisions = server
+        .storage
+        .get_revisions(&params.id, None)
+        .await
+        .map_err(internal_error)?;
+    if revisions.is_empty() {
+        let _ = server
+            .storage
+            .set_revisions(params.revisions)
+            .await
+            .map_err(internal_error)?;
+    }
+    empty()
+}
+
+pub(crate) async fn read_document_handler(
+    server: &SyncServer,
+    user_id: &str,
+    body: Bytes,
+) -> Result<FlowyResponse, ServerError> {
+    let params: TextBlockIdPB = parse_params(body)?;
+    let _ = server.document_store.check_owner(&params.value, user_id)?;
+    let revisions = server
+        .storage
+        .get_revisions(&params.value, None)
+        .await
+        .map_err(internal_error)?;
+    match make_document_from_revision_pbs(&params.value, revisions).map_err(internal_error)? {
+        None => Err(ServerError::record_not_found().context(format!("Can't find the document {}", params.value))),
+        Some(document) => success(document),
+    }
+}
+
+/// Replace the revisions of the document, the clients that opened the document receive the new
+/// revisions too.
+pub(crate) async fn reset_document_handler(
+    server: &SyncServer,
+    user_id: &str,
+    body: Bytes,
+) -> Result<FlowyResponse, ServerError> {
+    let params: ResetTextBlockParams = parse_params(body)?;
+    let _ = server.document_store.check_owner(&params.block_id, user_id)?;
+    let _ = server
+        .storage
+        .reset_object(&params.block_id, params.revisions.clone())
+        .await
+        .map_err(internal_error)?;
+    let _ = server
+        .local_server
+        .reset_document(&params.block_id, params.revisions)
+        .await
+        .map_err(internal_error)?;
+    empty()
+}
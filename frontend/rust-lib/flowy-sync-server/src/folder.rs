@@ -0,0 +1,246 @@
+use crate::{
+    router::{empty, internal_error, parse_params, success},
+    server::SyncServer,
+};
+use bytes::Bytes;
+use flowy_error::FlowyResult;
+use flowy_folder::{
+    entities::{
+        app::{AppIdPB, AppPB, CreateAppParams, CreateAppPayloadPB},
+        trash::RepeatedTrashPB,
+        view::{CreateViewParams, CreateViewPayloadPB, ViewIdPB, ViewPB},
+        workspace::{CreateWorkspaceParams, CreateWorkspacePayloadPB, RepeatedWorkspacePB, WorkspaceIdPB, WorkspacePB},
+    },
+    event_map::FolderCouldServiceV1,
+};
+use flowy_folder_data_model::revision::{AppRevision, ViewRevision, WorkspaceRevision};
+use http_flowy::{errors::ServerError, response::FlowyResponse};
+use hyper::Method;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, convert::TryInto, path::PathBuf};
+
+const FOLDER_STORE_FILE: &str = "folders.json";
+
+/// Keeps the workspaces, the apps and the views that each user created in a JSON file of the
+/// storage directory. The items are stored flat and nested into their parents when they are read.
+pub(crate) struct FolderStore {
+    path: PathBuf,
+    data: RwLock<HashMap<String, UserFolder>>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct UserFolder {
+    workspaces: Vec<WorkspaceRevision>,
+    apps: Vec<AppRevision>,
+    views: Vec<ViewRevision>,
+}
+
+impl UserFolder {
+    fn workspace(&self, workspace: &WorkspaceRevision) -> WorkspaceRevision {
+        let mut workspace = workspace.clone();
+        workspace.apps = self
+            .apps
+            .iter()
+            .filter(|app| app.workspace_id == workspace.id)
+            .map(|app| self.app(app))
+            .collect();
+        workspace
+    }
+
+    fn app(&self, app: &AppRevision) -> AppRevision {
+        let mut app = app.clone();
+        app.belongings = self.belongings(&app.id);
+        app
+    }
+
+    fn view(&self, view: &ViewRevision) -> ViewRevision {
+        let mut view = view.clone();
+        view.belongings = self.belongings(&view.id);
+        view
+    }
+
+    fn belongings(&self, belong_to_id: &str) -> Vec<ViewRevision> {
+        self.views
+            .iter()
+            .filter(|view| view.belong_to_id == belong_to_id)
+            .map(|view| self.view(view))
+            .collect()
+    }
+}
+
+impl FolderStore {
+    pub(crate) fn new(storage_dir: &str) -> FlowyResult<Self> {
+        let path = PathBuf::from(storage_dir).join(FOLDER_STORE_FILE);
+        let data = if path.exists() {
+            serde_json::from_slice(&std::fs::read(&path)?)?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self {
+            path,
+            data: RwLock::new(data),
+        })
+    }
+
+    fn add_workspace(&self, user_id: &str, workspace: WorkspaceRevision) -> Result<(), ServerError> {
+        let mut data = self.data.write();
+        data.entry(user_id.to_owned()).or_default().workspaces.push(workspace);
+        self.save(&data)
+    }
+
+    fn add_app(&self, user_id: &str, app: AppRevision) -> Result<(), ServerError> {
+        let mut data = self.data.write();
+        data.entry(user_id.to_owned()).or_default().apps.push(app);
+        self.save(&data)
+    }
+
+    fn add_view(&self, user_id: &str, view: ViewRevision) -> Result<(), ServerError> {
+        let mut data = self.data.write();
+        data.entry(user_id.to_owned()).or_default().views.push(view);
+        self.save(&data)
+    }
+
+    /// Return the user's workspaces, or only the one with the `workspace_id` if it's not None.
+    fn read_workspaces(&self, user_id: &str, workspace_id: Option<String>) -> Vec<WorkspaceRevision> {
+        let data = self.data.read();
+        match data.get(user_id) {
+            None => vec![],
+            Some(folder) => folder
+                .workspaces
+                .iter()
+                .filter(|workspace| workspace_id.as_ref().map_or(true, |id| &workspace.id == id))
+                .map(|workspace| folder.workspace(workspace))
+                .collect(),
+        }
+    }
+
+    fn read_app(&self, user_id: &str, app_id: &str) -> Option<AppRevision> {
+        let data = self.data.read();
+        let folder = data.get(user_id)?;
+        folder
+            .apps
+            .iter()
+            .find(|app| app.id == app_id)
+            .map(|app| folder.app(app))
+    }
+
+    fn read_view(&self, user_id: &str, view_id: &str) -> Option<ViewRevision> {
+        let data = self.data.read();
+        let folder = data.get(user_id)?;
+        folder
+            .views
+            .iter()
+            .find(|view| view.id == view_id)
+            .map(|view| folder.view(view))
+    }
+
+    fn save(&self, data: &HashMap<String, UserFolder>) -> Result<(), ServerError> {
+        let bytes = serde_json::to_vec(data).map_err(internal_error)?;
+        std::fs::write(&self.path, bytes).map_err(internal_error)?;
+        Ok(())
+    }
+}
+
+// The structure of the folder is also synchronized through the WebSocket endpoint. These routes
+// keep the items that each user created, so they can be read back by the user.
+
+pub(crate) async fn workspace_handler(
+    server: &SyncServer,
+    token: &str,
+    user_id: &str,
+    method: Method,
+    body: Bytes,
+) -> Result<FlowyResponse, ServerError> {
+    match method {
+        Method::POST => {
+            let payload: CreateWorkspacePayloadPB = parse_params(body)?;
+            let params: CreateWorkspaceParams = payload.try_into().map_err(internal_error)?;
+            let workspace_rev = server
+                .local_server
+                .create_workspace(token, params)
+                .await
+                .map_err(internal_error)?;
+            let _ = server.folder_store.add_workspace(user_id, workspace_rev.clone())?;
+            success(WorkspacePB::from(workspace_rev))
+        }
+        Method::GET => {
+            let params: WorkspaceIdPB = parse_params(body)?;
+            let items = server
+                .folder_store
+                .read_workspaces(user_id, params.value)
+                .into_iter()
+                .map(WorkspacePB::from)
+                .collect();
+            success(RepeatedWorkspacePB { items })
+        }
+        _ => empty(),
+    }
+}
+
+pub(crate) async fn app_handler(
+    server: &SyncServer,
+    token: &str,
+    user_id: &str,
+    method: Method,
+    body: Bytes,
+) -> Result<FlowyResponse, ServerError> {
+    match method {
+        Method::POST => {
+            let payload: CreateAppPayloadPB = parse_params(body)?;
+            let params: CreateAppParams = payload.try_into().map_err(internal_error)?;
+            let app_rev = server
+                .local_server
+                .create_app(token, params)
+                .await
+                .map_err(internal_error)?;
+            let _ = server.folder_store.add_app(user_id, app_rev.clone())?;
+            success(AppPB::from(app_rev))
+        }
+        Method::GET => {
+            let params: AppIdPB = parse_params(body)?;
+            match server.folder_store.read_app(user_id, &params.value) {
+                None => Err(ServerError::record_not_found().context(format!("Can't find the app {}", params.value))),
+                Some(app_rev) => success(AppPB::from(app_rev)),
+            }
+        }
+        _ => empty(),
+    }
+}
+
+pub(crate) async fn view_handler(
+    server: &SyncServer,
+    token: &str,
+    user_id: &str,
+    method: Method,
+    body: Bytes,
+) -> Result<FlowyResponse, ServerError> {
+    match method {
+        Method::POST => {
+            let payload: CreateViewPayloadPB = parse_params(body)?;
+            let params: CreateViewParams = payload.try_into().map_err(internal_error)?;
+            let view_rev = server
+                .local_server
+                .create_view(token, params)
+                .await
+                .map_err(internal_error)?;
+            let _ = server.folder_store.add_view(user_id, view_rev.clone())?;
+            success(ViewPB::from(view_rev))
+        }
+        Method::GET => {
+            let params: ViewIdPB = parse_params(body)?;
+            match server.folder_store.read_view(user_id, &params.value) {
+                None => Err(ServerError::record_not_found().context(format!("Can't find the view {}", params.value))),
+                Some(view_rev) => success(ViewPB::from(view_rev)),
+            }
+        }
+        _ => empty(),
+    }
+}
+
+pub(crate) fn trash_handler(method: Method) -> Result<FlowyResponse, ServerError> {
+    match method {
+        Method::GET => success(RepeatedTrashPB::default()),
+        _ => empty(),
+    }
+}
@@ -0,0 +1,8 @@
+mod document;
+mod folder;
+mod router;
+mod server;
+mod user;
+mod ws;
+
+pub use server::*;
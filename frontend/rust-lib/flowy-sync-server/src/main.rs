@@ -0,0 +1,30 @@
+use flowy_net::get_client_server_configuration;
+use flowy_sync_server::SyncServer;
+use std::net::TcpListener;
+
+const DEFAULT_STORAGE_DIR: &str = "./flowy-sync-server";
+
+/// Usage: flowy-sync-server [storage_dir] [address]
+///
+/// The address defaults to the host and the port of the client's server configuration, so the
+/// clients that are built with the same configuration connect to this server.
+#[tokio::main]
+async fn main() {
+    let mut args = std::env::args().skip(1);
+    let storage_dir = args.next().unwrap_or_else(|| DEFAULT_STORAGE_DIR.to_owned());
+    let addr = args.next().unwrap_or_else(|| {
+        let config = get_client_server_configuration().expect("Failed to read the server configuration");
+        format!("{}:{}", config.host, config.port)
+    });
+
+    let server = SyncServer::new(&storage_dir).expect("Failed to open the storage of the sync server");
+    let _ = lib_log::Builder::new("flowy-sync-server", &storage_dir)
+        .env_filter("info")
+        .build();
+
+    let listener = TcpListener::bind(&addr).expect("Failed to bind the address");
+    tracing::info!("The sync server is listening on {}", addr);
+    if let Err(e) = server.run(listener).await {
+        tracing::error!("The sync server stopped: {}", e);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{document::*, folder::*, server::SyncServer, user::*, ws::upgrade_ws_connection};
+use bytes::Bytes;
+use flowy_net::HEADER_TOKEN;
+use http_flowy::{errors::ServerError, response::FlowyResponse};
+use hyper::{header::CONTENT_TYPE, Body, Method, Request, Response, StatusCode};
+use protobuf::ProtobufError;
+use std::{
+    convert::{Infallible, TryFrom, TryInto},
+    sync::Arc,
+};
+
+const WS_PATH_PREFIX: &str = "/ws/";
+
+/// Dispatch the request to the handler of its route. The HTTP routes reply the `FlowyResponse`
+/// in JSON, which is what the `HttpRequestBuilder` of flowy-net expects.
+pub(crate) async fn handle_request(
+    server: Arc<SyncServer>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    let path = request.uri().path().to_owned();
+    if let Some(token) = path.strip_prefix(WS_PATH_PREFIX) {
+        let token = token.trim_end_matches('/').to_owned();
+        return Ok(upgrade_ws_connection(server, &token, request));
+    }
+
+    let method = request.method().clone();
+    let token = request
+        .headers()
+        .get(HEADER_TOKEN)
+        .and_then(|header| header.to_str().ok())
+        .unwrap_or_default()
+        .to_owned();
+    let result = match hyper::body::to_bytes(request.into_body()).await {
+        Ok(body) => route(&server, method, &path, &token, body).await,
+        Err(e) => Err(internal_error(e)),
+    };
+    let response = match result {
+        Ok(response) => response,
+        Err(error) => FlowyResponse {
+            data: Bytes::new(),
+            error: Some(error),
+        },
+    };
+    let body = serde_json::to_vec(&response).unwrap_or_default();
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap())
+}
+
+async fn route(
+    server: &SyncServer,
+    method: Method,
+    path: &str,
+    token: &str,
+    body: Bytes,
+) -> Result<FlowyResponse, ServerError> {
+    let user_store = &server.user_store;
+    match (method, path) {
+        (Method::POST, "/api/register") => sign_up_handler(user_store, body).await,
+        (Method::POST, "/api/auth") => sign_in_handler(user_store, body).await,
+        (Method::DELETE, "/api/auth") => sign_out_handler(user_store, token),
+        (Method::GET, "/api/user") => get_user_handler(user_store, token),
+        (Method::PATCH, "/api/user") => update_user_handler(user_store, token, body).await,
+        (method, path) => {
+            // The routes of the documents and the folder are only served to the signed-in users.
+            let user_id = match user_store.user_id(token) {
+                None => return Err(unauthorized("The token is invalid")),
+                Some(user_id) => user_id,
+            };
+            match (method, path) {
+                (Method::POST, "/api/doc") => create_document_handler(server, &user_id, body).await,
+                (Method::GET, "/api/doc") => read_document_handler(server, &user_id, body).await,
+                (Method::PATCH, "/api/doc") => reset_document_handler(server, &user_id, body).await,
+                (method, "/api/workspace") => workspace_handler(server, token, &user_id, method, body).await,
+                (method, "/api/app") => app_handler(server, token, &user_id, method, body).await,
+                (method, "/api/view") => view_handler(server, token, &user_id, method, body).await,
+                (method, "/api/trash") => trash_handler(method),
+                (method, path) => {
+                    Err(ServerError::record_not_found().context(format!("{} {} not found", method, path)))
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn parse_params<T>(body: Bytes) -> Result<T, ServerError>
+where
+    T: TryFrom<Bytes, Error = ProtobufError>,
+{
+    let params = T::try_from(body)?;
+    Ok(params)
+}
+
+pub(crate) fn success<T>(data: T) -> Result<FlowyResponse, ServerError>
+where
+    T: TryInto<Bytes, Error = ProtobufError>,
+{
+    let data: Bytes = data.try_into()?;
+    Ok(FlowyResponse { data, error: None })
+}
+
+pub(crate) fn empty() -> Result<FlowyResponse, ServerError> {
+    Ok(FlowyResponse {
+        data: Bytes::new(),
+        error: None,
+    })
+}
+
+pub(crate) fn unauthorized(msg: &str) -> ServerError {
+    ServerError::unauthorized().context(msg.to_owned())
+}
+
+pub(crate) fn internal_error<T: std::fmt::Debug>(e: T) -> ServerError {
+    ServerError::internal().context(e)
+}
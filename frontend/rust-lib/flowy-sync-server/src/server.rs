@@ -0,0 +1,56 @@
+use crate::{document::DocumentStore, folder::FolderStore, router::handle_request, user::UserStore};
+use flowy_error::FlowyResult;
+use flowy_net::local_server::{LocalServer, RevisionCloudStorage, SQLiteRevisionCloudStorage};
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Server,
+};
+use std::{convert::Infallible, net::TcpListener, sync::Arc};
+use tokio::sync::{broadcast, mpsc};
+
+/// Hosts the server managers of flowy-sync behind a WebSocket endpoint, so the clients on the
+/// same network can synchronize their revisions without a cloud server. It also serves the HTTP
+/// routes that the `http_server` services of flowy-net request.
+///
+/// The users, their folders, the owners of the documents and the revisions are stored in the
+/// `storage_dir`, so they survive the restart of the server.
+pub struct SyncServer {
+    pub(crate) local_server: LocalServer,
+    pub(crate) storage: Arc<dyn RevisionCloudStorage>,
+    pub(crate) user_store: UserStore,
+    pub(crate) folder_store: FolderStore,
+    pub(crate) document_store: DocumentStore,
+}
+
+impl SyncServer {
+    pub fn new(storage_dir: &str) -> FlowyResult<Self> {
+        let storage: Arc<dyn RevisionCloudStorage> = Arc::new(SQLiteRevisionCloudStorage::new(storage_dir)?);
+        let user_store = UserStore::new(storage_dir)?;
+        let folder_store = FolderStore::new(storage_dir)?;
+        let document_store = DocumentStore::new(storage_dir)?;
+
+        // The clients connect through the WebSocket endpoint, so the channels of the client that
+        // the local server is built with are never used.
+        let (client_ws_sender, _) = mpsc::unbounded_channel();
+        let (client_ws_receiver, _) = broadcast::channel(1);
+        let local_server = LocalServer::new(client_ws_sender, client_ws_receiver, storage.clone());
+
+        Ok(Self {
+            local_server,
+            storage,
+            user_store,
+            folder_store,
+            document_store,
+        })
+    }
+
+    /// Serve the HTTP routes and the WebSocket endpoint on the listener until the server stops.
+    pub async fn run(self, listener: TcpListener) -> Result<(), hyper::Error> {
+        let server = Arc::new(self);
+        let make_service = make_service_fn(move |_| {
+            let server = server.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |request| handle_request(server.clone(), request))) }
+        });
+        Server::from_tcp(listener)?.serve(make_service).await
+    }
+}
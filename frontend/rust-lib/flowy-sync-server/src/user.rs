@@ -0,0 +1,242 @@
+use crate::router::{empty, internal_error, parse_params, success, unauthorized};
+use bytes::Bytes;
+use flowy_error::FlowyResult;
+use flowy_user::entities::{
+    SignInParams, SignInResponse, SignUpParams, SignUpResponse, UpdateUserProfileParams, UserProfilePB,
+};
+use http_flowy::{
+    errors::{ErrorCode as ServerErrorCode, ServerError},
+    response::FlowyResponse,
+};
+use nanoid::nanoid;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::PathBuf};
+
+const USER_STORE_FILE: &str = "users.json";
+
+/// Keeps the users and their tokens in a JSON file of the storage directory. The passwords are
+/// stored as bcrypt hashes.
+pub(crate) struct UserStore {
+    path: PathBuf,
+    data: RwLock<UserStoreData>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct UserStoreData {
+    /// The users keyed by the user id.
+    users: HashMap<String, UserRecord>,
+
+    /// The user ids keyed by the tokens that are signed in.
+    tokens: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct UserRecord {
+    id: String,
+    name: String,
+    email: String,
+    password_hash: String,
+}
+
+impl UserStore {
+    pub(crate) fn new(storage_dir: &str) -> FlowyResult<Self> {
+        let path = PathBuf::from(storage_dir).join(USER_STORE_FILE);
+        let data = if path.exists() {
+            serde_json::from_slice(&std::fs::read(&path)?)?
+        } else {
+            UserStoreData::default()
+        };
+        Ok(Self {
+            path,
+            data: RwLock::new(data),
+        })
+    }
+
+    /// Return the id of the user that signed in with the token.
+    pub(crate) fn user_id(&self, token: &str) -> Option<String> {
+        self.data.read().tokens.get(token).cloned()
+    }
+
+    fn is_email_registered(data: &UserStoreData, email: &str) -> bool {
+        data.users.values().any(|user| user.email == email)
+    }
+
+    // The bcrypt calls are slow, so they run without holding the lock. The records are checked
+    // again after taking the write lock.
+    async fn sign_up(&self, params: SignUpParams) -> Result<SignUpResponse, ServerError> {
+        if Self::is_email_registered(&self.data.read(), &params.email) {
+            return Err(email_already_exists(&params.email));
+        }
+
+        let user = UserRecord {
+            id: nanoid!(20),
+            name: params.name,
+            email: params.email,
+            password_hash: hash_password(params.password).await?,
+        };
+        let mut data = self.data.write();
+        if Self::is_email_registered(&data, &user.email) {
+            return Err(email_already_exists(&user.email));
+        }
+        let token = nanoid!(32);
+        data.tokens.insert(token.clone(), user.id.clone());
+        data.users.insert(user.id.clone(), user.clone());
+        let _ = self.save(&data)?;
+        Ok(SignUpResponse {
+            user_id: user.id,
+            name: user.name,
+            email: user.email,
+            token,
+        })
+    }
+
+    async fn sign_in(&self, params: SignInParams) -> Result<SignInResponse, ServerError> {
+        let user = self
+            .data
+            .read()
+            .users
+            .values()
+            .find(|user| user.email == params.email)
+            .cloned()
+            .ok_or_else(|| unauthorized("The email or the password is incorrect"))?;
+        let is_match = verify_password(params.password, user.password_hash.clone()).await?;
+        if !is_match {
+            return Err(unauthorized("The email or the password is incorrect"));
+        }
+
+        let mut data = self.data.write();
+        if !data.users.contains_key(&user.id) {
+            return Err(unauthorized("The email or the password is incorrect"));
+        }
+        let token = nanoid!(32);
+        data.tokens.insert(token.clone(), user.id.clone());
+        let _ = self.save(&data)?;
+        Ok(SignInResponse {
+            user_id: user.id,
+            name: user.name,
+            email: user.email,
+            token,
+        })
+    }
+
+    fn sign_out(&self, token: &str) -> Result<(), ServerError> {
+        let mut data = self.data.write();
+        if data.tokens.remove(token).is_some() {
+            let _ = self.save(&data)?;
+        }
+        Ok(())
+    }
+
+    fn get_user(&self, token: &str) -> Result<UserProfilePB, ServerError> {
+        let data = self.data.read();
+        let user = data
+            .tokens
+            .get(token)
+            .and_then(|user_id| data.users.get(user_id))
+            .ok_or_else(|| unauthorized("The token is invalid"))?;
+        Ok(UserProfilePB {
+            id: user.id.clone(),
+            email: user.email.clone(),
+            name: user.name.clone(),
+            token: token.to_owned(),
+        })
+    }
+
+    async fn update_user(&self, token: &str, params: UpdateUserProfileParams) -> Result<(), ServerError> {
+        let _ = Self::check_user_token(&self.data.read(), token, &params.id)?;
+        let password_hash = match params.password {
+            None => None,
+            Some(password) => Some(hash_password(password).await?),
+        };
+
+        let mut data = self.data.write();
+        let _ = Self::check_user_token(&data, token, &params.id)?;
+        if let Some(email) = params.email.as_ref() {
+            if data
+                .users
+                .values()
+                .any(|user| user.id != params.id && &user.email == email)
+            {
+                return Err(email_already_exists(email));
+            }
+        }
+        if let Some(user) = data.users.get_mut(&params.id) {
+            if let Some(name) = params.name {
+                user.name = name;
+            }
+            if let Some(email) = params.email {
+                user.email = email;
+            }
+            if let Some(password_hash) = password_hash {
+                user.password_hash = password_hash;
+            }
+        }
+        self.save(&data)
+    }
+
+    fn check_user_token(data: &UserStoreData, token: &str, user_id: &str) -> Result<(), ServerError> {
+        match data.tokens.get(token) {
+            Some(token_user_id) if token_user_id == user_id => Ok(()),
+            _ => Err(unauthorized("The token is invalid")),
+        }
+    }
+
+    fn save(&self, data: &UserStoreData) -> Result<(), ServerError> {
+        let bytes = serde_json::to_vec(data).map_err(internal_error)?;
+        std::fs::write(&self.path, bytes).map_err(internal_error)?;
+        Ok(())
+    }
+}
+
+fn email_already_exists(email: &str) -> ServerError {
+    let mut error = ServerError::internal().context(format!("The email {} is already registered", email));
+    error.code = ServerErrorCode::EmailAlreadyExists;
+    error
+}
+
+async fn hash_password(password: String) -> Result<String, ServerError> {
+    tokio::task::spawn_blocking(move || bcrypt::hash(password, bcrypt::DEFAULT_COST))
+        .await
+        .map_err(internal_error)?
+        .map_err(internal_error)
+}
+
+async fn verify_password(password: String, password_hash: String) -> Result<bool, ServerError> {
+    tokio::task::spawn_blocking(move || bcrypt::verify(password, &password_hash))
+        .await
+        .map_err(internal_error)?
+        .map_err(internal_error)
+}
+
+pub(crate) async fn sign_up_handler(user_store: &UserStore, body: Bytes) -> Result<FlowyResponse, ServerError> {
+    let params: SignUpParams = parse_params(body)?;
+    let response = user_store.sign_up(params).await?;
+    success(response)
+}
+
+pub(crate) async fn sign_in_handler(user_store: &UserStore, body: Bytes) -> Result<FlowyResponse, ServerError> {
+    let params: SignInParams = parse_params(body)?;
+    let response = user_store.sign_in(params).await?;
+    success(response)
+}
+
+pub(crate) fn sign_out_handler(user_store: &UserStore, token: &str) -> Result<FlowyResponse, ServerError> {
+    let _ = user_store.sign_out(token)?;
+    empty()
+}
+
+pub(crate) fn get_user_handler(user_store: &UserStore, token: &str) -> Result<FlowyResponse, ServerError> {
+    let profile = user_store.get_user(token)?;
+    success(profile)
+}
+
+pub(crate) async fn update_user_handler(
+    user_store: &UserStore,
+    token: &str,
+    body: Bytes,
+) -> Result<FlowyResponse, ServerError> {
+    let params: UpdateUserProfileParams = parse_params(body)?;
+    let _ = user_store.update_user(token, params).await?;
+    empty()
+}
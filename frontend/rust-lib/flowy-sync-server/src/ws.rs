@@ -0,0 +1,98 @@
+use crate::server::SyncServer;
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt};
+use hyper::{
+    header::{CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, UPGRADE},
+    upgrade::Upgraded,
+    Body, Request, Response, StatusCode,
+};
+use lib_ws::WebSocketRawMessage;
+use std::{convert::TryFrom, sync::Arc};
+use tokio::sync::{broadcast, mpsc};
+use tokio_tungstenite::{
+    tungstenite::{handshake::derive_accept_key, protocol::Role, Message},
+    WebSocketStream,
+};
+
+// The number of the messages that are received from the client but not yet handled by the
+// server. The messages beyond it are dropped.
+const CLIENT_MESSAGE_CAPACITY: usize = 1024;
+
+/// Upgrade the request to a WebSocket connection of the user that signed in with the token.
+pub(crate) fn upgrade_ws_connection(
+    server: Arc<SyncServer>,
+    token: &str,
+    mut request: Request<Body>,
+) -> Response<Body> {
+    let user_id = match server.user_store.user_id(token) {
+        None => return status_response(StatusCode::UNAUTHORIZED),
+        Some(user_id) => user_id,
+    };
+    let accept_key = match request.headers().get(SEC_WEBSOCKET_KEY) {
+        None => return status_response(StatusCode::BAD_REQUEST),
+        Some(key) => derive_accept_key(key.as_bytes()),
+    };
+
+    tokio::spawn(async move {
+        match hyper::upgrade::on(&mut request).await {
+            Ok(upgraded) => {
+                let ws_stream = WebSocketStream::from_raw_socket(upgraded, Role::Server, None).await;
+                serve_ws_connection(server, user_id, ws_stream).await;
+            }
+            Err(e) => tracing::error!("Upgrade the WebSocket connection failed: {}", e),
+        }
+    });
+
+    Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header(CONNECTION, "Upgrade")
+        .header(UPGRADE, "websocket")
+        .header(SEC_WEBSOCKET_ACCEPT, accept_key)
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// Forward the messages of the connection to the local server, and its responses back to the
+/// connection, until the client disconnects.
+async fn serve_ws_connection(server: Arc<SyncServer>, user_id: String, ws_stream: WebSocketStream<Upgraded>) {
+    tracing::trace!("[SyncServer] {} connected", user_id);
+    let (mut ws_sink, mut ws_stream) = ws_stream.split();
+    let (client_ws_sender, mut server_ws_receiver) = mpsc::unbounded_channel::<WebSocketRawMessage>();
+    let (server_ws_sender, client_ws_receiver) = broadcast::channel(CLIENT_MESSAGE_CAPACITY);
+    server
+        .local_server
+        .connect_with_channel(&user_id, client_ws_sender, client_ws_receiver);
+
+    tokio::spawn(async move {
+        while let Some(message) = server_ws_receiver.recv().await {
+            if let Err(e) = ws_sink.send(message.into()).await {
+                tracing::error!("[SyncServer] send message failed: {}", e);
+                break;
+            }
+        }
+    });
+
+    while let Some(result) = ws_stream.next().await {
+        match result {
+            Ok(Message::Binary(bytes)) => match WebSocketRawMessage::try_from(Bytes::from(bytes)) {
+                Ok(message) => {
+                    let _ = server_ws_sender.send(message);
+                }
+                Err(e) => tracing::error!("[SyncServer] deserialize message failed: {:?}", e),
+            },
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(e) => {
+                tracing::error!("[SyncServer] receive message failed: {}", e);
+                break;
+            }
+        }
+    }
+    // Dropping the sender stops the runner of the local server, which then drops the
+    // `client_ws_sender` and ends the sending task.
+    tracing::trace!("[SyncServer] {} disconnected", user_id);
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    Response::builder().status(status).body(Body::empty()).unwrap()
+}
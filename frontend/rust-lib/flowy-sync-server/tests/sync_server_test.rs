@@ -0,0 +1,282 @@
+use bytes::Bytes;
+use flowy_folder::entities::{
+    app::{AppIdPB, AppPB, CreateAppPayloadPB},
+    workspace::{CreateWorkspacePayloadPB, RepeatedWorkspacePB, WorkspaceIdPB, WorkspacePB},
+};
+use flowy_net::{
+    get_client_server_configuration,
+    http_server::{document::*, user::*},
+    ClientServerConfiguration, HEADER_TOKEN,
+};
+use flowy_sync::{
+    client_document::default::initial_quill_delta_string,
+    entities::{
+        revision::{RepeatedRevision, Revision},
+        text_block::{CreateTextBlockParams, ResetTextBlockParams, TextBlockIdPB},
+    },
+};
+use flowy_sync_server::SyncServer;
+use flowy_user::entities::{SignInParams, SignUpParams, SignUpResponse, UpdateUserProfileParams};
+use http_flowy::{
+    errors::{ErrorCode as ServerErrorCode, ServerError},
+    response::FlowyResponse,
+};
+use nanoid::nanoid;
+use protobuf::ProtobufError;
+use reqwest::Method;
+use std::{
+    convert::{TryFrom, TryInto},
+    net::TcpListener,
+};
+
+fn run_sync_server(storage_dir: &str) -> ClientServerConfiguration {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = SyncServer::new(storage_dir).unwrap();
+    tokio::spawn(server.run(listener));
+
+    let mut config = get_client_server_configuration().unwrap();
+    config.reset_host_with_port("127.0.0.1", port);
+    config
+}
+
+fn storage_dir() -> String {
+    let path = std::env::temp_dir().join(nanoid!(6));
+    path.to_str().unwrap().to_owned()
+}
+
+async fn sign_up(config: &ClientServerConfiguration, email: &str) -> SignUpResponse {
+    let params = SignUpParams {
+        email: email.to_owned(),
+        name: "annie".to_owned(),
+        password: "HelloWorld!123".to_owned(),
+    };
+    user_sign_up_request(params, &config.sign_up_url()).await.unwrap()
+}
+
+// The folder requests of flowy-net's http_server aren't implemented yet, so the routes are
+// requested directly.
+async fn folder_request<P, T>(method: Method, token: &str, url: &str, payload: P) -> Result<T, ServerError>
+where
+    P: TryInto<Bytes, Error = ProtobufError>,
+    T: TryFrom<Bytes, Error = ProtobufError>,
+{
+    let body: Bytes = payload.try_into().unwrap();
+    let bytes = reqwest::Client::new()
+        .request(method, url)
+        .header(HEADER_TOKEN, token)
+        .body(body)
+        .send()
+        .await
+        .unwrap()
+        .bytes()
+        .await
+        .unwrap();
+    let response: FlowyResponse = serde_json::from_slice(&bytes).unwrap();
+    match response.error {
+        None => Ok(T::try_from(response.data).unwrap()),
+        Some(error) => Err(error),
+    }
+}
+
+#[tokio::test]
+async fn sync_server_sign_up_and_sign_in_test() {
+    let storage_dir = storage_dir();
+    let config = run_sync_server(&storage_dir);
+    let sign_up = SignUpParams {
+        email: "annie@appflowy.io".to_owned(),
+        name: "annie".to_owned(),
+        password: "HelloWorld!123".to_owned(),
+    };
+    let response = user_sign_up_request(sign_up, &config.sign_up_url()).await.unwrap();
+    let profile = get_user_profile_request(&response.token, &config.user_profile_url())
+        .await
+        .unwrap();
+    assert_eq!(profile.id, response.user_id);
+    assert_eq!(profile.email, "annie@appflowy.io");
+
+    // The users are kept after the server restarts.
+    let config = run_sync_server(&storage_dir);
+    let sign_in = SignInParams {
+        email: "annie@appflowy.io".to_owned(),
+        password: "HelloWorld!123".to_owned(),
+        name: "annie".to_owned(),
+    };
+    let sign_in_response = user_sign_in_request(sign_in, &config.sign_in_url()).await.unwrap();
+    assert_eq!(sign_in_response.user_id, response.user_id);
+
+    let wrong_password = SignInParams {
+        email: "annie@appflowy.io".to_owned(),
+        password: "wrong".to_owned(),
+        name: "annie".to_owned(),
+    };
+    assert!(user_sign_in_request(wrong_password, &config.sign_in_url())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn sync_server_sign_up_with_registered_email_test() {
+    let config = run_sync_server(&storage_dir());
+    let _ = sign_up(&config, "annie@appflowy.io").await;
+    let params = SignUpParams {
+        email: "annie@appflowy.io".to_owned(),
+        name: "annie".to_owned(),
+        password: "HelloWorld!123".to_owned(),
+    };
+    let error = user_sign_up_request(params, &config.sign_up_url()).await.unwrap_err();
+    assert!(matches!(error.code, ServerErrorCode::EmailAlreadyExists));
+}
+
+#[tokio::test]
+async fn sync_server_update_user_with_registered_email_test() {
+    let config = run_sync_server(&storage_dir());
+    let _ = sign_up(&config, "annie@appflowy.io").await;
+    let user = sign_up(&config, "nathan@appflowy.io").await;
+    let params = UpdateUserProfileParams {
+        id: user.user_id.clone(),
+        name: None,
+        email: Some("annie@appflowy.io".to_owned()),
+        password: None,
+    };
+    let error = update_user_profile_request(&user.token, params, &config.user_profile_url())
+        .await
+        .unwrap_err();
+    assert!(matches!(error.code, ServerErrorCode::EmailAlreadyExists));
+
+    let profile = get_user_profile_request(&user.token, &config.user_profile_url())
+        .await
+        .unwrap();
+    assert_eq!(profile.email, "nathan@appflowy.io");
+}
+
+#[tokio::test]
+async fn sync_server_create_and_read_folder_test() {
+    let storage_dir = storage_dir();
+    let config = run_sync_server(&storage_dir);
+    let user = sign_up(&config, "annie@appflowy.io").await;
+    let other_user = sign_up(&config, "nathan@appflowy.io").await;
+
+    let payload = CreateWorkspacePayloadPB {
+        name: "my workspace".to_owned(),
+        desc: "".to_owned(),
+    };
+    let workspace: WorkspacePB = folder_request(Method::POST, &user.token, &config.workspace_url(), payload)
+        .await
+        .unwrap();
+    let payload = CreateAppPayloadPB {
+        workspace_id: workspace.id.clone(),
+        name: "my app".to_owned(),
+        ..Default::default()
+    };
+    let app: AppPB = folder_request(Method::POST, &user.token, &config.app_url(), payload)
+        .await
+        .unwrap();
+
+    // The folders are kept after the server restarts.
+    let config = run_sync_server(&storage_dir);
+    let workspace_id = WorkspaceIdPB { value: None };
+    let workspaces: RepeatedWorkspacePB =
+        folder_request(Method::GET, &user.token, &config.workspace_url(), workspace_id.clone())
+            .await
+            .unwrap();
+    assert_eq!(workspaces.items.len(), 1);
+    assert_eq!(workspaces.items[0].name, "my workspace");
+    assert_eq!(workspaces.items[0].apps.items[0].id, app.id);
+
+    let app_id = AppIdPB { value: app.id.clone() };
+    let read_app: AppPB = folder_request(Method::GET, &user.token, &config.app_url(), app_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(read_app.name, "my app");
+
+    // The folders of one user aren't visible to the others.
+    let workspaces: RepeatedWorkspacePB =
+        folder_request(Method::GET, &other_user.token, &config.workspace_url(), workspace_id)
+            .await
+            .unwrap();
+    assert!(workspaces.items.is_empty());
+    let error = folder_request::<_, AppPB>(Method::GET, &other_user.token, &config.app_url(), app_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(error.code, ServerErrorCode::RecordNotFound));
+}
+
+#[tokio::test]
+async fn sync_server_create_and_read_document_test() {
+    let config = run_sync_server(&storage_dir());
+    let sign_up = SignUpParams {
+        email: "nathan@appflowy.io".to_owned(),
+        name: "nathan".to_owned(),
+        password: "HelloWorld!123".to_owned(),
+    };
+    let user = user_sign_up_request(sign_up, &config.sign_up_url()).await.unwrap();
+
+    let doc_id = nanoid!(10);
+    let delta_data = Bytes::from(initial_quill_delta_string());
+    let revision = Revision::initial_revision(&user.user_id, &doc_id, delta_data);
+    let params = CreateTextBlockParams {
+        id: doc_id.clone(),
+        revisions: RepeatedRevision::new(vec![revision]),
+    };
+    let _ = create_document_request(&user.token, params, &config.doc_url())
+        .await
+        .unwrap();
+
+    let doc_id_pb = TextBlockIdPB { value: doc_id.clone() };
+    let document = read_document_request(&user.token, doc_id_pb, &config.doc_url())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(document.block_id, doc_id);
+    assert_eq!(document.text, initial_quill_delta_string());
+
+    let unknown_doc_id = TextBlockIdPB { value: nanoid!(10) };
+    let document = read_document_request(&user.token, unknown_doc_id, &config.doc_url())
+        .await
+        .unwrap();
+    assert!(document.is_none());
+}
+
+#[tokio::test]
+async fn sync_server_document_owner_test() {
+    let storage_dir = storage_dir();
+    let config = run_sync_server(&storage_dir);
+    let user = sign_up(&config, "annie@appflowy.io").await;
+    let other_user = sign_up(&config, "nathan@appflowy.io").await;
+
+    let doc_id = nanoid!(10);
+    let delta_data = Bytes::from(initial_quill_delta_string());
+    let revision = Revision::initial_revision(&user.user_id, &doc_id, delta_data);
+    let params = CreateTextBlockParams {
+        id: doc_id.clone(),
+        revisions: RepeatedRevision::new(vec![revision.clone()]),
+    };
+    let _ = create_document_request(&user.token, params.clone(), &config.doc_url())
+        .await
+        .unwrap();
+
+    // The owners of the documents are kept after the server restarts.
+    let config = run_sync_server(&storage_dir);
+    let doc_id_pb = TextBlockIdPB { value: doc_id.clone() };
+    let document = read_document_request(&user.token, doc_id_pb.clone(), &config.doc_url())
+        .await
+        .unwrap();
+    assert!(document.is_some());
+
+    // The documents of one user can't be read, reset or created again by the others.
+    let document = read_document_request(&other_user.token, doc_id_pb, &config.doc_url())
+        .await
+        .unwrap();
+    assert!(document.is_none());
+    let reset_params = ResetTextBlockParams {
+        block_id: doc_id.clone(),
+        revisions: RepeatedRevision::new(vec![revision]),
+    };
+    assert!(reset_doc_request(&other_user.token, reset_params, &config.doc_url())
+        .await
+        .is_err());
+    assert!(create_document_request(&other_user.token, params, &config.doc_url())
+        .await
+        .is_err());
+}
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use dart_notify::{entities::SubscribeObject, sink::NotificationReceiver};
 use flowy_folder::entities::WorkspaceIdPB;
 use flowy_folder::{
     entities::{
@@ -14,7 +15,7 @@ use flowy_user::{
     event_map::UserEvent::{InitUser, SignIn, SignOut, SignUp},
 };
 use lib_dispatch::prelude::{EventDispatcher, ModuleRequest, ToBytes};
-use std::{fs, path::PathBuf, sync::Arc};
+use std::{fs, path::PathBuf, sync::Arc, time::Duration};
 
 pub struct ViewTest {
     pub sdk: FlowySDKTest,
@@ -209,3 +210,24 @@ fn sign_in(dispatch: Arc<EventDispatcher>) -> UserProfilePB {
 fn logout(dispatch: Arc<EventDispatcher>) {
     let _ = EventDispatcher::sync_send(dispatch, ModuleRequest::new(SignOut));
 }
+
+/// Wait for the next notification of the type that the receiver observes, the notifications of
+/// other types are skipped. Panics if it isn't sent within a few seconds.
+pub async fn receive_notification<T: Into<i32>>(receiver: &mut NotificationReceiver, ty: T) -> SubscribeObject {
+    let ty = ty.into();
+    let receive = async {
+        loop {
+            match receiver.recv().await {
+                None => panic!("The notification sink is closed"),
+                Some(subject) => {
+                    if subject.ty == ty {
+                        return subject;
+                    }
+                }
+            }
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(5), receive)
+        .await
+        .unwrap_or_else(|_| panic!("No notification of type {} was sent", ty))
+}
@@ -2,6 +2,7 @@ pub mod event_builder;
 pub mod helper;
 
 use crate::helper::*;
+use flowy_folder::services::TrashRetention;
 use flowy_net::{get_client_server_configuration, ClientServerConfiguration};
 use flowy_sdk::{FlowySDK, FlowySDKConfig};
 use flowy_user::entities::UserProfilePB;
@@ -42,6 +43,30 @@ impl FlowySDKTest {
         Self { inner: sdk }
     }
 
+    /// Create a client whose trash gets purged according to the retention.
+    pub fn with_trash_retention(trash_retention: TrashRetention) -> Self {
+        let server_config = get_client_server_configuration().unwrap();
+        let config = FlowySDKConfig::new(&root_dir(), server_config, &nanoid!(6))
+            .log_filter("info")
+            .trash_retention(trash_retention);
+        let sdk = std::thread::spawn(|| FlowySDK::new(config)).join().unwrap();
+        std::mem::forget(sdk.dispatcher());
+        Self { inner: sdk }
+    }
+
+    /// Create another client that connects to the local server of this one, so that both clients
+    /// synchronize their revisions through the same server.
+    pub fn connect(&self) -> Self {
+        let server_config = get_client_server_configuration().unwrap();
+        let mut config = FlowySDKConfig::new(&root_dir(), server_config, &nanoid!(6)).log_filter("info");
+        if let Some(local_server) = self.inner.local_server.clone() {
+            config = config.local_server(local_server);
+        }
+        let sdk = std::thread::spawn(|| FlowySDK::new(config)).join().unwrap();
+        std::mem::forget(sdk.dispatcher());
+        Self { inner: sdk }
+    }
+
     pub async fn sign_up(&self) -> SignUpContext {
         let context = async_sign_up(self.inner.dispatcher()).await;
         context
@@ -0,0 +1,177 @@
+use crate::entities::{DiffLinePB, DiffLineType};
+use crate::format::delta_to_markdown;
+use lib_ot::rich_text::{RichTextAttributes, RichTextDelta};
+
+/// Diff the two documents line by line. The documents are compared in their Markdown form, so the
+/// changes of the format, e.g. the bold or the header, are also shown in the lines.
+pub(crate) fn diff_documents(from: &RichTextDelta, to: &RichTextDelta) -> Vec<DiffLinePB> {
+    let from = delta_to_markdown(from);
+    let to = delta_to_markdown(to);
+    let from_lines = from.lines().collect::<Vec<&str>>();
+    let to_lines = to.lines().collect::<Vec<&str>>();
+    diff_lines(&from_lines, &to_lines)
+}
+
+fn diff_lines(from: &[&str], to: &[&str]) -> Vec<DiffLinePB> {
+    // Only the lines between the common prefix and the common suffix need the LCS table.
+    let prefix_len = from.iter().zip(to.iter()).take_while(|(a, b)| a == b).count();
+    let suffix_len = from[prefix_len..]
+        .iter()
+        .rev()
+        .zip(to[prefix_len..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let from_middle = &from[prefix_len..from.len() - suffix_len];
+    let to_middle = &to[prefix_len..to.len() - suffix_len];
+
+    // lcs[i][j] is the length of the longest common subsequence of from_middle[i..] and to_middle[j..]
+    let mut lcs = vec![vec![0_u32; to_middle.len() + 1]; from_middle.len() + 1];
+    for i in (0..from_middle.len()).rev() {
+        for j in (0..to_middle.len()).rev() {
+            lcs[i][j] = if from_middle[i] == to_middle[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = from[..prefix_len]
+        .iter()
+        .map(|text| make_line(DiffLineType::Equal, text))
+        .collect::<Vec<DiffLinePB>>();
+    let (mut i, mut j) = (0, 0);
+    while i < from_middle.len() && j < to_middle.len() {
+        if from_middle[i] == to_middle[j] {
+            lines.push(make_line(DiffLineType::Equal, from_middle[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(make_line(DiffLineType::Delete, from_middle[i]));
+            i += 1;
+        } else {
+            lines.push(make_line(DiffLineType::Insert, to_middle[j]));
+            j += 1;
+        }
+    }
+    lines.extend(
+        from_middle[i..]
+            .iter()
+            .map(|text| make_line(DiffLineType::Delete, text)),
+    );
+    lines.extend(to_middle[j..].iter().map(|text| make_line(DiffLineType::Insert, text)));
+    lines.extend(
+        from[from.len() - suffix_len..]
+            .iter()
+            .map(|text| make_line(DiffLineType::Equal, text)),
+    );
+    lines
+}
+
+/// Return the delta that changes the `from` document to the `to` document. Only the part between the
+/// common prefix and the common suffix is replaced, so the rest of the document is kept untouched.
+pub(crate) fn diff_deltas(from: &RichTextDelta, to: &RichTextDelta) -> RichTextDelta {
+    let from_chars = delta_chars(from);
+    let to_chars = delta_chars(to);
+    let prefix_len = from_chars
+        .iter()
+        .zip(to_chars.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix_len = from_chars[prefix_len..]
+        .iter()
+        .rev()
+        .zip(to_chars[prefix_len..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut delta = RichTextDelta::new();
+    delta.retain(utf16_len(&from_chars[..prefix_len]), RichTextAttributes::default());
+    delta.delete(utf16_len(&from_chars[prefix_len..from_chars.len() - suffix_len]));
+    for (c, attributes) in &to_chars[prefix_len..to_chars.len() - suffix_len] {
+        delta.insert(c.encode_utf8(&mut [0; 4]), attributes.clone());
+    }
+    delta
+}
+
+fn delta_chars(delta: &RichTextDelta) -> Vec<(char, RichTextAttributes)> {
+    delta
+        .ops
+        .iter()
+        .filter(|op| op.is_insert())
+        .flat_map(|op| {
+            let attributes = op.get_attributes();
+            op.get_data()
+                .chars()
+                .map(move |c| (c, attributes.clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn utf16_len(chars: &[(char, RichTextAttributes)]) -> usize {
+    chars.iter().map(|(c, _)| c.len_utf16()).sum()
+}
+
+fn make_line(line_type: DiffLineType, text: &str) -> DiffLinePB {
+    DiffLinePB {
+        line_type,
+        text: text.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib_ot::core::OperationTransformable;
+
+    fn line_types(lines: &[DiffLinePB]) -> Vec<(DiffLineType, &str)> {
+        lines
+            .iter()
+            .map(|line| (line.line_type.clone(), line.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn diff_lines_test() {
+        let lines = diff_lines(&["a", "b", "c", "d"], &["a", "c", "x", "d"]);
+        assert_eq!(
+            line_types(&lines),
+            vec![
+                (DiffLineType::Equal, "a"),
+                (DiffLineType::Delete, "b"),
+                (DiffLineType::Equal, "c"),
+                (DiffLineType::Insert, "x"),
+                (DiffLineType::Equal, "d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_documents_format_test() {
+        let from = RichTextDelta::from_json_str(r#"[{"insert":"Hello\nWorld\n"}]"#).unwrap();
+        let to = RichTextDelta::from_json_str(
+            r#"[{"insert":"Hello"},{"insert":"\n","attributes":{"header":1}},{"insert":"World\n"}]"#,
+        )
+        .unwrap();
+        let lines = diff_documents(&from, &to);
+        assert!(lines.contains(&make_line(DiffLineType::Delete, "Hello")));
+        assert!(lines.contains(&make_line(DiffLineType::Insert, "# Hello")));
+        assert!(lines.contains(&make_line(DiffLineType::Equal, "World")));
+    }
+
+    #[test]
+    fn diff_deltas_test() {
+        let from = RichTextDelta::from_json_str(
+            r#"[{"insert":"123"},{"insert":"4","attributes":{"bold":"true"}},{"insert":"\n"}]"#,
+        )
+        .unwrap();
+        let to = RichTextDelta::from_json_str(
+            r#"[{"insert":"1"},{"insert":"4","attributes":{"bold":"true"}},{"insert":"\n"}]"#,
+        )
+        .unwrap();
+        let delta = diff_deltas(&from, &to);
+        assert_eq!(delta.to_json_str(), r#"[{"retain":1},{"delete":2}]"#);
+        assert_eq!(from.compose(&delta).unwrap(), to);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::diff::diff_deltas;
 use crate::web_socket::EditorCommandSender;
 use crate::{
     errors::FlowyError,
@@ -6,7 +7,9 @@ use crate::{
 };
 use bytes::Bytes;
 use flowy_error::{internal_error, FlowyResult};
-use flowy_revision::{RevisionCloudService, RevisionManager, RevisionObjectBuilder, RevisionWebSocket};
+use flowy_revision::{
+    RevisionCloudService, RevisionHistory, RevisionManager, RevisionObjectBuilder, RevisionWebSocket,
+};
 use flowy_sync::entities::ws_data::ServerRevisionWSData;
 use flowy_sync::{
     entities::{revision::Revision, text_block::DocumentPB},
@@ -15,7 +18,7 @@ use flowy_sync::{
 };
 use lib_ot::{
     core::{Interval, Operation},
-    rich_text::{RichTextAttribute, RichTextDelta},
+    rich_text::{RichTextAttribute, RichTextAttributes, RichTextDelta},
 };
 use lib_ws::WSConnectState;
 use std::sync::Arc;
@@ -23,7 +26,6 @@ use tokio::sync::{mpsc, oneshot};
 
 pub struct TextBlockEditor {
     pub doc_id: String,
-    #[allow(dead_code)]
     rev_manager: Arc<RevisionManager>,
     #[cfg(feature = "sync")]
     ws_manager: Arc<flowy_revision::RevisionWebSocketManager>,
@@ -147,6 +149,41 @@ impl TextBlockEditor {
         Ok(json)
     }
 
+    /// Return the text of the document without any format.
+    pub async fn plain_text(&self) -> FlowyResult<String> {
+        let (ret, rx) = oneshot::channel::<CollaborateResult<String>>();
+        let msg = EditorCommand::ReadPlainText { ret };
+        let _ = self.edit_cmd_tx.send(msg).await;
+        let text = rx.await.map_err(internal_error)??;
+        Ok(text)
+    }
+
+    /// Return the histories of the document, ordered by the rev_id.
+    pub async fn read_revision_histories(&self) -> FlowyResult<Vec<RevisionHistory>> {
+        self.rev_manager.read_revision_histories().await
+    }
+
+    /// Return the document at the revision with `rev_id`. It's rebuilt from the snapshot, the
+    /// revisions and the histories, and returns the record_not_found error if it can't be rebuilt.
+    pub async fn delta_at_revision(&self, rev_id: i64) -> FlowyResult<RichTextDelta> {
+        let revisions = self.rev_manager.revisions_at(rev_id).await?;
+        let delta = make_delta_from_revisions::<RichTextAttributes>(revisions)?;
+        Ok(delta)
+    }
+
+    /// Restore the document to the revision with `rev_id`. The restoring is saved as a new local
+    /// revision that only changes the differences, so it will be synced like other changes.
+    #[tracing::instrument(level = "debug", skip(self), err)]
+    pub async fn restore_revision(&self, rev_id: i64) -> FlowyResult<()> {
+        let delta = self.delta_at_revision(rev_id).await?;
+        let current_delta = RichTextDelta::from_json_str(&self.delta_str().await?)?;
+        let restore_delta = diff_deltas(&current_delta, &delta);
+        if restore_delta.is_empty() {
+            return Ok(());
+        }
+        self.compose_local_delta(restore_delta.to_json_bytes()).await
+    }
+
     #[tracing::instrument(level = "trace", skip(self, data), err)]
     pub(crate) async fn compose_local_delta(&self, data: Bytes) -> Result<(), FlowyError> {
         let delta = RichTextDelta::from_bytes(&data)?;
@@ -1,12 +1,16 @@
 use crate::errors::ErrorCode;
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_revision::RevisionHistory;
 use std::convert::TryInto;
 
 #[derive(PartialEq, Debug, ProtoBuf_Enum, Clone)]
 pub enum ExportType {
+    /// The plain text of the document without any formatting.
     Text = 0,
     Markdown = 1,
+    /// The link that opens the document's view in the app, see [crate::format::view_link].
     Link = 2,
+    Html = 3,
 }
 
 impl std::default::Default for ExportType {
@@ -21,6 +25,7 @@ impl std::convert::From<i32> for ExportType {
             0 => ExportType::Text,
             1 => ExportType::Markdown,
             2 => ExportType::Link,
+            3 => ExportType::Html,
             _ => {
                 log::error!("Invalid export type: {}", val);
                 ExportType::Text
@@ -62,3 +67,118 @@ pub struct ExportDataPB {
     #[pb(index = 2)]
     pub export_type: ExportType,
 }
+
+#[derive(PartialEq, Debug, ProtoBuf_Enum, Clone)]
+pub enum ImportDataType {
+    Markdown = 0,
+    Html = 1,
+}
+
+impl std::default::Default for ImportDataType {
+    fn default() -> Self {
+        ImportDataType::Markdown
+    }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct ImportDataPayloadPB {
+    #[pb(index = 1)]
+    pub data: String,
+
+    #[pb(index = 2)]
+    pub data_type: ImportDataType,
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct ImportDataPB {
+    #[pb(index = 1)]
+    pub delta_str: String,
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct RevisionHistoryPB {
+    #[pb(index = 1)]
+    pub start_rev_id: i64,
+
+    #[pb(index = 2)]
+    pub end_rev_id: i64,
+
+    #[pb(index = 3)]
+    pub user_id: String,
+
+    #[pb(index = 4)]
+    pub timestamp: i64,
+}
+
+impl std::convert::From<RevisionHistory> for RevisionHistoryPB {
+    fn from(history: RevisionHistory) -> Self {
+        Self {
+            start_rev_id: history.start_rev_id,
+            end_rev_id: history.end_rev_id,
+            user_id: history.user_id,
+            timestamp: history.timestamp,
+        }
+    }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct RepeatedRevisionHistoryPB {
+    #[pb(index = 1)]
+    pub items: Vec<RevisionHistoryPB>,
+}
+
+impl std::convert::From<Vec<RevisionHistory>> for RepeatedRevisionHistoryPB {
+    fn from(histories: Vec<RevisionHistory>) -> Self {
+        let items = histories.into_iter().map(|history| history.into()).collect();
+        Self { items }
+    }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct DocumentRevisionPayloadPB {
+    #[pb(index = 1)]
+    pub view_id: String,
+
+    #[pb(index = 2)]
+    pub rev_id: i64,
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct DiffRevisionsPayloadPB {
+    #[pb(index = 1)]
+    pub view_id: String,
+
+    #[pb(index = 2)]
+    pub from_rev_id: i64,
+
+    #[pb(index = 3)]
+    pub to_rev_id: i64,
+}
+
+#[derive(PartialEq, Debug, ProtoBuf_Enum, Clone)]
+pub enum DiffLineType {
+    Equal = 0,
+    Insert = 1,
+    Delete = 2,
+}
+
+impl std::default::Default for DiffLineType {
+    fn default() -> Self {
+        DiffLineType::Equal
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, ProtoBuf)]
+pub struct DiffLinePB {
+    #[pb(index = 1)]
+    pub line_type: DiffLineType,
+
+    #[pb(index = 2)]
+    pub text: String,
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct DocumentDiffPB {
+    #[pb(index = 1)]
+    pub lines: Vec<DiffLinePB>,
+}
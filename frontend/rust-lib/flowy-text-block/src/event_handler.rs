@@ -1,8 +1,14 @@
-use crate::entities::{ExportDataPB, ExportParams, ExportPayloadPB};
+use crate::diff::diff_documents;
+use crate::entities::{
+    DiffRevisionsPayloadPB, DocumentDiffPB, DocumentRevisionPayloadPB, ExportDataPB, ExportParams, ExportPayloadPB,
+    ExportType, ImportDataPB, ImportDataPayloadPB, ImportDataType, RepeatedRevisionHistoryPB,
+};
+use crate::format::{delta_to_html, delta_to_markdown, html_to_delta, markdown_to_delta, view_link};
 use crate::TextBlockManager;
 use flowy_error::FlowyError;
 use flowy_sync::entities::text_block::{TextBlockDeltaPB, TextBlockIdPB};
 use lib_dispatch::prelude::{data_result, AppData, Data, DataResult};
+use lib_ot::rich_text::RichTextDelta;
 use std::convert::TryInto;
 use std::sync::Arc;
 
@@ -34,9 +40,77 @@ pub(crate) async fn export_handler(
 ) -> DataResult<ExportDataPB, FlowyError> {
     let params: ExportParams = data.into_inner().try_into()?;
     let editor = manager.open_block(&params.view_id).await?;
-    let delta_json = editor.delta_str().await?;
+    let data = match params.export_type {
+        ExportType::Text => editor.plain_text().await?,
+        ExportType::Markdown => delta_to_markdown(&RichTextDelta::from_json_str(&editor.delta_str().await?)?),
+        ExportType::Html => delta_to_html(&RichTextDelta::from_json_str(&editor.delta_str().await?)?),
+        ExportType::Link => view_link(&params.view_id),
+    };
     data_result(ExportDataPB {
-        data: delta_json,
+        data,
         export_type: params.export_type,
     })
 }
+
+/// Convert the pasted or the imported content into the delta, e.g. the HTML in the clipboard.
+pub(crate) async fn import_data_handler(data: Data<ImportDataPayloadPB>) -> DataResult<ImportDataPB, FlowyError> {
+    let payload = data.into_inner();
+    let delta = match payload.data_type {
+        ImportDataType::Markdown => markdown_to_delta(&payload.data),
+        ImportDataType::Html => html_to_delta(&payload.data),
+    };
+    data_result(ImportDataPB {
+        delta_str: delta.to_json_str(),
+    })
+}
+
+pub(crate) async fn read_revision_histories_handler(
+    data: Data<TextBlockIdPB>,
+    manager: AppData<Arc<TextBlockManager>>,
+) -> DataResult<RepeatedRevisionHistoryPB, FlowyError> {
+    let block_id: TextBlockIdPB = data.into_inner();
+    let editor = manager.open_block(&block_id).await?;
+    let histories = editor.read_revision_histories().await?;
+    data_result(histories.into())
+}
+
+pub(crate) async fn read_document_at_revision_handler(
+    data: Data<DocumentRevisionPayloadPB>,
+    manager: AppData<Arc<TextBlockManager>>,
+) -> DataResult<TextBlockDeltaPB, FlowyError> {
+    let payload = data.into_inner();
+    let editor = manager.open_block(&payload.view_id).await?;
+    let delta = editor.delta_at_revision(payload.rev_id).await?;
+    data_result(TextBlockDeltaPB {
+        block_id: payload.view_id,
+        delta_str: delta.to_json_str(),
+    })
+}
+
+pub(crate) async fn diff_revisions_handler(
+    data: Data<DiffRevisionsPayloadPB>,
+    manager: AppData<Arc<TextBlockManager>>,
+) -> DataResult<DocumentDiffPB, FlowyError> {
+    let payload = data.into_inner();
+    let editor = manager.open_block(&payload.view_id).await?;
+    let from = editor.delta_at_revision(payload.from_rev_id).await?;
+    let to = editor.delta_at_revision(payload.to_rev_id).await?;
+    data_result(DocumentDiffPB {
+        lines: diff_documents(&from, &to),
+    })
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn restore_revision_handler(
+    data: Data<DocumentRevisionPayloadPB>,
+    manager: AppData<Arc<TextBlockManager>>,
+) -> DataResult<TextBlockDeltaPB, FlowyError> {
+    let payload = data.into_inner();
+    let editor = manager.open_block(&payload.view_id).await?;
+    let _ = editor.restore_revision(payload.rev_id).await?;
+    let delta_str = editor.delta_str().await?;
+    data_result(TextBlockDeltaPB {
+        block_id: payload.view_id,
+        delta_str,
+    })
+}
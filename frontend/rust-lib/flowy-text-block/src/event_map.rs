@@ -11,7 +11,15 @@ pub fn create(block_manager: Arc<TextBlockManager>) -> Module {
     module = module
         .event(TextBlockEvent::GetBlockData, get_block_data_handler)
         .event(TextBlockEvent::ApplyDelta, apply_delta_handler)
-        .event(TextBlockEvent::ExportDocument, export_handler);
+        .event(TextBlockEvent::ExportDocument, export_handler)
+        .event(TextBlockEvent::ImportData, import_data_handler)
+        .event(TextBlockEvent::ReadRevisionHistories, read_revision_histories_handler)
+        .event(
+            TextBlockEvent::ReadDocumentAtRevision,
+            read_document_at_revision_handler,
+        )
+        .event(TextBlockEvent::DiffRevisions, diff_revisions_handler)
+        .event(TextBlockEvent::RestoreRevision, restore_revision_handler);
 
     module
 }
@@ -27,4 +35,19 @@ pub enum TextBlockEvent {
 
     #[event(input = "ExportPayloadPB", output = "ExportDataPB")]
     ExportDocument = 2,
+
+    #[event(input = "ImportDataPayloadPB", output = "ImportDataPB")]
+    ImportData = 3,
+
+    #[event(input = "TextBlockIdPB", output = "RepeatedRevisionHistoryPB")]
+    ReadRevisionHistories = 4,
+
+    #[event(input = "DocumentRevisionPayloadPB", output = "TextBlockDeltaPB")]
+    ReadDocumentAtRevision = 5,
+
+    #[event(input = "DiffRevisionsPayloadPB", output = "DocumentDiffPB")]
+    DiffRevisions = 6,
+
+    #[event(input = "DocumentRevisionPayloadPB", output = "TextBlockDeltaPB")]
+    RestoreRevision = 7,
 }
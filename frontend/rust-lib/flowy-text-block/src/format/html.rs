@@ -0,0 +1,915 @@
+use crate::format::line::{
+    attribute_value, is_attribute_true, split_lines, DeltaLine, BULLET_LIST, CHECKED_LIST, ORDERED_LIST, UNCHECKED_LIST,
+};
+use lib_ot::rich_text::{RichTextAttribute, RichTextAttributeKey, RichTextAttributes, RichTextDelta};
+use std::collections::HashMap;
+
+/// The indent of the paragraph is written as the `padding-left` of `INDENT_WIDTH_IN_EM * indent`.
+const INDENT_WIDTH_IN_EM: usize = 3;
+
+/// The deepest indent that is imported from HTML, the deeper ones are clamped to it.
+const MAX_INDENT: usize = 8;
+
+/// The elements whose content is never shown as text.
+const RAW_TEXT_ELEMENTS: [&str; 5] = ["script", "style", "title", "template", "textarea"];
+
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track", "wbr",
+];
+
+const BLOCK_ELEMENTS: [&str; 16] = [
+    "p",
+    "div",
+    "section",
+    "article",
+    "header",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "blockquote",
+    "pre",
+    "li",
+    "tr",
+];
+
+/// Convert the delta of the text block into HTML. The nested lists are written as the nested
+/// `ul` or `ol` elements.
+pub fn delta_to_html(delta: &RichTextDelta) -> String {
+    let lines = split_lines(delta);
+    let mut html = String::new();
+    let mut lists: Vec<(usize, String)> = vec![];
+    let mut index = 0;
+    while index < lines.len() {
+        let line = &lines[index];
+        let list = match line.list() {
+            Some(list) if !line.is_code_block() => list,
+            _ => {
+                close_lists(&mut html, &mut lists, None);
+                ""
+            }
+        };
+
+        if line.is_code_block() {
+            let end = lines[index..]
+                .iter()
+                .position(|other| !other.is_code_block() || other.is_quote() != line.is_quote())
+                .map(|position| index + position)
+                .unwrap_or(lines.len());
+            let code = lines[index..end]
+                .iter()
+                .map(|line| escape_text(&line.plain_text()))
+                .collect::<Vec<String>>()
+                .join("\n");
+            write_quoted(&mut html, line.is_quote(), &format!("<pre>{}</pre>", code));
+            index = end;
+            continue;
+        }
+
+        index += 1;
+        if !list.is_empty() {
+            write_list_item(&mut html, &mut lists, line, list);
+            continue;
+        }
+
+        let mut content = String::new();
+        write_inline(&mut content, &line.segments);
+        let style = block_style(line, true);
+        if line.header() > 0 {
+            let tag = format!("h{}", line.header().min(6));
+            write_quoted(
+                &mut html,
+                line.is_quote(),
+                &format!("<{}{}>{}</{}>", tag, style, content, tag),
+            );
+        } else if line.is_quote() {
+            html.push_str(&format!("<blockquote{}>{}</blockquote>", style, content));
+        } else {
+            html.push_str(&format!("<p{}>{}</p>", style, content));
+        }
+    }
+    close_lists(&mut html, &mut lists, None);
+    html
+}
+
+fn write_quoted(html: &mut String, is_quote: bool, content: &str) {
+    if is_quote {
+        html.push_str(&format!("<blockquote>{}</blockquote>", content));
+    } else {
+        html.push_str(content);
+    }
+}
+
+/// The `li` is left open, so the list of the next item that is indented deeper is nested in it.
+fn write_list_item(html: &mut String, lists: &mut Vec<(usize, String)>, line: &DeltaLine, list: &str) {
+    let indent = line.indent();
+    close_lists(html, lists, Some((indent, list)));
+    match lists.last() {
+        Some((level, _)) if *level == indent => html.push_str("</li>"),
+        _ => {
+            let (tag, attributes) = list_element(list);
+            html.push_str(&format!("<{}{}>", tag, attributes));
+            lists.push((indent, list.to_owned()));
+        }
+    }
+
+    html.push_str(&format!("<li{}>", block_style(line, false)));
+    write_inline(html, &line.segments);
+}
+
+/// Close the lists that are indented deeper than the item, and the list of the other type at the
+/// same level. Close all the lists if the item is None.
+fn close_lists(html: &mut String, lists: &mut Vec<(usize, String)>, item: Option<(usize, &str)>) {
+    while let Some((level, list)) = lists.last() {
+        let should_close = match item {
+            None => true,
+            Some((indent, kind)) => *level > indent || (*level == indent && list.as_str() != kind),
+        };
+        if !should_close {
+            break;
+        }
+        html.push_str(&format!("</li></{}>", list_element(list).0));
+        lists.pop();
+    }
+}
+
+fn list_element(list: &str) -> (&'static str, &'static str) {
+    match list {
+        ORDERED_LIST => ("ol", ""),
+        CHECKED_LIST => ("ul", " data-checked=\"true\""),
+        UNCHECKED_LIST => ("ul", " data-checked=\"false\""),
+        _ => ("ul", ""),
+    }
+}
+
+fn block_style(line: &DeltaLine, with_indent: bool) -> String {
+    let mut styles = vec![];
+    if let Some(align) = line.align() {
+        styles.push(format!("text-align: {}", escape_attribute(align)));
+    }
+    if with_indent && line.indent() > 0 {
+        styles.push(format!(
+            "padding-left: {}em",
+            line.indent().saturating_mul(INDENT_WIDTH_IN_EM)
+        ));
+    }
+    style_attribute(styles)
+}
+
+fn style_attribute(styles: Vec<String>) -> String {
+    if styles.is_empty() {
+        "".to_owned()
+    } else {
+        format!(" style=\"{}\"", styles.join("; "))
+    }
+}
+
+fn write_inline(html: &mut String, segments: &[(String, RichTextAttributes)]) {
+    // The empty line keeps its height, the same as the editor shows it.
+    if segments.is_empty() {
+        html.push_str("<br>");
+        return;
+    }
+
+    for (text, attributes) in segments {
+        let mut s = style_span(escape_text(text), attributes);
+
+        let elements = [
+            (RichTextAttributeKey::InlineCode, "code"),
+            (RichTextAttributeKey::Underline, "u"),
+            (RichTextAttributeKey::StrikeThrough, "s"),
+            (RichTextAttributeKey::Italic, "em"),
+            (RichTextAttributeKey::Bold, "strong"),
+        ];
+        for (key, tag) in elements.iter() {
+            if is_attribute_true(attributes, key.clone()) {
+                s = format!("<{}>{}</{}>", tag, s, tag);
+            }
+        }
+        if let Some(link) = attribute_value(attributes, RichTextAttributeKey::Link) {
+            s = format!("<a href=\"{}\">{}</a>", escape_attribute(link), s);
+        }
+        html.push_str(&s);
+    }
+}
+
+/// Wrap the text with the `span` that has the color, the background, the size and the font of
+/// the text. The text is returned as it is if it has none of them.
+pub(crate) fn style_span(s: String, attributes: &RichTextAttributes) -> String {
+    let mut styles = vec![];
+    if let Some(color) = attribute_value(attributes, RichTextAttributeKey::Color) {
+        styles.push(format!("color: {}", escape_attribute(color)));
+    }
+    if let Some(background) = attribute_value(attributes, RichTextAttributeKey::Background) {
+        styles.push(format!("background-color: {}", escape_attribute(background)));
+    }
+    if let Some(size) = attribute_value(attributes, RichTextAttributeKey::Size) {
+        styles.push(format!("font-size: {}px", escape_attribute(size)));
+    }
+    let font = attribute_value(attributes, RichTextAttributeKey::Font)
+        .map(|font| format!(" data-font=\"{}\"", escape_attribute(font)))
+        .unwrap_or_default();
+    if styles.is_empty() && font.is_empty() {
+        s
+    } else {
+        format!("<span{}{}>{}</span>", style_attribute(styles), font, s)
+    }
+}
+
+/// The inline tag of the raw HTML that is embedded in other formats, e.g. the `<u>` or the
+/// `<span style="color: red">` that the Markdown export writes.
+pub(crate) enum InlineHtmlTag {
+    Start {
+        name: String,
+        attributes: RichTextAttributes,
+    },
+    End(String),
+}
+
+/// Parse the HTML that contains nothing but one tag. The styles are sanitized the same as the
+/// `html_to_delta` does.
+pub(crate) fn parse_inline_html_tag(html: &str) -> Option<InlineHtmlTag> {
+    let mut tokens = tokenize(html.trim());
+    if tokens.len() != 1 {
+        return None;
+    }
+    match tokens.pop()? {
+        HtmlToken::Start { name, attributes } if !VOID_ELEMENTS.contains(&name.as_str()) => {
+            let attributes = inline_attributes(&name, &attributes, false);
+            Some(InlineHtmlTag::Start { name, attributes })
+        }
+        HtmlToken::End(name) => Some(InlineHtmlTag::End(name)),
+        _ => None,
+    }
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn escape_attribute(value: &str) -> String {
+    escape_text(value).replace('"', "&quot;")
+}
+
+/// Parse the HTML into the delta of the text block. Only the elements and the styles that the
+/// text block supports are kept, e.g. the scripts, the event handlers and the `javascript:` links
+/// are dropped, and the text of the unknown elements is kept as plain text.
+pub fn html_to_delta(html: &str) -> RichTextDelta {
+    let mut writer = HtmlDeltaWriter::new();
+    for token in tokenize(html) {
+        match token {
+            HtmlToken::Start { name, attributes } => writer.start_element(name, attributes),
+            HtmlToken::End(name) => writer.end_element(&name),
+            HtmlToken::Text(text) => writer.write_text(&text),
+        }
+    }
+    writer.finish()
+}
+
+struct BlockElement {
+    name: String,
+    attributes: RichTextAttributes,
+    /// The number of the lines that were written before the element began.
+    line_count: usize,
+}
+
+struct HtmlDeltaWriter {
+    delta: RichTextDelta,
+    line_count: usize,
+    is_line_empty: bool,
+    /// The whitespace that is written only if there is any text after it in the same line.
+    pending_space: Option<RichTextAttributes>,
+    inline_elements: Vec<(String, RichTextAttributes)>,
+    block_elements: Vec<BlockElement>,
+    lists: Vec<&'static str>,
+}
+
+impl HtmlDeltaWriter {
+    fn new() -> Self {
+        Self {
+            delta: RichTextDelta::new(),
+            line_count: 0,
+            is_line_empty: true,
+            pending_space: None,
+            inline_elements: vec![],
+            block_elements: vec![],
+            lists: vec![],
+        }
+    }
+
+    fn start_element(&mut self, name: String, attributes: HashMap<String, String>) {
+        if name == "br" {
+            self.end_line();
+            return;
+        }
+        if VOID_ELEMENTS.contains(&name.as_str()) {
+            return;
+        }
+
+        let is_code_block = self.is_code_block();
+        self.inline_elements
+            .push((name.clone(), inline_attributes(&name, &attributes, is_code_block)));
+        match name.as_str() {
+            "ul" | "ol" => {
+                self.close_line();
+                let list = if name == "ol" {
+                    ORDERED_LIST
+                } else {
+                    checked_list(&attributes).unwrap_or(BULLET_LIST)
+                };
+                self.lists.push(list);
+            }
+            _ if BLOCK_ELEMENTS.contains(&name.as_str()) => {
+                self.close_line();
+                let mut block_attributes = block_attributes(&name, &attributes);
+                if name == "li" {
+                    if let Some(list) = self.lists.last() {
+                        let list = checked_list(&attributes).unwrap_or(*list);
+                        block_attributes.add(RichTextAttribute::List(list));
+                        // The indent of the item is the depth of its list.
+                        block_attributes.remove(RichTextAttributeKey::Indent);
+                        if self.lists.len() > 1 {
+                            let indent = (self.lists.len() - 1).min(MAX_INDENT);
+                            block_attributes.add(RichTextAttribute::Indent(indent));
+                        }
+                    }
+                }
+                self.block_elements.push(BlockElement {
+                    name,
+                    attributes: block_attributes,
+                    line_count: self.line_count,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn end_element(&mut self, name: &str) {
+        if let Some(index) = self.inline_elements.iter().rposition(|(element, _)| element == name) {
+            self.inline_elements.truncate(index);
+        }
+
+        match name {
+            "ul" | "ol" => {
+                self.close_line();
+                self.lists.pop();
+            }
+            _ if BLOCK_ELEMENTS.contains(&name) => {
+                let index = match self.block_elements.iter().rposition(|element| element.name == name) {
+                    None => return,
+                    Some(index) => index,
+                };
+                // The empty item is still a line of the list.
+                let is_empty_item = name == "li" && self.block_elements[index].line_count == self.line_count;
+                if is_empty_item {
+                    self.end_line();
+                } else {
+                    self.close_line();
+                }
+                self.block_elements.truncate(index);
+            }
+            _ => {}
+        }
+    }
+
+    fn write_text(&mut self, text: &str) {
+        if self.is_code_block() {
+            let mut lines = text.split('\n').peekable();
+            while let Some(line) = lines.next() {
+                if !line.is_empty() {
+                    self.insert(line, self.inline_attributes());
+                }
+                if lines.peek().is_some() {
+                    self.end_line();
+                }
+            }
+            return;
+        }
+
+        // The whitespaces are collapsed into one space, and are dropped at the start or the end
+        // of the line.
+        let attributes = self.inline_attributes();
+        let content = text.split_ascii_whitespace().collect::<Vec<&str>>().join(" ");
+        let starts_with_space = text.starts_with(|c: char| c.is_ascii_whitespace());
+        let ends_with_space = text.ends_with(|c: char| c.is_ascii_whitespace());
+        if starts_with_space && !self.is_line_empty && self.pending_space.is_none() {
+            self.pending_space = Some(attributes.clone());
+        }
+        if content.is_empty() {
+            return;
+        }
+
+        if let Some(space_attributes) = self.pending_space.take() {
+            self.insert(" ", space_attributes);
+        }
+        self.insert(&content, attributes.clone());
+        if ends_with_space {
+            self.pending_space = Some(attributes);
+        }
+    }
+
+    fn is_code_block(&self) -> bool {
+        self.block_elements.iter().any(|element| element.name == "pre")
+    }
+
+    fn insert(&mut self, s: &str, attributes: RichTextAttributes) {
+        self.delta.insert(s, attributes);
+        self.is_line_empty = false;
+    }
+
+    fn end_line(&mut self) {
+        let mut attributes = RichTextAttributes::new();
+        for element in self.block_elements.iter() {
+            for (key, value) in element.attributes.iter() {
+                attributes.insert(key.clone(), value.clone());
+            }
+        }
+        self.delta.insert("\n", attributes);
+        self.line_count += 1;
+        self.is_line_empty = true;
+        self.pending_space = None;
+    }
+
+    fn close_line(&mut self) {
+        if !self.is_line_empty {
+            self.end_line();
+        }
+    }
+
+    fn inline_attributes(&self) -> RichTextAttributes {
+        let mut attributes = RichTextAttributes::new();
+        for (_, element_attributes) in self.inline_elements.iter() {
+            for (key, value) in element_attributes.iter() {
+                attributes.insert(key.clone(), value.clone());
+            }
+        }
+        attributes
+    }
+
+    fn finish(mut self) -> RichTextDelta {
+        self.close_line();
+        // The text block always ends with a "\n".
+        if self.delta.is_empty() {
+            self.delta.insert("\n", RichTextAttributes::new());
+        }
+        self.delta
+    }
+}
+
+fn checked_list(attributes: &HashMap<String, String>) -> Option<&'static str> {
+    match attributes.get("data-checked").map(|value| value.as_str()) {
+        Some("true") => Some(CHECKED_LIST),
+        Some("false") => Some(UNCHECKED_LIST),
+        _ => None,
+    }
+}
+
+fn block_attributes(name: &str, attributes: &HashMap<String, String>) -> RichTextAttributes {
+    let mut block_attributes = RichTextAttributes::new();
+    match name {
+        "blockquote" => block_attributes.add(RichTextAttribute::BlockQuote(true)),
+        "pre" => block_attributes.add(RichTextAttribute::CodeBlock(true)),
+        _ => {
+            if let Some(level) = name.strip_prefix('h').and_then(|level| level.parse::<usize>().ok()) {
+                block_attributes.add(RichTextAttribute::Header(level));
+            }
+        }
+    }
+
+    for (property, value) in styles(attributes) {
+        match property.as_str() {
+            "text-align" if value != "left" && value != "start" && is_safe_css_value(&value) => {
+                block_attributes.add(RichTextAttribute::Align(value))
+            }
+            "padding-left" | "margin-left" => {
+                if let Some(em) = value.strip_suffix("em").and_then(|em| em.trim().parse::<f64>().ok()) {
+                    let indent = (em / INDENT_WIDTH_IN_EM as f64).round() as usize;
+                    if indent > 0 {
+                        block_attributes.add(RichTextAttribute::Indent(indent.min(MAX_INDENT)));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // The classes that the Quill editor uses, e.g. `ql-indent-1` or `ql-align-center`.
+    for class in attributes
+        .get("class")
+        .map(|class| class.split_whitespace())
+        .into_iter()
+        .flatten()
+    {
+        let indent = class
+            .strip_prefix("ql-indent-")
+            .and_then(|indent| indent.parse::<usize>().ok())
+            .filter(|indent| *indent > 0);
+        if let Some(indent) = indent {
+            block_attributes.add(RichTextAttribute::Indent(indent.min(MAX_INDENT)));
+        }
+        if let Some(align) = class.strip_prefix("ql-align-").filter(|align| !align.is_empty()) {
+            block_attributes.add(RichTextAttribute::Align(align.to_owned()));
+        }
+    }
+    block_attributes
+}
+
+fn inline_attributes(name: &str, attributes: &HashMap<String, String>, is_code_block: bool) -> RichTextAttributes {
+    let mut inline_attributes = RichTextAttributes::new();
+    match name {
+        "b" | "strong" => inline_attributes.add(RichTextAttribute::Bold(true)),
+        "i" | "em" => inline_attributes.add(RichTextAttribute::Italic(true)),
+        "u" | "ins" => inline_attributes.add(RichTextAttribute::Underline(true)),
+        "s" | "strike" | "del" => inline_attributes.add(RichTextAttribute::StrikeThrough(true)),
+        "code" if !is_code_block => inline_attributes.add(RichTextAttribute::InlineCode(true)),
+        "a" => {
+            if let Some(href) = attributes.get("href").filter(|href| is_safe_link(href)) {
+                inline_attributes.add(RichTextAttribute::Link(href.trim()));
+            }
+        }
+        _ => {}
+    }
+
+    for (property, value) in styles(attributes) {
+        match property.as_str() {
+            "font-weight" if value == "bold" || value == "bolder" || value.parse::<usize>().unwrap_or(0) >= 600 => {
+                inline_attributes.add(RichTextAttribute::Bold(true))
+            }
+            "font-style" if value == "italic" => inline_attributes.add(RichTextAttribute::Italic(true)),
+            "text-decoration" | "text-decoration-line" => {
+                if value.contains("underline") {
+                    inline_attributes.add(RichTextAttribute::Underline(true));
+                }
+                if value.contains("line-through") {
+                    inline_attributes.add(RichTextAttribute::StrikeThrough(true));
+                }
+            }
+            "color" if is_safe_css_value(&value) => inline_attributes.add(RichTextAttribute::Color(value)),
+            "background-color" | "background" if is_safe_css_value(&value) => {
+                inline_attributes.add(RichTextAttribute::Background(value))
+            }
+            "font-size" => {
+                let size = value
+                    .strip_suffix("px")
+                    .and_then(|size| size.trim().parse::<f64>().ok())
+                    .map(|size| size.round() as usize)
+                    .filter(|size| *size > 0);
+                if let Some(size) = size {
+                    inline_attributes.add(RichTextAttribute::Size(size));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let font = attributes
+        .get("data-font")
+        .and_then(|font| font.parse::<usize>().ok())
+        .filter(|font| *font > 0);
+    if let Some(font) = font {
+        inline_attributes.add(RichTextAttribute::Font(font));
+    }
+    inline_attributes
+}
+
+/// Return the properties of the `style` attribute with their values, e.g. `color: red`.
+fn styles(attributes: &HashMap<String, String>) -> Vec<(String, String)> {
+    match attributes.get("style") {
+        None => vec![],
+        Some(style) => style
+            .split(';')
+            .flat_map(|declaration| declaration.split_once(':'))
+            .map(|(property, value)| (property.trim().to_lowercase(), value.trim().to_owned()))
+            .collect(),
+    }
+}
+
+/// The value can't contain anything that loads the resource, e.g. `url(...)`.
+fn is_safe_css_value(value: &str) -> bool {
+    let lowercase = value.to_lowercase();
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '#' | '(' | ')' | ',' | '.' | '%' | ' ' | '-'))
+        && !lowercase.contains("url")
+        && !lowercase.contains("expression")
+}
+
+fn is_safe_link(href: &str) -> bool {
+    let normalized = href
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_lowercase();
+    match normalized.split_once(':') {
+        // The relative link, e.g. `/docs` or `#title`.
+        None => true,
+        Some((scheme, _)) if scheme.contains('/') || scheme.contains('#') || scheme.contains('?') => true,
+        Some((scheme, _)) => matches!(scheme, "http" | "https" | "mailto" | "tel"),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum HtmlToken {
+    Start {
+        name: String,
+        attributes: HashMap<String, String>,
+    },
+    End(String),
+    Text(String),
+}
+
+/// Split the HTML into the tags and the texts. The comments, the doctype and the content of the
+/// raw text elements, e.g. `script`, are skipped.
+fn tokenize(html: &str) -> Vec<HtmlToken> {
+    let mut tokens = vec![];
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map(|end| &comment[end + 3..]).unwrap_or("");
+            continue;
+        }
+
+        let is_tag = rest.starts_with('<')
+            && rest[1..]
+                .chars()
+                .next()
+                .map(|c| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?'))
+                .unwrap_or(false);
+        if !is_tag {
+            // The text begins with a char that may be the `<` that doesn't start any tag.
+            let first_len = rest.chars().next().map(|c| c.len_utf8()).unwrap_or(0);
+            let end = rest[first_len..]
+                .find('<')
+                .map(|end| end + first_len)
+                .unwrap_or(rest.len());
+            tokens.push(HtmlToken::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+            continue;
+        }
+
+        let end = tag_end(rest).unwrap_or(rest.len());
+        let tag = &rest[1..end];
+        rest = if end < rest.len() { &rest[end + 1..] } else { "" };
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(HtmlToken::End(name.trim().to_lowercase()));
+            continue;
+        }
+
+        let (name, attributes) = parse_start_tag(tag);
+        if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+            let close_tag = format!("</{}", name);
+            rest = rest
+                .as_bytes()
+                .windows(close_tag.len())
+                .position(|window| window.eq_ignore_ascii_case(close_tag.as_bytes()))
+                .map(|start| {
+                    let after_close = &rest[start..];
+                    after_close.find('>').map(|end| &after_close[end + 1..]).unwrap_or("")
+                })
+                .unwrap_or("");
+            continue;
+        }
+        tokens.push(HtmlToken::Start { name, attributes });
+    }
+    tokens
+}
+
+/// Return the index of the `>` that ends the tag at the start of the text. The `>` in the
+/// quoted attribute value is skipped.
+fn tag_end(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in s.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '>' => return Some(index),
+            None => {}
+        }
+    }
+    None
+}
+
+fn parse_start_tag(tag: &str) -> (String, HashMap<String, String>) {
+    let tag = tag.trim_end_matches('/');
+    let name_end = tag
+        .find(|c: char| c.is_ascii_whitespace() || c == '/')
+        .unwrap_or(tag.len());
+    let name = tag[..name_end].to_lowercase();
+
+    let mut attributes = HashMap::new();
+    let mut rest = tag[name_end..].trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let mut value = String::new();
+        if let Some(after_equal) = rest.strip_prefix('=') {
+            let after_equal = after_equal.trim_start();
+            let quote = after_equal.chars().next().filter(|c| *c == '"' || *c == '\'');
+            let (raw_value, after_value) = match quote {
+                Some(quote) => {
+                    let quoted = &after_equal[1..];
+                    let end = quoted.find(quote).unwrap_or(quoted.len());
+                    (&quoted[..end], quoted.get(end + 1..).unwrap_or(""))
+                }
+                None => {
+                    let end = after_equal
+                        .find(|c: char| c.is_ascii_whitespace())
+                        .unwrap_or(after_equal.len());
+                    (&after_equal[..end], &after_equal[end..])
+                }
+            };
+            value = decode_entities(raw_value);
+            rest = after_value;
+        }
+
+        if !key.is_empty() {
+            attributes.entry(key).or_insert(value);
+        }
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+    }
+    (name, attributes)
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut decoded = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end + 1]).map(|c| (c, end + 2)));
+        match entity {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(hex) = entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+        return u32::from_str_radix(hex, 16).ok().and_then(std::char::from_u32);
+    }
+    if let Some(decimal) = entity.strip_prefix('#') {
+        return decimal.parse::<u32>().ok().and_then(std::char::from_u32);
+    }
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{delta_to_html, html_to_delta};
+    use lib_ot::rich_text::{AttributeBuilder, RichTextAttribute, RichTextDeltaBuilder};
+
+    #[test]
+    fn delta_to_html_test() {
+        let delta = RichTextDeltaBuilder::new()
+            .insert("Hello ")
+            .insert_with_attributes("AppFlowy", RichTextAttribute::Bold(true).into())
+            .insert_with_attributes("\n", RichTextAttribute::Header(1).into())
+            .insert("a < b")
+            .insert_with_attributes("\n", RichTextAttribute::Bullet(true).into())
+            .build();
+        assert_eq!(
+            delta_to_html(&delta),
+            "<h1>Hello <strong>AppFlowy</strong></h1><ul><li>a &lt; b</li></ul>"
+        );
+    }
+
+    #[test]
+    fn html_round_trip_test() {
+        let styled = AttributeBuilder::new()
+            .add_attr(RichTextAttribute::Color("#ff0000".to_owned()))
+            .add_attr(RichTextAttribute::Background("rgb(0, 255, 0)".to_owned()))
+            .add_attr(RichTextAttribute::Size(14))
+            .add_attr(RichTextAttribute::Font(2))
+            .add_attr(RichTextAttribute::Underline(true))
+            .build();
+        let linked = AttributeBuilder::new()
+            .add_attr(RichTextAttribute::Link("https://appflowy.io"))
+            .add_attr(RichTextAttribute::Italic(true))
+            .add_attr(RichTextAttribute::StrikeThrough(true))
+            .build();
+        let centered_header = AttributeBuilder::new()
+            .add_attr(RichTextAttribute::Header(2))
+            .add_attr(RichTextAttribute::Align("center".to_owned()))
+            .build();
+        let nested_item = AttributeBuilder::new()
+            .add_attr(RichTextAttribute::Ordered(true))
+            .add_attr(RichTextAttribute::Indent(1))
+            .build();
+        let quoted_code = AttributeBuilder::new()
+            .add_attr(RichTextAttribute::BlockQuote(true))
+            .add_attr(RichTextAttribute::CodeBlock(true))
+            .build();
+        let delta = RichTextDeltaBuilder::new()
+            .insert("Title")
+            .insert_with_attributes("\n", centered_header)
+            .insert_with_attributes("Styled", styled)
+            .insert(" and ")
+            .insert_with_attributes("linked", linked)
+            .insert(" & ")
+            .insert_with_attributes("code", RichTextAttribute::InlineCode(true).into())
+            .insert("\n\n")
+            .insert("indented")
+            .insert_with_attributes("\n", RichTextAttribute::Indent(2).into())
+            .insert("first")
+            .insert_with_attributes("\n", RichTextAttribute::Bullet(true).into())
+            .insert("nested")
+            .insert_with_attributes("\n", nested_item)
+            .insert("done")
+            .insert_with_attributes("\n", RichTextAttribute::Checked(true).into())
+            .insert("quoted")
+            .insert_with_attributes("\n", RichTextAttribute::BlockQuote(true).into())
+            .insert("fn main() {")
+            .insert_with_attributes("\n", quoted_code.clone())
+            .insert("    if a < b {}")
+            .insert_with_attributes("\n", quoted_code)
+            .build();
+        assert_eq!(html_to_delta(&delta_to_html(&delta)), delta);
+    }
+
+    #[test]
+    fn html_indent_round_trip_test() {
+        let html = r#"<p style="padding-left: 1e30em">deep</p><p class="ql-indent-1000">deeper</p>"#;
+        let expected = RichTextDeltaBuilder::new()
+            .insert("deep")
+            .insert_with_attributes("\n", RichTextAttribute::Indent(8).into())
+            .insert("deeper")
+            .insert_with_attributes("\n", RichTextAttribute::Indent(8).into())
+            .build();
+        let delta = html_to_delta(html);
+        assert_eq!(delta, expected);
+        assert_eq!(html_to_delta(&delta_to_html(&delta)), expected);
+
+        let delta = RichTextDeltaBuilder::new()
+            .insert("deep")
+            .insert_with_attributes("\n", RichTextAttribute::Indent(usize::MAX).into())
+            .build();
+        let expected = RichTextDeltaBuilder::new()
+            .insert("deep")
+            .insert_with_attributes("\n", RichTextAttribute::Indent(8).into())
+            .build();
+        assert_eq!(html_to_delta(&delta_to_html(&delta)), expected);
+    }
+
+    #[test]
+    fn html_to_delta_sanitize_test() {
+        let html = r#"<html><head><title>Page</title><style>p { color: red; }</style></head>
+            <body>
+              <p onclick="alert(1)">Hello <script>alert("</p>")</script>
+                <a href=" javascript:alert(1)">world</a> &amp;
+                <span style="color: url(https://evil.com)">friends</span></p>
+              <!-- <p>comment</p> -->
+            </body></html>"#;
+        let expected = RichTextDeltaBuilder::new().insert("Hello world & friends\n").build();
+        assert_eq!(html_to_delta(html), expected);
+    }
+
+    #[test]
+    fn html_to_delta_test() {
+        let html = "<div><b>Bold</b><br/><i style='font-weight: 700'>both</i></div><p><br></p><ul><li></li></ul>";
+        let both = AttributeBuilder::new()
+            .add_attr(RichTextAttribute::Italic(true))
+            .add_attr(RichTextAttribute::Bold(true))
+            .build();
+        let expected = RichTextDeltaBuilder::new()
+            .insert_with_attributes("Bold", RichTextAttribute::Bold(true).into())
+            .insert("\n")
+            .insert_with_attributes("both", both)
+            .insert("\n\n")
+            .insert_with_attributes("\n", RichTextAttribute::Bullet(true).into())
+            .build();
+        assert_eq!(html_to_delta(html), expected);
+    }
+}
@@ -0,0 +1,85 @@
+use lib_ot::rich_text::{RichTextAttributeKey, RichTextAttributes, RichTextDelta};
+
+pub(crate) const BULLET_LIST: &str = "bullet";
+pub(crate) const ORDERED_LIST: &str = "ordered";
+pub(crate) const CHECKED_LIST: &str = "checked";
+pub(crate) const UNCHECKED_LIST: &str = "unchecked";
+
+/// The line of the text block and the texts it contains.
+pub(crate) struct DeltaLine {
+    pub segments: Vec<(String, RichTextAttributes)>,
+    /// The attributes of the "\n" that ends the line.
+    pub attributes: RichTextAttributes,
+}
+
+impl DeltaLine {
+    pub fn header(&self) -> usize {
+        attribute_value(&self.attributes, RichTextAttributeKey::Header)
+            .and_then(|value| value.parse::<usize>().ok())
+            .unwrap_or(0)
+    }
+
+    pub fn list(&self) -> Option<&str> {
+        attribute_value(&self.attributes, RichTextAttributeKey::List)
+    }
+
+    pub fn indent(&self) -> usize {
+        attribute_value(&self.attributes, RichTextAttributeKey::Indent)
+            .and_then(|value| value.parse::<usize>().ok())
+            .unwrap_or(0)
+    }
+
+    pub fn align(&self) -> Option<&str> {
+        attribute_value(&self.attributes, RichTextAttributeKey::Align)
+    }
+
+    pub fn is_quote(&self) -> bool {
+        is_attribute_true(&self.attributes, RichTextAttributeKey::BlockQuote)
+    }
+
+    pub fn is_code_block(&self) -> bool {
+        is_attribute_true(&self.attributes, RichTextAttributeKey::CodeBlock)
+    }
+
+    pub fn plain_text(&self) -> String {
+        self.segments.iter().map(|(text, _)| text.as_str()).collect()
+    }
+}
+
+/// Split the inserted texts of the delta into lines. The text after the last "\n" is returned
+/// as the line without the block attributes.
+pub(crate) fn split_lines(delta: &RichTextDelta) -> Vec<DeltaLine> {
+    let mut lines = vec![];
+    let mut segments = vec![];
+    for op in delta.ops.iter().filter(|op| op.is_insert()) {
+        let attributes = op.get_attributes();
+        let mut texts = op.get_data().split('\n').peekable();
+        while let Some(text) = texts.next() {
+            if !text.is_empty() {
+                segments.push((text.to_owned(), attributes.clone()));
+            }
+            if texts.peek().is_some() {
+                lines.push(DeltaLine {
+                    segments: std::mem::take(&mut segments),
+                    attributes: attributes.clone(),
+                });
+            }
+        }
+    }
+
+    if !segments.is_empty() {
+        lines.push(DeltaLine {
+            segments,
+            attributes: RichTextAttributes::new(),
+        });
+    }
+    lines
+}
+
+pub(crate) fn attribute_value(attributes: &RichTextAttributes, key: RichTextAttributeKey) -> Option<&str> {
+    attributes.get(&key).and_then(|value| value.0.as_deref())
+}
+
+pub(crate) fn is_attribute_true(attributes: &RichTextAttributes, key: RichTextAttributeKey) -> bool {
+    attribute_value(attributes, key) == Some("true")
+}
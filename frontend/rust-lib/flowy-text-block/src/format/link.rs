@@ -0,0 +1,32 @@
+/// The scheme of the links that open the view in the app.
+pub const VIEW_LINK_SCHEME: &str = "appflowy";
+
+/// Return the link of the view that can be shared, e.g. `appflowy://view/W5kBq2D1`. The text block
+/// has the same id as the view that it belongs to.
+pub fn view_link(view_id: &str) -> String {
+    format!("{}://view/{}", VIEW_LINK_SCHEME, view_id)
+}
+
+/// Return the id of the view that the link points to, or None if it isn't a view link.
+pub fn view_id_from_link(link: &str) -> Option<&str> {
+    let view_id = link.strip_prefix(VIEW_LINK_SCHEME)?.strip_prefix("://view/")?;
+    if view_id.is_empty() || view_id.contains('/') {
+        None
+    } else {
+        Some(view_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{view_id_from_link, view_link};
+
+    #[test]
+    fn view_link_test() {
+        let link = view_link("W5kBq2D1");
+        assert_eq!(link, "appflowy://view/W5kBq2D1");
+        assert_eq!(view_id_from_link(&link), Some("W5kBq2D1"));
+        assert_eq!(view_id_from_link("https://appflowy.io/view/W5kBq2D1"), None);
+        assert_eq!(view_id_from_link("appflowy://view/"), None);
+    }
+}
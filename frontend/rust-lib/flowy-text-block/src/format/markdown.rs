@@ -0,0 +1,589 @@
+use crate::format::html::{parse_inline_html_tag, style_span, InlineHtmlTag};
+use crate::format::line::{
+    attribute_value, is_attribute_true, split_lines, DeltaLine, BULLET_LIST, CHECKED_LIST, ORDERED_LIST, UNCHECKED_LIST,
+};
+use lib_ot::rich_text::{RichTextAttribute, RichTextAttributeKey, RichTextAttributes, RichTextDelta};
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+/// Parse the CommonMark text into the delta of the text block. The block attributes, e.g. the
+/// header or the list, are set on the "\n" that ends the line, the same as the editor does. The
+/// raw HTML is skipped, so it never ends up in the document as text, except the inline tags that
+/// format the text, e.g. the `<u>` and the `<span style>` that `delta_to_markdown` writes.
+pub fn markdown_to_delta(markdown: &str) -> RichTextDelta {
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut writer = DeltaWriter::new();
+    for event in Parser::new_ext(markdown, options) {
+        writer.write_event(event);
+    }
+    writer.finish()
+}
+
+struct DeltaWriter {
+    delta: RichTextDelta,
+    is_line_empty: bool,
+    bold: usize,
+    italic: usize,
+    strike: usize,
+    links: Vec<String>,
+    html_tags: Vec<(String, RichTextAttributes)>,
+    header: usize,
+    lists: Vec<&'static str>,
+    task: Option<&'static str>,
+    quote_depth: usize,
+    is_code_block: bool,
+}
+
+impl DeltaWriter {
+    fn new() -> Self {
+        Self {
+            delta: RichTextDelta::new(),
+            is_line_empty: true,
+            bold: 0,
+            italic: 0,
+            strike: 0,
+            links: vec![],
+            html_tags: vec![],
+            header: 0,
+            lists: vec![],
+            task: None,
+            quote_depth: 0,
+            is_code_block: false,
+        }
+    }
+
+    fn write_event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start_tag(tag),
+            Event::End(tag) => self.end_tag(tag),
+            Event::Text(text) => self.write_text(&text),
+            Event::Html(html) => self.write_html(&html),
+            Event::Code(code) => {
+                let mut attributes = self.inline_attributes();
+                attributes.add(RichTextAttribute::InlineCode(true));
+                self.insert(&code, attributes);
+            }
+            Event::SoftBreak => self.insert(" ", self.inline_attributes()),
+            Event::HardBreak => self.end_line(),
+            Event::TaskListMarker(checked) => {
+                self.task = Some(if checked { CHECKED_LIST } else { UNCHECKED_LIST });
+            }
+            Event::Rule | Event::FootnoteReference(_) => {}
+        }
+    }
+
+    fn start_tag(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading(level, _, _) => self.header = level as usize,
+            Tag::BlockQuote => self.quote_depth += 1,
+            Tag::CodeBlock(_) => self.is_code_block = true,
+            Tag::List(start) => {
+                // The text of the parent item ends before the nested list begins.
+                self.close_line();
+                self.lists
+                    .push(if start.is_some() { ORDERED_LIST } else { BULLET_LIST });
+            }
+            Tag::Item => {
+                self.close_line();
+                self.task = None;
+            }
+            Tag::Emphasis => self.italic += 1,
+            Tag::Strong => self.bold += 1,
+            Tag::Strikethrough => self.strike += 1,
+            Tag::Link(_, url, _) | Tag::Image(_, url, _) => self.links.push(url.to_string()),
+            _ => {}
+        }
+    }
+
+    fn end_tag(&mut self, tag: Tag) {
+        match tag {
+            // The inline tags that aren't closed don't format the following blocks.
+            Tag::Paragraph => {
+                self.close_line();
+                self.html_tags.clear();
+            }
+            Tag::Heading(_, _, _) => {
+                self.end_line();
+                self.header = 0;
+                self.html_tags.clear();
+            }
+            Tag::BlockQuote => {
+                self.close_line();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+            }
+            Tag::CodeBlock(_) => {
+                self.close_line();
+                self.is_code_block = false;
+            }
+            Tag::List(_) => {
+                self.close_line();
+                self.lists.pop();
+            }
+            Tag::Item => {
+                self.close_line();
+                self.task = None;
+            }
+            Tag::Emphasis => self.italic = self.italic.saturating_sub(1),
+            Tag::Strong => self.bold = self.bold.saturating_sub(1),
+            Tag::Strikethrough => self.strike = self.strike.saturating_sub(1),
+            Tag::Link(_, _, _) | Tag::Image(_, _, _) => {
+                self.links.pop();
+            }
+            _ => {}
+        }
+    }
+
+    fn write_html(&mut self, html: &str) {
+        match parse_inline_html_tag(html) {
+            None => {}
+            Some(InlineHtmlTag::Start { name, attributes }) => self.html_tags.push((name, attributes)),
+            Some(InlineHtmlTag::End(name)) => {
+                if let Some(index) = self.html_tags.iter().rposition(|(open_name, _)| open_name == &name) {
+                    self.html_tags.truncate(index);
+                }
+            }
+        }
+    }
+
+    /// The text of the code block contains the line breaks, e.g. `fn main() {}\n`.
+    fn write_text(&mut self, text: &str) {
+        let mut lines = text.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            if !line.is_empty() {
+                self.insert(line, self.inline_attributes());
+            }
+            if lines.peek().is_some() {
+                self.end_line();
+            }
+        }
+    }
+
+    fn insert(&mut self, s: &str, attributes: RichTextAttributes) {
+        self.delta.insert(s, attributes);
+        self.is_line_empty = false;
+    }
+
+    fn end_line(&mut self) {
+        let attributes = self.block_attributes();
+        self.delta.insert("\n", attributes);
+        self.is_line_empty = true;
+    }
+
+    /// End the line if there is any text that isn't followed by a "\n".
+    fn close_line(&mut self) {
+        if !self.is_line_empty {
+            self.end_line();
+        }
+    }
+
+    fn inline_attributes(&self) -> RichTextAttributes {
+        let mut attributes = RichTextAttributes::new();
+        if self.bold > 0 {
+            attributes.add(RichTextAttribute::Bold(true));
+        }
+        if self.italic > 0 {
+            attributes.add(RichTextAttribute::Italic(true));
+        }
+        if self.strike > 0 {
+            attributes.add(RichTextAttribute::StrikeThrough(true));
+        }
+        if let Some(link) = self.links.last() {
+            attributes.add(RichTextAttribute::Link(link));
+        }
+        for (_, html_attributes) in self.html_tags.iter() {
+            for (key, value) in html_attributes.iter() {
+                attributes.insert(key.clone(), value.clone());
+            }
+        }
+        attributes
+    }
+
+    fn block_attributes(&self) -> RichTextAttributes {
+        let mut attributes = RichTextAttributes::new();
+        if self.header > 0 {
+            attributes.add(RichTextAttribute::Header(self.header));
+        }
+        if let Some(list) = self.lists.last() {
+            attributes.add(RichTextAttribute::List(self.task.unwrap_or(*list)));
+            if self.lists.len() > 1 {
+                attributes.add(RichTextAttribute::Indent(self.lists.len() - 1));
+            }
+        }
+        if self.quote_depth > 0 {
+            attributes.add(RichTextAttribute::BlockQuote(true));
+        }
+        if self.is_code_block {
+            attributes.add(RichTextAttribute::CodeBlock(true));
+        }
+        attributes
+    }
+
+    fn finish(mut self) -> RichTextDelta {
+        self.close_line();
+        // The text block always ends with a "\n".
+        if self.delta.is_empty() {
+            self.delta.insert("\n", RichTextAttributes::new());
+        }
+        self.delta
+    }
+}
+
+/// Convert the delta of the text block into CommonMark. The empty lines are dropped because
+/// CommonMark has no empty paragraph. The underline and the styles of the text, e.g. the color
+/// or the font, are written as the inline HTML. The align, the width and the height have no
+/// Markdown form and the indent is only kept for the list items, so they are dropped.
+pub fn delta_to_markdown(delta: &RichTextDelta) -> String {
+    let lines = split_lines(delta);
+    let mut markdown = String::new();
+    let mut previous: Option<&DeltaLine> = None;
+    let mut ordered_numbers: Vec<usize> = vec![];
+    let mut index = 0;
+    while index < lines.len() {
+        let line = &lines[index];
+        if line.is_code_block() {
+            let end = lines[index..]
+                .iter()
+                .position(|other| !other.is_code_block() || other.is_quote() != line.is_quote())
+                .map(|position| index + position)
+                .unwrap_or(lines.len());
+            if !markdown.is_empty() {
+                markdown.push('\n');
+            }
+            write_code_block(&mut markdown, &lines[index..end], line.is_quote());
+            previous = Some(line);
+            ordered_numbers.clear();
+            index = end;
+            continue;
+        }
+
+        index += 1;
+        if line.segments.is_empty() && line.header() == 0 {
+            continue;
+        }
+
+        // The items of the same list are written without the blank line between them.
+        let is_same_list = previous
+            .map(|previous| {
+                previous.list().is_some() && line.list().is_some() && previous.is_quote() == line.is_quote()
+            })
+            .unwrap_or(false);
+        if !markdown.is_empty() && !is_same_list {
+            markdown.push('\n');
+        }
+
+        if line.is_quote() {
+            markdown.push_str("> ");
+        }
+        match line.list() {
+            None => ordered_numbers.clear(),
+            Some(list) => {
+                // The item can only be nested one level deeper than the previous item.
+                let max_indent = if is_same_list { ordered_numbers.len() } else { 0 };
+                let indent = line.indent().min(max_indent);
+                ordered_numbers.resize(indent + 1, 0);
+                markdown.push_str(&"    ".repeat(indent));
+                match list {
+                    ORDERED_LIST => {
+                        ordered_numbers[indent] += 1;
+                        markdown.push_str(&format!("{}. ", ordered_numbers[indent]));
+                    }
+                    CHECKED_LIST => markdown.push_str("- [x] "),
+                    UNCHECKED_LIST => markdown.push_str("- [ ] "),
+                    _ => markdown.push_str("- "),
+                }
+                if list != ORDERED_LIST {
+                    ordered_numbers[indent] = 0;
+                }
+            }
+        }
+        if line.header() > 0 {
+            markdown.push_str(&"#".repeat(line.header().min(6)));
+            markdown.push(' ');
+        }
+        markdown.push_str(&escape_line_start(inline_markdown(&line.segments).trim_start()));
+        markdown.push('\n');
+        previous = Some(line);
+    }
+    markdown
+}
+
+fn write_code_block(markdown: &mut String, lines: &[DeltaLine], is_quote: bool) {
+    let prefix = if is_quote { "> " } else { "" };
+    let texts = lines.iter().map(|line| line.plain_text()).collect::<Vec<String>>();
+    // The fence must be longer than any run of backticks in the code.
+    let fence = "`".repeat(
+        texts
+            .iter()
+            .map(|text| longest_backtick_run(text) + 1)
+            .fold(3, usize::max),
+    );
+    markdown.push_str(&format!("{}{}\n", prefix, fence));
+    for text in texts {
+        markdown.push_str(&format!("{}{}\n", prefix, text));
+    }
+    markdown.push_str(&format!("{}{}\n", prefix, fence));
+}
+
+fn inline_markdown(segments: &[(String, RichTextAttributes)]) -> String {
+    segments
+        .iter()
+        .map(|(text, attributes)| {
+            if is_attribute_true(attributes, RichTextAttributeKey::InlineCode) {
+                return wrap_inline(code_span(text), attributes);
+            }
+
+            // The emphasis can't begin or end with the whitespace, e.g. `** bold**`.
+            let content = text.trim();
+            if content.is_empty() {
+                return text.clone();
+            }
+            let leading = &text[..text.len() - text.trim_start().len()];
+            let trailing = &text[text.trim_end().len()..];
+            format!(
+                "{}{}{}",
+                leading,
+                wrap_inline(escape_text(content), attributes),
+                trailing
+            )
+        })
+        .collect()
+}
+
+fn wrap_inline(s: String, attributes: &RichTextAttributes) -> String {
+    let mut s = style_span(s, attributes);
+    if is_attribute_true(attributes, RichTextAttributeKey::Underline) {
+        s = format!("<u>{}</u>", s);
+    }
+    if is_attribute_true(attributes, RichTextAttributeKey::Italic) {
+        s = format!("*{}*", s);
+    }
+    if is_attribute_true(attributes, RichTextAttributeKey::Bold) {
+        s = format!("**{}**", s);
+    }
+    if is_attribute_true(attributes, RichTextAttributeKey::StrikeThrough) {
+        s = format!("~~{}~~", s);
+    }
+    if let Some(link) = attribute_value(attributes, RichTextAttributeKey::Link) {
+        if link.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+            s = format!("[{}](<{}>)", s, link);
+        } else {
+            s = format!("[{}]({})", s, link);
+        }
+    }
+    s
+}
+
+fn code_span(code: &str) -> String {
+    let fence = "`".repeat(longest_backtick_run(code) + 1);
+    if code.starts_with('`') || code.ends_with('`') {
+        format!("{} {} {}", fence, code, fence)
+    } else {
+        format!("{}{}{}", fence, code, fence)
+    }
+}
+
+fn longest_backtick_run(s: &str) -> usize {
+    s.split(|c| c != '`').map(|run| run.len()).max().unwrap_or(0)
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '~' | '[' | ']' | '<' | '>' | '#' | '&' | '|'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape the text that would begin the list if it's at the start of the line, e.g. `- ` or `1. `.
+fn escape_line_start(line: &str) -> String {
+    if line.starts_with('-') || line.starts_with('+') {
+        return format!("\\{}", line);
+    }
+
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 && (line[digits..].starts_with('.') || line[digits..].starts_with(')')) {
+        return format!("{}\\{}", &line[..digits], &line[digits..]);
+    }
+    line.to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{delta_to_markdown, markdown_to_delta};
+    use lib_ot::rich_text::{AttributeBuilder, RichTextAttribute, RichTextDelta, RichTextDeltaBuilder};
+
+    fn inline_line(attribute: RichTextAttribute) -> RichTextDelta {
+        RichTextDeltaBuilder::new()
+            .insert_with_attributes("text", attribute.into())
+            .insert("\n")
+            .build()
+    }
+
+    fn block_line(attribute: RichTextAttribute) -> RichTextDelta {
+        RichTextDeltaBuilder::new()
+            .insert("text")
+            .insert_with_attributes("\n", attribute.into())
+            .build()
+    }
+
+    #[test]
+    fn delta_to_markdown_attributes_test() {
+        let nested_list = RichTextDeltaBuilder::new()
+            .insert("parent")
+            .insert_with_attributes("\n", RichTextAttribute::Bullet(true).into())
+            .insert("child")
+            .insert_with_attributes(
+                "\n",
+                AttributeBuilder::new()
+                    .add_attr(RichTextAttribute::Bullet(true))
+                    .add_attr(RichTextAttribute::Indent(1))
+                    .build(),
+            )
+            .build();
+        let cases = vec![
+            (inline_line(RichTextAttribute::Bold(true)), "**text**\n"),
+            (inline_line(RichTextAttribute::Italic(true)), "*text*\n"),
+            (inline_line(RichTextAttribute::Underline(true)), "<u>text</u>\n"),
+            (inline_line(RichTextAttribute::StrikeThrough(true)), "~~text~~\n"),
+            (
+                inline_line(RichTextAttribute::Link("https://appflowy.io")),
+                "[text](https://appflowy.io)\n",
+            ),
+            (
+                inline_line(RichTextAttribute::Color("#ff0000".to_owned())),
+                "<span style=\"color: #ff0000\">text</span>\n",
+            ),
+            (
+                inline_line(RichTextAttribute::Background("#00ff00".to_owned())),
+                "<span style=\"background-color: #00ff00\">text</span>\n",
+            ),
+            (
+                inline_line(RichTextAttribute::Font(2)),
+                "<span data-font=\"2\">text</span>\n",
+            ),
+            (
+                inline_line(RichTextAttribute::Size(20)),
+                "<span style=\"font-size: 20px\">text</span>\n",
+            ),
+            (inline_line(RichTextAttribute::InlineCode(true)), "`text`\n"),
+            (block_line(RichTextAttribute::Header(2)), "## text\n"),
+            (block_line(RichTextAttribute::Bullet(true)), "- text\n"),
+            (block_line(RichTextAttribute::Ordered(true)), "1. text\n"),
+            (block_line(RichTextAttribute::Checked(true)), "- [x] text\n"),
+            (block_line(RichTextAttribute::UnChecked(true)), "- [ ] text\n"),
+            (block_line(RichTextAttribute::BlockQuote(true)), "> text\n"),
+            (block_line(RichTextAttribute::CodeBlock(true)), "```\ntext\n```\n"),
+            (nested_list, "- parent\n    - child\n"),
+            // The attributes that have no Markdown form are dropped.
+            (block_line(RichTextAttribute::Indent(1)), "text\n"),
+            (block_line(RichTextAttribute::Align("center".to_owned())), "text\n"),
+            (inline_line(RichTextAttribute::Width(100)), "text\n"),
+            (inline_line(RichTextAttribute::Height(100)), "text\n"),
+        ];
+        for (delta, expected) in cases {
+            assert_eq!(delta_to_markdown(&delta), expected, "{}", delta.to_json_str());
+        }
+    }
+
+    #[test]
+    fn markdown_to_delta_test() {
+        let delta = markdown_to_delta("# Hello **AppFlowy**\n\n- [x] Done\n");
+        let expected = RichTextDeltaBuilder::new()
+            .insert("Hello ")
+            .insert_with_attributes("AppFlowy", RichTextAttribute::Bold(true).into())
+            .insert_with_attributes("\n", RichTextAttribute::Header(1).into())
+            .insert("Done")
+            .insert_with_attributes("\n", RichTextAttribute::Checked(true).into())
+            .build();
+        assert_eq!(delta, expected);
+    }
+
+    #[test]
+    fn markdown_to_delta_skip_html_test() {
+        let delta = markdown_to_delta("<div>\n<script>alert(1)</script>\n</div>\n\nHello <i>world</i>\n");
+        let expected = RichTextDeltaBuilder::new()
+            .insert("Hello ")
+            .insert_with_attributes("world", RichTextAttribute::Italic(true).into())
+            .insert("\n")
+            .build();
+        assert_eq!(delta, expected);
+
+        // The styles that would load the resources are dropped.
+        let delta = markdown_to_delta("<span style=\"background: url(x)\" onclick=\"alert(1)\">text</span>\n");
+        assert_eq!(delta, RichTextDeltaBuilder::new().insert("text\n").build());
+    }
+
+    #[test]
+    fn markdown_to_delta_empty_test() {
+        let delta = markdown_to_delta("");
+        assert_eq!(delta, RichTextDeltaBuilder::new().insert("\n").build());
+        assert_eq!(delta_to_markdown(&delta), "");
+    }
+
+    #[test]
+    fn markdown_round_trip_test() {
+        let markdown = r#"# Title
+
+Some **bold**, *italic*, ~~strike~~ and `code` with a [link](https://appflowy.io).
+
+- first
+- second
+    1. one
+    2. two
+- [x] done
+- [ ] todo
+
+> quoted
+
+```
+fn main() {}
+```
+"#;
+        assert_eq!(delta_to_markdown(&markdown_to_delta(markdown)), markdown);
+    }
+
+    #[test]
+    fn delta_round_trip_inline_html_test() {
+        let styles = AttributeBuilder::new()
+            .add_attr(RichTextAttribute::Underline(true))
+            .add_attr(RichTextAttribute::Color("#ff0000".to_owned()))
+            .add_attr(RichTextAttribute::Background("#00ff00".to_owned()))
+            .add_attr(RichTextAttribute::Font(2))
+            .add_attr(RichTextAttribute::Size(20))
+            .add_attr(RichTextAttribute::Bold(true))
+            .build();
+        let delta = RichTextDeltaBuilder::new()
+            .insert_with_attributes("underline", RichTextAttribute::Underline(true).into())
+            .insert(" and ")
+            .insert_with_attributes("red", RichTextAttribute::Color("#ff0000".to_owned()).into())
+            .insert(" and ")
+            .insert_with_attributes("styled", styles)
+            .insert("\n")
+            .build();
+        assert_eq!(markdown_to_delta(&delta_to_markdown(&delta)), delta);
+    }
+
+    #[test]
+    fn delta_round_trip_test() {
+        let quoted_code = AttributeBuilder::new()
+            .add_attr(RichTextAttribute::BlockQuote(true))
+            .add_attr(RichTextAttribute::CodeBlock(true))
+            .build();
+        let delta = RichTextDeltaBuilder::new()
+            .insert_with_attributes("1. Not a list * item", RichTextAttribute::Italic(true).into())
+            .insert("\n")
+            .insert_with_attributes("let s = \"`\";", RichTextAttribute::InlineCode(true).into())
+            .insert("\n")
+            .insert("a\n")
+            .insert_with_attributes("\n", quoted_code.clone())
+            .insert("```")
+            .insert_with_attributes("\n", quoted_code)
+            .build();
+        assert_eq!(markdown_to_delta(&delta_to_markdown(&delta)), delta);
+    }
+}
@@ -0,0 +1,8 @@
+mod html;
+mod line;
+mod link;
+mod markdown;
+
+pub use html::*;
+pub use link::*;
+pub use markdown::*;
@@ -1,7 +1,9 @@
+mod diff;
 pub mod editor;
-mod entities;
+pub mod entities;
 mod event_handler;
 pub mod event_map;
+pub mod format;
 pub mod manager;
 mod queue;
 mod web_socket;
@@ -6,7 +6,8 @@ use flowy_database::ConnectionPool;
 use flowy_error::FlowyResult;
 use flowy_revision::disk::SQLiteTextBlockRevisionPersistence;
 use flowy_revision::{
-    RevisionCloudService, RevisionManager, RevisionPersistence, RevisionWebSocket, SQLiteRevisionSnapshotPersistence,
+    delete_revision_object, RevisionCloudService, RevisionManager, RevisionPersistence, RevisionWebSocket,
+    SQLiteRevisionHistoryPersistence, SQLiteRevisionSnapshotPersistence,
 };
 use flowy_sync::entities::{
     revision::{md5, RepeatedRevision, Revision},
@@ -15,6 +16,8 @@ use flowy_sync::entities::{
 };
 use lib_infra::future::FutureResult;
 use std::{convert::TryInto, sync::Arc};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
 
 pub trait TextBlockUser: Send + Sync {
     fn user_dir(&self) -> Result<String, FlowyError>;
@@ -28,6 +31,8 @@ pub struct TextBlockManager {
     rev_web_socket: Arc<dyn RevisionWebSocket>,
     editor_map: Arc<TextBlockEditorMap>,
     user: Arc<dyn TextBlockUser>,
+    /// Notifies the id of the text block whose content was changed, either locally or remotely.
+    block_changed_notifier: broadcast::Sender<String>,
 }
 
 impl TextBlockManager {
@@ -36,11 +41,13 @@ impl TextBlockManager {
         text_block_user: Arc<dyn TextBlockUser>,
         rev_web_socket: Arc<dyn RevisionWebSocket>,
     ) -> Self {
+        let (block_changed_notifier, _) = broadcast::channel(100);
         Self {
             cloud_service,
             rev_web_socket,
             editor_map: Arc::new(TextBlockEditorMap::new()),
             user: text_block_user,
+            block_changed_notifier,
         }
     }
 
@@ -70,7 +77,11 @@ impl TextBlockManager {
         let doc_id = doc_id.as_ref();
         tracing::Span::current().record("doc_id", &doc_id);
         self.editor_map.remove(doc_id);
-        Ok(())
+
+        let user_id = self.user.user_id()?;
+        let pool = self.user.db_pool()?;
+        let disk_cache = SQLiteTextBlockRevisionPersistence::new(&user_id, pool.clone());
+        delete_revision_object(doc_id, disk_cache, pool)
     }
 
     #[tracing::instrument(level = "debug", skip(self, delta), fields(doc_id = %delta.block_id), err)]
@@ -93,6 +104,10 @@ impl TextBlockManager {
         Ok(())
     }
 
+    pub fn subscribe_block_changed(&self) -> broadcast::Receiver<String> {
+        self.block_changed_notifier.subscribe()
+    }
+
     pub async fn receive_ws_data(&self, data: Bytes) {
         let result: Result<ServerRevisionWSData, protobuf::ProtobufError> = data.try_into();
         match result {
@@ -130,6 +145,7 @@ impl TextBlockManager {
         let user = self.user.clone();
         let token = self.user.token()?;
         let rev_manager = self.make_rev_manager(block_id, pool.clone())?;
+        listen_revisions(block_id, &rev_manager, self.block_changed_notifier.clone());
         let cloud_service = Arc::new(TextBlockRevisionCloudService {
             token,
             server: self.cloud_service.clone(),
@@ -144,21 +160,33 @@ impl TextBlockManager {
         let user_id = self.user.user_id()?;
         let disk_cache = SQLiteTextBlockRevisionPersistence::new(&user_id, pool.clone());
         let rev_persistence = RevisionPersistence::new(&user_id, doc_id, disk_cache);
-        // let history_persistence = SQLiteRevisionHistoryPersistence::new(doc_id, pool.clone());
+        let history_persistence = SQLiteRevisionHistoryPersistence::new(doc_id, pool.clone());
         let snapshot_persistence = SQLiteRevisionSnapshotPersistence::new(doc_id, pool);
         let rev_compactor = TextBlockRevisionCompactor();
 
-        Ok(RevisionManager::new(
-            &user_id,
-            doc_id,
-            rev_persistence,
-            rev_compactor,
-            // history_persistence,
-            snapshot_persistence,
-        ))
+        Ok(
+            RevisionManager::new(&user_id, doc_id, rev_persistence, rev_compactor, snapshot_persistence)
+                .with_history(history_persistence),
+        )
     }
 }
 
+fn listen_revisions(block_id: &str, rev_manager: &RevisionManager, notifier: broadcast::Sender<String>) {
+    let block_id = block_id.to_owned();
+    let mut rx = rev_manager.subscribe_revisions();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                // Some revisions were skipped, but the block was changed anyway.
+                Ok(_) | Err(RecvError::Lagged(_)) => {
+                    let _ = notifier.send(block_id.clone());
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
 struct TextBlockRevisionCloudService {
     token: String,
     server: Arc<dyn BlockCloudService>,
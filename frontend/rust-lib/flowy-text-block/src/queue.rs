@@ -170,6 +170,10 @@ impl EditBlockQueue {
                 let delta = self.document.read().await.delta().clone();
                 let _ = ret.send(Ok(delta));
             }
+            EditorCommand::ReadPlainText { ret } => {
+                let text = self.document.read().await.to_plain_string();
+                let _ = ret.send(Ok(text));
+            }
         }
         Ok(())
     }
@@ -256,6 +260,9 @@ pub(crate) enum EditorCommand {
     ReadDelta {
         ret: Ret<RichTextDelta>,
     },
+    ReadPlainText {
+        ret: Ret<String>,
+    },
 }
 
 impl std::fmt::Debug for EditorCommand {
@@ -275,6 +282,7 @@ impl std::fmt::Debug for EditorCommand {
             EditorCommand::Redo { .. } => "Redo",
             EditorCommand::ReadDeltaStr { .. } => "ReadDeltaStr",
             EditorCommand::ReadDelta { .. } => "ReadDocumentAsDelta",
+            EditorCommand::ReadPlainText { .. } => "ReadPlainText",
         };
         f.write_str(s)
     }
@@ -0,0 +1,32 @@
+use crate::document::script::{EditorScript::*, *};
+
+#[tokio::test]
+async fn text_block_history_read_test() {
+    let scripts = vec![
+        InsertText("1", 0),
+        InsertText("2", 1),
+        InsertText("3", 2),
+        AssertLatestHistoryRevId(3),
+        AssertJsonAtRevision(0, r#"[{"insert":"\n"}]"#),
+        AssertJsonAtRevision(2, r#"[{"insert":"12\n"}]"#),
+        AssertJsonAtRevision(3, r#"[{"insert":"123\n"}]"#),
+        AssertRevisionNotFound(4),
+    ];
+    TextBlockEditorTest::new().await.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn text_block_history_restore_test() {
+    let scripts = vec![
+        InsertText("1", 0),
+        InsertText("2", 1),
+        InsertText("3", 2),
+        RestoreRevision(1),
+        AssertJson(r#"[{"insert":"1\n"}]"#),
+        AssertCurrentRevId(4),
+        AssertNextSyncRevId(None),
+        AssertJsonAtRevision(3, r#"[{"insert":"123\n"}]"#),
+        AssertLatestHistoryRevId(4),
+    ];
+    TextBlockEditorTest::new().await.run_scripts(scripts).await;
+}
@@ -1,2 +1,4 @@
+mod history_test;
 mod script;
+mod snapshot_test;
 mod text_block_test;
@@ -1,3 +1,4 @@
+use flowy_error::ErrorCode;
 use flowy_revision::disk::RevisionState;
 use flowy_test::{helper::ViewTest, FlowySDKTest};
 use flowy_text_block::editor::TextBlockEditor;
@@ -15,6 +16,11 @@ pub enum EditorScript {
     AssertNextSyncRevId(Option<i64>),
     AssertCurrentRevId(i64),
     AssertJson(&'static str),
+
+    AssertLatestHistoryRevId(i64),
+    AssertJsonAtRevision(i64, &'static str),
+    AssertRevisionNotFound(i64),
+    RestoreRevision(i64),
 }
 
 pub struct TextBlockEditorTest {
@@ -79,6 +85,22 @@ impl TextBlockEditorTest {
                 }
                 assert_eq!(expected_delta, delta);
             }
+            EditorScript::AssertLatestHistoryRevId(rev_id) => {
+                let histories = self.editor.read_revision_histories().await.unwrap();
+                assert_eq!(histories.last().unwrap().end_rev_id, rev_id);
+            }
+            EditorScript::AssertJsonAtRevision(rev_id, expected) => {
+                let expected_delta: RichTextDelta = serde_json::from_str(expected).unwrap();
+                let delta = self.editor.delta_at_revision(rev_id).await.unwrap();
+                assert_eq!(expected_delta, delta);
+            }
+            EditorScript::AssertRevisionNotFound(rev_id) => {
+                let error = self.editor.delta_at_revision(rev_id).await.unwrap_err();
+                assert_eq!(error.code, ErrorCode::RecordNotFound.value());
+            }
+            EditorScript::RestoreRevision(rev_id) => {
+                self.editor.restore_revision(rev_id).await.unwrap();
+            }
         }
         sleep(Duration::from_millis(TEXT_BLOCK_SYNC_INTERVAL_IN_MILLIS)).await;
     }
@@ -0,0 +1,51 @@
+use crate::document::script::TextBlockEditorTest;
+use flowy_text_block::editor::TextBlockEditor;
+use flowy_text_block::TEXT_BLOCK_SYNC_INTERVAL_IN_MILLIS;
+use std::sync::Arc;
+use tokio::time::{sleep, Duration};
+
+// Each insertion waits for the ack, so every insertion is saved as a single revision instead of
+// being compacted with the pending ones.
+async fn insert_revisions(editor: &Arc<TextBlockEditor>, count: usize) {
+    let rev_manager = editor.rev_manager();
+    for _ in 0..count {
+        let mut notify = rev_manager.ack_notify();
+        let index = editor.plain_text().await.unwrap().len();
+        editor.insert(index, "a").await.unwrap();
+        let _ = notify.recv().await;
+    }
+}
+
+async fn reopen(test: &TextBlockEditorTest, editor: Arc<TextBlockEditor>) -> Arc<TextBlockEditor> {
+    let doc_id = editor.doc_id.clone();
+    test.sdk.text_block_manager.close_block(&doc_id).unwrap();
+    drop(editor);
+    // Wait for the revisions and the snapshots to be written to the disk.
+    sleep(Duration::from_millis(TEXT_BLOCK_SYNC_INTERVAL_IN_MILLIS)).await;
+    test.sdk.text_block_manager.open_block(&doc_id).await.unwrap()
+}
+
+// The document is loaded from the snapshot, so only the snapshot and the revisions after it are
+// composed.
+fn assert_loaded_from_snapshot(editor: &Arc<TextBlockEditor>) {
+    let rev_manager = editor.rev_manager();
+    let snapshot = rev_manager.revision_snapshot().read_last_snapshot().unwrap().unwrap();
+    let later_revision_count = (rev_manager.rev_id() - snapshot.rev_id) as usize;
+    assert_eq!(rev_manager.loaded_revision_count(), later_revision_count + 1);
+}
+
+#[tokio::test]
+async fn text_block_open_with_snapshot_test() {
+    let test = TextBlockEditorTest::new().await;
+    let editor = test.editor.clone();
+    insert_revisions(&editor, 200).await;
+    let editor = reopen(&test, editor).await;
+    assert_eq!(editor.plain_text().await.unwrap(), format!("{}\n", "a".repeat(200)));
+    assert_loaded_from_snapshot(&editor);
+
+    insert_revisions(&editor, 850).await;
+    let editor = reopen(&test, editor).await;
+    assert_eq!(editor.plain_text().await.unwrap(), format!("{}\n", "a".repeat(1050)));
+    assert_loaded_from_snapshot(&editor);
+    assert!(editor.rev_manager().loaded_revision_count() < 100);
+}
@@ -1,7 +1,9 @@
 use crate::helper::*;
+use flowy_net::get_client_server_configuration;
 use flowy_test::{event_builder::UserModuleEventBuilder, FlowySDKTest};
 use flowy_user::entities::{SignInPayloadPB, SignUpPayloadPB, UserProfilePB};
 use flowy_user::{errors::ErrorCode, event_map::UserEvent::*};
+use nanoid::nanoid;
 
 #[tokio::test]
 async fn sign_up_with_invalid_email() {
@@ -107,3 +109,18 @@ async fn sign_in_with_invalid_password() {
             .assert_error();
     }
 }
+
+#[tokio::test]
+async fn sign_up_with_unwritable_local_server_storage() {
+    // The storage dir can't be created under a regular file, so the local server keeps the
+    // revisions in memory.
+    let file_path = std::env::temp_dir().join(nanoid!(6));
+    std::fs::write(&file_path, b"").unwrap();
+    let mut server_config = get_client_server_configuration().unwrap();
+    server_config.set_local_server_storage_dir(file_path.join("storage").to_str().unwrap());
+
+    let sdk = FlowySDKTest::new(server_config);
+    let context = sdk.sign_up().await;
+    assert!(!context.user_profile.id.is_empty());
+    let _ = std::fs::remove_file(&file_path);
+}
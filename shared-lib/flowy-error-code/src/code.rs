@@ -110,6 +110,19 @@ pub enum ErrorCode {
 
     #[display(fmt = "Field's type option data should not be empty")]
     TypeOptionDataIsEmpty = 450,
+    #[display(fmt = "The expression of the formula is invalid")]
+    InvalidFormula = 451,
+    #[display(fmt = "The formulas reference each other in a cycle")]
+    FormulaCycleDetected = 452,
+    #[display(fmt = "The relation field is invalid")]
+    InvalidRelation = 453,
+    #[display(fmt = "The rollup field is invalid")]
+    InvalidRollup = 454,
+    #[display(fmt = "The CSV content is invalid")]
+    InvalidCSV = 455,
+
+    #[display(fmt = "Group id is empty")]
+    GroupIdIsEmpty = 460,
 
     #[display(fmt = "Invalid date time format")]
     InvalidDateTimeFormat = 500,
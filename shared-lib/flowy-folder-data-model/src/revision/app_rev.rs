@@ -32,6 +32,7 @@ impl std::convert::From<AppRevision> for TrashRevision {
             modified_time: app_rev.modified_time,
             create_time: app_rev.create_time,
             ty: TrashTypeRevision::TrashApp,
+            deleted_time: 0,
         }
     }
 }
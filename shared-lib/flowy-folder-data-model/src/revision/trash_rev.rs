@@ -13,6 +13,11 @@ pub struct TrashRevision {
     pub create_time: i64,
 
     pub ty: TrashTypeRevision,
+
+    /// The time, in seconds, the item was moved to the trash. It's 0 for the trash that was created
+    /// before the deleted time was recorded.
+    #[serde(default)]
+    pub deleted_time: i64,
 }
 
 #[derive(Eq, PartialEq, Debug, Clone, Serialize_repr)]
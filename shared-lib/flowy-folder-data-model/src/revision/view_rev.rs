@@ -45,6 +45,7 @@ impl std::convert::From<ViewRevision> for TrashRevision {
             modified_time: view_rev.modified_time,
             create_time: view_rev.create_time,
             ty: TrashTypeRevision::TrashView,
+            deleted_time: 0,
         }
     }
 }
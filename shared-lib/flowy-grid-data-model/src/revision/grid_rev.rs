@@ -249,6 +249,34 @@ impl BuildGridContext {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Give the blocks and the rows new ids, so the grids built from the same context don't share
+    /// any block or row. The field ids are kept, they are only unique within a grid.
+    pub fn regenerate_ids(&mut self) {
+        let mut block_ids = HashMap::new();
+        for block in self.blocks.iter_mut() {
+            let block_id = gen_block_id();
+            block_ids.insert(std::mem::replace(&mut block.block_id, block_id.clone()), block_id);
+        }
+
+        for block_meta_data in self.blocks_meta_data.iter_mut() {
+            let block_id = block_ids
+                .entry(block_meta_data.block_id.clone())
+                .or_insert_with(gen_block_id)
+                .clone();
+            block_meta_data.block_id = block_id.clone();
+            block_meta_data.rows = block_meta_data
+                .rows
+                .iter()
+                .map(|row_rev| {
+                    let mut row_rev = row_rev.as_ref().clone();
+                    row_rev.id = gen_row_id();
+                    row_rev.block_id = block_id.clone();
+                    Arc::new(row_rev)
+                })
+                .collect();
+        }
+    }
 }
 
 impl std::convert::From<BuildGridContext> for Bytes {
@@ -18,6 +18,10 @@ pub fn gen_grid_sort_id() -> String {
     nanoid!(6)
 }
 
+pub fn gen_grid_filter_group_id() -> String {
+    nanoid!(6)
+}
+
 /// Each layout contains multiple key/value.
 /// Key:    field_id
 /// Value:  this value also contains key/value.
@@ -38,7 +42,15 @@ pub fn gen_grid_sort_id() -> String {
 ///                   FieldType: GridFilterRevision
 ///                   FieldType: GridFilterRevision
 ///
-/// Group and sorts will be the same structure as filters.
+/// Groups are stored per layout as an ordered list, the Board layout uses the first group to
+/// bucket its rows. Sorts are stored per layout as an ordered list, the first
+/// sort has the highest priority when ordering the rows.
+///
+/// Calculations are stored per layout as a list, each field has at most one calculation that is
+/// displayed in the summary row of the layout.
+///
+/// Each layout has at most one root filter group that combines the filters of the layout with
+/// AND/OR logic. The filters that are not referenced by the filter group are combined with AND.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, Eq, PartialEq)]
 pub struct GridSettingRevision {
     pub layout: GridLayoutRevision,
@@ -46,23 +58,180 @@ pub struct GridSettingRevision {
     #[serde(with = "indexmap::serde_seq")]
     filters: IndexMap<GridLayoutRevision, IndexMap<String, GridFilterRevisionMap>>,
 
-    #[serde(skip, with = "indexmap::serde_seq")]
-    pub groups: IndexMap<GridLayoutRevision, Vec<GridGroupRevision>>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty", with = "indexmap::serde_seq")]
+    pub groups: IndexMap<GridLayoutRevision, Vec<Arc<GridGroupRevision>>>,
+
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty", with = "indexmap::serde_seq")]
+    pub sorts: IndexMap<GridLayoutRevision, Vec<Arc<GridSortRevision>>>,
 
-    #[serde(skip, with = "indexmap::serde_seq")]
-    pub sorts: IndexMap<GridLayoutRevision, Vec<GridSortRevision>>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty", with = "indexmap::serde_seq")]
+    pub filter_groups: IndexMap<GridLayoutRevision, Arc<GridFilterGroupRevision>>,
+
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty", with = "indexmap::serde_seq")]
+    pub calculations: IndexMap<GridLayoutRevision, Vec<Arc<GridCalculationRevision>>>,
 }
 
 pub type FiltersByFieldId = HashMap<String, Vec<Arc<GridFilterRevision>>>;
 pub type GroupsByFieldId = HashMap<String, Vec<Arc<GridGroupRevision>>>;
 pub type SortsByFieldId = HashMap<String, Vec<Arc<GridSortRevision>>>;
 impl GridSettingRevision {
-    pub fn get_all_group(&self) -> Option<GroupsByFieldId> {
-        None
+    /// Return the Groups of the current layout
+    pub fn get_all_group(&self, field_revs: &[Arc<FieldRevision>]) -> Option<GroupsByFieldId> {
+        let group_revs = self.groups.get(&self.layout)?;
+        let groups_by_field_id = field_revs
+            .iter()
+            .flat_map(|field_rev| {
+                let groups = group_revs
+                    .iter()
+                    .filter(|group_rev| group_rev.field_id.as_ref() == Some(&field_rev.id))
+                    .cloned()
+                    .collect::<Vec<Arc<GridGroupRevision>>>();
+                if groups.is_empty() {
+                    None
+                } else {
+                    Some((field_rev.id.clone(), groups))
+                }
+            })
+            .collect::<GroupsByFieldId>();
+        Some(groups_by_field_id)
+    }
+
+    pub fn get_groups(&self, layout: &GridLayoutRevision) -> Option<Vec<Arc<GridGroupRevision>>> {
+        self.groups.get(layout).cloned()
+    }
+
+    /// Insert the group into the layout. If there is already a group of the same field, it will be replaced.
+    pub fn insert_group(&mut self, layout: &GridLayoutRevision, group_rev: GridGroupRevision) {
+        let group_revs = self.groups.entry(layout.clone()).or_insert_with(Vec::new);
+        match group_revs.iter().position(|rev| rev.field_id == group_rev.field_id) {
+            None => group_revs.push(Arc::new(group_rev)),
+            Some(index) => group_revs[index] = Arc::new(group_rev),
+        }
+    }
+
+    /// Return true if the group was removed.
+    pub fn delete_group(&mut self, layout: &GridLayoutRevision, group_id: &str) -> bool {
+        match self.groups.get_mut(layout) {
+            None => false,
+            Some(group_revs) => {
+                let len = group_revs.len();
+                group_revs.retain(|group_rev| group_rev.id != group_id);
+                group_revs.len() != len
+            }
+        }
+    }
+
+    /// Return the Sorts of the current layout
+    pub fn get_all_sort(&self, field_revs: &[Arc<FieldRevision>]) -> Option<SortsByFieldId> {
+        let sort_revs = self.sorts.get(&self.layout)?;
+        let sorts_by_field_id = field_revs
+            .iter()
+            .flat_map(|field_rev| {
+                let sorts = sort_revs
+                    .iter()
+                    .filter(|sort_rev| sort_rev.field_id == field_rev.id)
+                    .cloned()
+                    .collect::<Vec<Arc<GridSortRevision>>>();
+                if sorts.is_empty() {
+                    None
+                } else {
+                    Some((field_rev.id.clone(), sorts))
+                }
+            })
+            .collect::<SortsByFieldId>();
+        Some(sorts_by_field_id)
     }
 
-    pub fn get_all_sort(&self) -> Option<SortsByFieldId> {
-        None
+    /// Return the Sorts of the layout ordered by priority.
+    pub fn get_sorts(&self, layout: &GridLayoutRevision) -> Option<Vec<Arc<GridSortRevision>>> {
+        self.sorts.get(layout).cloned()
+    }
+
+    /// Insert the sort into the layout. If there is already a sort of the same field, it will be replaced
+    /// without changing its priority.
+    pub fn insert_sort(&mut self, layout: &GridLayoutRevision, sort_rev: GridSortRevision) {
+        let sort_revs = self.sorts.entry(layout.clone()).or_insert_with(Vec::new);
+        match sort_revs.iter().position(|rev| rev.field_id == sort_rev.field_id) {
+            None => sort_revs.push(Arc::new(sort_rev)),
+            Some(index) => sort_revs[index] = Arc::new(sort_rev),
+        }
+    }
+
+    /// Return true if the sort was removed.
+    pub fn delete_sort(&mut self, layout: &GridLayoutRevision, sort_id: &str) -> bool {
+        match self.sorts.get_mut(layout) {
+            None => false,
+            Some(sort_revs) => {
+                let len = sort_revs.len();
+                sort_revs.retain(|sort_rev| sort_rev.id != sort_id);
+                sort_revs.len() != len
+            }
+        }
+    }
+
+    pub fn get_calculations(&self, layout: &GridLayoutRevision) -> Option<Vec<Arc<GridCalculationRevision>>> {
+        self.calculations.get(layout).cloned()
+    }
+
+    /// Insert the calculation into the layout. If there is already a calculation of the same field, it
+    /// will be replaced.
+    pub fn insert_calculation(&mut self, layout: &GridLayoutRevision, calculation_rev: GridCalculationRevision) {
+        let calculation_revs = self.calculations.entry(layout.clone()).or_insert_with(Vec::new);
+        match calculation_revs
+            .iter()
+            .position(|rev| rev.field_id == calculation_rev.field_id)
+        {
+            None => calculation_revs.push(Arc::new(calculation_rev)),
+            Some(index) => calculation_revs[index] = Arc::new(calculation_rev),
+        }
+    }
+
+    /// Return true if the calculation of the field was removed.
+    pub fn delete_calculation(&mut self, layout: &GridLayoutRevision, field_id: &str) -> bool {
+        match self.calculations.get_mut(layout) {
+            None => false,
+            Some(calculation_revs) => {
+                let len = calculation_revs.len();
+                calculation_revs.retain(|calculation_rev| calculation_rev.field_id != field_id);
+                calculation_revs.len() != len
+            }
+        }
+    }
+
+    /// Return the root filter group of the layout
+    pub fn get_filter_group(&self, layout: &GridLayoutRevision) -> Option<Arc<GridFilterGroupRevision>> {
+        self.filter_groups.get(layout).cloned()
+    }
+
+    /// Insert the root filter group of the layout. The existing one will be replaced.
+    pub fn insert_filter_group(&mut self, layout: &GridLayoutRevision, filter_group_rev: GridFilterGroupRevision) {
+        self.filter_groups.insert(layout.clone(), Arc::new(filter_group_rev));
+    }
+
+    /// Return true if the filter group was removed.
+    pub fn delete_filter_group(&mut self, layout: &GridLayoutRevision, filter_group_id: &str) -> bool {
+        match self.filter_groups.get(layout) {
+            Some(filter_group_rev) if filter_group_rev.id == filter_group_id => {
+                self.filter_groups.shift_remove(layout);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove the filter from the filter group of the layout. Return true if the filter group was changed.
+    pub fn remove_filter_from_group(&mut self, layout: &GridLayoutRevision, filter_id: &str) -> bool {
+        match self.filter_groups.get_mut(layout) {
+            None => false,
+            Some(filter_group_rev) => {
+                if filter_group_rev.contains_filter(filter_id) {
+                    Arc::make_mut(filter_group_rev).remove_filter(filter_id);
+                    true
+                } else {
+                    false
+                }
+            }
+        }
     }
 
     /// Return the Filters of the current layout
@@ -190,6 +359,45 @@ pub struct GridFilterRevision {
     pub content: Option<String>,
 }
 
+/// The filter group combines its filters and its sub-groups with the same logic. The filters are
+/// referenced by the ids of the GridFilterRevisions of the same layout.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct GridFilterGroupRevision {
+    pub id: String,
+    pub logic: GridFilterLogicRevision,
+
+    #[serde(default)]
+    pub filter_ids: Vec<String>,
+
+    #[serde(default)]
+    pub groups: Vec<GridFilterGroupRevision>,
+}
+
+impl GridFilterGroupRevision {
+    pub fn contains_filter(&self, filter_id: &str) -> bool {
+        self.filter_ids.iter().any(|id| id == filter_id)
+            || self.groups.iter().any(|group| group.contains_filter(filter_id))
+    }
+
+    pub fn remove_filter(&mut self, filter_id: &str) {
+        self.filter_ids.retain(|id| id != filter_id);
+        self.groups.iter_mut().for_each(|group| group.remove_filter(filter_id));
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum GridFilterLogicRevision {
+    And = 0,
+    Or = 1,
+}
+
+impl std::default::Default for GridFilterLogicRevision {
+    fn default() -> Self {
+        GridFilterLogicRevision::And
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct GridGroupRevision {
     pub id: String,
@@ -200,5 +408,45 @@ pub struct GridGroupRevision {
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct GridSortRevision {
     pub id: String,
-    pub field_id: Option<String>,
+    pub field_id: String,
+    pub field_type_rev: FieldTypeRevision,
+    pub condition: GridSortConditionRevision,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum GridSortConditionRevision {
+    Ascending = 0,
+    Descending = 1,
+}
+
+impl std::default::Default for GridSortConditionRevision {
+    fn default() -> Self {
+        GridSortConditionRevision::Ascending
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct GridCalculationRevision {
+    pub field_id: String,
+    pub calculation_type: GridCalculationTypeRevision,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum GridCalculationTypeRevision {
+    Sum = 0,
+    Average = 1,
+    Min = 2,
+    Max = 3,
+    Count = 4,
+    CountEmpty = 5,
+    CountUnique = 6,
+    PercentChecked = 7,
+}
+
+impl std::default::Default for GridCalculationTypeRevision {
+    fn default() -> Self {
+        GridCalculationTypeRevision::Count
+    }
 }
@@ -1,4 +1,5 @@
 use flowy_grid_data_model::revision::*;
+use std::sync::Arc;
 
 #[test]
 fn grid_default_serde_test() {
@@ -11,3 +12,27 @@ fn grid_default_serde_test() {
         r#"{"grid_id":"1","fields":[],"blocks":[],"setting":{"layout":0,"filters":[]}}"#
     )
 }
+
+#[test]
+fn grid_build_context_regenerate_ids_test() {
+    let block = GridBlockMetaRevision::new();
+    let mut row = RowRevision::new(&block.block_id);
+    row.cells
+        .insert("field".to_owned(), CellRevision::new("hello".to_owned()));
+    let mut build_context = BuildGridContext::new();
+    build_context.blocks_meta_data.push(GridBlockRevision {
+        block_id: block.block_id.clone(),
+        rows: vec![Arc::new(row.clone())],
+    });
+    build_context.blocks.push(block.clone());
+
+    build_context.regenerate_ids();
+    let new_block_id = build_context.blocks[0].block_id.clone();
+    assert_ne!(new_block_id, block.block_id);
+    assert_eq!(build_context.blocks_meta_data[0].block_id, new_block_id);
+
+    let new_row = build_context.blocks_meta_data[0].rows[0].clone();
+    assert_ne!(new_row.id, row.id);
+    assert_eq!(new_row.block_id, new_block_id);
+    assert_eq!(new_row.cells, row.cells);
+}
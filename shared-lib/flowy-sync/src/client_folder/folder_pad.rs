@@ -181,15 +181,16 @@ impl FolderPad {
         })
     }
 
+    /// Create the view under the app or the view that the `belong_to_id` points to.
     #[tracing::instrument(level = "trace", skip(self), fields(view_name=%view_rev.name), err)]
     pub fn create_view(&mut self, view_rev: ViewRevision) -> CollaborateResult<Option<FolderChange>> {
-        let app_id = view_rev.belong_to_id.clone();
-        self.with_app(&app_id, move |app| {
-            if app.belongings.contains(&view_rev) {
+        let belong_to_id = view_rev.belong_to_id.clone();
+        self.with_belongings(&belong_to_id, move |belongings| {
+            if belongings.contains(&view_rev) {
                 tracing::warn!("[RootFolder]: Duplicate view");
                 return Ok(None);
             }
-            app.belongings.push(view_rev);
+            belongings.push(view_rev);
             Ok(Some(()))
         })
     }
@@ -197,7 +198,7 @@ impl FolderPad {
     pub fn read_view(&self, view_id: &str) -> CollaborateResult<ViewRevision> {
         for workspace in &self.workspaces {
             for app in &(*workspace.apps) {
-                if let Some(view) = app.belongings.iter().find(|b| b.id == view_id) {
+                if let Some(view) = find_view(&app.belongings, view_id) {
                     return Ok(view.clone());
                 }
             }
@@ -205,12 +206,17 @@ impl FolderPad {
         Err(CollaborateError::record_not_found().context(format!("Can't find view with id {}", view_id)))
     }
 
+    /// Return the views that belong to the app or the view.
     pub fn read_views(&self, belong_to_id: &str) -> CollaborateResult<Vec<ViewRevision>> {
         for workspace in &self.workspaces {
             for app in &(*workspace.apps) {
                 if app.id == belong_to_id {
                     return Ok(app.belongings.to_vec());
                 }
+
+                if let Some(view) = find_view(&app.belongings, belong_to_id) {
+                    return Ok(view.belongings.to_vec());
+                }
             }
         }
         Ok(vec![])
@@ -223,8 +229,7 @@ impl FolderPad {
         desc: Option<String>,
         modified_time: i64,
     ) -> CollaborateResult<Option<FolderChange>> {
-        let view = self.read_view(view_id)?;
-        self.with_view(&view.belong_to_id, view_id, |view| {
+        self.with_view(view_id, |view| {
             if let Some(name) = name {
                 view.name = name;
             }
@@ -238,11 +243,12 @@ impl FolderPad {
         })
     }
 
+    /// Delete the view with all its child views.
     #[tracing::instrument(level = "trace", skip(self), err)]
     pub fn delete_view(&mut self, view_id: &str) -> CollaborateResult<Option<FolderChange>> {
         let view = self.read_view(view_id)?;
-        self.with_app(&view.belong_to_id, |app| {
-            app.belongings.retain(|view| view.id != view_id);
+        self.with_belongings(&view.belong_to_id, |belongings| {
+            belongings.retain(|view| view.id != view_id);
             Ok(Some(()))
         })
     }
@@ -250,14 +256,69 @@ impl FolderPad {
     #[tracing::instrument(level = "trace", skip(self), err)]
     pub fn move_view(&mut self, view_id: &str, from: usize, to: usize) -> CollaborateResult<Option<FolderChange>> {
         let view = self.read_view(view_id)?;
-        self.with_app(&view.belong_to_id, |app| {
-            match move_vec_element(&mut app.belongings, |view| view.id == view_id, from, to).map_err(internal_error)? {
+        self.with_belongings(&view.belong_to_id, |belongings| {
+            match move_vec_element(belongings, |view| view.id == view_id, from, to).map_err(internal_error)? {
                 true => Ok(Some(())),
                 false => Ok(None),
             }
         })
     }
 
+    /// Move the view with all its child views to the `index` of the belongings of another app or
+    /// view. The view can't be moved under itself or any of its child views.
+    #[tracing::instrument(level = "trace", skip(self), err)]
+    pub fn move_view_to(
+        &mut self,
+        view_id: &str,
+        belong_to_id: &str,
+        index: usize,
+    ) -> CollaborateResult<Option<FolderChange>> {
+        let view = self.read_view(view_id)?;
+        if view.id == belong_to_id || find_view(&view.belongings, belong_to_id).is_some() {
+            return Err(CollaborateError::internal().context(format!(
+                "Can't move the view {} under itself or its child views",
+                view_id
+            )));
+        }
+
+        let to_workspace_id = match self.find_workspace_id(belong_to_id) {
+            None => {
+                return Err(CollaborateError::record_not_found()
+                    .context(format!("Can't find app or view with id {}", belong_to_id)))
+            }
+            Some(workspace_id) => workspace_id,
+        };
+        // It's ok to unwrap because the view was read successfully.
+        let from_workspace_id = self.find_workspace_id(view_id).unwrap();
+
+        self.modify_workspaces(|workspaces| {
+            let mut view = None;
+            if let Some(workspace) = workspaces
+                .iter_mut()
+                .find(|workspace| workspace.id == from_workspace_id)
+            {
+                view = Arc::make_mut(workspace)
+                    .apps
+                    .iter_mut()
+                    .find_map(|app| remove_view(&mut app.belongings, view_id));
+            }
+
+            let mut view = match view {
+                None => return Ok(None),
+                Some(view) => view,
+            };
+            view.belong_to_id = belong_to_id.to_owned();
+            if let Some(workspace) = workspaces.iter_mut().find(|workspace| workspace.id == to_workspace_id) {
+                if let Some(belongings) = belongings_mut(&mut Arc::make_mut(workspace).apps, belong_to_id) {
+                    let index = index.min(belongings.len());
+                    belongings.insert(index, view);
+                    return Ok(Some(()));
+                }
+            }
+            Ok(None)
+        })
+    }
+
     pub fn create_trash(&mut self, trash: Vec<TrashRevision>) -> CollaborateResult<Option<FolderChange>> {
         self.with_trash(|t| {
             let mut new_trash = trash.into_iter().map(Arc::new).collect::<Vec<Arc<TrashRevision>>>();
@@ -383,12 +444,24 @@ impl FolderPad {
         })
     }
 
-    fn with_view<F>(&mut self, belong_to_id: &str, view_id: &str, f: F) -> CollaborateResult<Option<FolderChange>>
+    fn with_view<F>(&mut self, view_id: &str, f: F) -> CollaborateResult<Option<FolderChange>>
     where
         F: FnOnce(&mut ViewRevision) -> CollaborateResult<Option<()>>,
     {
-        self.with_app(belong_to_id, |app| {
-            match app.belongings.iter_mut().find(|view| view_id == view.id) {
+        let workspace_id = match self.find_workspace_id(view_id) {
+            None => {
+                tracing::warn!("[FolderPad]: Can't find any view with id: {}", view_id);
+                return Ok(None);
+            }
+            Some(workspace_id) => workspace_id,
+        };
+
+        self.with_workspace(&workspace_id, |workspace| {
+            match workspace
+                .apps
+                .iter_mut()
+                .find_map(|app| find_view_mut(&mut app.belongings, view_id))
+            {
                 None => {
                     tracing::warn!("[FolderPad]: Can't find any view with id: {}", view_id);
                     Ok(None)
@@ -397,6 +470,87 @@ impl FolderPad {
             }
         })
     }
+
+    /// Modify the belongings of the app or the view that the `belong_to_id` points to.
+    fn with_belongings<F>(&mut self, belong_to_id: &str, f: F) -> CollaborateResult<Option<FolderChange>>
+    where
+        F: FnOnce(&mut Vec<ViewRevision>) -> CollaborateResult<Option<()>>,
+    {
+        let workspace_id = match self.find_workspace_id(belong_to_id) {
+            None => {
+                tracing::warn!("[FolderPad]: Can't find any app or view with id: {}", belong_to_id);
+                return Ok(None);
+            }
+            Some(workspace_id) => workspace_id,
+        };
+
+        self.with_workspace(&workspace_id, |workspace| {
+            // It's ok to unwrap because we get the workspace from the belong_to_id.
+            f(belongings_mut(&mut workspace.apps, belong_to_id).unwrap())
+        })
+    }
+
+    /// Return the id of the workspace that contains the app or the view.
+    fn find_workspace_id(&self, id: &str) -> Option<String> {
+        self.workspaces
+            .iter()
+            .find(|workspace| {
+                workspace
+                    .apps
+                    .iter()
+                    .any(|app| app.id == id || find_view(&app.belongings, id).is_some())
+            })
+            .map(|workspace| workspace.id.clone())
+    }
+}
+
+fn find_view<'a>(views: &'a [ViewRevision], view_id: &str) -> Option<&'a ViewRevision> {
+    for view in views {
+        if view.id == view_id {
+            return Some(view);
+        }
+
+        if let Some(view) = find_view(&view.belongings, view_id) {
+            return Some(view);
+        }
+    }
+    None
+}
+
+fn find_view_mut<'a>(views: &'a mut [ViewRevision], view_id: &str) -> Option<&'a mut ViewRevision> {
+    for view in views.iter_mut() {
+        if view.id == view_id {
+            return Some(view);
+        }
+
+        if let Some(view) = find_view_mut(&mut view.belongings, view_id) {
+            return Some(view);
+        }
+    }
+    None
+}
+
+fn remove_view(views: &mut Vec<ViewRevision>, view_id: &str) -> Option<ViewRevision> {
+    if let Some(index) = views.iter().position(|view| view.id == view_id) {
+        return Some(views.remove(index));
+    }
+    views
+        .iter_mut()
+        .find_map(|view| remove_view(&mut view.belongings, view_id))
+}
+
+/// Return the belongings of the app or the view.
+fn belongings_mut<'a>(apps: &'a mut [AppRevision], belong_to_id: &str) -> Option<&'a mut Vec<ViewRevision>> {
+    for app in apps.iter_mut() {
+        if app.id == belong_to_id {
+            return Some(&mut app.belongings);
+        }
+
+        if let Some(view) = find_view_mut(&mut app.belongings, belong_to_id) {
+            return Some(&mut view.belongings);
+        }
+    }
+    None
 }
 
 pub fn default_folder_delta() -> FolderDelta {
@@ -709,6 +863,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn folder_add_nested_view() {
+        let (mut folder, initial_delta, view) = test_nested_view_folder();
+        let child_view = test_child_view(&view.id, "child");
+        let delta = folder.create_view(child_view.clone()).unwrap().unwrap().delta;
+
+        let new_folder = make_folder_from_delta(initial_delta, vec![delta]);
+        assert_eq!(folder, new_folder);
+        assert_eq!(folder.read_view(&child_view.id).unwrap(), child_view);
+        assert_eq!(folder.read_views(&view.id).unwrap(), vec![child_view.clone()]);
+        assert_eq!(folder.read_view(&view.id).unwrap().belongings, vec![child_view]);
+    }
+
+    #[test]
+    fn folder_update_nested_view() {
+        let (mut folder, _, view) = test_nested_view_folder();
+        let child_view = test_child_view(&view.id, "child");
+        folder.create_view(child_view.clone()).unwrap();
+        folder
+            .update_view(&child_view.id, Some("😦 rename child".to_owned()), None, 123)
+            .unwrap()
+            .unwrap();
+
+        let child_view = folder.read_view(&child_view.id).unwrap();
+        assert_eq!(child_view.name, "😦 rename child");
+        assert_eq!(child_view.modified_time, 123);
+    }
+
+    #[test]
+    fn folder_delete_view_with_children() {
+        let (mut folder, _, view) = test_nested_view_folder();
+        let child_view = test_child_view(&view.id, "child");
+        let grandchild_view = test_child_view(&child_view.id, "grandchild");
+        folder.create_view(child_view.clone()).unwrap();
+        folder.create_view(grandchild_view.clone()).unwrap();
+
+        folder.delete_view(&child_view.id).unwrap().unwrap();
+        assert!(folder.read_view(&child_view.id).is_err());
+        assert!(folder.read_view(&grandchild_view.id).is_err());
+        assert!(folder.read_views(&view.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn folder_move_view_to_another_view() {
+        let (mut folder, initial_delta, view) = test_nested_view_folder();
+        let view_a = test_child_view(&view.id, "a");
+        let view_b = test_child_view(&view.id, "b");
+        let child_view = test_child_view(&view_a.id, "child");
+        let mut deltas = vec![];
+        for view_rev in vec![view_a.clone(), view_b.clone(), child_view.clone()] {
+            deltas.push(folder.create_view(view_rev).unwrap().unwrap().delta);
+        }
+
+        // Move the view a with its child view under the view b.
+        deltas.push(folder.move_view_to(&view_a.id, &view_b.id, 0).unwrap().unwrap().delta);
+        let new_folder = make_folder_from_delta(initial_delta, deltas);
+        assert_eq!(folder, new_folder);
+
+        let moved_view = folder.read_view(&view_a.id).unwrap();
+        assert_eq!(moved_view.belong_to_id, view_b.id);
+        assert_eq!(moved_view.belongings, vec![child_view.clone()]);
+        assert_eq!(
+            folder.read_views(&view.id).unwrap(),
+            vec![folder.read_view(&view_b.id).unwrap()]
+        );
+
+        // Can't move the view under its child view.
+        assert!(folder.move_view_to(&view_b.id, &child_view.id, 0).is_err());
+        assert!(folder.move_view_to(&view_b.id, &view_b.id, 0).is_err());
+    }
+
     #[test]
     fn folder_add_trash() {
         let (folder, initial_delta, _trash) = test_trash();
@@ -788,6 +1013,25 @@ mod tests {
         (folder, initial_delta, view_rev)
     }
 
+    /// The id of the view in `test_view_folder` is empty, which is the same as the id of the app.
+    fn test_nested_view_folder() -> (FolderPad, FolderDelta, ViewRevision) {
+        let (mut folder, mut initial_delta, app) = test_app_folder();
+        let view_rev = test_child_view(&app.id, "parent");
+        initial_delta = initial_delta
+            .compose(&folder.create_view(view_rev.clone()).unwrap().unwrap().delta)
+            .unwrap();
+
+        (folder, initial_delta, view_rev)
+    }
+
+    fn test_child_view(belong_to_id: &str, id: &str) -> ViewRevision {
+        let mut view_rev = ViewRevision::default();
+        view_rev.id = id.to_owned();
+        view_rev.belong_to_id = belong_to_id.to_owned();
+        view_rev.name = format!("🎃 {} view", id);
+        view_rev
+    }
+
     fn test_trash() -> (FolderPad, FolderDelta, TrashRevision) {
         let mut folder = FolderPad::default();
         let folder_json = serde_json::to_string(&folder).unwrap();
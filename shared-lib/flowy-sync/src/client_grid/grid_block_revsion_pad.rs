@@ -1,3 +1,4 @@
+use crate::client_grid::{GridHistory, GridHistoryItem};
 use crate::entities::revision::{md5, RepeatedRevision, Revision};
 use crate::errors::{CollaborateError, CollaborateResult};
 use crate::util::{cal_diff, make_delta_from_revisions};
@@ -16,6 +17,30 @@ pub type GridBlockRevisionDeltaBuilder = PlainTextDeltaBuilder;
 pub struct GridBlockRevisionPad {
     block_revision: GridBlockRevision,
     pub(crate) delta: GridBlockRevisionDelta,
+    history: GridHistory<RowHistoryOperation>,
+}
+
+/// The operation that reverts the change of the rows.
+#[derive(Debug, Clone)]
+pub enum RowHistoryOperation {
+    /// Insert the deleted rows at their original positions, which are in ascending order.
+    InsertRows {
+        row_revs: Vec<(usize, Arc<RowRevision>)>,
+    },
+    DeleteRows {
+        row_ids: Vec<String>,
+    },
+    MoveRow {
+        row_id: String,
+        index: usize,
+    },
+    /// Restore the properties of the row. The cell is removed if its value is None.
+    UpdateRow {
+        row_id: String,
+        height: Option<i32>,
+        visibility: Option<bool>,
+        cells: HashMap<String, Option<CellRevision>>,
+    },
 }
 
 impl std::ops::Deref for GridBlockRevisionPad {
@@ -52,7 +77,11 @@ impl GridBlockRevisionPad {
             tracing::error!("{}", s);
             CollaborateError::internal().context(msg)
         })?;
-        Ok(Self { block_revision, delta })
+        Ok(Self {
+            block_revision,
+            delta,
+            history: GridHistory::new(),
+        })
     }
 
     pub fn from_revisions(_grid_id: &str, revisions: Vec<Revision>) -> CollaborateResult<Self> {
@@ -61,12 +90,31 @@ impl GridBlockRevisionPad {
     }
 
     #[tracing::instrument(level = "trace", skip(self, row), err)]
+    pub fn delta(&self) -> &GridBlockRevisionDelta {
+        &self.delta
+    }
+
+    pub fn reset_block(&mut self, delta: GridBlockRevisionDelta) -> CollaborateResult<String> {
+        let block = GridBlockRevisionPad::from_delta(delta)?;
+        self.block_revision = block.block_revision;
+        self.delta = block.delta;
+        Ok(self.md5())
+    }
+
+    pub fn compose_remote_delta(&mut self, delta: GridBlockRevisionDelta) -> CollaborateResult<String> {
+        let composed_delta = self.delta.compose(&delta)?;
+        self.reset_block(composed_delta)
+    }
+
     pub fn add_row_rev(
         &mut self,
         row: RowRevision,
         start_row_id: Option<String>,
     ) -> CollaborateResult<Option<GridBlockMetaChange>> {
-        self.modify(|rows| {
+        let operation = RowHistoryOperation::DeleteRows {
+            row_ids: vec![row.id.clone()],
+        };
+        let change = self.modify(|rows| {
             if let Some(start_row_id) = start_row_id {
                 if !start_row_id.is_empty() {
                     if let Some(index) = rows.iter().position(|row| row.id == start_row_id) {
@@ -78,14 +126,28 @@ impl GridBlockRevisionPad {
 
             rows.push(Arc::new(row));
             Ok(Some(()))
-        })
+        })?;
+        self.record_history(&change, operation);
+        Ok(change)
     }
 
     pub fn delete_rows(&mut self, row_ids: Vec<Cow<'_, String>>) -> CollaborateResult<Option<GridBlockMetaChange>> {
-        self.modify(|rows| {
+        let operation = RowHistoryOperation::InsertRows {
+            row_revs: self
+                .block_revision
+                .rows
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| row_ids.contains(&Cow::Borrowed(&row.id)))
+                .map(|(index, row)| (index, row.clone()))
+                .collect(),
+        };
+        let change = self.modify(|rows| {
             rows.retain(|row| !row_ids.contains(&Cow::Borrowed(&row.id)));
             Ok(Some(()))
-        })
+        })?;
+        self.record_history(&change, operation);
+        Ok(change)
     }
 
     pub fn get_row_revs<T>(&self, row_ids: Option<Vec<Cow<'_, T>>>) -> CollaborateResult<Vec<Arc<RowRevision>>>
@@ -148,6 +210,32 @@ impl GridBlockRevisionPad {
     }
 
     pub fn update_row(&mut self, changeset: RowMetaChangeset) -> CollaborateResult<Option<GridBlockMetaChange>> {
+        let operation = self.index_of_row(&changeset.row_id).map(|index| {
+            let row_rev = &self.block_revision.rows[index as usize];
+            RowHistoryOperation::UpdateRow {
+                row_id: row_rev.id.clone(),
+                height: changeset.height.map(|_| row_rev.height),
+                visibility: changeset.visibility.map(|_| row_rev.visibility),
+                cells: changeset
+                    .cell_by_field_id
+                    .keys()
+                    .map(|field_id| (field_id.clone(), row_rev.cells.get(field_id).cloned()))
+                    .collect(),
+            }
+        });
+        let change = self.update_row_without_history(changeset)?;
+        if let Some(operation) = operation {
+            self.record_history(&change, operation);
+        }
+        Ok(change)
+    }
+
+    /// Update the row without recording the change in the history. It's used for the cells that
+    /// are computed from the other cells, which are recomputed after undoing or redoing.
+    pub fn update_row_without_history(
+        &mut self,
+        changeset: RowMetaChangeset,
+    ) -> CollaborateResult<Option<GridBlockMetaChange>> {
         let row_id = changeset.row_id.clone();
         self.modify_row(&row_id, |row| {
             let mut is_changed = None;
@@ -173,7 +261,11 @@ impl GridBlockRevisionPad {
     }
 
     pub fn move_row(&mut self, row_id: &str, from: usize, to: usize) -> CollaborateResult<Option<GridBlockMetaChange>> {
-        self.modify(|row_revs| {
+        let operation = RowHistoryOperation::MoveRow {
+            row_id: row_id.to_owned(),
+            index: from,
+        };
+        let change = self.modify(|row_revs| {
             if let Some(position) = row_revs.iter().position(|row_rev| row_rev.id == row_id) {
                 debug_assert_eq!(from, position);
                 let row_rev = row_revs.remove(position);
@@ -182,18 +274,170 @@ impl GridBlockRevisionPad {
             } else {
                 Ok(None)
             }
-        })
+        })?;
+        self.record_history(&change, operation);
+        Ok(change)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    pub fn history(&self) -> &GridHistory<RowHistoryOperation> {
+        &self.history
+    }
+
+    /// Revert the last change of the rows. The change is dropped if it can't be reverted anymore,
+    /// e.g. the row was deleted by the remote revision, and None is returned.
+    pub fn undo(&mut self) -> CollaborateResult<Option<GridBlockMetaChange>> {
+        match self.history.undo() {
+            None => Ok(None),
+            Some(item) => match self.apply_history_operation(item.operation)? {
+                None => Ok(None),
+                Some((change, operation)) => {
+                    self.history.add_redo(GridHistoryItem {
+                        seq: item.seq,
+                        operation,
+                    });
+                    Ok(Some(change))
+                }
+            },
+        }
+    }
+
+    /// Reapply the last reverted change of the rows. The change is dropped if it can't be
+    /// reapplied anymore, and None is returned.
+    pub fn redo(&mut self) -> CollaborateResult<Option<GridBlockMetaChange>> {
+        match self.history.redo() {
+            None => Ok(None),
+            Some(item) => match self.apply_history_operation(item.operation)? {
+                None => Ok(None),
+                Some((change, operation)) => {
+                    self.history.add_undo(GridHistoryItem {
+                        seq: item.seq,
+                        operation,
+                    });
+                    Ok(Some(change))
+                }
+            },
+        }
+    }
+
+    fn record_history(&mut self, change: &Option<GridBlockMetaChange>, operation: RowHistoryOperation) {
+        if change.is_some() {
+            self.history.record(operation);
+        }
+    }
+
+    /// Apply the operation without recording it. Return the change and the operation that reverts
+    /// it, or None if the operation can't be applied to the current rows.
+    fn apply_history_operation(
+        &mut self,
+        operation: RowHistoryOperation,
+    ) -> CollaborateResult<Option<(GridBlockMetaChange, RowHistoryOperation)>> {
+        let (change, operation) = match operation {
+            RowHistoryOperation::InsertRows { row_revs } => {
+                let row_revs = row_revs
+                    .into_iter()
+                    .filter(|(_, row_rev)| self.index_of_row(&row_rev.id).is_none())
+                    .collect::<Vec<(usize, Arc<RowRevision>)>>();
+                let row_ids = row_revs.iter().map(|(_, row_rev)| row_rev.id.clone()).collect();
+                let change = self.modify(|rows| {
+                    for (index, row_rev) in row_revs {
+                        let index = index.min(rows.len());
+                        rows.insert(index, row_rev);
+                    }
+                    Ok(Some(()))
+                })?;
+                (change, RowHistoryOperation::DeleteRows { row_ids })
+            }
+            RowHistoryOperation::DeleteRows { row_ids } => {
+                let row_revs = self
+                    .block_revision
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, row_rev)| row_ids.contains(&row_rev.id))
+                    .map(|(index, row_rev)| (index, row_rev.clone()))
+                    .collect::<Vec<(usize, Arc<RowRevision>)>>();
+                let change = self.modify(|rows| {
+                    rows.retain(|row_rev| !row_ids.contains(&row_rev.id));
+                    Ok(Some(()))
+                })?;
+                (change, RowHistoryOperation::InsertRows { row_revs })
+            }
+            RowHistoryOperation::MoveRow { row_id, index } => {
+                let from_index = match self.index_of_row(&row_id) {
+                    None => return Ok(None),
+                    Some(from_index) => from_index as usize,
+                };
+                let change = self.modify(|rows| {
+                    let row_rev = rows.remove(from_index);
+                    let index = index.min(rows.len());
+                    rows.insert(index, row_rev);
+                    Ok(Some(()))
+                })?;
+                let operation = RowHistoryOperation::MoveRow {
+                    row_id,
+                    index: from_index,
+                };
+                (change, operation)
+            }
+            RowHistoryOperation::UpdateRow {
+                row_id,
+                height,
+                visibility,
+                cells,
+            } => {
+                let mut operation = None;
+                let change = self.modify_row(&row_id, |row_rev| {
+                    let old_cells = cells
+                        .keys()
+                        .map(|field_id| (field_id.clone(), row_rev.cells.get(field_id).cloned()))
+                        .collect();
+                    operation = Some(RowHistoryOperation::UpdateRow {
+                        row_id: row_rev.id.clone(),
+                        height: height.map(|_| row_rev.height),
+                        visibility: visibility.map(|_| row_rev.visibility),
+                        cells: old_cells,
+                    });
+
+                    if let Some(height) = height {
+                        row_rev.height = height;
+                    }
+                    if let Some(visibility) = visibility {
+                        row_rev.visibility = visibility;
+                    }
+                    for (field_id, cell_rev) in cells {
+                        match cell_rev {
+                            None => row_rev.cells.shift_remove(&field_id),
+                            Some(cell_rev) => row_rev.cells.insert(field_id, cell_rev),
+                        };
+                    }
+                    Ok(Some(()))
+                })?;
+                match operation {
+                    None => return Ok(None),
+                    Some(operation) => (change, operation),
+                }
+            }
+        };
+        Ok(change.map(|change| (change, operation)))
     }
 
     pub fn modify<F>(&mut self, f: F) -> CollaborateResult<Option<GridBlockMetaChange>>
     where
         F: for<'a> FnOnce(&'a mut Vec<Arc<RowRevision>>) -> CollaborateResult<Option<()>>,
     {
-        let cloned_self = self.clone();
+        let cloned_block_revision = self.block_revision.clone();
         match f(&mut self.block_revision.rows)? {
             None => Ok(None),
             Some(_) => {
-                let old = cloned_self.to_json()?;
+                let old = make_block_rev_json_str(&cloned_block_revision)?;
                 let new = self.to_json()?;
                 match cal_diff::<PhantomAttributes>(old, new) {
                     None => Ok(None),
@@ -226,8 +470,7 @@ impl GridBlockRevisionPad {
     }
 
     pub fn to_json(&self) -> CollaborateResult<String> {
-        serde_json::to_string(&self.block_revision)
-            .map_err(|e| CollaborateError::internal().context(format!("serial trash to json failed: {}", e)))
+        make_block_rev_json_str(&self.block_revision)
     }
 
     pub fn md5(&self) -> String {
@@ -239,6 +482,11 @@ impl GridBlockRevisionPad {
     }
 }
 
+fn make_block_rev_json_str(block_rev: &GridBlockRevision) -> CollaborateResult<String> {
+    serde_json::to_string(block_rev)
+        .map_err(|e| CollaborateError::internal().context(format!("serial trash to json failed: {}", e)))
+}
+
 pub struct GridBlockMetaChange {
     pub delta: GridBlockRevisionDelta,
     /// md5: the md5 of the grid after applying the change.
@@ -265,15 +513,20 @@ impl std::default::Default for GridBlockRevisionPad {
         };
 
         let delta = make_grid_block_delta(&block_revision);
-        GridBlockRevisionPad { block_revision, delta }
+        GridBlockRevisionPad {
+            block_revision,
+            delta,
+            history: GridHistory::new(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::client_grid::{GridBlockRevisionDelta, GridBlockRevisionPad};
-    use flowy_grid_data_model::revision::{RowMetaChangeset, RowRevision};
+    use flowy_grid_data_model::revision::{CellRevision, RowMetaChangeset, RowRevision};
     use std::borrow::Cow;
+    use std::collections::HashMap;
 
     #[test]
     fn block_meta_add_row() {
@@ -420,6 +673,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn block_meta_undo_redo_delete_rows() {
+        let mut pad = test_pad();
+        let row_1 = test_row_rev("1", &pad);
+        let row_2 = test_row_rev("2", &pad);
+        let row_3 = test_row_rev("3", &pad);
+        let _ = pad.add_row_rev(row_1.clone(), None).unwrap().unwrap();
+        let _ = pad.add_row_rev(row_2.clone(), None).unwrap().unwrap();
+        let _ = pad.add_row_rev(row_3.clone(), None).unwrap().unwrap();
+        let delta_str = pad.delta_str();
+
+        let _ = pad
+            .delete_rows(vec![Cow::Borrowed(&row_1.id), Cow::Borrowed(&row_3.id)])
+            .unwrap()
+            .unwrap();
+        assert_eq!(pad.rows.len(), 1);
+
+        // The deleted rows are inserted at their original positions.
+        let _ = pad.undo().unwrap().unwrap();
+        assert_eq!(*pad.rows[0], row_1);
+        assert_eq!(*pad.rows[1], row_2);
+        assert_eq!(*pad.rows[2], row_3);
+        assert_eq!(pad.delta_str(), delta_str);
+
+        let _ = pad.redo().unwrap().unwrap();
+        assert_eq!(pad.rows.len(), 1);
+        assert_eq!(*pad.rows[0], row_2);
+    }
+
+    #[test]
+    fn block_meta_undo_update_row() {
+        let mut pad = test_pad();
+        let mut row = test_row_rev("1", &pad);
+        row.cells.insert("a".to_owned(), CellRevision::new("1".to_owned()));
+        let _ = pad.add_row_rev(row.clone(), None).unwrap().unwrap();
+
+        let mut cell_by_field_id = HashMap::new();
+        cell_by_field_id.insert("a".to_owned(), CellRevision::new("2".to_owned()));
+        cell_by_field_id.insert("b".to_owned(), CellRevision::new("3".to_owned()));
+        let changeset = RowMetaChangeset {
+            row_id: row.id.clone(),
+            height: Some(100),
+            visibility: None,
+            cell_by_field_id,
+        };
+        let _ = pad.update_row(changeset).unwrap().unwrap();
+
+        // The updated cell is restored and the inserted cell is removed.
+        let _ = pad.undo().unwrap().unwrap();
+        assert_eq!(*pad.rows[0], row);
+
+        let _ = pad.redo().unwrap().unwrap();
+        assert_eq!(pad.rows[0].height, 100);
+        assert_eq!(pad.rows[0].cells.get("a").unwrap().data, "2");
+        assert_eq!(pad.rows[0].cells.get("b").unwrap().data, "3");
+    }
+
+    #[test]
+    fn block_meta_update_row_without_history() {
+        let mut pad = test_pad();
+        let row = test_row_rev("1", &pad);
+        let _ = pad.add_row_rev(row.clone(), None).unwrap().unwrap();
+        let changeset = RowMetaChangeset {
+            row_id: row.id.clone(),
+            height: Some(100),
+            visibility: None,
+            cell_by_field_id: Default::default(),
+        };
+        let _ = pad.update_row_without_history(changeset).unwrap().unwrap();
+
+        // Undo reverts the insertion of the row instead of the update.
+        let _ = pad.undo().unwrap().unwrap();
+        assert!(pad.rows.is_empty());
+        assert!(!pad.can_undo());
+    }
+
+    #[test]
+    fn block_meta_undo_after_composing_remote_delta() {
+        let mut pad = test_pad();
+        let row_1 = test_row_rev("1", &pad);
+        let row_2 = test_row_rev("2", &pad);
+        let row_3 = test_row_rev("3", &pad);
+        let _ = pad.add_row_rev(row_1.clone(), None).unwrap().unwrap();
+        let _ = pad.add_row_rev(row_2.clone(), None).unwrap().unwrap();
+        let _ = pad.add_row_rev(row_3.clone(), None).unwrap().unwrap();
+        let _ = pad.move_row(&row_3.id, 2, 0).unwrap().unwrap();
+
+        // Delete the first row remotely.
+        let mut remote_pad = GridBlockRevisionPad::from_delta(pad.delta.clone()).unwrap();
+        let remote_change = remote_pad.delete_rows(vec![Cow::Borrowed(&row_1.id)]).unwrap().unwrap();
+        let _ = pad.compose_remote_delta(remote_change.delta).unwrap();
+        assert_eq!(pad.rows.len(), 2);
+
+        // Undo the move, the row is moved back to the end of the rows.
+        let _ = pad.undo().unwrap().unwrap();
+        assert_eq!(*pad.rows[0], row_2);
+        assert_eq!(*pad.rows[1], row_3);
+
+        // Undo the insertions of the third and the second row. The insertion of the first row is
+        // dropped because the row was already deleted.
+        let _ = pad.undo().unwrap().unwrap();
+        let _ = pad.undo().unwrap().unwrap();
+        assert!(pad.rows.is_empty());
+        assert!(pad.undo().unwrap().is_none());
+        assert!(!pad.can_undo());
+    }
+
     fn test_pad() -> GridBlockRevisionPad {
         let delta =
             GridBlockRevisionDelta::from_json_str(r#"[{"insert":"{\"block_id\":\"1\",\"rows\":[]}"}]"#).unwrap();
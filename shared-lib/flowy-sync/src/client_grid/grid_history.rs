@@ -0,0 +1,122 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+
+const MAX_UNDOES: usize = 20;
+
+/// The sequence is shared by all the histories, so the items recorded by the grid and the items
+/// recorded by its blocks can be ordered.
+static HISTORY_SEQ: AtomicI64 = AtomicI64::new(0);
+
+#[derive(Debug, Clone)]
+pub struct GridHistoryItem<T> {
+    /// The order in which the change was recorded. It stays the same when the item is moved between
+    /// the undo stack and the redo stack.
+    pub seq: i64,
+
+    /// The operation that reverts the change.
+    pub operation: T,
+}
+
+/// Records the operations that revert the changes. The operations refer to the fields and the rows
+/// by their ids instead of the positions in the delta, so they're still valid after composing the
+/// remote revisions.
+#[derive(Debug, Clone)]
+pub struct GridHistory<T> {
+    undoes: Vec<GridHistoryItem<T>>,
+    redoes: Vec<GridHistoryItem<T>>,
+    capacity: usize,
+}
+
+impl<T> std::default::Default for GridHistory<T> {
+    fn default() -> Self {
+        GridHistory {
+            undoes: Vec::new(),
+            redoes: Vec::new(),
+            capacity: MAX_UNDOES,
+        }
+    }
+}
+
+impl<T> GridHistory<T> {
+    pub fn new() -> Self {
+        GridHistory::default()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undoes.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redoes.is_empty()
+    }
+
+    pub fn last_undo(&self) -> Option<&GridHistoryItem<T>> {
+        self.undoes.last()
+    }
+
+    pub fn last_redo(&self) -> Option<&GridHistoryItem<T>> {
+        self.redoes.last()
+    }
+
+    pub fn add_undo(&mut self, item: GridHistoryItem<T>) {
+        self.undoes.push(item);
+    }
+
+    pub fn add_redo(&mut self, item: GridHistoryItem<T>) {
+        self.redoes.push(item);
+    }
+
+    /// Record the operation that reverts the new change. The redo stack is cleared.
+    pub fn record(&mut self, operation: T) {
+        self.redoes.clear();
+        let seq = HISTORY_SEQ.fetch_add(1, Ordering::SeqCst) + 1;
+        self.add_undo(GridHistoryItem { seq, operation });
+
+        if self.undoes.len() > self.capacity {
+            self.undoes.remove(0);
+        }
+    }
+
+    pub fn undo(&mut self) -> Option<GridHistoryItem<T>> {
+        self.undoes.pop()
+    }
+
+    pub fn redo(&mut self) -> Option<GridHistoryItem<T>> {
+        self.redoes.pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client_grid::GridHistory;
+
+    #[test]
+    fn grid_history_record_test() {
+        let mut history = GridHistory::new();
+        history.record("a");
+        history.record("b");
+        let seq_a = history.undoes[0].seq;
+        let seq_b = history.undoes[1].seq;
+        assert!(seq_b > seq_a);
+
+        let item = history.undo().unwrap();
+        assert_eq!(item.operation, "b");
+        history.add_redo(item);
+        assert!(history.can_redo());
+        assert_eq!(history.last_redo().unwrap().seq, seq_b);
+
+        // Recording a new change clears the redo stack.
+        history.record("c");
+        assert!(!history.can_redo());
+        assert_eq!(history.last_undo().unwrap().operation, "c");
+    }
+
+    #[test]
+    fn grid_history_capacity_test() {
+        let mut history = GridHistory::new();
+        for i in 0..30 {
+            history.record(i);
+        }
+        assert_eq!(history.undoes.len(), 20);
+        assert_eq!(history.undoes[0].operation, 10);
+    }
+}
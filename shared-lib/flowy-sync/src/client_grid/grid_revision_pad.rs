@@ -1,12 +1,14 @@
-use crate::entities::grid::{FieldChangesetParams, GridSettingChangesetParams};
+use crate::client_grid::{GridHistory, GridHistoryItem};
+use crate::entities::grid::{CreateGridFilterGroupParams, FieldChangesetParams, GridSettingChangesetParams};
 use crate::entities::revision::{md5, RepeatedRevision, Revision};
 use crate::errors::{internal_error, CollaborateError, CollaborateResult};
 use crate::util::{cal_diff, make_delta_from_revisions};
 use bytes::Bytes;
 use flowy_grid_data_model::revision::{
-    gen_block_id, gen_grid_filter_id, gen_grid_group_id, gen_grid_id, gen_grid_sort_id, FieldRevision,
-    FieldTypeRevision, GridBlockMetaRevision, GridBlockMetaRevisionChangeset, GridFilterRevision, GridGroupRevision,
-    GridLayoutRevision, GridRevision, GridSettingRevision, GridSortRevision,
+    gen_block_id, gen_grid_filter_group_id, gen_grid_filter_id, gen_grid_group_id, gen_grid_id, gen_grid_sort_id,
+    FieldRevision, FieldTypeRevision, GridBlockMetaRevision, GridBlockMetaRevisionChangeset, GridCalculationRevision,
+    GridFilterGroupRevision, GridFilterRevision, GridGroupRevision, GridLayoutRevision, GridRevision,
+    GridSettingRevision, GridSortRevision,
 };
 use lib_infra::util::move_vec_element;
 use lib_ot::core::{OperationTransformable, PhantomAttributes, PlainTextDelta, PlainTextDeltaBuilder};
@@ -19,6 +21,28 @@ pub type GridRevisionDeltaBuilder = PlainTextDeltaBuilder;
 pub struct GridRevisionPad {
     grid_rev: Arc<GridRevision>,
     delta: GridRevisionDelta,
+    history: GridHistory<FieldHistoryOperation>,
+}
+
+/// The operation that reverts the change of the fields.
+#[derive(Debug, Clone)]
+pub enum FieldHistoryOperation {
+    /// Insert the deleted field at its original position.
+    InsertField {
+        field_rev: Arc<FieldRevision>,
+        index: usize,
+    },
+    DeleteField {
+        field_id: String,
+    },
+    /// Replace the field with the revision before it was updated.
+    ReplaceField {
+        field_rev: Arc<FieldRevision>,
+    },
+    MoveField {
+        field_id: String,
+        index: usize,
+    },
 }
 
 pub trait JsonDeserializer {
@@ -59,6 +83,7 @@ impl GridRevisionPad {
         Ok(Self {
             grid_rev: Arc::new(grid),
             delta,
+            history: GridHistory::new(),
         })
     }
 
@@ -67,13 +92,32 @@ impl GridRevisionPad {
         Self::from_delta(grid_delta)
     }
 
+    pub fn delta(&self) -> &GridRevisionDelta {
+        &self.delta
+    }
+
+    pub fn reset_grid(&mut self, delta: GridRevisionDelta) -> CollaborateResult<String> {
+        let grid = GridRevisionPad::from_delta(delta)?;
+        self.grid_rev = grid.grid_rev;
+        self.delta = grid.delta;
+        Ok(self.md5())
+    }
+
+    pub fn compose_remote_delta(&mut self, delta: GridRevisionDelta) -> CollaborateResult<String> {
+        let composed_delta = self.delta.compose(&delta)?;
+        self.reset_grid(composed_delta)
+    }
+
     #[tracing::instrument(level = "debug", skip_all, err)]
     pub fn create_field_rev(
         &mut self,
         new_field_rev: FieldRevision,
         start_field_id: Option<String>,
     ) -> CollaborateResult<Option<GridChangeset>> {
-        self.modify_grid(|grid_meta| {
+        let operation = FieldHistoryOperation::DeleteField {
+            field_id: new_field_rev.id.clone(),
+        };
+        let changeset = self.modify_grid(|grid_meta| {
             // Check if the field exists or not
             if grid_meta
                 .fields
@@ -94,19 +138,31 @@ impl GridRevisionPad {
                 Some(index) => grid_meta.fields.insert(index, new_field_rev),
             }
             Ok(Some(()))
-        })
+        })?;
+        self.record_history(&changeset, operation);
+        Ok(changeset)
     }
 
     pub fn delete_field_rev(&mut self, field_id: &str) -> CollaborateResult<Option<GridChangeset>> {
-        self.modify_grid(
-            |grid_meta| match grid_meta.fields.iter().position(|field| field.id == field_id) {
-                None => Ok(None),
-                Some(index) => {
-                    grid_meta.fields.remove(index);
-                    Ok(Some(()))
-                }
+        let operation = match self.get_field_rev(field_id) {
+            None => return Ok(None),
+            Some((index, field_rev)) => FieldHistoryOperation::InsertField {
+                field_rev: field_rev.clone(),
+                index,
             },
-        )
+        };
+        let changeset =
+            self.modify_grid(
+                |grid_meta| match grid_meta.fields.iter().position(|field| field.id == field_id) {
+                    None => Ok(None),
+                    Some(index) => {
+                        grid_meta.fields.remove(index);
+                        Ok(Some(()))
+                    }
+                },
+            )?;
+        self.record_history(&changeset, operation);
+        Ok(changeset)
     }
 
     pub fn duplicate_field_rev(
@@ -114,18 +170,24 @@ impl GridRevisionPad {
         field_id: &str,
         duplicated_field_id: &str,
     ) -> CollaborateResult<Option<GridChangeset>> {
-        self.modify_grid(
-            |grid_meta| match grid_meta.fields.iter().position(|field| field.id == field_id) {
-                None => Ok(None),
-                Some(index) => {
-                    let mut duplicate_field_rev = grid_meta.fields[index].as_ref().clone();
-                    duplicate_field_rev.id = duplicated_field_id.to_string();
-                    duplicate_field_rev.name = format!("{} (copy)", duplicate_field_rev.name);
-                    grid_meta.fields.insert(index + 1, Arc::new(duplicate_field_rev));
-                    Ok(Some(()))
-                }
-            },
-        )
+        let operation = FieldHistoryOperation::DeleteField {
+            field_id: duplicated_field_id.to_owned(),
+        };
+        let changeset =
+            self.modify_grid(
+                |grid_meta| match grid_meta.fields.iter().position(|field| field.id == field_id) {
+                    None => Ok(None),
+                    Some(index) => {
+                        let mut duplicate_field_rev = grid_meta.fields[index].as_ref().clone();
+                        duplicate_field_rev.id = duplicated_field_id.to_string();
+                        duplicate_field_rev.name = format!("{} (copy)", duplicate_field_rev.name);
+                        grid_meta.fields.insert(index + 1, Arc::new(duplicate_field_rev));
+                        Ok(Some(()))
+                    }
+                },
+            )?;
+        self.record_history(&changeset, operation);
+        Ok(changeset)
     }
 
     pub fn switch_to_field<B, T>(
@@ -139,7 +201,8 @@ impl GridRevisionPad {
         T: Into<FieldTypeRevision>,
     {
         let field_type = field_type.into();
-        self.modify_grid(|grid_meta| {
+        let operation = self.replace_field_operation(field_id);
+        let changeset = self.modify_grid(|grid_meta| {
             //
             match grid_meta.fields.iter_mut().find(|field_rev| field_rev.id == field_id) {
                 None => {
@@ -157,7 +220,11 @@ impl GridRevisionPad {
                     Ok(Some(()))
                 }
             }
-        })
+        })?;
+        if let Some(operation) = operation {
+            self.record_history(&changeset, operation);
+        }
+        Ok(changeset)
     }
 
     pub fn update_field_rev<T: JsonDeserializer>(
@@ -166,7 +233,8 @@ impl GridRevisionPad {
         deserializer: T,
     ) -> CollaborateResult<Option<GridChangeset>> {
         let field_id = changeset.field_id.clone();
-        self.modify_field(&field_id, |field| {
+        let operation = self.replace_field_operation(&field_id);
+        let changeset = self.modify_field(&field_id, |field| {
             let mut is_changed = None;
             if let Some(name) = changeset.name {
                 field.name = name;
@@ -212,7 +280,11 @@ impl GridRevisionPad {
             }
 
             Ok(is_changed)
-        })
+        })?;
+        if let Some(operation) = operation {
+            self.record_history(&changeset, operation);
+        }
+        Ok(changeset)
     }
 
     pub fn get_field_rev(&self, field_id: &str) -> Option<(usize, &Arc<FieldRevision>)> {
@@ -224,16 +296,22 @@ impl GridRevisionPad {
     }
 
     pub fn replace_field_rev(&mut self, field_rev: Arc<FieldRevision>) -> CollaborateResult<Option<GridChangeset>> {
-        self.modify_grid(
-            |grid_meta| match grid_meta.fields.iter().position(|field| field.id == field_rev.id) {
-                None => Ok(None),
-                Some(index) => {
-                    grid_meta.fields.remove(index);
-                    grid_meta.fields.insert(index, field_rev);
-                    Ok(Some(()))
-                }
-            },
-        )
+        let operation = self.replace_field_operation(&field_rev.id);
+        let changeset =
+            self.modify_grid(
+                |grid_meta| match grid_meta.fields.iter().position(|field| field.id == field_rev.id) {
+                    None => Ok(None),
+                    Some(index) => {
+                        grid_meta.fields.remove(index);
+                        grid_meta.fields.insert(index, field_rev);
+                        Ok(Some(()))
+                    }
+                },
+            )?;
+        if let Some(operation) = operation {
+            self.record_history(&changeset, operation);
+        }
+        Ok(changeset)
     }
 
     pub fn move_field(
@@ -242,7 +320,11 @@ impl GridRevisionPad {
         from_index: usize,
         to_index: usize,
     ) -> CollaborateResult<Option<GridChangeset>> {
-        self.modify_grid(|grid_meta| {
+        let operation = FieldHistoryOperation::MoveField {
+            field_id: field_id.to_owned(),
+            index: from_index,
+        };
+        let changeset = self.modify_grid(|grid_meta| {
             match move_vec_element(
                 &mut grid_meta.fields,
                 |field| field.id == field_id,
@@ -254,7 +336,57 @@ impl GridRevisionPad {
                 true => Ok(Some(())),
                 false => Ok(None),
             }
-        })
+        })?;
+        self.record_history(&changeset, operation);
+        Ok(changeset)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    pub fn history(&self) -> &GridHistory<FieldHistoryOperation> {
+        &self.history
+    }
+
+    /// Revert the last change of the fields. The change is dropped if it can't be reverted anymore,
+    /// e.g. the field was deleted by the remote revision, and None is returned.
+    pub fn undo(&mut self) -> CollaborateResult<Option<GridChangeset>> {
+        match self.history.undo() {
+            None => Ok(None),
+            Some(item) => match self.apply_history_operation(item.operation)? {
+                None => Ok(None),
+                Some((changeset, operation)) => {
+                    self.history.add_redo(GridHistoryItem {
+                        seq: item.seq,
+                        operation,
+                    });
+                    Ok(Some(changeset))
+                }
+            },
+        }
+    }
+
+    /// Reapply the last reverted change of the fields. The change is dropped if it can't be
+    /// reapplied anymore, and None is returned.
+    pub fn redo(&mut self) -> CollaborateResult<Option<GridChangeset>> {
+        match self.history.redo() {
+            None => Ok(None),
+            Some(item) => match self.apply_history_operation(item.operation)? {
+                None => Ok(None),
+                Some((changeset, operation)) => {
+                    self.history.add_undo(GridHistoryItem {
+                        seq: item.seq,
+                        operation,
+                    });
+                    Ok(Some(changeset))
+                }
+            },
+        }
     }
 
     pub fn contain_field(&self, field_id: &str) -> bool {
@@ -370,6 +502,63 @@ impl GridRevisionPad {
         Some(filter_revs)
     }
 
+    /// Return the root filter group of the layout. If layout is None, the current layout will be used.
+    pub fn get_filter_group(&self, layout: Option<&GridLayoutRevision>) -> Option<Arc<GridFilterGroupRevision>> {
+        let layout_ty = layout.unwrap_or(&self.grid_rev.setting.layout);
+        self.grid_rev.setting.get_filter_group(layout_ty)
+    }
+
+    /// Return the groups of the layout. The group will be ignored if its field doesn't exist.
+    /// If layout is None, the current layout will be used.
+    pub fn get_groups(&self, layout: Option<&GridLayoutRevision>) -> Vec<Arc<GridGroupRevision>> {
+        let layout_ty = layout.unwrap_or(&self.grid_rev.setting.layout);
+        self.grid_rev
+            .setting
+            .get_groups(layout_ty)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|group_rev| match &group_rev.field_id {
+                None => false,
+                Some(field_id) => self.grid_rev.fields.iter().any(|field_rev| &field_rev.id == field_id),
+            })
+            .collect()
+    }
+
+    /// Return the sorts ordered by priority. The sort will be ignored if its field doesn't exist or
+    /// the type of the field was changed. If layout is None, the current layout will be used.
+    pub fn get_sorts(&self, layout: Option<&GridLayoutRevision>) -> Vec<Arc<GridSortRevision>> {
+        let layout_ty = layout.unwrap_or(&self.grid_rev.setting.layout);
+        self.grid_rev
+            .setting
+            .get_sorts(layout_ty)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|sort_rev| {
+                self.grid_rev.fields.iter().any(|field_rev| {
+                    field_rev.id == sort_rev.field_id && field_rev.field_type_rev == sort_rev.field_type_rev
+                })
+            })
+            .collect()
+    }
+
+    /// Return the calculations of the layout. The calculation will be ignored if its field doesn't
+    /// exist. If layout is None, the current layout will be used.
+    pub fn get_calculations(&self, layout: Option<&GridLayoutRevision>) -> Vec<Arc<GridCalculationRevision>> {
+        let layout_ty = layout.unwrap_or(&self.grid_rev.setting.layout);
+        self.grid_rev
+            .setting
+            .get_calculations(layout_ty)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|calculation_rev| {
+                self.grid_rev
+                    .fields
+                    .iter()
+                    .any(|field_rev| field_rev.id == calculation_rev.field_id)
+            })
+            .collect()
+    }
+
     pub fn update_grid_setting_rev(
         &mut self,
         changeset: GridSettingChangesetParams,
@@ -399,6 +588,10 @@ impl GridRevisionPad {
                 {
                     Some(filters) => {
                         filters.retain(|filter| filter.id != params.filter_id);
+                        grid_rev
+                            .setting
+                            .remove_filter_from_group(&layout_rev, &params.filter_id);
+                        is_changed = Some(())
                     }
                     None => {
                         tracing::warn!("Can't find the filter with {:?}", layout_rev);
@@ -412,44 +605,69 @@ impl GridRevisionPad {
                     sub_field_id: params.sub_field_id,
                 };
 
-                grid_rev
-                    .setting
-                    .groups
-                    .entry(layout_rev.clone())
-                    .or_insert_with(std::vec::Vec::new)
-                    .push(rev);
-
+                grid_rev.setting.insert_group(&layout_rev, rev);
                 is_changed = Some(())
             }
             if let Some(delete_group_id) = changeset.delete_group {
-                match grid_rev.setting.groups.get_mut(&layout_rev) {
-                    Some(groups) => groups.retain(|group| group.id != delete_group_id),
-                    None => {
-                        tracing::warn!("Can't find the group with {:?}", layout_rev);
-                    }
+                if grid_rev.setting.delete_group(&layout_rev, &delete_group_id) {
+                    is_changed = Some(())
+                } else {
+                    tracing::warn!("Can't find the group with {:?}", layout_rev);
                 }
             }
-            if let Some(sort) = changeset.insert_sort {
+            if let Some(params) = changeset.insert_sort {
                 let rev = GridSortRevision {
                     id: gen_grid_sort_id(),
-                    field_id: sort.field_id,
+                    field_id: params.field_id,
+                    field_type_rev: params.field_type_rev,
+                    condition: params.condition,
                 };
 
+                grid_rev.setting.insert_sort(&layout_rev, rev);
+                is_changed = Some(())
+            }
+
+            if let Some(delete_sort_id) = changeset.delete_sort {
+                if grid_rev.setting.delete_sort(&layout_rev, &delete_sort_id) {
+                    is_changed = Some(())
+                } else {
+                    tracing::warn!("Can't find the sort with {:?}", layout_rev);
+                }
+            }
+
+            if let Some(params) = changeset.insert_filter_group {
                 grid_rev
                     .setting
-                    .sorts
-                    .entry(layout_rev.clone())
-                    .or_insert_with(std::vec::Vec::new)
-                    .push(rev);
+                    .insert_filter_group(&layout_rev, make_filter_group_rev(params));
                 is_changed = Some(())
             }
 
-            if let Some(delete_sort_id) = changeset.delete_sort {
-                match grid_rev.setting.sorts.get_mut(&layout_rev) {
-                    Some(sorts) => sorts.retain(|sort| sort.id != delete_sort_id),
-                    None => {
-                        tracing::warn!("Can't find the sort with {:?}", layout_rev);
-                    }
+            if let Some(delete_filter_group_id) = changeset.delete_filter_group {
+                if grid_rev
+                    .setting
+                    .delete_filter_group(&layout_rev, &delete_filter_group_id)
+                {
+                    is_changed = Some(())
+                } else {
+                    tracing::warn!("Can't find the filter group with {:?}", layout_rev);
+                }
+            }
+
+            if let Some(params) = changeset.insert_calculation {
+                let rev = GridCalculationRevision {
+                    field_id: params.field_id,
+                    calculation_type: params.calculation_type,
+                };
+
+                grid_rev.setting.insert_calculation(&layout_rev, rev);
+                is_changed = Some(())
+            }
+
+            if let Some(field_id) = changeset.delete_calculation {
+                if grid_rev.setting.delete_calculation(&layout_rev, &field_id) {
+                    is_changed = Some(())
+                } else {
+                    tracing::warn!("Can't find the calculation with {:?}", layout_rev);
                 }
             }
             Ok(is_changed)
@@ -529,6 +747,85 @@ impl GridRevisionPad {
         )
     }
 
+    /// Return the operation that replaces the field with its current revision.
+    fn replace_field_operation(&self, field_id: &str) -> Option<FieldHistoryOperation> {
+        let (_, field_rev) = self.get_field_rev(field_id)?;
+        Some(FieldHistoryOperation::ReplaceField {
+            field_rev: field_rev.clone(),
+        })
+    }
+
+    fn record_history(&mut self, changeset: &Option<GridChangeset>, operation: FieldHistoryOperation) {
+        if changeset.is_some() {
+            self.history.record(operation);
+        }
+    }
+
+    /// Apply the operation without recording it. Return the changeset and the operation that reverts
+    /// it, or None if the operation can't be applied to the current fields.
+    fn apply_history_operation(
+        &mut self,
+        operation: FieldHistoryOperation,
+    ) -> CollaborateResult<Option<(GridChangeset, FieldHistoryOperation)>> {
+        let (changeset, operation) = match operation {
+            FieldHistoryOperation::InsertField { field_rev, index } => {
+                if self.contain_field(&field_rev.id) {
+                    return Ok(None);
+                }
+                let field_id = field_rev.id.clone();
+                let changeset = self.modify_grid(|grid_rev| {
+                    let index = index.min(grid_rev.fields.len());
+                    grid_rev.fields.insert(index, field_rev);
+                    Ok(Some(()))
+                })?;
+                (changeset, FieldHistoryOperation::DeleteField { field_id })
+            }
+            FieldHistoryOperation::DeleteField { field_id } => {
+                let (index, field_rev) = match self.get_field_rev(&field_id) {
+                    None => return Ok(None),
+                    Some((index, field_rev)) => (index, field_rev.clone()),
+                };
+                let changeset = self.modify_grid(|grid_rev| {
+                    grid_rev.fields.remove(index);
+                    Ok(Some(()))
+                })?;
+                (changeset, FieldHistoryOperation::InsertField { field_rev, index })
+            }
+            FieldHistoryOperation::ReplaceField { field_rev } => {
+                let (index, old_field_rev) = match self.get_field_rev(&field_rev.id) {
+                    None => return Ok(None),
+                    Some((index, old_field_rev)) => (index, old_field_rev.clone()),
+                };
+                let changeset = self.modify_grid(|grid_rev| {
+                    grid_rev.fields[index] = field_rev;
+                    Ok(Some(()))
+                })?;
+                let operation = FieldHistoryOperation::ReplaceField {
+                    field_rev: old_field_rev,
+                };
+                (changeset, operation)
+            }
+            FieldHistoryOperation::MoveField { field_id, index } => {
+                let from_index = match self.get_field_rev(&field_id) {
+                    None => return Ok(None),
+                    Some((from_index, _)) => from_index,
+                };
+                let changeset = self.modify_grid(|grid_rev| {
+                    let field_rev = grid_rev.fields.remove(from_index);
+                    let index = index.min(grid_rev.fields.len());
+                    grid_rev.fields.insert(index, field_rev);
+                    Ok(Some(()))
+                })?;
+                let operation = FieldHistoryOperation::MoveField {
+                    field_id,
+                    index: from_index,
+                };
+                (changeset, operation)
+            }
+        };
+        Ok(changeset.map(|changeset| (changeset, operation)))
+    }
+
     pub fn json_str(&self) -> CollaborateResult<String> {
         make_grid_rev_json_str(&self.grid_rev)
     }
@@ -540,6 +837,15 @@ pub fn make_grid_rev_json_str(grid: &GridRevision) -> CollaborateResult<String>
     Ok(json)
 }
 
+fn make_filter_group_rev(params: CreateGridFilterGroupParams) -> GridFilterGroupRevision {
+    GridFilterGroupRevision {
+        id: gen_grid_filter_group_id(),
+        logic: params.logic,
+        filter_ids: params.filter_ids,
+        groups: params.groups.into_iter().map(make_filter_group_rev).collect(),
+    }
+}
+
 pub struct GridChangeset {
     pub delta: GridRevisionDelta,
     /// md5: the md5 of the grid after applying the change.
@@ -565,6 +871,84 @@ impl std::default::Default for GridRevisionPad {
         GridRevisionPad {
             grid_rev: Arc::new(grid),
             delta,
+            history: GridHistory::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::client_grid::GridRevisionPad;
+    use flowy_grid_data_model::revision::FieldRevision;
+    use std::sync::Arc;
+
+    #[test]
+    fn grid_pad_undo_redo_delete_field_test() {
+        let mut pad = GridRevisionPad::default();
+        let field_rev = FieldRevision::new("Name", "", 0, 150, true);
+        let field_id = field_rev.id.clone();
+        let _ = pad.create_field_rev(field_rev, None).unwrap().unwrap();
+        let _ = pad
+            .create_field_rev(FieldRevision::new("Price", "", 1, 150, false), None)
+            .unwrap()
+            .unwrap();
+        let _ = pad.delete_field_rev(&field_id).unwrap().unwrap();
+        assert!(!pad.contain_field(&field_id));
+
+        // The deleted field is inserted at its original position.
+        let _ = pad.undo().unwrap().unwrap();
+        assert_eq!(pad.fields()[0].id, field_id);
+        assert!(pad.can_redo());
+
+        let _ = pad.redo().unwrap().unwrap();
+        assert!(!pad.contain_field(&field_id));
+        assert!(!pad.can_redo());
+    }
+
+    #[test]
+    fn grid_pad_undo_replace_field_test() {
+        let mut pad = GridRevisionPad::default();
+        let field_rev = FieldRevision::new("Name", "", 0, 150, true);
+        let _ = pad.create_field_rev(field_rev.clone(), None).unwrap().unwrap();
+
+        let mut renamed_field_rev = field_rev.clone();
+        renamed_field_rev.name = "Title".to_owned();
+        let _ = pad.replace_field_rev(Arc::new(renamed_field_rev)).unwrap().unwrap();
+        assert_eq!(pad.fields()[0].name, "Title");
+
+        let _ = pad.undo().unwrap().unwrap();
+        assert_eq!(pad.fields()[0].name, "Name");
+        let _ = pad.redo().unwrap().unwrap();
+        assert_eq!(pad.fields()[0].name, "Title");
+    }
+
+    #[test]
+    fn grid_pad_undo_after_composing_remote_delta_test() {
+        let mut pad = GridRevisionPad::default();
+        let name_field_rev = FieldRevision::new("Name", "", 0, 150, true);
+        let price_field_rev = FieldRevision::new("Price", "", 1, 150, false);
+        let _ = pad.create_field_rev(name_field_rev.clone(), None).unwrap().unwrap();
+        let _ = pad.create_field_rev(price_field_rev.clone(), None).unwrap().unwrap();
+
+        let mut renamed_field_rev = price_field_rev.clone();
+        renamed_field_rev.name = "Cost".to_owned();
+        let _ = pad.replace_field_rev(Arc::new(renamed_field_rev)).unwrap().unwrap();
+
+        // Delete the name field remotely.
+        let mut remote_pad = GridRevisionPad::from_delta(pad.delta().clone()).unwrap();
+        let remote_changeset = remote_pad.delete_field_rev(&name_field_rev.id).unwrap().unwrap();
+        let _ = pad.compose_remote_delta(remote_changeset.delta).unwrap();
+
+        // Undo reverts the local change and keeps the remote one.
+        let _ = pad.undo().unwrap().unwrap();
+        assert_eq!(pad.fields().len(), 1);
+        assert_eq!(pad.fields()[0].name, "Price");
+
+        // The creation of the price field is reverted, then the creation of the name field is
+        // dropped because the field was already deleted.
+        let _ = pad.undo().unwrap().unwrap();
+        assert!(pad.fields().is_empty());
+        assert!(pad.undo().unwrap().is_none());
+        assert!(!pad.can_undo());
+    }
+}
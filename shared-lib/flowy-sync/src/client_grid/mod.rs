@@ -1,7 +1,9 @@
 mod grid_block_revsion_pad;
 mod grid_builder;
+mod grid_history;
 mod grid_revision_pad;
 
 pub use grid_block_revsion_pad::*;
 pub use grid_builder::*;
+pub use grid_history::*;
 pub use grid_revision_pad::*;
@@ -1,4 +1,21 @@
-use flowy_grid_data_model::revision::{FieldTypeRevision, GridLayoutRevision};
+use flowy_grid_data_model::revision::{
+    FieldTypeRevision, GridCalculationTypeRevision, GridFilterLogicRevision, GridLayoutRevision,
+    GridSortConditionRevision,
+};
+use lib_ot::core::PlainTextDelta;
+
+/// The delta of the grid or the grid block.
+pub type GridDelta = PlainTextDelta;
+
+/// The grid or the grid block that is composed from its revisions on the server side. The
+/// `object_id` is either the grid id or the block id.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct GridInfo {
+    pub object_id: String,
+    pub text: String,
+    pub rev_id: i64,
+    pub base_rev_id: i64,
+}
 
 pub struct GridSettingChangesetParams {
     pub grid_id: String,
@@ -9,11 +26,31 @@ pub struct GridSettingChangesetParams {
     pub delete_group: Option<String>,
     pub insert_sort: Option<CreateGridSortParams>,
     pub delete_sort: Option<String>,
+    pub insert_filter_group: Option<CreateGridFilterGroupParams>,
+    pub delete_filter_group: Option<String>,
+    pub insert_calculation: Option<CreateGridCalculationParams>,
+    /// The id of the field whose calculation will be removed.
+    pub delete_calculation: Option<String>,
 }
 
 impl GridSettingChangesetParams {
     pub fn is_filter_changed(&self) -> bool {
-        self.insert_filter.is_some() || self.delete_filter.is_some()
+        self.insert_filter.is_some()
+            || self.delete_filter.is_some()
+            || self.insert_filter_group.is_some()
+            || self.delete_filter_group.is_some()
+    }
+
+    pub fn is_group_changed(&self) -> bool {
+        self.insert_group.is_some() || self.delete_group.is_some()
+    }
+
+    pub fn is_sort_changed(&self) -> bool {
+        self.insert_sort.is_some() || self.delete_sort.is_some()
+    }
+
+    pub fn is_calculation_changed(&self) -> bool {
+        self.insert_calculation.is_some() || self.delete_calculation.is_some()
     }
 }
 pub struct CreateGridFilterParams {
@@ -28,12 +65,25 @@ pub struct DeleteFilterParams {
     pub filter_id: String,
     pub field_type_rev: FieldTypeRevision,
 }
+pub struct CreateGridFilterGroupParams {
+    pub logic: GridFilterLogicRevision,
+    pub filter_ids: Vec<String>,
+    pub groups: Vec<CreateGridFilterGroupParams>,
+}
+
 pub struct CreateGridGroupParams {
     pub field_id: Option<String>,
     pub sub_field_id: Option<String>,
 }
 pub struct CreateGridSortParams {
-    pub field_id: Option<String>,
+    pub field_id: String,
+    pub field_type_rev: FieldTypeRevision,
+    pub condition: GridSortConditionRevision,
+}
+
+pub struct CreateGridCalculationParams {
+    pub field_id: String,
+    pub calculation_type: GridCalculationTypeRevision,
 }
 
 #[derive(Debug, Clone, Default)]
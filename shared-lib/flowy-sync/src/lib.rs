@@ -6,6 +6,7 @@ pub mod errors;
 pub mod protobuf;
 pub mod server_document;
 pub mod server_folder;
+pub mod server_grid;
 pub mod synchronizer;
 pub mod util;
 
@@ -0,0 +1,333 @@
+use crate::entities::revision::{RepeatedRevision, Revision};
+use crate::{
+    entities::{
+        grid::{GridDelta, GridInfo},
+        ws_data::ServerRevisionWSDataBuilder,
+    },
+    errors::{internal_error, CollaborateError, CollaborateResult},
+    protobuf::ClientRevisionWSData,
+    server_grid::grid_pad::ServerGrid,
+    synchronizer::{RevisionSyncPersistence, RevisionSyncResponse, RevisionSynchronizer, RevisionUser},
+    util::rev_id_from_str,
+};
+use async_stream::stream;
+use futures::stream::StreamExt;
+use lib_infra::future::BoxResultFuture;
+use lib_ot::core::PhantomAttributes;
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
+use tokio::{
+    sync::{mpsc, oneshot, RwLock},
+    task::spawn_blocking,
+};
+
+pub trait GridCloudPersistence: Send + Sync + Debug {
+    fn read_grid(&self, user_id: &str, object_id: &str) -> BoxResultFuture<GridInfo, CollaborateError>;
+
+    fn create_grid(
+        &self,
+        user_id: &str,
+        object_id: &str,
+        repeated_revision: RepeatedRevision,
+    ) -> BoxResultFuture<Option<GridInfo>, CollaborateError>;
+
+    fn save_grid_revisions(&self, repeated_revision: RepeatedRevision) -> BoxResultFuture<(), CollaborateError>;
+
+    fn read_grid_revisions(
+        &self,
+        object_id: &str,
+        rev_ids: Option<Vec<i64>>,
+    ) -> BoxResultFuture<Vec<Revision>, CollaborateError>;
+
+    fn reset_grid(&self, object_id: &str, repeated_revision: RepeatedRevision)
+        -> BoxResultFuture<(), CollaborateError>;
+}
+
+impl RevisionSyncPersistence for Arc<dyn GridCloudPersistence> {
+    fn read_revisions(
+        &self,
+        object_id: &str,
+        rev_ids: Option<Vec<i64>>,
+    ) -> BoxResultFuture<Vec<Revision>, CollaborateError> {
+        (**self).read_grid_revisions(object_id, rev_ids)
+    }
+
+    fn save_revisions(&self, repeated_revision: RepeatedRevision) -> BoxResultFuture<(), CollaborateError> {
+        (**self).save_grid_revisions(repeated_revision)
+    }
+
+    fn reset_object(
+        &self,
+        object_id: &str,
+        repeated_revision: RepeatedRevision,
+    ) -> BoxResultFuture<(), CollaborateError> {
+        (**self).reset_grid(object_id, repeated_revision)
+    }
+}
+
+/// Handles the revisions of the grids and the grid blocks. Each of them is synchronized by its own
+/// handler that is keyed by the object id, which is either the grid id or the block id.
+pub struct ServerGridManager {
+    grid_handlers: Arc<RwLock<HashMap<String, Arc<OpenGridHandler>>>>,
+    persistence: Arc<dyn GridCloudPersistence>,
+}
+
+impl ServerGridManager {
+    pub fn new(persistence: Arc<dyn GridCloudPersistence>) -> Self {
+        Self {
+            grid_handlers: Arc::new(RwLock::new(HashMap::new())),
+            persistence,
+        }
+    }
+
+    pub async fn handle_client_revisions(
+        &self,
+        user: Arc<dyn RevisionUser>,
+        mut client_data: ClientRevisionWSData,
+    ) -> Result<(), CollaborateError> {
+        let repeated_revision: RepeatedRevision = client_data.take_revisions().into();
+        let cloned_user = user.clone();
+        let ack_id = rev_id_from_str(&client_data.data_id)?;
+        let object_id = client_data.object_id;
+        let user_id = user.user_id();
+
+        let result = match self.get_grid_handler(&user_id, &object_id).await {
+            None => {
+                let _ = self
+                    .create_grid(&user_id, &object_id, repeated_revision)
+                    .await
+                    .map_err(|e| CollaborateError::internal().context(format!("Server create grid failed: {}", e)))?;
+                Ok(())
+            }
+            Some(handler) => {
+                let _ = handler.apply_revisions(user, repeated_revision).await?;
+                Ok(())
+            }
+        };
+
+        if result.is_ok() {
+            cloned_user.receive(RevisionSyncResponse::Ack(
+                ServerRevisionWSDataBuilder::build_ack_message(&object_id, ack_id),
+            ));
+        }
+        result
+    }
+
+    pub async fn handle_client_ping(
+        &self,
+        user: Arc<dyn RevisionUser>,
+        client_data: ClientRevisionWSData,
+    ) -> Result<(), CollaborateError> {
+        let user_id = user.user_id();
+        let rev_id = rev_id_from_str(&client_data.data_id)?;
+        let object_id = client_data.object_id.clone();
+        match self.get_grid_handler(&user_id, &object_id).await {
+            None => {
+                tracing::trace!("Grid:{} doesn't exist, ignore client ping", object_id);
+                Ok(())
+            }
+            Some(handler) => {
+                let _ = handler.apply_ping(rev_id, user).await?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn get_grid_handler(&self, user_id: &str, object_id: &str) -> Option<Arc<OpenGridHandler>> {
+        let object_id = object_id.to_owned();
+        if let Some(handler) = self.grid_handlers.read().await.get(&object_id).cloned() {
+            return Some(handler);
+        }
+
+        let mut write_guard = self.grid_handlers.write().await;
+        match self.persistence.read_grid(user_id, &object_id).await {
+            Ok(grid_info) => match self.create_grid_handler(grid_info).await {
+                Ok(handler) => {
+                    write_guard.insert(object_id, handler.clone());
+                    drop(write_guard);
+                    Some(handler)
+                }
+                Err(e) => {
+                    tracing::error!("Open grid:{} failed: {:?}", object_id, e);
+                    None
+                }
+            },
+            Err(_) => None,
+        }
+    }
+
+    async fn create_grid_handler(&self, grid_info: GridInfo) -> Result<Arc<OpenGridHandler>, CollaborateError> {
+        let persistence = self.persistence.clone();
+        let handle = spawn_blocking(|| OpenGridHandler::new(grid_info, persistence))
+            .await
+            .map_err(|e| CollaborateError::internal().context(format!("Create grid handler failed: {}", e)))?;
+        Ok(Arc::new(handle?))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, repeated_revision), err)]
+    async fn create_grid(
+        &self,
+        user_id: &str,
+        object_id: &str,
+        repeated_revision: RepeatedRevision,
+    ) -> Result<Arc<OpenGridHandler>, CollaborateError> {
+        match self
+            .persistence
+            .create_grid(user_id, object_id, repeated_revision)
+            .await?
+        {
+            Some(grid_info) => {
+                let handler = self.create_grid_handler(grid_info).await?;
+                self.grid_handlers
+                    .write()
+                    .await
+                    .insert(object_id.to_owned(), handler.clone());
+                Ok(handler)
+            }
+            None => Err(CollaborateError::internal().context(String::new())),
+        }
+    }
+}
+
+type GridRevisionSynchronizer = RevisionSynchronizer<PhantomAttributes>;
+
+struct OpenGridHandler {
+    object_id: String,
+    sender: mpsc::Sender<GridCommand>,
+}
+
+impl OpenGridHandler {
+    fn new(grid_info: GridInfo, persistence: Arc<dyn GridCloudPersistence>) -> CollaborateResult<Self> {
+        let (sender, receiver) = mpsc::channel(1000);
+        let object_id = grid_info.object_id.clone();
+        let delta = GridDelta::from_bytes(&grid_info.text)?;
+        let sync_object = ServerGrid::from_delta(&object_id, delta);
+        let synchronizer = Arc::new(GridRevisionSynchronizer::new(
+            grid_info.rev_id,
+            sync_object,
+            persistence,
+        ));
+
+        let queue = GridCommandRunner::new(&object_id, receiver, synchronizer);
+        tokio::task::spawn(queue.run());
+
+        Ok(Self { object_id, sender })
+    }
+
+    #[tracing::instrument(
+        name = "server_grid_apply_revision",
+        level = "trace",
+        skip(self, user, repeated_revision),
+        err
+    )]
+    async fn apply_revisions(
+        &self,
+        user: Arc<dyn RevisionUser>,
+        repeated_revision: RepeatedRevision,
+    ) -> CollaborateResult<()> {
+        let (ret, rx) = oneshot::channel();
+        let msg = GridCommand::ApplyRevisions {
+            user,
+            repeated_revision,
+            ret,
+        };
+
+        self.send(msg, rx).await?
+    }
+
+    async fn apply_ping(&self, rev_id: i64, user: Arc<dyn RevisionUser>) -> Result<(), CollaborateError> {
+        let (ret, rx) = oneshot::channel();
+        let msg = GridCommand::Ping { user, rev_id, ret };
+        self.send(msg, rx).await?
+    }
+
+    async fn send<T>(&self, msg: GridCommand, rx: oneshot::Receiver<T>) -> CollaborateResult<T> {
+        let _ = self
+            .sender
+            .send(msg)
+            .await
+            .map_err(|e| CollaborateError::internal().context(format!("Send grid command failed: {}", e)))?;
+        Ok(rx.await.map_err(internal_error)?)
+    }
+}
+
+impl std::ops::Drop for OpenGridHandler {
+    fn drop(&mut self) {
+        tracing::trace!("{} OpenGridHandler was dropped", self.object_id);
+    }
+}
+
+enum GridCommand {
+    ApplyRevisions {
+        user: Arc<dyn RevisionUser>,
+        repeated_revision: RepeatedRevision,
+        ret: oneshot::Sender<CollaborateResult<()>>,
+    },
+    Ping {
+        user: Arc<dyn RevisionUser>,
+        rev_id: i64,
+        ret: oneshot::Sender<CollaborateResult<()>>,
+    },
+}
+
+struct GridCommandRunner {
+    object_id: String,
+    receiver: Option<mpsc::Receiver<GridCommand>>,
+    synchronizer: Arc<GridRevisionSynchronizer>,
+}
+impl GridCommandRunner {
+    fn new(
+        object_id: &str,
+        receiver: mpsc::Receiver<GridCommand>,
+        synchronizer: Arc<GridRevisionSynchronizer>,
+    ) -> Self {
+        Self {
+            object_id: object_id.to_owned(),
+            receiver: Some(receiver),
+            synchronizer,
+        }
+    }
+
+    async fn run(mut self) {
+        let mut receiver = self
+            .receiver
+            .take()
+            .expect("GridCommandRunner's receiver should only take one time");
+
+        let stream = stream! {
+            loop {
+                match receiver.recv().await {
+                    Some(msg) => yield msg,
+                    None => break,
+                }
+            }
+        };
+        stream.for_each(|msg| self.handle_message(msg)).await;
+    }
+
+    async fn handle_message(&self, msg: GridCommand) {
+        match msg {
+            GridCommand::ApplyRevisions {
+                user,
+                repeated_revision,
+                ret,
+            } => {
+                let result = self
+                    .synchronizer
+                    .sync_revisions(user, repeated_revision)
+                    .await
+                    .map_err(internal_error);
+                let _ = ret.send(result);
+            }
+            GridCommand::Ping { user, rev_id, ret } => {
+                let result = self.synchronizer.pong(user, rev_id).await.map_err(internal_error);
+                let _ = ret.send(result);
+            }
+        }
+    }
+}
+
+impl std::ops::Drop for GridCommandRunner {
+    fn drop(&mut self) {
+        tracing::trace!("{} GridCommandRunner was dropped", self.object_id);
+    }
+}
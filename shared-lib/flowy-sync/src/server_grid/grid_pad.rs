@@ -0,0 +1,43 @@
+use crate::{entities::grid::GridDelta, errors::CollaborateError, synchronizer::RevisionSyncObject};
+use lib_ot::core::{OperationTransformable, PhantomAttributes, PlainTextDelta};
+
+/// The server side of the grid and the grid block. Both of them are stored as plain text deltas,
+/// so the server only needs to compose and transform the deltas.
+pub struct ServerGrid {
+    object_id: String,
+    delta: GridDelta,
+}
+
+impl ServerGrid {
+    pub fn from_delta(object_id: &str, delta: GridDelta) -> Self {
+        Self {
+            object_id: object_id.to_owned(),
+            delta,
+        }
+    }
+}
+
+impl RevisionSyncObject<PhantomAttributes> for ServerGrid {
+    fn id(&self) -> &str {
+        &self.object_id
+    }
+
+    fn compose(&mut self, other: &PlainTextDelta) -> Result<(), CollaborateError> {
+        let new_delta = self.delta.compose(other)?;
+        self.delta = new_delta;
+        Ok(())
+    }
+
+    fn transform(&self, other: &PlainTextDelta) -> Result<(PlainTextDelta, PlainTextDelta), CollaborateError> {
+        let value = self.delta.transform(other)?;
+        Ok(value)
+    }
+
+    fn to_json(&self) -> String {
+        self.delta.to_json_str()
+    }
+
+    fn set_delta(&mut self, new_delta: PlainTextDelta) {
+        self.delta = new_delta;
+    }
+}
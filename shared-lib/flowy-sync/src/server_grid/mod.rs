@@ -0,0 +1,4 @@
+mod grid_manager;
+mod grid_pad;
+
+pub use grid_manager::*;
@@ -1,6 +1,7 @@
 use crate::{
     entities::{
         folder::{FolderDelta, FolderInfo},
+        grid::{GridDelta, GridInfo},
         revision::{RepeatedRevision, Revision},
         text_block::DocumentPB,
     },
@@ -158,6 +159,38 @@ pub fn make_folder_from_revisions_pb(
     }))
 }
 
+#[inline]
+pub fn make_grid_from_revisions_pb(
+    object_id: &str,
+    revisions: RepeatedRevision,
+) -> Result<Option<GridInfo>, CollaborateError> {
+    let revisions = revisions.into_inner();
+    if revisions.is_empty() {
+        return Ok(None);
+    }
+
+    let mut grid_delta = GridDelta::new();
+    let mut base_rev_id = 0;
+    let mut rev_id = 0;
+    for revision in revisions {
+        base_rev_id = revision.base_rev_id;
+        rev_id = revision.rev_id;
+        if revision.delta_data.is_empty() {
+            tracing::warn!("revision delta_data is empty");
+        }
+        let delta = GridDelta::from_bytes(revision.delta_data)?;
+        grid_delta = grid_delta.compose(&delta)?;
+    }
+
+    let text = grid_delta.to_json_str();
+    Ok(Some(GridInfo {
+        object_id: object_id.to_string(),
+        text,
+        rev_id,
+        base_rev_id,
+    }))
+}
+
 #[inline]
 pub fn make_document_from_revision_pbs(
     doc_id: &str,